@@ -0,0 +1,1064 @@
+// ***************************************************************************
+// build.rs
+// ***************************************************************************
+// Generate the application event wrappers from resources/events.fbs.
+//
+// The flatc compiler generates the low-level flatbuffer accessors in
+// src/events_generated.rs.  This build script reads the same schema and
+// generates the application events that wrap those accessors: the event
+// structs, their constructors and accessors, the to_bytes/from_bytes and
+// new_from_gen conversions, the event registry invocation and a round-trip
+// test for each event.  The output is written to $OUT_DIR/events_wrappers.rs
+// and included by src/events.rs.
+//
+// Only the subset of the flatbuffers schema language used by events.fbs is
+// understood.  The code generation is guided by the ct_* attributes declared
+// at the top of events.fbs, which flatc ignores.
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+const SCHEMA_FILE: &str = "resources/events.fbs";
+const OUTPUT_FILE: &str = "events_wrappers.rs";
+
+// Table attributes.
+const ATTR_PREFIX: &str = "ct_prefix";
+const ATTR_NEW: &str = "ct_new";
+const ATTR_VERSION: &str = "ct_version";
+
+// Field attributes.
+const ATTR_CREATED: &str = "ct_created";
+const ATTR_UUID: &str = "ct_uuid";
+const ATTR_NAME: &str = "ct_name";
+const ATTR_NONEMPTY: &str = "ct_nonempty";
+const ATTR_DATETIME: &str = "ct_datetime";
+const ATTR_NO_JSON: &str = "ct_no_json";
+const ATTR_OPTIONAL: &str = "ct_optional";
+const ATTR_COMPRESSED: &str = "ct_compressed";
+
+// The enum that records how a ct_compressed vector is compressed.
+const COMPRESSION_ENUM: &str = "ImageCompression";
+
+// ***************************************************************************
+// MAIN
+// ***************************************************************************
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={}", SCHEMA_FILE);
+
+    let text = fs::read_to_string(SCHEMA_FILE)
+        .unwrap_or_else(|e| panic!("Unable to read {}: {}", SCHEMA_FILE, e));
+    let schema = match Schema::parse(&text) {
+        Ok(s) => s,
+        Err(e) => panic!("Unable to parse {}: {}", SCHEMA_FILE, e),
+    };
+    let code = match Generator::new(&schema).generate() {
+        Ok(c) => c,
+        Err(e) => panic!("Unable to generate event wrappers from {}: {}", SCHEMA_FILE, e),
+    };
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set by cargo");
+    let out_file = Path::new(&out_dir).join(OUTPUT_FILE);
+    fs::write(&out_file, code)
+        .unwrap_or_else(|e| panic!("Unable to write {}: {}", out_file.display(), e));
+}
+
+// ***************************************************************************
+// SCHEMA
+// ***************************************************************************
+type Attributes = HashMap<String, Option<String>>;
+
+struct EnumDef {
+    name: String,
+    doc: Vec<String>,
+    values: Vec<(String, i64)>,
+}
+
+struct FieldDef {
+    name: String,
+    // Either a type name or, for vectors, the element type name.
+    type_name: String,
+    vector: bool,
+    attrs: Attributes,
+}
+
+struct TableDef {
+    name: String,
+    doc: Vec<String>,
+    attrs: Attributes,
+    fields: Vec<FieldDef>,
+}
+
+#[derive(Default)]
+struct Schema {
+    enums: Vec<EnumDef>,
+    tables: Vec<TableDef>,
+    union_members: Vec<String>,
+    root_type: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Punct(char),
+    // Comment text without the leading slashes, None for a blank line.
+    Comment(Option<String>),
+}
+
+impl Schema {
+    // ----------------------------------------------------------------------
+    // parse:
+    // ----------------------------------------------------------------------
+    fn parse(text: &str) -> Result<Schema, String> {
+        let tokens = tokenize(text)?;
+        let mut parser = Parser { tokens, pos: 0, doc: vec![] };
+        let mut schema = Schema::default();
+
+        while let Some(tok) = parser.next() {
+            let keyword = match tok {
+                Token::Ident(s) => s,
+                t => return Err(format!("unexpected token {:?}", t)),
+            };
+            let doc = std::mem::take(&mut parser.doc);
+            match keyword.as_str() {
+                "table" => {
+                    let mut table = parser.parse_table()?;
+                    table.doc = doc;
+                    schema.tables.push(table);
+                },
+                "enum" => {
+                    let mut enum_def = parser.parse_enum()?;
+                    enum_def.doc = doc;
+                    schema.enums.push(enum_def);
+                },
+                "union" => {
+                    let (_, members) = parser.parse_union()?;
+                    schema.union_members = members;
+                },
+                "root_type" => {
+                    schema.root_type = Some(parser.expect_ident()?);
+                    parser.expect(';')?;
+                },
+                // namespace, attribute, include, file_identifier, etc.
+                _ => parser.skip_statement()?,
+            }
+        }
+
+        Ok(schema)
+    }
+
+    fn get_table(&self, name: &str) -> Option<&TableDef> {
+        self.tables.iter().find(|t| t.name == name)
+    }
+
+    fn get_enum(&self, name: &str) -> Option<&EnumDef> {
+        self.enums.iter().find(|e| e.name == name)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// tokenize:
+// ---------------------------------------------------------------------------
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    for line in text.lines() {
+        let mut chars = line.trim().chars().peekable();
+        if chars.peek().is_none() {
+            tokens.push(Token::Comment(None));
+            continue;
+        }
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+            } else if c == '/' {
+                let rest: String = chars.by_ref().collect();
+                let comment = match rest.strip_prefix("//") {
+                    Some(s) => s.strip_prefix(' ').unwrap_or(s),
+                    None => return Err(format!("invalid comment: {}", rest)),
+                };
+                tokens.push(Token::Comment(Some(comment.trim_end().to_string())));
+            } else if c == '"' {
+                chars.next();
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => s.push(c),
+                        None => return Err(format!("unterminated string: {}", line)),
+                    }
+                }
+                tokens.push(Token::Str(s));
+            } else if c.is_alphanumeric() || c == '_' || c == '-' || c == '.' {
+                let mut s = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_alphanumeric() || c == '_' || c == '-' || c == '.' {
+                        s.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Ident(s));
+            } else {
+                tokens.push(Token::Punct(c));
+                chars.next();
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+// ***************************************************************************
+// PARSER
+// ***************************************************************************
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    // Comment lines immediately preceding the current declaration.
+    doc: Vec<String>,
+}
+
+impl Parser {
+    // Return the next non-comment token, collecting comments as documentation.
+    fn next(&mut self) -> Option<Token> {
+        while self.pos < self.tokens.len() {
+            let tok = self.tokens[self.pos].clone();
+            self.pos += 1;
+            match tok {
+                Token::Comment(Some(c)) => self.doc.push(c),
+                Token::Comment(None) => self.doc.clear(),
+                t => return Some(t),
+            }
+        }
+        None
+    }
+
+    fn peek(&mut self) -> Option<Token> {
+        let pos = self.pos;
+        let doc = self.doc.clone();
+        let tok = self.next();
+        self.pos = pos;
+        self.doc = doc;
+        tok
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        match self.next() {
+            Some(Token::Punct(p)) if p == c => Ok(()),
+            t => Err(format!("expected '{}', found {:?}", c, t)),
+        }
+    }
+
+    fn expect_ident(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(Token::Ident(s)) => Ok(s),
+            t => Err(format!("expected identifier, found {:?}", t)),
+        }
+    }
+
+    fn skip_statement(&mut self) -> Result<(), String> {
+        loop {
+            match self.next() {
+                Some(Token::Punct(';')) => return Ok(()),
+                Some(_) => (),
+                None => return Err("unexpected end of schema".to_string()),
+            }
+        }
+    }
+
+    // Parse an optional parenthesized attribute list: (a, b: "x", c: 1).
+    fn parse_attributes(&mut self) -> Result<Attributes, String> {
+        let mut attrs = Attributes::new();
+        if self.peek() != Some(Token::Punct('(')) {
+            return Ok(attrs);
+        }
+        self.expect('(')?;
+        loop {
+            let key = self.expect_ident()?;
+            let mut value = None;
+            if self.peek() == Some(Token::Punct(':')) {
+                self.expect(':')?;
+                value = match self.next() {
+                    Some(Token::Str(s)) | Some(Token::Ident(s)) => Some(s),
+                    t => return Err(format!("invalid value for attribute {}: {:?}", key, t)),
+                };
+            }
+            attrs.insert(key, value);
+            match self.next() {
+                Some(Token::Punct(',')) => continue,
+                Some(Token::Punct(')')) => return Ok(attrs),
+                t => return Err(format!("expected ',' or ')' in attributes, found {:?}", t)),
+            }
+        }
+    }
+
+    fn parse_table(&mut self) -> Result<TableDef, String> {
+        let name = self.expect_ident()?;
+        let attrs = self.parse_attributes()?;
+        self.expect('{')?;
+        let mut fields = vec![];
+        loop {
+            let field_name = match self.next() {
+                Some(Token::Punct('}')) => break,
+                Some(Token::Ident(s)) => s,
+                t => return Err(format!("expected field in table {}, found {:?}", name, t)),
+            };
+            self.expect(':')?;
+            let (type_name, vector) = match self.next() {
+                Some(Token::Ident(s)) => (s, false),
+                Some(Token::Punct('[')) => {
+                    let s = self.expect_ident()?;
+                    self.expect(']')?;
+                    (s, true)
+                },
+                t => return Err(format!("expected type for {}.{}, found {:?}", name, field_name, t)),
+            };
+            // Skip default values.
+            if self.peek() == Some(Token::Punct('=')) {
+                self.expect('=')?;
+                self.expect_ident()?;
+            }
+            let attrs = self.parse_attributes()?;
+            self.expect(';')?;
+            fields.push(FieldDef { name: field_name, type_name, vector, attrs });
+        }
+        Ok(TableDef { name, doc: vec![], attrs, fields })
+    }
+
+    fn parse_enum(&mut self) -> Result<EnumDef, String> {
+        let name = self.expect_ident()?;
+        self.expect(':')?;
+        self.expect_ident()?;
+        self.parse_attributes()?;
+        self.expect('{')?;
+        let mut values = vec![];
+        let mut next_value = 0;
+        loop {
+            let value_name = match self.next() {
+                Some(Token::Punct('}')) => break,
+                Some(Token::Punct(',')) => continue,
+                Some(Token::Ident(s)) => s,
+                t => return Err(format!("expected value in enum {}, found {:?}", name, t)),
+            };
+            if self.peek() == Some(Token::Punct('=')) {
+                self.expect('=')?;
+                let v = self.expect_ident()?;
+                next_value = v.parse::<i64>().map_err(|e| format!("invalid value {} in enum {}: {}", v, name, e))?;
+            }
+            values.push((value_name, next_value));
+            next_value += 1;
+        }
+        Ok(EnumDef { name, doc: vec![], values })
+    }
+
+    fn parse_union(&mut self) -> Result<(String, Vec<String>), String> {
+        let name = self.expect_ident()?;
+        self.parse_attributes()?;
+        self.expect('{')?;
+        let mut members = vec![];
+        loop {
+            match self.next() {
+                Some(Token::Punct('}')) => break,
+                Some(Token::Punct(',')) => continue,
+                Some(Token::Ident(s)) => members.push(s),
+                t => return Err(format!("expected member in union {}, found {:?}", name, t)),
+            }
+        }
+        Ok((name, members))
+    }
+}
+
+// ***************************************************************************
+// FIELD KINDS
+// ***************************************************************************
+/** How a schema field is represented in the application event. */
+#[derive(Clone, PartialEq)]
+enum Kind {
+    Created,
+    Str,
+    Uuid,
+    Scalar(String),
+    Enum(String),
+    Table(String),
+    Bytes,
+    ScalarVec(String),
+    EnumVec(String),
+    TableVec(String),
+    StrVec,
+}
+
+struct Field<'a> {
+    def: &'a FieldDef,
+    rust_name: String,
+    kind: Kind,
+    optional: bool,
+    // An optional scalar, which is not a new() parameter and defaults to zero.
+    defaulted: bool,
+}
+
+impl Field<'_> {
+    fn has(&self, attr: &str) -> bool {
+        self.def.attrs.contains_key(attr)
+    }
+
+    // The Rust type of the field, not including any Option wrapper.
+    fn base_type(&self) -> String {
+        match &self.kind {
+            Kind::Created | Kind::Str => "String".to_string(),
+            Kind::Uuid => "Uuid".to_string(),
+            Kind::Scalar(t) => t.clone(),
+            Kind::Enum(t) | Kind::Table(t) => t.clone(),
+            Kind::Bytes => "Vec<u8>".to_string(),
+            Kind::ScalarVec(t) | Kind::EnumVec(t) | Kind::TableVec(t) => format!("Vec<{}>", t),
+            Kind::StrVec => "Vec<String>".to_string(),
+        }
+    }
+
+    fn rust_type(&self) -> String {
+        if self.optional {
+            format!("Option<{}>", self.base_type())
+        } else {
+            self.base_type()
+        }
+    }
+
+    // Scalars and enums are returned by value from accessors.
+    fn is_copy(&self) -> bool {
+        matches!(self.kind, Kind::Scalar(_) | Kind::Enum(_))
+    }
+
+    // The flatbuffer name of the field that records how this field is compressed.
+    fn compressed_by(&self) -> Option<&str> {
+        self.def.attrs.get(ATTR_COMPRESSED).and_then(|v| v.as_deref())
+    }
+}
+
+fn scalar_type(fbs_type: &str) -> Option<&'static str> {
+    let t = match fbs_type {
+        "bool" => "bool",
+        "byte" | "int8" => "i8",
+        "ubyte" | "uint8" => "u8",
+        "short" | "int16" => "i16",
+        "ushort" | "uint16" => "u16",
+        "int" | "int32" => "i32",
+        "uint" | "uint32" => "u32",
+        "long" | "int64" => "i64",
+        "ulong" | "uint64" => "u64",
+        "float" | "float32" => "f32",
+        "double" | "float64" => "f64",
+        _ => return None,
+    };
+    Some(t)
+}
+
+// ***************************************************************************
+// GENERATOR
+// ***************************************************************************
+struct Generator<'a> {
+    schema: &'a Schema,
+    out: String,
+}
+
+impl<'a> Generator<'a> {
+    fn new(schema: &'a Schema) -> Self {
+        Generator { schema, out: String::new() }
+    }
+
+    // ----------------------------------------------------------------------
+    // generate:
+    // ----------------------------------------------------------------------
+    fn generate(mut self) -> Result<String, String> {
+        self.line("// Generated by build.rs from resources/events.fbs, do not edit.");
+        self.line("");
+
+        self.gen_version()?;
+        for enum_def in &self.schema.enums {
+            self.gen_enum(enum_def);
+        }
+        for table in &self.schema.tables {
+            if Some(&table.name) == self.schema.root_type.as_ref() {
+                continue;
+            }
+            self.gen_table(table)?;
+        }
+        self.gen_registry()?;
+        self.gen_tests()?;
+        Ok(self.out)
+    }
+
+    fn line(&mut self, s: &str) {
+        self.out.push_str(s);
+        self.out.push('\n');
+    }
+
+    fn doc(&mut self, doc: &[String]) {
+        for d in doc {
+            let _ = writeln!(self.out, "///{}{}", if d.is_empty() {""} else {" "}, d);
+        }
+    }
+
+    fn is_event(&self, table: &TableDef) -> bool {
+        self.schema.union_members.contains(&table.name)
+    }
+
+    // ----------------------------------------------------------------------
+    // fields:
+    // ----------------------------------------------------------------------
+    /** Classify each of a table's fields. */
+    fn fields(&self, table: &'a TableDef) -> Result<Vec<Field<'a>>, String> {
+        let mut fields = vec![];
+        for def in &table.fields {
+            let has = |attr: &str| def.attrs.contains_key(attr);
+            let t = def.type_name.as_str();
+            let kind = if def.vector {
+                if t == "ubyte" || t == "uint8" {
+                    Kind::Bytes
+                } else if t == "string" {
+                    Kind::StrVec
+                } else if let Some(s) = scalar_type(t) {
+                    Kind::ScalarVec(s.to_string())
+                } else if self.schema.get_enum(t).is_some() {
+                    Kind::EnumVec(t.to_string())
+                } else if self.schema.get_table(t).is_some() {
+                    Kind::TableVec(t.to_string())
+                } else {
+                    return Err(format!("unknown type [{}] for {}.{}", t, table.name, def.name));
+                }
+            } else if t == "string" {
+                if has(ATTR_CREATED) {
+                    Kind::Created
+                } else if has(ATTR_UUID) {
+                    Kind::Uuid
+                } else {
+                    Kind::Str
+                }
+            } else if let Some(s) = scalar_type(t) {
+                Kind::Scalar(s.to_string())
+            } else if self.schema.get_enum(t).is_some() {
+                Kind::Enum(t.to_string())
+            } else if self.schema.get_table(t).is_some() {
+                Kind::Table(t.to_string())
+            } else {
+                return Err(format!("unknown type {} for {}.{}", t, table.name, def.name));
+            };
+
+            let rust_name = match def.attrs.get(ATTR_NAME) {
+                Some(Some(n)) => n.clone(),
+                Some(None) => return Err(format!("{} requires a value on {}.{}", ATTR_NAME, table.name, def.name)),
+                None if kind == Kind::Created => "created".to_string(),
+                None => def.name.clone(),
+            };
+
+            // Scalars always have a value in flatbuffers.
+            let optional = has(ATTR_OPTIONAL) && !matches!(kind, Kind::Scalar(_) | Kind::Enum(_) | Kind::Created);
+            let defaulted = has(ATTR_OPTIONAL) && matches!(kind, Kind::Scalar(_) | Kind::Enum(_));
+            if (has(ATTR_NONEMPTY) && !def.vector) || (has(ATTR_DATETIME) && kind != Kind::Str) {
+                return Err(format!("invalid attribute on {}.{}", table.name, def.name));
+            }
+            if has(ATTR_COMPRESSED) && (kind != Kind::Bytes || optional) {
+                return Err(format!("{} requires a required [ubyte] field on {}.{}", ATTR_COMPRESSED, table.name, def.name));
+            }
+            fields.push(Field { def, rust_name, kind, optional, defaulted });
+        }
+
+        // Compressed vectors name the field that records their compression.
+        for f in &fields {
+            if let Some(flag) = f.compressed_by() {
+                let valid = fields.iter().any(|g| g.def.name == flag && g.defaulted
+                                                  && g.kind == Kind::Enum(COMPRESSION_ENUM.to_string()));
+                if !valid {
+                    return Err(format!("{} on {}.{} must name an optional {} field",
+                                       ATTR_COMPRESSED, table.name, f.def.name, COMPRESSION_ENUM));
+                }
+            }
+        }
+        Ok(fields)
+    }
+
+    // ----------------------------------------------------------------------
+    // new_params:
+    // ----------------------------------------------------------------------
+    /** The fields passed to the new() constructor in parameter order. */
+    fn new_params<'f>(&self, table: &TableDef, fields: &'f [Field<'a>]) -> Result<Vec<&'f Field<'a>>, String> {
+        let params: Vec<&Field> = fields.iter().filter(|f| f.kind != Kind::Created && !f.optional && !f.defaulted).collect();
+        match table.attrs.get(ATTR_NEW) {
+            Some(Some(order)) => {
+                let mut ordered = vec![];
+                for name in order.split(',').map(|s| s.trim()) {
+                    match params.iter().find(|f| f.rust_name == name) {
+                        Some(f) => ordered.push(*f),
+                        None => return Err(format!("{} names unknown field {} in {}", ATTR_NEW, name, table.name)),
+                    }
+                }
+                if ordered.len() != params.len() {
+                    return Err(format!("{} must name every required field in {}", ATTR_NEW, table.name));
+                }
+                Ok(ordered)
+            },
+            Some(None) => Err(format!("{} requires a value on {}", ATTR_NEW, table.name)),
+            None => Ok(params),
+        }
+    }
+
+    // ----------------------------------------------------------------------
+    // gen_enum:
+    // ----------------------------------------------------------------------
+    fn gen_enum(&mut self, e: &EnumDef) {
+        let banner = format!("// {}:", e.name);
+        self.line("// ===========================================================================");
+        self.line(&banner);
+        self.line("// ===========================================================================");
+        self.doc(&e.doc);
+        self.line("#[allow(clippy::upper_case_acronyms)]");
+        self.line("#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]");
+        let _ = writeln!(self.out, "pub enum {} {{", e.name);
+        for (n, v) in &e.values {
+            let _ = writeln!(self.out, "    {} = {},", n, v);
+        }
+        self.line("}");
+        self.line("");
+        let _ = writeln!(self.out, "impl {} {{", e.name);
+        let _ = writeln!(self.out, "    fn to_gen(self) -> gen_events::{} {{", e.name);
+        self.line("        match self {");
+        for (n, _) in &e.values {
+            let _ = writeln!(self.out, "            {0}::{1} => gen_events::{0}::{1},", e.name, n);
+        }
+        self.line("        }");
+        self.line("    }");
+        self.line("");
+        let _ = writeln!(self.out, "    fn from_gen(gen: gen_events::{}) -> Result<Self, Errors> {{", e.name);
+        self.line("        match gen {");
+        for (n, _) in &e.values {
+            let _ = writeln!(self.out, "            gen_events::{0}::{1} => Result::Ok({0}::{1}),", e.name, n);
+        }
+        let _ = writeln!(self.out,
+            "            _ => Result::Err(Errors::EventReadFlatbuffer(format!(\"{} Unknown -> {{}}\", gen.0))),", e.name);
+        self.line("        }");
+        self.line("    }");
+        self.line("}");
+        self.line("");
+    }
+
+    // ----------------------------------------------------------------------
+    // gen_version:
+    // ----------------------------------------------------------------------
+    /** Generate the schema version constants from the root table's version. */
+    fn gen_version(&mut self) -> Result<(), String> {
+        let root = self.schema.root_type.as_ref().and_then(|r| self.schema.get_table(r))
+            .ok_or("the schema requires a root_type table")?;
+        let version = match root.attrs.get(ATTR_VERSION) {
+            Some(Some(v)) => v,
+            _ => return Err(format!("root table {} requires a {} attribute", root.name, ATTR_VERSION)),
+        };
+        let (major, minor) = version.split_once('.')
+            .and_then(|(major, minor)| Some((major.parse::<u16>().ok()?, minor.parse::<u16>().ok()?)))
+            .ok_or(format!("invalid {} \"{}\", expected \"major.minor\"", ATTR_VERSION, version))?;
+        self.line("/// The event schema version written into every event.");
+        let _ = writeln!(self.out, "pub const EVENT_SCHEMA_MAJOR: u16 = {};", major);
+        let _ = writeln!(self.out, "pub const EVENT_SCHEMA_MINOR: u16 = {};", minor);
+        self.line("");
+        Ok(())
+    }
+
+    // ----------------------------------------------------------------------
+    // gen_table:
+    // ----------------------------------------------------------------------
+    fn gen_table(&mut self, table: &'a TableDef) -> Result<(), String> {
+        let fields = self.fields(table)?;
+        let name = &table.name;
+        let is_event = self.is_event(table);
+
+        // ------ Struct
+        let banner = format!("// {}:", name);
+        self.line("// ===========================================================================");
+        self.line(&banner);
+        self.line("// ===========================================================================");
+        self.doc(&table.doc);
+        self.line("#[derive(Serialize, Deserialize)]");
+        let _ = writeln!(self.out, "pub struct {} {{", name);
+        for f in &fields {
+            if f.has(ATTR_NO_JSON) {
+                self.line("    #[serde(skip_serializing, default)]");
+            } else if f.optional {
+                self.line("    #[serde(skip_serializing_if = \"Option::is_none\")]");
+            }
+            let _ = writeln!(self.out, "    {}: {},", f.rust_name, f.rust_type());
+        }
+        self.line("}");
+        self.line("");
+
+        // ------ Trait Event
+        if is_event {
+            let prefix = prefix_name(name);
+            let _ = writeln!(self.out, "impl Event for {} {{", name);
+            self.line("    /** Convert the event to a raw byte array (prefix + flatbuffer). */");
+            self.line("    fn to_bytes(&self) -> Result<Vec<u8>, EngineError> {");
+            self.line("        let mut fbuf = FlatBufferBuilder::new();");
+            self.line("        let event_offset = self.to_gen(&mut fbuf);");
+            self.line("        let union_args = gen_events::EventArgs {");
+            let _ = writeln!(self.out, "            event_type: gen_events::EventType::{},", name);
+            self.line("            event: Some(event_offset.as_union_value()),");
+            self.line("            ..Default::default()");
+            self.line("        };");
+            let _ = writeln!(self.out, "        Ok(serialize_flatbuffer({}, fbuf, union_args))", prefix);
+            self.line("    }");
+            self.line("");
+            let _ = writeln!(self.out, "    /** Get a {} from raw event bytes that do NOT include the zqm prefix. */", name);
+            let _ = writeln!(self.out, "    fn from_bytes(bytes: Vec<u8>) -> Result<{}, Box<dyn Error>>", name);
+            self.line("    where");
+            self.line("        Self: Sized,");
+            self.line("    {");
+            self.line("        let event = bytes_to_gen_event(&bytes)?;");
+            let _ = writeln!(self.out, "        check_event_type(\"{}\", &event)?;", name);
+            let _ = writeln!(self.out, "        Ok({}::from_gen_event(event)?)", name);
+            self.line("    }");
+            self.line("}");
+            self.line("");
+        }
+
+        // ------ Associated Functions
+        let params = self.new_params(table, &fields)?;
+        self.line("#[allow(dead_code)]");
+        let _ = writeln!(self.out, "impl {} {{", name);
+
+        // new
+        let param_list: Vec<String> = params.iter().map(|f| format!("{}: {}", f.rust_name, f.rust_type())).collect();
+        let _ = writeln!(self.out, "    pub fn new({}) -> Self {{", param_list.join(", "));
+        let _ = writeln!(self.out, "        {} {{", name);
+        for f in &fields {
+            if f.kind == Kind::Created {
+                let _ = writeln!(self.out, "            {}: timestamp_str(),", f.rust_name);
+            } else if f.optional {
+                let _ = writeln!(self.out, "            {}: None,", f.rust_name);
+            } else if f.defaulted {
+                let _ = writeln!(self.out, "            {}: {},", f.rust_name, self.zero_value(f)?);
+            } else {
+                let _ = writeln!(self.out, "            {},", f.rust_name);
+            }
+        }
+        self.line("        }");
+        self.line("    }");
+        self.line("");
+
+        // accessors
+        for f in &fields {
+            let (ret, body) = if f.is_copy() {
+                (f.rust_type(), format!("self.{}", f.rust_name))
+            } else if f.optional {
+                (format!("Option<&{}>", f.base_type()), format!("self.{}.as_ref()", f.rust_name))
+            } else {
+                (format!("&{}", f.rust_type()), format!("&self.{}", f.rust_name))
+            };
+            let _ = writeln!(self.out, "    pub fn get_{}(&self) -> {} {{", f.rust_name, ret);
+            let _ = writeln!(self.out, "        {}", body);
+            self.line("    }");
+        }
+        for f in fields.iter().filter(|f| f.optional) {
+            let _ = writeln!(self.out, "    pub fn set_{}(&mut self, {}: Option<{}>) {{", f.rust_name, f.rust_name, f.base_type());
+            let _ = writeln!(self.out, "        self.{0} = {0};", f.rust_name);
+            self.line("    }");
+        }
+        for f in fields.iter().filter(|f| f.defaulted) {
+            let _ = writeln!(self.out, "    pub fn set_{}(&mut self, {}: {}) {{", f.rust_name, f.rust_name, f.base_type());
+            let _ = writeln!(self.out, "        self.{0} = {0};", f.rust_name);
+            self.line("    }");
+        }
+        self.line("");
+
+        // new_from_gen
+        self.line("    /** Construct a new object from a generated flatbuffer object. */");
+        let _ = writeln!(self.out, "    pub fn new_from_gen(ev: gen_events::{}) -> Result<Self, Errors> {{", name);
+        for f in &fields {
+            let read = self.read_field(f);
+            let _ = writeln!(self.out, "        let {}: {} = {};", f.rust_name, f.rust_type(), read);
+            if f.has(ATTR_NONEMPTY) {
+                let target = if f.optional {format!("{}.as_deref().unwrap_or_default()", f.rust_name)} else {format!("&{}", f.rust_name)};
+                let _ = writeln!(self.out, "        check_not_empty({}, \"{}\", \"{}\")?;", target, name, f.rust_name);
+            }
+            if f.has(ATTR_DATETIME) {
+                let target = if f.optional {format!("{}.as_deref().unwrap_or_default()", f.rust_name)} else {format!("&{}", f.rust_name)};
+                let _ = writeln!(self.out, "        check_datetime({})?;", target);
+            }
+        }
+        let names: Vec<&str> = fields.iter().map(|f| f.rust_name.as_str()).collect();
+        let _ = writeln!(self.out, "        Result::Ok({} {{ {} }})", name, names.join(", "));
+        self.line("    }");
+        self.line("");
+
+        // to_gen
+        let _ = writeln!(self.out,
+            "    fn to_gen<'b>(&self, fbuf: &mut FlatBufferBuilder<'b>) -> flatbuffers::WIPOffset<gen_events::{}<'b>> {{", name);
+        let mut args = vec![];
+        for f in &fields {
+            // Compressed vectors also determine the compression that is recorded,
+            // which is assigned to a local variable named after the flag field.
+            if let Some(flag) = f.compressed_by() {
+                let _ = writeln!(self.out,
+                    "        let ({0}, {1}) = {{ let (v, c) = compress_image_bytes(&self.{2}, self.{3}); (Some(fbuf.create_vector(&v)), c) }};",
+                    f.def.name, flag, f.rust_name, self.rust_name_of(&fields, flag));
+                args.push(f.def.name.clone());
+                continue;
+            }
+            if fields.iter().any(|g| g.compressed_by() == Some(f.def.name.as_str())) {
+                args.push(format!("{0}: {0}.to_gen()", f.def.name));
+                continue;
+            }
+            match self.write_field(f) {
+                Some(stmt) => {
+                    let _ = writeln!(self.out, "        let {} = {};", f.def.name, stmt);
+                    args.push(f.def.name.clone());
+                },
+                None => {
+                    let value = match f.kind {
+                        Kind::Enum(_) => format!("self.{}.to_gen()", f.rust_name),
+                        _ => format!("self.{}", f.rust_name),
+                    };
+                    args.push(format!("{}: {}", f.def.name, value));
+                },
+            }
+        }
+        let _ = writeln!(self.out, "        gen_events::{}::create(fbuf, &gen_events::{}Args {{ {} }})", name, name, args.join(", "));
+        self.line("    }");
+        self.line("}");
+        self.line("");
+        Ok(())
+    }
+
+    // The application name of the field with the given flatbuffer name.
+    fn rust_name_of(&self, fields: &[Field], fbs_name: &str) -> String {
+        fields.iter().find(|f| f.def.name == fbs_name).map(|f| f.rust_name.clone()).unwrap_or_default()
+    }
+
+    // The zero value of an optional scalar.
+    fn zero_value(&self, f: &Field) -> Result<String, String> {
+        let value = match &f.kind {
+            Kind::Enum(t) => {
+                let e = self.schema.get_enum(t).ok_or(format!("unknown enum {}", t))?;
+                match e.values.iter().find(|v| v.1 == 0) {
+                    Some(v) => format!("{}::{}", t, v.0),
+                    None => return Err(format!("enum {} has no zero value for {}", t, f.def.name)),
+                }
+            },
+            Kind::Scalar(t) if t == "bool" => "false".to_string(),
+            Kind::Scalar(t) if t.starts_with('f') => "0.0".to_string(),
+            _ => "0".to_string(),
+        };
+        Ok(value)
+    }
+
+    // ----------------------------------------------------------------------
+    // read_field:
+    // ----------------------------------------------------------------------
+    /** The expression that reads a field from the generated object. */
+    fn read_field(&self, f: &Field) -> String {
+        let fbs = &f.def.name;
+        let rust = &f.rust_name;
+        // Convert the generated value v into the application value.
+        let convert = match &f.kind {
+            Kind::Created | Kind::Str => "String::from(v)".to_string(),
+            Kind::Uuid => format!("parse_uuid(v, \"{}\")?", fbs),
+            Kind::Scalar(_) | Kind::Enum(_) => String::new(),
+            Kind::Table(t) => format!("{}::new_from_gen(v)?", t),
+            Kind::Bytes => match f.compressed_by() {
+                Some(flag) => format!("decompress_image_bytes(v, ev.{}())?", flag),
+                None => "v.to_vec()".to_string(),
+            },
+            Kind::ScalarVec(_) => "v.iter().collect()".to_string(),
+            Kind::EnumVec(t) => format!("v.iter().map({}::from_gen).collect::<Result<_, Errors>>()?", t),
+            Kind::TableVec(t) => format!("v.iter().map({}::new_from_gen).collect::<Result<_, Errors>>()?", t),
+            Kind::StrVec => "v.iter().map(String::from).collect()".to_string(),
+        };
+        match &f.kind {
+            Kind::Scalar(_) => format!("ev.{}()", fbs),
+            Kind::Enum(t) => format!("{}::from_gen(ev.{}())?", t, fbs),
+            _ if f.optional => {
+                if convert.ends_with('?') {
+                    let conv = convert.trim_end_matches('?');
+                    format!("ev.{}().map(|v| {}).transpose()?", fbs, conv)
+                } else if convert == "String::from(v)" {
+                    format!("ev.{}().map(String::from)", fbs)
+                } else {
+                    format!("ev.{}().map(|v| {})", fbs, convert)
+                }
+            },
+            _ => {
+                let read = format!("read_required(ev.{}(), \"{}\")?", fbs, rust);
+                match convert.as_str() {
+                    "String::from(v)" => format!("String::from({})", read),
+                    _ => format!("{{ let v = {}; {} }}", read, convert),
+                }
+            },
+        }
+    }
+
+    // ----------------------------------------------------------------------
+    // write_field:
+    // ----------------------------------------------------------------------
+    /** The expression that creates a field's offset in the flatbuffer, or
+     * None for scalars which are assigned directly.
+     */
+    fn write_field(&self, f: &Field) -> Option<String> {
+        // Create the offset from the application value v, which is a reference.
+        let create = match &f.kind {
+            Kind::Created | Kind::Str => "fbuf.create_string(v)".to_string(),
+            Kind::Uuid => "fbuf.create_string(&v.hyphenated().to_string())".to_string(),
+            Kind::Scalar(_) | Kind::Enum(_) => return None,
+            Kind::Table(_) => "v.to_gen(fbuf)".to_string(),
+            Kind::Bytes | Kind::ScalarVec(_) => "fbuf.create_vector(v)".to_string(),
+            Kind::EnumVec(_) =>
+                "{ let gen: Vec<_> = v.iter().map(|e| e.to_gen()).collect(); fbuf.create_vector(&gen) }".to_string(),
+            Kind::TableVec(_) =>
+                "{ let gen: Vec<_> = v.iter().map(|e| e.to_gen(fbuf)).collect(); fbuf.create_vector(&gen) }".to_string(),
+            Kind::StrVec =>
+                "{ let gen: Vec<_> = v.iter().map(|e| fbuf.create_string(e)).collect(); fbuf.create_vector(&gen) }".to_string(),
+        };
+        if f.optional {
+            Some(format!("self.{}.as_ref().map(|v| {})", f.rust_name, create))
+        } else {
+            Some(format!("{{ let v = &self.{}; Some({}) }}", f.rust_name, create))
+        }
+    }
+
+    // ----------------------------------------------------------------------
+    // gen_registry:
+    // ----------------------------------------------------------------------
+    fn gen_registry(&mut self) -> Result<(), String> {
+        self.line("// Each event is assigned a binary prefix that zqm uses to route incoming");
+        self.line("// binary streams to all of the event's subscribers.");
+        self.line("event_registry! {");
+        for member in &self.schema.union_members {
+            let table = match self.schema.get_table(member) {
+                Some(t) => t,
+                None => return Err(format!("union member {} is not a table", member)),
+            };
+            let prefix = match table.attrs.get(ATTR_PREFIX) {
+                Some(Some(p)) => p,
+                _ => return Err(format!("event {} requires a {} attribute", member, ATTR_PREFIX)),
+            };
+            let _ = writeln!(self.out, "    {} => {}: [{}], event_as_{};",
+                             member, prefix_name(member), prefix, snake_case(member));
+        }
+        self.line("}");
+        self.line("");
+        Ok(())
+    }
+
+    // ----------------------------------------------------------------------
+    // gen_tests:
+    // ----------------------------------------------------------------------
+    /** Generate a round-trip test for each event: the event is serialized,
+     * deserialized and serialized again, and both the JSON and the bytes must
+     * match the original.  The event must also survive a round trip through
+     * its JSON representation.
+     */
+    fn gen_tests(&mut self) -> Result<(), String> {
+        self.line("#[cfg(test)]");
+        self.line("mod generated_tests {");
+        self.line("    use super::*;");
+        for member in &self.schema.union_members {
+            let table = self.schema.get_table(member).ok_or(format!("unknown event {}", member))?;
+            let construct = self.sample_value(&Kind::Table(table.name.clone()), None)?;
+            let fields = self.fields(table)?;
+            self.line("");
+            self.line("    #[test]");
+            let _ = writeln!(self.out, "    fn round_trip_{}() {{", snake_case(member));
+            if fields.iter().any(|f| f.optional || f.defaulted) {
+                let _ = writeln!(self.out, "        let mut event = {};", construct);
+                for f in fields.iter().filter(|f| f.optional) {
+                    let value = self.sample_value(&f.kind, Some(f))?;
+                    let _ = writeln!(self.out, "        event.set_{}(Some({}));", f.rust_name, value);
+                }
+                for f in fields.iter().filter(|f| f.defaulted) {
+                    let value = self.sample_value(&f.kind, Some(f))?;
+                    let _ = writeln!(self.out, "        event.set_{}({});", f.rust_name, value);
+                }
+            } else {
+                let _ = writeln!(self.out, "        let event = {};", construct);
+            }
+            self.line("        let bytes = event.to_bytes().unwrap();");
+            let _ = writeln!(self.out, "        assert_eq!(bytes[..EVENT_PREFIX_LEN], {});", prefix_name(member));
+            let _ = writeln!(self.out, "        let decoded = {}::from_bytes(bytes[EVENT_PREFIX_LEN..].to_vec()).unwrap();", member);
+            self.line("        assert_eq!(serde_json::to_value(&decoded).unwrap(), serde_json::to_value(&event).unwrap());");
+            self.line("        assert_eq!(decoded.to_bytes().unwrap(), bytes);");
+            let _ = writeln!(self.out, "        let from_json: {} = serde_json::from_value(serde_json::to_value(&event).unwrap()).unwrap();", member);
+            self.line("        assert_eq!(serde_json::to_value(&from_json).unwrap(), serde_json::to_value(&event).unwrap());");
+            self.line("    }");
+        }
+        self.line("}");
+        Ok(())
+    }
+
+    // A sample value of the given kind used in the generated tests.
+    fn sample_value(&self, kind: &Kind, field: Option<&Field>) -> Result<String, String> {
+        let value = match kind {
+            Kind::Created => "timestamp_str()".to_string(),
+            Kind::Str => match field {
+                Some(f) if f.has(ATTR_DATETIME) => "timestamp_str()".to_string(),
+                Some(f) => format!("String::from(\"{}\")", f.rust_name),
+                None => "String::from(\"test\")".to_string(),
+            },
+            Kind::Uuid => "Uuid::new_v4()".to_string(),
+            Kind::Scalar(t) => match t.as_str() {
+                "bool" => "true".to_string(),
+                "f32" | "f64" => "0.5".to_string(),
+                _ => "7".to_string(),
+            },
+            Kind::Enum(t) => {
+                let e = self.schema.get_enum(t).ok_or(format!("unknown enum {}", t))?;
+                format!("{}::{}", t, e.values.last().map(|v| v.0.as_str()).unwrap_or_default())
+            },
+            Kind::Table(t) => {
+                let table = self.schema.get_table(t).ok_or(format!("unknown table {}", t))?;
+                let fields = self.fields(table)?;
+                let mut args = vec![];
+                for f in self.new_params(table, &fields)? {
+                    args.push(self.sample_value(&f.kind, Some(f))?);
+                }
+                format!("{}::new({})", t, args.join(", "))
+            },
+            Kind::Bytes | Kind::ScalarVec(_) => "vec![1, 2, 3]".to_string(),
+            Kind::EnumVec(t) => {
+                let e = self.schema.get_enum(t).ok_or(format!("unknown enum {}", t))?;
+                let values: Vec<String> = e.values.iter().map(|v| format!("{}::{}", t, v.0)).collect();
+                format!("vec![{}]", values.join(", "))
+            },
+            Kind::TableVec(t) => {
+                let one = self.sample_value(&Kind::Table(t.clone()), None)?;
+                format!("vec![{}, {}]", one, one)
+            },
+            Kind::StrVec => "vec![String::from(\"a\"), String::from(\"b\")]".to_string(),
+        };
+        Ok(value)
+    }
+}
+
+// ***************************************************************************
+// NAMING
+// ***************************************************************************
+// NewImageEvent -> new_image_event
+fn snake_case(name: &str) -> String {
+    let mut s = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                s.push('_');
+            }
+            s.extend(c.to_lowercase());
+        } else {
+            s.push(c);
+        }
+    }
+    s
+}
+
+// NewImageEvent -> NEW_IMAGE_PREFIX
+fn prefix_name(name: &str) -> String {
+    let base = name.strip_suffix("Event").unwrap_or(name);
+    format!("{}_PREFIX", snake_case(base).to_uppercase())
+}
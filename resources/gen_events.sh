@@ -0,0 +1,49 @@
+#!/bin/bash
+# ***************************************************************************
+# gen_events.sh
+# ***************************************************************************
+# Generate the flatbuffers code for Rust (src/events_generated.rs) and Python
+# (src/python/ctevents/gen_events) from resources/events.fbs.
+#
+#   resources/gen_events.sh          regenerate the checked in code
+#   resources/gen_events.sh --check  exit with 1 if the checked in code differs
+#                                    from flatc's output
+#
+# The flatc executable can be set with the FLATC environment variable.
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+FLATC=${FLATC:-flatc}
+SCHEMA=resources/events.fbs
+RUST_FILE=src/events_generated.rs
+PYTHON_DIR=src/python/ctevents/gen_events
+
+OUT=$(mktemp -d)
+trap 'rm -rf "$OUT"' EXIT
+
+"$FLATC" --rust -o "$OUT/rust" "$SCHEMA"
+"$FLATC" --python -o "$OUT/python" "$SCHEMA"
+
+# Insert the lint header after flatc's first line.  unknown_lints keeps older
+# toolchains from warning about lints they don't have.
+{
+    head -n 1 "$OUT/rust/events_generated.rs"
+    echo "// ADDED BY HAND TO ELIMINATE NOISE"
+    echo "#![allow(unknown_lints, dead_code, unused_imports, mismatched_lifetime_syntaxes, clippy::all)]"
+    tail -n +2 "$OUT/rust/events_generated.rs"
+} > "$OUT/events_generated.rs"
+
+if [[ "${1:-}" == "--check" ]]; then
+    stale=0
+    diff -u "$RUST_FILE" "$OUT/events_generated.rs" || stale=1
+    diff -ru -x __pycache__ "$PYTHON_DIR" "$OUT/python/gen_events" || stale=1
+    if [[ $stale -ne 0 ]]; then
+        echo "The generated event code is stale, run resources/gen_events.sh and commit the result." >&2
+        exit 1
+    fi
+    echo "The generated event code is up to date."
+else
+    cp "$OUT/events_generated.rs" "$RUST_FILE"
+    rm -rf "$PYTHON_DIR"
+    cp -r "$OUT/python/gen_events" "$PYTHON_DIR"
+fi
@@ -0,0 +1,278 @@
+use std::any::Any;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::config::config::Config;
+use crate::config::errors::Errors;
+use crate::events;
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+/** The internal plugins that run action chains.  Every action belongs to one
+ * of these plugins.
+ */
+pub const ACTION_PLUGIN_NAMES: &[&str] = &[
+    "image_gen_plugin", "image_recv_plugin", "image_score_plugin", "image_store_plugin", "observer_plugin",
+];
+
+// ***************************************************************************
+// ACTIONS
+// ***************************************************************************
+/** An action that an internal plugin runs as part of its action chain.  The
+ * action's name is what's listed in the [plugins] internal_actions
+ * configuration and, by convention, starts with its plugin's name without
+ * "plugin".  Actions are made available by adding them to the action registry
+ * with register_action() before the plugins start.
+ */
+pub trait Action: Send + Sync {
+    /** The name listed in the internal_actions configuration. */
+    fn name(&self) -> &str;
+
+    /** The settings the action accepts in its [plugins.config.<name>] table. */
+    fn config_schema(&self) -> Vec<ConfigSetting> {vec![]}
+
+    /** The resources the action needs from the application. */
+    fn requirements(&self) -> ActionRequirements {ActionRequirements::default()}
+
+    /** Run the action on the plugin's input.  The config is the action's
+     * [plugins.config.<name>] table, which is empty when the table is missing.
+     * An error stops the plugin's action chain.
+     */
+    fn execute(&self, input: &ActionInput, config: &toml::Value) -> Result<ActionOutcome, Errors>;
+}
+
+/** A setting in an action's configuration table.  The kind is the TOML type
+ * of the setting's value, such as "string" or "integer".
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigSetting {
+    pub key: &'static str,
+    pub kind: &'static str,
+    pub description: &'static str,
+}
+
+/** The resources an action needs.  The application checks that the local
+ * image directory is usable at start up only when an action needs it.
+ */
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActionRequirements {
+    pub local_image_dir: bool,
+}
+
+/** The plugin running an action chain.  The built-in actions downcast the
+ * plugin to reach its state; other actions only need its name and the
+ * application configuration.
+ */
+pub trait ActionContext {
+    /** The internal plugin's file name, such as image_recv_plugin. */
+    fn get_plugin_name(&self) -> &str;
+
+    /** The application configuration the plugin was started with. */
+    fn get_config(&self) -> &Config;
+
+    /** The plugin itself. */
+    fn as_any(&self) -> &dyn Any;
+}
+
+/** The input an internal plugin passes to each action in its chain.  The
+ * event is the complete event that triggered the chain, its two prefix bytes
+ * followed by the flatbuffer, and is empty when the plugin runs its chain
+ * without an event.  The image is the resolved image data of a NewImageEvent,
+ * whether it was embedded in or referenced by the event.
+ */
+pub struct ActionInput<'a> {
+    pub event: &'a [u8],
+    pub image: Option<&'a [u8]>,
+    pub context: &'a dyn ActionContext,
+}
+
+impl ActionInput<'_> {
+    // ---------------------------------------------------------------------------
+    // get_plugin_name:
+    // ---------------------------------------------------------------------------
+    /** The internal plugin that supplies this input. */
+    pub fn get_plugin_name(&self) -> &str {
+        self.context.get_plugin_name()
+    }
+
+    // ---------------------------------------------------------------------------
+    // get_event_name:
+    // ---------------------------------------------------------------------------
+    /** The name of the event's type, such as NewImageEvent, or None when there's
+     * no event or its prefix isn't a registered event type.
+     */
+    pub fn get_event_name(&self) -> Option<&'static str> {
+        if self.event.len() < events::EVENT_PREFIX_LEN {
+            return None;
+        }
+        events::get_event_info_by_prefix([self.event[0], self.event[1]]).map(|info| info.name)
+    }
+
+    // ---------------------------------------------------------------------------
+    // unexpected:
+    // ---------------------------------------------------------------------------
+    /** The error an action returns when it's run by a plugin it doesn't
+     * belong to.
+     */
+    pub fn unexpected(&self, action: &str) -> Errors {
+        Errors::ActionInputMismatch(action.to_string(), self.get_plugin_name().to_string())
+    }
+}
+
+/** The store decision of an image store action.  ErrorOut is only an output
+ * that fails the action.
+ */
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum StoreAction {
+    Delete,
+    ErrorOut,
+    Noop,
+    ReduceSave,
+    Save,
+}
+
+/** What an action did.  Image store actions report the store action taken
+ * and, when the image was kept, where the action stored it.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActionOutcome {
+    Done,
+    Stored(StoreAction, Option<String>),
+}
+
+// ***************************************************************************
+// ACTION REGISTRY
+// ***************************************************************************
+// The registered actions in the order they were registered.
+static ACTION_REGISTRY: OnceLock<RwLock<Vec<Arc<dyn Action>>>> = OnceLock::new();
+
+// ---------------------------------------------------------------------------
+// registry:
+// ---------------------------------------------------------------------------
+fn registry() -> &'static RwLock<Vec<Arc<dyn Action>>> {
+    ACTION_REGISTRY.get_or_init(|| RwLock::new(vec![]))
+}
+
+// ---------------------------------------------------------------------------
+// register_action:
+// ---------------------------------------------------------------------------
+/** Add an action to the registry so that it can be listed in the
+ * internal_actions configuration.  Actions must be registered before the
+ * plugins start.  The action's name must follow the naming convention of an
+ * internal plugin that runs action chains and not already be registered.
+ */
+pub fn register_action(action: Arc<dyn Action>) -> Result<(), Errors> {
+    let name = action.name().to_string();
+    if get_action_plugin(&name).is_none() {
+        return Result::Err(Errors::ActionRegisterError(name, "the name doesn't start with the name of an internal plugin that runs actions".to_string()));
+    }
+    let mut actions = registry().write().unwrap_or_else(|e| e.into_inner());
+    if actions.iter().any(|a| a.name() == name) {
+        return Result::Err(Errors::ActionRegisterError(name, "an action with this name is already registered".to_string()));
+    }
+    actions.push(action);
+    Result::Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// get_action:
+// ---------------------------------------------------------------------------
+/** The registered action with the given name. */
+pub fn get_action(name: &str) -> Option<Arc<dyn Action>> {
+    registry().read().unwrap_or_else(|e| e.into_inner()).iter().find(|a| a.name() == name).cloned()
+}
+
+// ---------------------------------------------------------------------------
+// get_action_names:
+// ---------------------------------------------------------------------------
+/** The names of the registered actions in the order they were registered. */
+pub fn get_action_names() -> Vec<String> {
+    registry().read().unwrap_or_else(|e| e.into_inner()).iter().map(|a| a.name().to_string()).collect()
+}
+
+// ---------------------------------------------------------------------------
+// get_action_plugin:
+// ---------------------------------------------------------------------------
+/** The internal plugin an action belongs to by the naming convention that an
+ * action starts with its plugin's name without "plugin".  Only plugins that
+ * run action chains have actions, so names such as oracle_x_action belong to
+ * no plugin.
+ */
+pub fn get_action_plugin(action: &str) -> Option<&'static str> {
+    ACTION_PLUGIN_NAMES.iter().copied().find(|p| action.starts_with(p.trim_end_matches("plugin")))
+}
+
+// ---------------------------------------------------------------------------
+// check_action_config:
+// ---------------------------------------------------------------------------
+/** Check an action's configuration table against the action's schema and
+ * return a message for each setting that isn't in the schema or has the
+ * wrong type.
+ */
+pub fn check_action_config(action: &dyn Action, config: &toml::Value) -> Vec<String> {
+    let table = match config.as_table() {
+        Some(t) => t,
+        None => return vec![format!("expected a table, found {}", config.type_str())],
+    };
+    let schema = action.config_schema();
+    let mut problems = vec![];
+    for (key, value) in table {
+        match schema.iter().find(|s| s.key == key) {
+            None => problems.push(format!("{} is not a setting of {}", key, action.name())),
+            Some(s) if s.kind != value.type_str() =>
+                problems.push(format!("{} must be a {}, found {}", key, s.kind, value.type_str())),
+            Some(_) => (),
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: action_registry.rs");
+    }
+
+    // An action that does nothing.
+    struct TestAction(&'static str);
+    impl Action for TestAction {
+        fn name(&self) -> &str {self.0}
+        fn config_schema(&self) -> Vec<ConfigSetting> {
+            vec![ConfigSetting {key: "fail", kind: "boolean", description: "Fail every time"}]
+        }
+        fn execute(&self, _input: &ActionInput, _config: &toml::Value) -> Result<ActionOutcome, Errors> {
+            Result::Ok(ActionOutcome::Done)
+        }
+    }
+
+    #[test]
+    fn action_plugins() {
+        // Actions belong to the plugins that run action chains.
+        assert_eq!(get_action_plugin("image_store_file_action"), Some("image_store_plugin"));
+        assert_eq!(get_action_plugin("observer_journal_action"), Some("observer_plugin"));
+        for name in ["oracle_x_action", "mqtt_publisher_x_action", "detection_reporter_x_action",
+                     "power_monitor_x_action", "thumbnail_action"] {
+            assert_eq!(get_action_plugin(name), None, "{}", name);
+        }
+
+        // Actions are registered once and only for plugins that run them.
+        register_action(Arc::new(TestAction("image_gen_registry_test_action"))).unwrap();
+        assert!(get_action("image_gen_registry_test_action").is_some());
+        assert!(get_action_names().iter().any(|n| n == "image_gen_registry_test_action"));
+        assert!(matches!(register_action(Arc::new(TestAction("image_gen_registry_test_action"))),
+                         Err(Errors::ActionRegisterError(..))));
+        assert!(matches!(register_action(Arc::new(TestAction("oracle_registry_test_action"))),
+                         Err(Errors::ActionRegisterError(..))));
+
+        // Tables are checked against the schema.
+        let action = TestAction("image_gen_schema_test_action");
+        let table: toml::Value = toml::from_str("fail = 1\ncolor = \"red\"").unwrap();
+        let mut problems = check_action_config(&action, &table);
+        problems.sort();
+        assert_eq!(problems, ["color is not a setting of image_gen_schema_test_action", "fail must be a boolean, found integer"]);
+        assert!(check_action_config(&action, &toml::from_str("fail = true").unwrap()).is_empty());
+    }
+}
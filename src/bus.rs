@@ -0,0 +1,354 @@
+use std::fs;
+use std::io::Write;
+use std::{thread, time};
+
+use clap::Args;
+use serde_json::{Map, Value};
+
+use crate::config::errors::Errors;
+use crate::events::{self, TraceIds, EVENT_PREFIX_LEN};
+use crate::image_ref;
+use crate::journal::{self, JournalEntry};
+use crate::traps_utils::{self, timestamp_str};
+
+use log::{info, warn};
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+// The default endpoints of a locally running application's publish_port, where
+// the event engine receives events, and subscribe_port, where it sends them.
+const DEFAULT_SEND_ENDPOINT: &str = "tcp://localhost:5559";
+const DEFAULT_TAIL_ENDPOINT: &str = "tcp://localhost:5560";
+
+// The source_plugin recorded in events sent from the command line.
+const SEND_EVENT_SOURCE: &str = "camera-traps send-event";
+
+// Time given to zmq to establish the publisher's connection before sending.
+const SEND_CONNECT_WAIT_MILLIS: u64 = 1000;
+
+// ***************************************************************************
+// SEND EVENT
+// ***************************************************************************
+/** Inject a single event into a running camera-traps application. */
+#[derive(Args, Debug)]
+pub struct SendEventParms {
+    /// The event type, for example ImageScoredEvent
+    pub event_name: String,
+    /// The event's fields as a JSON object; created and the tracing fields are filled in when missing
+    pub json: Option<String>,
+    /// Read the event's JSON fields from a file instead
+    #[arg(long, value_name = "FILE", conflicts_with = "json")]
+    pub file: Option<String>,
+    /// Reference this image file from a NewImageEvent's image_ref field instead of embedding the image
+    #[arg(long, value_name = "IMAGE_FILE")]
+    pub image_ref: Option<String>,
+    /// The zmq endpoint of the application's publish_port
+    #[arg(long, default_value = DEFAULT_SEND_ENDPOINT)]
+    pub endpoint: String,
+}
+
+// ---------------------------------------------------------------------------
+// send_event:
+// ---------------------------------------------------------------------------
+/** Create the event described by the parameters and publish it to the
+ * application's event engine.
+ */
+pub fn send_event(parms: &SendEventParms) -> Result<(), Errors> {
+    // Get the event's fields.
+    let text = match &parms.file {
+        Some(file) => {
+            let file = traps_utils::get_absolute_path(file);
+            fs::read_to_string(&file).map_err(|e| Errors::EventFromJsonError(parms.event_name.clone(),
+                                                                             format!("{}: {}", file, e)))?
+        },
+        None => parms.json.clone().unwrap_or_else(|| "{}".to_string()),
+    };
+    let mut json: Value = serde_json::from_str(&text)
+        .map_err(|e| Errors::EventFromJsonError(parms.event_name.clone(), e.to_string()))?;
+    if let Some(image_file) = &parms.image_ref {
+        add_image_ref(&parms.event_name, &mut json, image_file)?;
+    }
+    let bytes = build_event_bytes(&parms.event_name, json)?;
+
+    // Connect a publisher to the application and send the event.
+    let socket = connect_publisher(&parms.endpoint)?;
+    if let Err(e) = socket.send(bytes, 0) {
+        return Result::Err(Errors::SocketSendError("send-event".to_string(), parms.event_name.clone(), e.to_string()));
+    }
+
+    info!("{}", Errors::EventSent(parms.event_name.clone(), parms.endpoint.clone()));
+    Result::Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// connect_publisher:
+// ---------------------------------------------------------------------------
+/** Connect a publisher socket to the application's publish_port endpoint and
+ * give zmq time to establish the connection so that the first event isn't
+ * dropped.
+ */
+pub fn connect_publisher(endpoint: &str) -> Result<zmq::Socket, Errors> {
+    let socket = open_publisher(endpoint)?;
+    thread::sleep(time::Duration::from_millis(SEND_CONNECT_WAIT_MILLIS));
+    Result::Ok(socket)
+}
+
+// ---------------------------------------------------------------------------
+// open_publisher:
+// ---------------------------------------------------------------------------
+/** Create a publisher socket connected to the application's publish_port
+ * endpoint without waiting for the connection.  Long-lived publishers use this
+ * since zmq establishes the connection in the background well before their
+ * first event.
+ */
+pub fn open_publisher(endpoint: &str) -> Result<zmq::Socket, Errors> {
+    let context = zmq::Context::new();
+    let socket = match context.socket(zmq::PUB) {
+        Ok(s) => s,
+        Err(e) => return Result::Err(Errors::SocketConnectError(endpoint.to_string(), e.to_string())),
+    };
+    if let Err(e) = socket.connect(endpoint) {
+        return Result::Err(Errors::SocketConnectError(endpoint.to_string(), e.to_string()));
+    }
+    Result::Ok(socket)
+}
+
+// ---------------------------------------------------------------------------
+// add_image_ref:
+// ---------------------------------------------------------------------------
+/** Set the image_ref field of a NewImageEvent's JSON fields to a FilePath
+ * reference to the image file.  The receiving application only resolves the
+ * reference if the file is in its image_ref_dir.
+ */
+pub fn add_image_ref(event_name: &str, json: &mut Value, image_file: &str) -> Result<(), Errors> {
+    if event_name != "NewImageEvent" {
+        return Result::Err(Errors::EventFromJsonError(event_name.to_string(),
+                               "only a NewImageEvent can reference an image".to_string()));
+    }
+    let image_file = traps_utils::get_absolute_path(image_file);
+    let image_ref = image_ref::file_reference(&image_file)?;
+    let image_ref = serde_json::to_value(&image_ref)
+        .map_err(|e| Errors::EventFromJsonError(event_name.to_string(), e.to_string()))?;
+    match json {
+        Value::Object(fields) => {
+            fields.insert("image_ref".to_string(), image_ref);
+            Result::Ok(())
+        },
+        other => Result::Err(Errors::EventFromJsonError(event_name.to_string(),
+                                 format!("expected a JSON object, found {}", other))),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// build_event_bytes:
+// ---------------------------------------------------------------------------
+/** Convert an event's fields as JSON into wire format bytes.  Fields that are
+ * required by every event but tedious to write by hand are filled in when they
+ * are missing: created is set to the current time and the event starts a new
+ * trace chain whose source is the command line.
+ */
+pub fn build_event_bytes(event_name: &str, json: Value) -> Result<Vec<u8>, Errors> {
+    let info = events::get_event_info_by_name(event_name)
+        .ok_or_else(|| Errors::EventNameUnknown(event_name.to_string()))?;
+    let mut fields: Map<String, Value> = match json {
+        Value::Object(m) => m,
+        other => return Result::Err(Errors::EventFromJsonError(event_name.to_string(),
+                                        format!("expected a JSON object, found {}", other))),
+    };
+
+    // Fill in the defaults.
+    let ids = TraceIds::caused_by(None, SEND_EVENT_SOURCE);
+    let defaults = [
+        ("created", Some(timestamp_str())),
+        ("event_uuid", ids.event_uuid.map(|u| u.hyphenated().to_string())),
+        ("correlation_id", ids.correlation_id.map(|u| u.hyphenated().to_string())),
+        ("source_plugin", ids.source_plugin),
+    ];
+    for (key, value) in defaults {
+        if let Some(v) = value {
+            fields.entry(key).or_insert(Value::String(v));
+        }
+    }
+
+    (info.json_to_bytes)(Value::Object(fields))
+}
+
+// ***************************************************************************
+// TAIL
+// ***************************************************************************
+/** Print the events flowing through a running camera-traps application. */
+#[derive(Args, Debug)]
+pub struct TailParms {
+    /// The zmq endpoint of the application's subscribe_port
+    #[arg(long, default_value = DEFAULT_TAIL_ENDPOINT)]
+    pub endpoint: String,
+    /// Only print events of this type; can be repeated
+    #[arg(long = "event", value_name = "EVENT_NAME")]
+    pub events: Vec<String>,
+    /// Print each event as a journal entry line, which the replay command accepts
+    #[arg(long)]
+    pub json: bool,
+    /// Exit after printing this many events
+    #[arg(long, value_name = "N")]
+    pub count: Option<usize>,
+}
+
+// ---------------------------------------------------------------------------
+// tail:
+// ---------------------------------------------------------------------------
+/** Subscribe to the application's events and write each one to out until the
+ * requested number of events has been printed or the process is interrupted.
+ * Events that can't be decoded are reported and skipped.
+ */
+pub fn tail(parms: &TailParms, out: &mut dyn Write) -> Result<(), Errors> {
+    // Resolve the event filters before connecting.
+    let mut prefixes = vec![];
+    for name in &parms.events {
+        match events::get_event_info_by_name(name) {
+            Some(info) => prefixes.push(info.prefix.to_vec()),
+            None => return Result::Err(Errors::EventNameUnknown(name.clone())),
+        }
+    }
+    if prefixes.is_empty() {
+        prefixes.push(vec![]);
+    }
+
+    // Connect a subscriber to the application.
+    let context = zmq::Context::new();
+    let socket = match context.socket(zmq::SUB) {
+        Ok(s) => s,
+        Err(e) => return Result::Err(Errors::SocketConnectError(parms.endpoint.clone(), e.to_string())),
+    };
+    if let Err(e) = socket.connect(&parms.endpoint) {
+        return Result::Err(Errors::SocketConnectError(parms.endpoint.clone(), e.to_string()));
+    }
+    for prefix in &prefixes {
+        if let Err(e) = socket.set_subscribe(prefix) {
+            return Result::Err(Errors::SocketConnectError(parms.endpoint.clone(), e.to_string()));
+        }
+    }
+
+    // Print events as they arrive.
+    let mut printed = 0;
+    while parms.count.map(|c| printed < c).unwrap_or(true) {
+        let bytes = match socket.recv_bytes(0) {
+            Ok(b) => b,
+            Err(e) => return Result::Err(Errors::SocketRecvError("tail".to_string(), e.to_string())),
+        };
+        match format_event(&bytes, parms.json) {
+            Ok(s) => {
+                out.write_all(s.as_bytes())?;
+                out.flush()?;
+                printed += 1;
+            },
+            Err(e) => warn!("{}", e),
+        }
+    }
+
+    Result::Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// format_event:
+// ---------------------------------------------------------------------------
+/** Decode wire format bytes into a printable event.  The pretty format is a
+ * header line with the receipt time and event name followed by the event's
+ * fields as indented JSON; the json format is a single journal entry line.
+ */
+pub fn format_event(bytes: &[u8], json: bool) -> Result<String, Errors> {
+    if bytes.len() < EVENT_PREFIX_LEN {
+        return Result::Err(Errors::EventInvalidLen("tail".to_string(), bytes.len()));
+    }
+    let prefix = [bytes[0], bytes[1]];
+    let gen_event = traps_utils::bytes_to_gen_event(&bytes[EVENT_PREFIX_LEN..])?;
+    if json {
+        let entry = JournalEntry::new(prefix, gen_event, bytes)?;
+        let line = serde_json::to_string(&entry)
+            .map_err(|e| Errors::EventToJsonError("tail".to_string(), entry.event_name.clone(), e.to_string()))?;
+        return Result::Ok(line + "\n");
+    }
+
+    let (event_name, fields) = journal::gen_event_to_json(prefix, gen_event)?;
+    let pretty = serde_json::to_string_pretty(&fields)
+        .map_err(|e| Errors::EventToJsonError("tail".to_string(), event_name.to_string(), e.to_string()))?;
+    Result::Ok(format!("{} {}\n{}\n", timestamp_str(), event_name, pretty))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: bus.rs");
+    }
+
+    #[test]
+    fn build_events() {
+        let uuid = "3a0e3a8c-9f5e-4a57-9d0c-1f2b3c4d5e6f";
+        let fields = json!({"image_uuid": uuid, "image_format": "jpg",
+                            "scores": [{"label": "deer", "probability": 0.9}]});
+        let bytes = build_event_bytes("ImageScoredEvent", fields).unwrap();
+        assert_eq!(bytes[..EVENT_PREFIX_LEN], events::IMAGE_SCORED_PREFIX);
+
+        // The decoded event has the given fields and the defaults.
+        let gen_event = traps_utils::bytes_to_gen_event(&bytes[EVENT_PREFIX_LEN..]).unwrap();
+        let (name, decoded) = journal::gen_event_to_json(events::IMAGE_SCORED_PREFIX, gen_event).unwrap();
+        assert_eq!(name, "ImageScoredEvent");
+        assert_eq!(decoded["image_uuid"], uuid);
+        assert_eq!(decoded["scores"][0]["label"], "deer");
+        assert_eq!(decoded["source_plugin"], SEND_EVENT_SOURCE);
+        assert_eq!(decoded["event_uuid"], decoded["correlation_id"]);
+        assert!(decoded["created"].is_string());
+
+        // The printed forms.
+        let pretty = format_event(&bytes, false).unwrap();
+        assert!(pretty.lines().next().unwrap().ends_with(" ImageScoredEvent"));
+        assert!(pretty.contains("\"label\": \"deer\""));
+        let line = format_event(&bytes, true).unwrap();
+        let entry: JournalEntry = serde_json::from_str(&line).unwrap();
+        assert_eq!(entry.get_raw_bytes().unwrap(), bytes);
+
+        // Given fields are not replaced.
+        let fields = json!({"image_uuid": uuid, "image_format": "jpg", "scores": [], "source_plugin": "me"});
+        let bytes = build_event_bytes("ImageScoredEvent", fields).unwrap();
+        let gen_event = traps_utils::bytes_to_gen_event(&bytes[EVENT_PREFIX_LEN..]).unwrap();
+        let (_, decoded) = journal::gen_event_to_json(events::IMAGE_SCORED_PREFIX, gen_event).unwrap();
+        assert_eq!(decoded["source_plugin"], "me");
+    }
+
+    #[test]
+    fn build_image_ref_event() {
+        let dir = std::env::temp_dir().join(format!("bus_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let image_file = dir.join("image.jpg").to_string_lossy().to_string();
+        fs::write(&image_file, [1u8, 2, 3]).unwrap();
+
+        // The reference is added to the event's fields.
+        let uuid = "3a0e3a8c-9f5e-4a57-9d0c-1f2b3c4d5e6f";
+        let mut fields = json!({"image_uuid": uuid, "image_format": "jpg", "image": [], "image_compression": "Uncompressed"});
+        add_image_ref("NewImageEvent", &mut fields, &image_file).unwrap();
+        let bytes = build_event_bytes("NewImageEvent", fields).unwrap();
+        let gen_event = traps_utils::bytes_to_gen_event(&bytes[EVENT_PREFIX_LEN..]).unwrap();
+        let (_, decoded) = journal::gen_event_to_json(events::NEW_IMAGE_PREFIX, gen_event).unwrap();
+        assert_eq!(decoded["image_ref"]["location"], image_file.as_str());
+        assert_eq!(decoded["image_ref"]["size"], 3);
+        assert_eq!(decoded["image_ref"]["checksum"], image_ref::checksum(&[1, 2, 3]).as_str());
+
+        // Only NewImageEvents reference images.
+        assert!(add_image_ref("ImageScoredEvent", &mut json!({}), &image_file).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn build_event_errors() {
+        assert!(matches!(build_event_bytes("NoSuchEvent", json!({})), Err(Errors::EventNameUnknown(_))));
+        assert!(matches!(build_event_bytes("ImageScoredEvent", json!([])), Err(Errors::EventFromJsonError(..))));
+        assert!(matches!(build_event_bytes("ImageScoredEvent", json!({"image_format": "jpg"})),
+                         Err(Errors::EventFromJsonError(..))));
+        assert!(format_event(&[1], false).is_err());
+    }
+}
@@ -0,0 +1,258 @@
+use clap::{Parser, Subcommand};
+use log::LevelFilter;
+
+use crate::bus::{SendEventParms, TailParms};
+use crate::config::config::Config;
+use crate::dead_letter::DeadLetterParms;
+use crate::evaluation::EvaluateParms;
+use crate::events::EVENT_REGISTRY;
+use crate::journal::ReplayParms;
+use crate::plugins::INTERNAL_PLUGIN_NAMES;
+use crate::plugins::actions;
+use crate::sweep::SweepParms;
+use crate::validate::ValidateParms;
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+// Secrets that print-config does not show.
+const REDACTED_KEYS: &[&str] = &["password"];
+const REDACTED_VALUE: &str = "********";
+
+// ***************************************************************************
+// COMMAND LINE
+// ***************************************************************************
+/** The camera-traps command line.  Without a subcommand the application runs
+ * using the optional configuration file, as it always has.
+ */
+#[derive(Parser, Debug)]
+#[command(name = "camera-traps", version, about = "The camera-traps event engine and its tools",
+          args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    /// The log4rs configuration file [default: $TRAPS_LOG4RS_CONFIG_FILE or resources/log4rs.yml]
+    #[arg(long, global = true, value_name = "FILE")]
+    pub log_config: Option<String>,
+    /// Override the root log level of the log4rs configuration (off, error, warn, info, debug or trace)
+    #[arg(long, global = true, value_name = "LEVEL", value_parser = parse_log_level)]
+    pub log_level: Option<LevelFilter>,
+    /// Override a configuration setting, such as plugins.external[0].external_port=6011; can be repeated
+    #[arg(long = "set", global = true, value_name = "KEY=VALUE")]
+    pub settings: Vec<String>,
+    /// The application configuration file, when no subcommand is given
+    #[arg(value_name = "CONFIG_FILE")]
+    pub config_file: Option<String>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/** The subcommands.  Each tool's parameters are defined next to the code
+ * that uses them.
+ */
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Run the application
+    Run {
+        /// The application configuration file [default: $TRAPS_CONFIG_FILE or ~/traps.toml]
+        #[arg(value_name = "CONFIG_FILE")]
+        config_file: Option<String>,
+    },
+    /// Report every problem in a configuration without running the application
+    Validate(ValidateParms),
+    /// Print the effective configuration, after applying the environment and command line settings, as TOML
+    PrintConfig {
+        /// The application configuration file [default: $TRAPS_CONFIG_FILE or ~/traps.toml]
+        #[arg(value_name = "CONFIG_FILE")]
+        config_file: Option<String>,
+    },
+    /// List the event types with their zmq prefixes
+    ListEvents,
+    /// List the internal plugins with their actions
+    ListActions,
+    /// Send an event described in JSON to a running application
+    SendEvent(SendEventParms),
+    /// Print the events flowing through a running application
+    Tail(TailParms),
+    /// Republish the events in an observer journal to a running application
+    Replay(ReplayParms),
+    /// List the payloads that plugins rejected
+    DeadLetters(DeadLetterParms),
+    /// Report a simulation run's image store decisions against the ground truth
+    Evaluate(EvaluateParms),
+    /// Replay a simulation run's scores through many image store thresholds
+    Sweep(SweepParms),
+}
+
+// ***************************************************************************
+// PUBLIC FUNCTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// format_event_list:
+// ---------------------------------------------------------------------------
+/** One line per event type with its name and zmq prefix bytes. */
+pub fn format_event_list() -> String {
+    let width = EVENT_REGISTRY.iter().map(|info| info.name.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    for info in EVENT_REGISTRY {
+        out.push_str(&format!("{:width$}  0x{:02x} 0x{:02x}\n", info.name, info.prefix[0], info.prefix[1]));
+    }
+    out
+}
+
+// ---------------------------------------------------------------------------
+// format_action_list:
+// ---------------------------------------------------------------------------
+/** Each internal plugin followed by its registered actions, their
+ * requirements and the settings of their [plugins.config.<name>] tables.  A
+ * plugin's no-op action is the one it uses when no action is configured.
+ */
+pub fn format_action_list() -> String {
+    let mut out = String::new();
+    for plugin in INTERNAL_PLUGIN_NAMES {
+        out.push_str(plugin);
+        out.push('\n');
+        let plugin_actions: Vec<String> = actions::get_action_names().into_iter()
+            .filter(|a| actions::get_action_plugin(a) == Some(*plugin)).collect();
+        if plugin_actions.is_empty() {
+            out.push_str("    (no actions)\n");
+        }
+        for name in plugin_actions {
+            let action = match actions::get_action(&name) {
+                Some(a) => a,
+                None => continue,
+            };
+            let default = if name.ends_with("_noop_action") {" (default)"} else {""};
+            let local = if action.requirements().local_image_dir {" (needs local image dir)"} else {""};
+            out.push_str(&format!("    {}{}{}\n", name, default, local));
+            for setting in action.config_schema() {
+                out.push_str(&format!("        {} ({}): {}\n", setting.key, setting.kind, setting.description));
+            }
+        }
+    }
+    out
+}
+
+// ---------------------------------------------------------------------------
+// format_config:
+// ---------------------------------------------------------------------------
+/** The configuration as TOML with its secrets redacted. */
+pub fn format_config(config: &Config) -> Result<String, String> {
+    let mut value = toml::Value::try_from(config).map_err(|e| e.to_string())?;
+    redact(&mut value);
+    toml::to_string(&value).map_err(|e| e.to_string())
+}
+
+// ***************************************************************************
+// PRIVATE FUNCTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// parse_log_level:
+// ---------------------------------------------------------------------------
+fn parse_log_level(s: &str) -> Result<LevelFilter, String> {
+    s.parse::<LevelFilter>().map_err(|_| format!("invalid log level {}", s))
+}
+
+// ---------------------------------------------------------------------------
+// redact:
+// ---------------------------------------------------------------------------
+fn redact(value: &mut toml::Value) {
+    match value {
+        toml::Value::Table(t) => {
+            for (key, v) in t.iter_mut() {
+                if REDACTED_KEYS.contains(&key.as_str()) {
+                    *v = toml::Value::String(REDACTED_VALUE.to_string());
+                } else {
+                    redact(v);
+                }
+            }
+        },
+        toml::Value::Array(a) => a.iter_mut().for_each(redact),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+    use crate::config::config::MqttPublisherConfig;
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: cli.rs");
+    }
+
+    #[test]
+    fn parse_command_lines() {
+        Cli::command().debug_assert();
+
+        // The original invocation still runs the application.
+        let cli = Cli::try_parse_from(["camera-traps", "traps.toml"]).unwrap();
+        assert_eq!(cli.config_file.as_deref(), Some("traps.toml"));
+        assert!(cli.command.is_none());
+
+        let cli = Cli::try_parse_from(["camera-traps", "run", "--log-level", "debug", "traps.toml"]).unwrap();
+        assert_eq!(cli.log_level, Some(LevelFilter::Debug));
+        assert!(matches!(cli.command, Some(Command::Run {config_file: Some(f)}) if f == "traps.toml"));
+        assert!(Cli::try_parse_from(["camera-traps", "--log-level", "loud"]).is_err());
+
+        // The tools' arguments are checked by clap.
+        let cli = Cli::try_parse_from(["camera-traps", "replay", "events.jsonl", "--speed", "0"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Replay(parms)) if parms.speed == 0.0 && parms.journal_file.ends_with("/events.jsonl")));
+        assert!(Cli::try_parse_from(["camera-traps", "replay", "events.jsonl", "--bogus"]).is_err());
+
+        // Settings after a tool's subcommand are still global.
+        let cli = Cli::try_parse_from(["camera-traps", "validate", "traps.toml", "--set", "publish_port=6559"]).unwrap();
+        assert_eq!(cli.settings, ["publish_port=6559"]);
+        assert!(matches!(cli.command, Some(Command::Validate(parms)) if parms.config_file.ends_with("/traps.toml")));
+
+        let cli = Cli::try_parse_from(["camera-traps", "sweep", "--ground-truth", "gt.csv", "--mapping", "m.json",
+                                       "--scores", "images", "--save", "0.5", "--format", "md"]).unwrap();
+        match cli.command {
+            Some(Command::Sweep(parms)) => {
+                assert_eq!(parms.save.thresholds, [0.5]);
+                assert!(parms.inputs.is_markdown());
+            },
+            other => panic!("{:?}", other),
+        }
+        assert!(Cli::try_parse_from(["camera-traps", "sweep", "--ground-truth", "gt.csv", "--mapping", "m.json",
+                                     "--scores", "images", "--reduce-ratio", "2"]).is_err());
+
+        let cli = Cli::try_parse_from(["camera-traps", "tail", "--event", "ImageScoredEvent", "--count", "2"]).unwrap();
+        match cli.command {
+            Some(Command::Tail(parms)) => {
+                assert_eq!(parms.events, ["ImageScoredEvent"]);
+                assert_eq!(parms.count, Some(2));
+            },
+            other => panic!("{:?}", other),
+        }
+        assert!(Cli::try_parse_from(["camera-traps", "send-event", "PluginTerminateEvent", "{}", "--file", "x"]).is_err());
+    }
+
+    #[test]
+    fn lists() {
+        actions::register_builtin_actions();
+        let events = format_event_list();
+        assert_eq!(events.lines().count(), EVENT_REGISTRY.len());
+        assert!(events.lines().any(|l| l.starts_with("ImageScoredEvent ") && l.ends_with("0x03 0x00")));
+
+        let actions = format_action_list();
+        assert!(actions.contains("image_store_plugin\n    image_store_noop_action (default)\n    image_store_file_action (needs local image dir)\n"));
+        assert!(actions.contains("    observer_journal_action\n        journal_dir (string): "));
+        assert!(actions.contains("oracle_plugin\n    (no actions)\n"));
+    }
+
+    #[test]
+    fn print_config() {
+        let mut config = Config::default();
+        config.plugins.internal = Some(vec!["image_recv_plugin".to_string()]);
+        let mqtt: toml::Value = toml::from_str("password = \"secret\"").unwrap();
+        config.plugins.config = Some([("mqtt_publisher_plugin".to_string(), mqtt)].into_iter().collect());
+        let text = format_config(&config).unwrap();
+        assert!(!text.contains("secret"));
+        let parsed: Config = toml::from_str(&text).unwrap();
+        assert_eq!(parsed.publish_port, 5559);
+        assert_eq!(parsed.plugins.internal, config.plugins.internal);
+        let mqtt: MqttPublisherConfig = parsed.get_plugin_config("mqtt_publisher_plugin").unwrap();
+        assert_eq!(mqtt.password.as_deref(), Some(REDACTED_VALUE));
+    }
+}
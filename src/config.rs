@@ -1,2 +1,4 @@
+#[allow(clippy::module_inception)]
 pub mod config;
-pub mod errors;
\ No newline at end of file
+pub mod errors;
+pub mod layers;
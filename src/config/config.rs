@@ -1,14 +1,27 @@
-use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
 
+use crate::config::errors::Errors;
 
-#[derive(Debug, Deserialize)]
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Config {
     pub title: Option<String>,
+    /// The root log level, such as info or debug, which replaces the level of
+    /// the log4rs configuration and can be changed while running.
+    pub log_level: Option<String>,
     pub plugins: Plugins,
     pub publish_port: u16,
     pub subscribe_port: u16,
     pub images_output_dir: String,
     pub image_file_prefix: Option<String>,
+    pub image_content_dir: Option<String>,
+    pub image_ref_dir: Option<String>,
+    pub plugin_monitor: Option<PluginMonitorConfig>,
+    pub shutdown: Option<ShutdownConfig>,
+    pub completion: Option<CompletionConfig>,
+    pub dead_letter: Option<DeadLetterConfig>,
 }
 
 impl Config {
@@ -16,29 +29,55 @@ impl Config {
     fn new() -> Self {
         Config::default()
     }
+
+    // ---------------------------------------------------------------------------
+    // get_plugin_config:
+    // ---------------------------------------------------------------------------
+    /** Deserialize the [plugins.config.<name>] table of an internal plugin or
+     * action into the plugin's settings type.  When the table is missing, the
+     * settings type's defaults are used.
+     */
+    pub fn get_plugin_config<T>(&self, name: &str) -> Result<T, Errors>
+    where T: DeserializeOwned + Default {
+        match self.plugins.config.as_ref().and_then(|c| c.get(name)) {
+            Some(value) => value.clone().try_into()
+                                .map_err(|e| Errors::PluginConfigInvalid(name.to_string(), e.to_string())),
+            None => Result::Ok(T::default()),
+        }
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             title: Default::default(),
+            log_level: None,
             plugins: Default::default(),
             publish_port: 5559,
             subscribe_port: 5560,
             images_output_dir: "~/camera-traps/images".to_string(),
             image_file_prefix: None,
+            image_content_dir: None,
+            image_ref_dir: None,
+            plugin_monitor: None,
+            shutdown: None,
+            completion: None,
+            dead_letter: None,
         }
     }
 }
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
 pub struct Plugins {
     pub internal: Option<Vec<String>>,
     pub internal_actions: Option<Vec<String>>,
     pub external: Option<Vec<ExtPluginConfig>>,
+    /// The [plugins.config.<name>] tables keyed by internal plugin or action
+    /// name.  Each plugin or action deserializes its own table.
+    pub config: Option<BTreeMap<String, toml::Value>>,
 }
 
-#[derive(Debug, Deserialize, Default, Clone)]
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
 pub struct ExtPluginConfig {
     pub plugin_name: String,
     pub id: String,
@@ -46,9 +85,142 @@ pub struct ExtPluginConfig {
     pub subscriptions: Vec<String>,
 }
 
+/// Settings for the event journal written by the observer_journal_action.
+/// Unassigned fields take the journal module's default values.
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct JournalConfig {
+    pub journal_dir: Option<String>,
+    pub max_file_bytes: Option<u64>,
+    pub max_files: Option<u32>,
+}
+
+/// Settings for the plugin monitor that tracks plugin liveness.  Unassigned
+/// fields take the plugin monitor's default values.
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct PluginMonitorConfig {
+    pub start_timeout_secs: Option<u64>,
+    pub status_interval_secs: Option<u64>,
+    pub status_file: Option<String>,
+    pub terminate_wait_secs: Option<u64>,
+}
+
+/// Settings for the graceful shutdown started by SIGINT or SIGTERM.
+/// Unassigned fields take the shutdown module's default values.
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct ShutdownConfig {
+    pub drain_timeout_secs: Option<u64>,
+}
+
+/// Settings for running to completion, in which the application shuts down
+/// once the generator plugin announces it's finished and every image has
+/// reached a terminal event, or once a cap is reached.  Unassigned fields take
+/// the shutdown module's default values.
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct CompletionConfig {
+    pub enabled: Option<bool>,
+    pub generator: Option<String>,
+    pub max_images: Option<usize>,
+    pub max_duration_secs: Option<u64>,
+}
+
+/// Settings for the dead letter queue that captures rejected event payloads.
+/// Dead letters are only captured when enabled is true.  Unassigned fields take
+/// the dead_letter module's default values.
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+pub struct DeadLetterConfig {
+    pub enabled: Option<bool>,
+    pub dead_letter_dir: Option<String>,
+    pub max_entries: Option<usize>,
+    pub max_payload_bytes: Option<usize>,
+}
+
+/// Settings for the image_store_plugin.  When the action_thresholds table is
+/// not assigned, the thresholds are read from the image store configuration
+/// file, traps-image-store.toml by default.
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ImageStoreConfig {
+    pub action_thresholds: Option<BTreeMap<String, f32>>,
+}
+
+/// Settings for the detection_reporter_plugin.  The per-label thresholds are
+/// read from the detection file unless the thresholds table is assigned.  The
+/// events file is written in the output directory.  Unassigned fields take the
+/// plugin's default values.
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct DetectionReporterConfig {
+    pub thresholds: Option<BTreeMap<String, f32>>,
+    pub detection_file: Option<String>,
+    pub output_dir: Option<String>,
+    pub events_file: Option<String>,
+}
+
+/// Settings for the mqtt_publisher_plugin.  The events list names the event
+/// types published to the broker and the overrides table customizes the
+/// topic, delivery and payload of individual event types.  Unassigned fields
+/// take the plugin's default values.
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct MqttPublisherConfig {
+    pub broker_host: Option<String>,
+    pub broker_port: Option<u16>,
+    pub client_id: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub keep_alive_secs: Option<u64>,
+    pub camera_id: Option<String>,
+    pub events: Option<Vec<String>>,
+    pub topic_template: Option<String>,
+    pub qos: Option<u8>,
+    pub retain: Option<bool>,
+    pub offline_queue_size: Option<usize>,
+    pub overrides: Option<HashMap<String, MqttEventConfig>>,
+}
+
+/// Per event type settings that replace the mqtt_publisher defaults.  When
+/// fields is set, only the listed event fields are included in the payload.
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct MqttEventConfig {
+    pub topic_template: Option<String>,
+    pub qos: Option<u8>,
+    pub retain: Option<bool>,
+    pub fields: Option<Vec<String>>,
+}
+
+/// Settings for the oracle_plugin.  The mapping file written by the image
+/// generator is read from, and the final mapping file and the optional updates
+/// file are written to, the output directory.  Unassigned fields take the
+/// plugin's default values; no updates file is written by default.
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct OracleConfig {
+    pub output_dir: Option<String>,
+    pub mapping_file: Option<String>,
+    pub final_file: Option<String>,
+    pub updates_file: Option<String>,
+    pub flush_interval_secs: Option<u64>,
+}
+
+/// Settings for the power_monitor_plugin.  Process statistics are read from
+/// the proc root and RAPL energy counters from the sysfs root, which can be
+/// pointed at fake directory trees for testing.  Unassigned fields take the
+/// plugin's default values.
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct PowerMonitorConfig {
+    pub log_dir: Option<String>,
+    pub proc_root: Option<String>,
+    pub sysfs_root: Option<String>,
+    pub sample_interval_secs: Option<u64>,
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::config::config::Config;
+    use crate::config::config::*;
+    use crate::config::errors::Errors;
 
     #[test]
     fn here_i_am() {
@@ -59,5 +231,34 @@ mod tests {
     fn print_config() {
         println!("{:?}", Config::new());
     }
-}
 
+    #[test]
+    fn plugin_config() {
+        let config: Config = toml::from_str(r#"
+            publish_port = 5559
+            subscribe_port = 5560
+            images_output_dir = "/tmp/images"
+            [plugins.config.oracle_plugin]
+            output_dir = "/tmp/oracle"
+            [plugins.config.power_monitor_plugin]
+            sample_interval_secs = "often"
+            [plugins.config.detection_reporter_plugin]
+            output_dir = "/tmp/reports"
+            outputdir = "/tmp/typo"
+        "#).unwrap();
+
+        // The plugin's table is deserialized into its settings.
+        let oracle: OracleConfig = config.get_plugin_config("oracle_plugin").unwrap();
+        assert_eq!(oracle.output_dir.as_deref(), Some("/tmp/oracle"));
+
+        // Missing tables take the defaults.
+        let journal: JournalConfig = config.get_plugin_config("observer_journal_action").unwrap();
+        assert!(journal.journal_dir.is_none());
+
+        // Tables must match the plugin's settings.
+        let result = config.get_plugin_config::<PowerMonitorConfig>("power_monitor_plugin");
+        assert!(matches!(result, Err(Errors::PluginConfigInvalid(name, _)) if name == "power_monitor_plugin"));
+        let result = config.get_plugin_config::<DetectionReporterConfig>("detection_reporter_plugin");
+        assert!(matches!(result, Err(Errors::PluginConfigInvalid(_, e)) if e.contains("outputdir")));
+    }
+}
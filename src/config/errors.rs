@@ -3,6 +3,9 @@ use thiserror::Error;
 /// Error enumerates the errors returned by this application.
 #[derive(Error, Debug)]
 pub enum Errors {
+    #[error("Action {1} in plugin {0} failed, stopping the plugin's action chain: {2}")]
+    ActionChainStopped(String, String, String),
+
     #[error("Internal plugin {0} is configured with action {1}.")]
     ActionConfigured(String, String),
 
@@ -15,9 +18,15 @@ pub enum Errors {
     #[error("Internal plugin {0} initialization failed because invalid action {1} was configured.")]
     ActionNotFound(String, String),
 
+    #[error("Action {0} cannot run in plugin {1}, which doesn't supply the action's input.")]
+    ActionInputMismatch(String, String),
+
     #[error("Action {1} in plugin {0} failed to open file {2}: {3}")]
     ActionOpenFileError(String, String, String, String),
 
+    #[error("Unable to register action {0}: {1}.")]
+    ActionRegisterError(String, String),
+
     #[error("Action {1} in plugin {0} failed to write file {2}: {3}")]
     ActionWriteFileError(String, String, String, String),
 
@@ -33,15 +42,80 @@ pub enum Errors {
     #[error("Camera-Traps application shutting down normally.")]
     AppNormalShutdown(),
 
+    #[error("Image generator {0} finished; waiting for {1} in-flight image(s) to reach a terminal event.")]
+    CompletionGeneratorFinished(String, usize),
+
+    #[error("Running to completion requires the image_store_plugin, whose ImageStoredEvent and ImageDeletedEvent finish each image.")]
+    CompletionImageStoreMissing(),
+
+    #[error("{0} image(s) failed and {1} image(s) did not finish; exiting with status {2}.")]
+    CompletionImagesFailed(usize, usize, i32),
+
+    #[error("The run is complete because {0}.")]
+    CompletionReached(String),
+
+    #[error("Run completed with {0} image(s): {1} stored, {2} deleted, {3} failed, {4} rejected at shutdown and {5} unfinished.")]
+    CompletionSummary(usize, usize, usize, usize, usize, usize),
+
+    #[error("Invalid configuration from {0}: {1}")]
+    ConfigLayerInvalid(String, String),
+
+    #[error("Reloaded configuration file {0}; applied settings: {1}")]
+    ConfigReloadApplied(String, String),
+
+    #[error("Unable to reload the configuration: {0}")]
+    ConfigReloadError(String),
+
+    #[error("Plugin {0} kept its previous settings because the reloaded configuration could not be applied: {1}")]
+    ConfigReloadPluginError(String, String),
+
+    #[error("Rejected the reloaded configuration file {0}, the running configuration is unchanged:\n{1}")]
+    ConfigReloadRejected(String, String),
+
+    #[error("Reloaded configuration settings that require a restart to take effect: {0}")]
+    ConfigReloadRestartRequired(String),
+
+    #[error("Capturing dead letters in {0} with a limit of {1} entries.")]
+    DeadLetterEnabled(String, usize),
+
+
+    #[error("Unable to read dead letter {0}: {1}")]
+    DeadLetterReadError(String, String),
+
+    #[error("Unable to write dead letter {0}: {1}")]
+    DeadLetterWriteError(String, String),
+
+    #[error("Image {0} was detected with {1}")]
+    DetectionReported(String, String),
+
+    #[error("Unable to read detection thresholds file {0}: {1}")]
+    DetectionThresholdsError(String, String),
+
+    #[error("Unable to write detection report {0}: {1}")]
+    DetectionWriteError(String, String),
+
+
+    #[error("Unable to read evaluation input {0}: {1}")]
+    EvaluationReadError(String, String),
+
+    #[error("Unable to write evaluation report {0}: {1}")]
+    EvaluationWriteError(String, String),
+
     #[error("Unable to create an event from a buffer of type {}.", .0)]
     EventCreateFromFlatbuffer(String),
 
+    #[error("Unable to create a {0} event from JSON: {1}")]
+    EventFromJsonError(String, String),
+
     #[error("Unable to create an event from buffer contents: {0}")]
     EventFromFlatbuffer(String),
 
     #[error("Plugin {0} received an ill-formed event length {0}.")]
     EventInvalidLen(String, usize),
 
+    #[error("Unknown event type {0}; the list-events command lists the event types.")]
+    EventNameUnknown(String),
+
     #[error("Plugin {0} received an unknown event type which it is ignoring.")]
     EventNoneError(String),
 
@@ -51,6 +125,12 @@ pub enum Errors {
     #[error("Plugin {0} received event with mismatched prefix ({1} and type ({2}.")]
     EventPrefixMismatch(String, String, String),
 
+    #[error("Plugin {0} rejected a {1} event from sender {2} with incompatible schema version {3}; this application uses schema version {4}.")]
+    EventSchemaVersionMismatch(String, String, String, String, String),
+
+    #[error("Plugin {0} received an event type added in newer schema version {1} which it is ignoring.")]
+    EventUnknownNewerType(String, String),
+
     #[error("Plugin {0} is processing event type {1}.")]
     EventProcessing(String, &'static str),
 
@@ -69,6 +149,9 @@ pub enum Errors {
     #[error("Plugin {} is unable to send a {} event: {}", .0, .1, .2)]
     EventSendError(String, String, String),
 
+    #[error("Sent {0} to {1}.")]
+    EventSent(String, String),
+
     #[error("Plugin {} is unable to convert a {} event to vector of bytes: {}", .0, .1, .2)]
     EventToBytesError(String, String, String),
 
@@ -87,6 +170,12 @@ pub enum Errors {
      #[error("File IO error: {}", .0)]
     FileIOError(String),
 
+    #[error("Unable to apply {0} image compression: {1}")]
+    ImageCompressionError(String, String),
+
+    #[error("Unable to resolve image reference {0}: {1}")]
+    ImageReferenceError(String, String),
+
     /// Input parameter logging.
     #[error("Camera-Traps input parameters:\n{}", .0)]
     InputParms(String),
@@ -95,10 +184,49 @@ pub enum Errors {
     #[error(transparent)]
     IOError(#[from] std::io::Error),
 
+    #[error("Unable to read event journal {0}: {1}")]
+    JournalReadError(String, String),
+
+    #[error("Unable to parse line {1} of event journal {0}: {2}")]
+    JournalParseError(String, usize, String),
+
+    #[error("Unable to rotate event journal {0}: {1}")]
+    JournalRotateError(String, String),
+
+    #[error("Unable to write event journal {0}: {1}")]
+    JournalWriteError(String, String),
+
     /// Inaccessible logger configuration file.
     #[error("Unable to access the Log4rs configuration file: {}", .0)]
     Log4rsInitialization(String),
 
+    #[error("MQTT publisher connected to broker {0}.")]
+    MqttConnected(String),
+
+    #[error("MQTT publisher lost its connection to broker {0}: {1}")]
+    MqttConnectionError(String, String),
+
+    #[error("Invalid mqtt_publisher configuration: {0}")]
+    MqttInvalidConfig(String),
+
+    #[error("Unable to publish an MQTT message to topic {0}: {1}")]
+    MqttPublishError(String, String),
+
+    #[error("MQTT offline queue is full ({0} messages); dropped the oldest message for topic {1}.")]
+    MqttQueueFull(usize, String),
+
+    #[error("Oracle wrote the final mapping with {0} decided images to {1}.")]
+    OracleFinalMapping(usize, String),
+
+    #[error("Unable to read the image generator's mapping file {0}: {1}")]
+    OracleMappingReadError(String, String),
+
+    #[error("Unable to write the oracle's image mapping file {0}: {1}")]
+    OracleWriteError(String, String),
+
+    #[error("Invalid [plugins.config.{0}] configuration: {1}")]
+    PluginConfigInvalid(String, String),
+
     #[error("Plugin {0} failed to deserialize expected {1} event.")]
     PluginEventDeserializationError(String, String),
 
@@ -111,6 +239,21 @@ pub enum Errors {
     #[error("Plugin {0} failed when trying to parse a {1} event's image_uuid: {2}")]
     PluginEventParseUuidError(String, String, String),
 
+    #[error("ALARM: Plugin {0} ({1}) has not started within {2} seconds.")]
+    PluginStartTimeout(String, String, u64),
+
+    #[error("Plugin status summary:\n{0}")]
+    PluginStatusSummary(String),
+
+    #[error("Unable to write plugin status file {0}: {1}")]
+    PluginStatusWriteError(String, String),
+
+    #[error("ALARM: Plugin {0} ({1}) terminated without being requested to terminate.")]
+    PluginUnexpectedTermination(String, String),
+
+    #[error("Plugin {0} received a lifecycle event from unregistered plugin {1} ({2}).")]
+    PluginUnregistered(String, String, String),
+
     #[error("\n**** Plugin {} ({}) starting execution.", .0, .1)]
     PluginStarted(String, String),
 
@@ -123,6 +266,15 @@ pub enum Errors {
     #[error("Unknown plugin {0} could not be registered, aborting application.")]
     PluginUnknown(String),
 
+    #[error("Power monitor found no RAPL energy counters under {0}; only process resources will be logged.")]
+    PowerMonitorNoEnergyCounters(String),
+
+    #[error("Power monitor does not measure {0} power; those measurements are ignored.")]
+    PowerMonitorUnsupportedDevice(String),
+
+    #[error("Unable to write power monitor report {0}: {1}")]
+    PowerMonitorWriteError(String, String),
+
     #[error("Reading application configuration file: {}", .0)]
     ReadingConfigFile(String),
 
@@ -138,18 +290,75 @@ pub enum Errors {
     #[error("\nRegistering {0} internal plugin(s).")]
     RegisteringNumInternalPlugins(usize),
 
+    #[error("Replayed {0} of {1} journal entries from {2} to {3}.")]
+    ReplayCompleted(usize, usize, String, String),
+
+
+    #[error("{0} in-flight image(s) did not finish processing within the {1} second drain timeout.")]
+    ShutdownDrainTimeout(usize, u64),
+
+    #[error("All in-flight images finished processing.")]
+    ShutdownDrained(),
+
+    #[error("No plugins are running, so there are no images to drain or plugins to terminate.")]
+    ShutdownNoPlugins(),
+
+    #[error("Stopped accepting new images; waiting up to {1} seconds for {0} in-flight image(s) to finish processing.")]
+    ShutdownDraining(usize, u64),
+
+    #[error("Received {0} again, exiting immediately.")]
+    ShutdownForced(String),
+
+    #[error("Plugin {0} rejected image {1} because the application is shutting down.")]
+    ShutdownImageRejected(String, String),
+
+    #[error("Received {0}, shutting down gracefully; send the signal again to exit immediately.")]
+    ShutdownRequested(String),
+
+    #[error("Broadcasting a PluginTerminateEvent to all plugins and waiting for them to terminate.")]
+    ShutdownTerminatingPlugins(),
+
+    #[error("Unable to connect to {0}: {1}")]
+    SocketConnectError(String, String),
+
     #[error("Plugin {0} failed to read a byte stream from its subscription socket: {1}")]
     SocketRecvError(String, String),
 
     #[error("Plugin {} is unable to send a {} event: {}", .0, .1, .2)]
     SocketSendError(String, String, String),
 
+    #[error("Plugin {0} read its action thresholds from {1}.")]
+    StoreThresholdsRead(String, String),
+
+    #[error("Invalid sweep argument: {0}")]
+    SweepInvalidArgument(String),
+
     #[error("Unable to parse string '{}' into a Date: {}", .0, .1)]
     DateParseError(String, String),
 
     #[error("Unable to parse TOML file: {}", .0)]
     TOMLParseError(String),
 
+    #[error("Configuration {0} is valid.")]
+    ValidateConfigValid(String),
+
+    #[error("Configuration {1} has {0} problem(s).")]
+    ValidateConfigInvalid(usize, String),
+
+
     #[error("Unable to parse string '{}' into a Uuid: {}", .0, .1)]
     UUIDParseError(String, String),
-}
\ No newline at end of file
+}
+
+impl Errors {
+    // ---------------------------------------------------------------------------
+    // code:
+    // ---------------------------------------------------------------------------
+    /** The name of the error's variant, which identifies the kind of error in
+     * events such as ImageProcessingErrorEvent.
+     */
+    pub fn code(&self) -> String {
+        let debug = format!("{:?}", self);
+        debug.split(['(', ' ', '{']).next().unwrap_or_default().to_string()
+    }
+}
@@ -0,0 +1,520 @@
+use std::fmt;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+use serde::de::{self, Deserializer, Visitor};
+use toml::Value;
+
+use crate::config::config::Config;
+use crate::config::errors::Errors;
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+// Environment variables that override configuration settings start with this
+// prefix and separate the keys of nested settings with a double underscore.
+const ENV_PREFIX: &str = "TRAPS_";
+const ENV_SEPARATOR: &str = "__";
+
+// The top-level keys of the application configuration, which are read from
+// Config's Deserialize implementation the first time they're needed.
+static CONFIG_KEYS: OnceLock<&'static [&'static str]> = OnceLock::new();
+
+// ***************************************************************************
+// LAYERS
+// ***************************************************************************
+/** The source of a configuration value.  The configuration file is the base
+ * layer, environment variables override the file and command line settings
+ * override both.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigLayer {
+    File(String),
+    Env(String),
+    Cli(String),
+}
+
+impl fmt::Display for ConfigLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigLayer::File(file) => write!(f, "configuration file {}", file),
+            ConfigLayer::Env(var) => write!(f, "environment variable {}", var),
+            ConfigLayer::Cli(setting) => write!(f, "command line setting --set {}", setting),
+        }
+    }
+}
+
+/** A single setting supplied by an environment variable or the command line.
+ * The path is the setting's key path with array indexes as numeric keys.
+ */
+#[derive(Debug, Clone)]
+pub struct Override {
+    pub path: Vec<String>,
+    pub value: Value,
+    pub layer: ConfigLayer,
+}
+
+/** Which layer supplied each setting of a loaded configuration.  Settings
+ * not overridden come from the configuration file.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct ConfigSources {
+    pub config_file: String,
+    overrides: Vec<(Vec<String>, ConfigLayer)>,
+}
+
+impl ConfigSources {
+    // ----------------------------------------------------------------------
+    // new:
+    // ----------------------------------------------------------------------
+    pub fn new(config_file: &str) -> Self {
+        ConfigSources {config_file: config_file.to_string(), overrides: vec![]}
+    }
+
+    // ----------------------------------------------------------------------
+    // get_layer:
+    // ----------------------------------------------------------------------
+    /** The layer that supplied the setting with the given key, such as
+     * plugins.external[1].id.  A setting is supplied by the last override of
+     * the setting itself or of a table or array that contains it.
+     */
+    pub fn get_layer(&self, key: &str) -> ConfigLayer {
+        let path = match parse_key(key) {
+            Ok(p) => p,
+            Err(_) => return ConfigLayer::File(self.config_file.clone()),
+        };
+        self.overrides.iter().rev()
+            .find(|(p, _)| path.starts_with(p))
+            .map(|(_, layer)| layer.clone())
+            .unwrap_or_else(|| ConfigLayer::File(self.config_file.clone()))
+    }
+
+    // ----------------------------------------------------------------------
+    // get_overridden:
+    // ----------------------------------------------------------------------
+    /** The overridden settings' keys with their layers, in the order applied. */
+    pub fn get_overridden(&self) -> Vec<(String, ConfigLayer)> {
+        self.overrides.iter().map(|(p, layer)| (format_key(p), layer.clone())).collect()
+    }
+
+    // ----------------------------------------------------------------------
+    // locate:
+    // ----------------------------------------------------------------------
+    /** Name a setting's location for messages.  Settings from the file are
+     * located by the file name, overridden settings by their layer.
+     */
+    pub fn locate(&self, key: &str) -> String {
+        match self.get_layer(key) {
+            ConfigLayer::File(file) => format!("{}: {}", file, key),
+            layer => format!("{}: {}", layer, key),
+        }
+    }
+}
+
+// ***************************************************************************
+// PUBLIC FUNCTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// get_env_overrides:
+// ---------------------------------------------------------------------------
+/** Collect the configuration overrides from environment variables.  The part
+ * of a variable's name after TRAPS_ is the setting's key path with the keys
+ * separated by double underscores, so TRAPS_SHUTDOWN__DRAIN_TIMEOUT_SECS sets
+ * drain_timeout_secs in the [shutdown] table and
+ * TRAPS_PLUGINS__EXTERNAL__0__EXTERNAL_PORT sets the port of the first
+ * external plugin.  Keys written entirely in upper case
+ * are lowercased, which matches the configuration's field names; keys with a
+ * lowercase letter are kept as written so that map keys such as the Save and
+ * ReduceSave action thresholds or label names can be set.  Variables that
+ * don't start with a configuration key are ignored.  Overrides of containing
+ * settings are applied before overrides of the settings they contain.
+ */
+pub fn get_env_overrides(vars: impl IntoIterator<Item = (String, String)>) -> Result<Vec<Override>, Errors> {
+    let mut overrides = vec![];
+    for (name, value) in vars {
+        let key = match name.strip_prefix(ENV_PREFIX) {
+            Some(k) => k,
+            None => continue,
+        };
+        let path: Vec<String> = key.split(ENV_SEPARATOR).map(env_key_to_setting_key).collect();
+        if !get_config_keys().contains(&path[0].as_str()) {
+            continue;
+        }
+        let layer = ConfigLayer::Env(name.clone());
+        if path.iter().any(|k| k.is_empty()) {
+            return Result::Err(Errors::ConfigLayerInvalid(layer.to_string(), format!("invalid setting name {}", key)));
+        }
+        overrides.push(Override {path, value: parse_value(&value), layer});
+    }
+    overrides.sort_by(|a, b| a.path.len().cmp(&b.path.len()).then_with(|| a.path.cmp(&b.path)));
+    Result::Ok(overrides)
+}
+
+// ---------------------------------------------------------------------------
+// get_cli_overrides:
+// ---------------------------------------------------------------------------
+/** Collect the configuration overrides from the command line's KEY=VALUE
+ * settings, such as plugins.external[0].external_port=6011, in the order given.
+ */
+pub fn get_cli_overrides(settings: &[String]) -> Result<Vec<Override>, Errors> {
+    let mut overrides = vec![];
+    for setting in settings {
+        let layer = ConfigLayer::Cli(setting.clone());
+        let (key, value) = match setting.split_once('=') {
+            Some((k, v)) => (k.trim(), v),
+            None => return Result::Err(Errors::ConfigLayerInvalid(layer.to_string(), "expected KEY=VALUE".to_string())),
+        };
+        let path = parse_key(key).map_err(|e| Errors::ConfigLayerInvalid(layer.to_string(), e))?;
+        if !get_config_keys().contains(&path[0].as_str()) {
+            return Result::Err(Errors::ConfigLayerInvalid(layer.to_string(), format!("unknown setting {}", key)));
+        }
+        overrides.push(Override {path, value: parse_value(value), layer});
+    }
+    Result::Ok(overrides)
+}
+
+// ---------------------------------------------------------------------------
+// load_config:
+// ---------------------------------------------------------------------------
+/** Parse the configuration file's contents, apply the overrides in order and
+ * return the resulting configuration with the layer of each setting.  When
+ * the result is invalid, the error names the layer that made it invalid: the
+ * last override after which the configuration no longer loaded or, if it never
+ * loaded, the configuration file.  Overrides of settings the configuration
+ * doesn't have are also reported.
+ */
+pub fn load_config(config_file: &str, contents: &str, overrides: &[Override]) -> Result<(Config, ConfigSources), Errors> {
+    let file_layer = ConfigLayer::File(config_file.to_string());
+    let mut value: Value = toml::from_str(contents)
+        .map_err(|e| Errors::ConfigLayerInvalid(file_layer.to_string(), e.to_string()))?;
+
+    // Apply the overrides, remembering the layer that last broke the configuration.
+    let mut loaded = value.clone().try_into::<Config>();
+    let mut culprit = &file_layer;
+    for o in overrides {
+        set_value(&mut value, &o.path, o.value.clone())
+            .map_err(|e| Errors::ConfigLayerInvalid(o.layer.to_string(), e))?;
+        let next = value.clone().try_into::<Config>();
+        if loaded.is_ok() && next.is_err() {
+            culprit = &o.layer;
+        }
+        loaded = next;
+    }
+    let config = loaded.map_err(|e| Errors::ConfigLayerInvalid(culprit.to_string(), e.to_string()))?;
+
+    // Overridden settings must exist in the loaded configuration.
+    let effective = Value::try_from(&config)
+        .map_err(|e| Errors::ConfigLayerInvalid(file_layer.to_string(), e.to_string()))?;
+    for o in overrides {
+        if get_value(&effective, &o.path).is_none() {
+            return Result::Err(Errors::ConfigLayerInvalid(o.layer.to_string(),
+                                   format!("unknown setting {}", format_key(&o.path))));
+        }
+    }
+
+    let sources = ConfigSources {
+        config_file: config_file.to_string(),
+        overrides: overrides.iter().map(|o| (o.path.clone(), o.layer.clone())).collect(),
+    };
+    Result::Ok((config, sources))
+}
+
+// ---------------------------------------------------------------------------
+// format_key:
+// ---------------------------------------------------------------------------
+/** Format a key path as it appears in messages, such as plugins.external[1].id. */
+pub fn format_key(path: &[String]) -> String {
+    let mut key = String::new();
+    for k in path {
+        if k.parse::<usize>().is_ok() {
+            key.push_str(&format!("[{}]", k));
+        } else {
+            if !key.is_empty() {key.push('.');}
+            key.push_str(k);
+        }
+    }
+    key
+}
+
+// ---------------------------------------------------------------------------
+// get_config_keys:
+// ---------------------------------------------------------------------------
+/** The top-level keys of the application configuration.  Only environment
+ * variables that start with one of these keys are configuration overrides,
+ * which leaves the other TRAPS_ variables to the plugins that use them.  The
+ * keys are the field names serde deserializes Config from, so they can't
+ * drift from the Config struct.
+ */
+pub fn get_config_keys() -> &'static [&'static str] {
+    CONFIG_KEYS.get_or_init(|| {
+        let mut fields: &'static [&'static str] = &[];
+        let _ = Config::deserialize(FieldNames(&mut fields));
+        fields
+    })
+}
+
+// ***************************************************************************
+// PRIVATE FUNCTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// env_key_to_setting_key:
+// ---------------------------------------------------------------------------
+/** Lowercase keys written entirely in upper case and keep the others as is. */
+fn env_key_to_setting_key(key: &str) -> String {
+    if key.chars().any(|c| c.is_lowercase()) {
+        key.to_string()
+    } else {
+        key.to_lowercase()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// FieldNames:
+// ---------------------------------------------------------------------------
+/** A deserializer that records the field names a struct's derived Deserialize
+ * implementation asks for and then fails, without reading any data.
+ */
+struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+impl<'de> Deserializer<'de> for FieldNames<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Result::Err(de::Error::custom("only structs have field names"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(self, _name: &'static str, fields: &'static [&'static str],
+                                           _visitor: V) -> Result<V::Value, Self::Error> {
+        *self.0 = fields;
+        Result::Err(de::Error::custom("field names recorded"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option unit
+        unit_struct newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+}
+
+// ---------------------------------------------------------------------------
+// parse_key:
+// ---------------------------------------------------------------------------
+/** Split a key such as plugins.external[1].id or plugins.external.1.id into
+ * its path.
+ */
+fn parse_key(key: &str) -> Result<Vec<String>, String> {
+    let path: Vec<String> = key.replace('[', ".").replace(']', "").split('.').map(String::from).collect();
+    if path.iter().any(|k| k.is_empty()) {
+        return Result::Err(format!("invalid setting name {}", key));
+    }
+    Result::Ok(path)
+}
+
+// ---------------------------------------------------------------------------
+// parse_value:
+// ---------------------------------------------------------------------------
+/** Values are written in TOML syntax, so numbers, booleans, arrays and inline
+ * tables have their TOML types.  Anything else, including dates, is a string,
+ * so strings only need quotes when they would otherwise have another type.
+ */
+fn parse_value(s: &str) -> Value {
+    if let Ok(Value::Table(mut t)) = toml::from_str::<Value>(&format!("v = {}", s)) {
+        if t.len() == 1 {
+            match t.remove("v") {
+                Some(Value::Datetime(_)) | None => (),
+                Some(v) => return v,
+            }
+        }
+    }
+    Value::String(s.to_string())
+}
+
+// ---------------------------------------------------------------------------
+// set_value:
+// ---------------------------------------------------------------------------
+/** Set the value at the path, creating any missing tables and arrays along the
+ * way.  An array index can name an existing element or the next one.
+ */
+fn set_value(node: &mut Value, path: &[String], value: Value) -> Result<(), String> {
+    let (key, rest) = match path.split_first() {
+        Some(p) => p,
+        None => {
+            *node = value;
+            return Result::Ok(());
+        },
+    };
+    let empty = || if rest.first().is_some_and(|k| k.parse::<usize>().is_ok()) {
+        Value::Array(vec![])
+    } else {
+        Value::Table(Default::default())
+    };
+
+    match node {
+        Value::Table(t) => {
+            let child = t.entry(key.clone()).or_insert_with(empty);
+            set_value(child, rest, value)
+        },
+        Value::Array(a) => {
+            let i = key.parse::<usize>().map_err(|_| format!("{} is not an array index", key))?;
+            if i == a.len() {
+                a.push(empty());
+            }
+            match a.get_mut(i) {
+                Some(child) => set_value(child, rest, value),
+                None => Result::Err(format!("index {} is beyond the end of an array with {} elements", i, a.len())),
+            }
+        },
+        _ => Result::Err(format!("{} is set on a value that is not a table or array", key)),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// get_value:
+// ---------------------------------------------------------------------------
+fn get_value<'a>(node: &'a Value, path: &[String]) -> Option<&'a Value> {
+    let (key, rest) = match path.split_first() {
+        Some(p) => p,
+        None => return Some(node),
+    };
+    match node {
+        Value::Table(t) => get_value(t.get(key)?, rest),
+        Value::Array(a) => get_value(a.get(key.parse::<usize>().ok()?)?, rest),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::config::{CompletionConfig, DeadLetterConfig, PluginMonitorConfig, Plugins, ShutdownConfig};
+
+    const CONFIG: &str = r#"
+        publish_port = 5559
+        subscribe_port = 5560
+        images_output_dir = "/tmp/images"
+        [plugins]
+        internal = ["image_recv_plugin"]
+        [[plugins.external]]
+        plugin_name = "ext_score"
+        id = "d6e8e42a-41ec-11ed-a36f-a3dcc1cc761a"
+        external_port = 6001
+        subscriptions = ["PluginTerminateEvent"]
+    "#;
+
+    fn env(vars: &[(&str, &str)]) -> Vec<Override> {
+        get_env_overrides(vars.iter().map(|(k, v)| (k.to_string(), v.to_string()))).unwrap()
+    }
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: layers.rs");
+    }
+
+    #[test]
+    fn config_keys() {
+        // Every field is set so that each serialized key is compared with the deserialized field names.
+        let config = Config {
+            title: Some(String::new()),
+            log_level: Some(String::new()),
+            plugins: Plugins::default(),
+            publish_port: 5559,
+            subscribe_port: 5560,
+            images_output_dir: String::new(),
+            image_file_prefix: Some(String::new()),
+            image_content_dir: Some(String::new()),
+            image_ref_dir: Some(String::new()),
+            plugin_monitor: Some(PluginMonitorConfig::default()),
+            shutdown: Some(ShutdownConfig::default()),
+            completion: Some(CompletionConfig::default()),
+            dead_letter: Some(DeadLetterConfig::default()),
+        };
+        let value = Value::try_from(&config).unwrap();
+        let mut keys: Vec<&str> = value.as_table().unwrap().keys().map(|k| k.as_str()).collect();
+        let mut expected = get_config_keys().to_vec();
+        keys.sort();
+        expected.sort();
+        assert_eq!(keys, expected);
+    }
+
+    #[test]
+    fn layered_config() {
+        // Environment variables override the file and the command line overrides both.
+        let mut overrides = env(&[
+            ("TRAPS_PUBLISH_PORT", "6559"),
+            ("TRAPS_IMAGES_OUTPUT_DIR", "/data/images"),
+            ("TRAPS_PLUGINS__INTERNAL", r#"["image_recv_plugin", "observer_plugin"]"#),
+            ("TRAPS_PLUGINS__EXTERNAL__0__EXTERNAL_PORT", "6011"),
+            ("TRAPS_PLUGIN_MONITOR__STATUS_INTERVAL_SECS", "3"),
+            ("TRAPS_IMAGE_STORE_FILE", "ignored.toml"),
+            ("HOME", "/root"),
+        ]);
+        overrides.extend(get_cli_overrides(&["publish_port=7559".to_string(),
+                                             "plugins.external[1].plugin_name=ext_gen".to_string(),
+                                             "plugins.external[1].id=\"5\"".to_string(),
+                                             "plugins.external[1].external_port=6012".to_string(),
+                                             "plugins.external[1].subscriptions=[]".to_string()]).unwrap());
+        let (config, sources) = load_config("traps.toml", CONFIG, &overrides).unwrap();
+        assert_eq!(config.publish_port, 7559);
+        assert_eq!(config.subscribe_port, 5560);
+        assert_eq!(config.images_output_dir, "/data/images");
+        assert_eq!(config.plugins.internal.unwrap(), ["image_recv_plugin", "observer_plugin"]);
+        let external = config.plugins.external.unwrap();
+        assert_eq!(external[0].external_port, 6011);
+        assert_eq!(external[0].plugin_name, "ext_score");
+        assert_eq!(external[1].id, "5");
+        assert_eq!(config.plugin_monitor.unwrap().status_interval_secs, Some(3));
+
+        // Each setting knows its layer.
+        assert_eq!(sources.locate("subscribe_port"), "traps.toml: subscribe_port");
+        assert_eq!(sources.get_layer("publish_port"), ConfigLayer::Cli("publish_port=7559".to_string()));
+        assert_eq!(sources.get_layer("plugins.internal[1]"), ConfigLayer::Env("TRAPS_PLUGINS__INTERNAL".to_string()));
+        assert_eq!(sources.locate("plugins.external[0].external_port"),
+                   "environment variable TRAPS_PLUGINS__EXTERNAL__0__EXTERNAL_PORT: plugins.external[0].external_port");
+        assert_eq!(sources.locate("plugins.external[0].id"), "traps.toml: plugins.external[0].id");
+    }
+
+    #[test]
+    fn env_key_case() {
+        // Upper case keys are field names and mixed case keys are map keys.
+        let overrides = env(&[("TRAPS_PLUGINS__CONFIG__IMAGE_STORE_PLUGIN__ACTION_THRESHOLDS__ReduceSave", "0.5"),
+                              ("TRAPS_PLUGINS__CONFIG__IMAGE_STORE_PLUGIN__ACTION_THRESHOLDS__Save", "0.8")]);
+        assert_eq!(overrides[0].path, ["plugins", "config", "image_store_plugin", "action_thresholds", "ReduceSave"]);
+        assert_eq!(overrides[1].path, ["plugins", "config", "image_store_plugin", "action_thresholds", "Save"]);
+    }
+
+    #[test]
+    fn layer_errors() {
+        let message = |r: Result<(Config, ConfigSources), Errors>| r.err().unwrap().to_string();
+
+        // A bad value names its layer.
+        let e = message(load_config("traps.toml", CONFIG, &env(&[("TRAPS_SUBSCRIBE_PORT", "port")])));
+        assert!(e.contains("environment variable TRAPS_SUBSCRIBE_PORT"), "{}", e);
+        let e = message(load_config("traps.toml", CONFIG, &get_cli_overrides(&["plugin_monitor.status_interval_secs=-1".to_string()]).unwrap()));
+        assert!(e.contains("command line setting --set plugin_monitor.status_interval_secs=-1"), "{}", e);
+
+        // An incomplete file can be completed by a later layer.
+        let partial = "publish_port = 5559\nimages_output_dir = \"/tmp\"\n[plugins]\n";
+        assert!(load_config("traps.toml", partial, &env(&[("TRAPS_SUBSCRIBE_PORT", "5560")])).is_ok());
+        let e = message(load_config("traps.toml", partial, &env(&[("TRAPS_TITLE", "\"x\"")])));
+        assert!(e.contains("configuration file traps.toml"), "{}", e);
+
+        // Settings must exist and be well formed.
+        let e = message(load_config("traps.toml", CONFIG, &env(&[("TRAPS_PLUGIN_MONITOR__STATUS_INTERVAL", "3")])));
+        assert!(e.contains("TRAPS_PLUGIN_MONITOR__STATUS_INTERVAL") && e.contains("unknown setting plugin_monitor.status_interval"), "{}", e);
+        let e = message(load_config("traps.toml", CONFIG, &env(&[("TRAPS_PLUGINS__EXTERNAL__3__ID", "x")])));
+        assert!(e.contains("beyond the end"), "{}", e);
+        assert!(get_cli_overrides(&["publish_port".to_string()]).is_err());
+        assert!(get_cli_overrides(&["no_such_key=1".to_string()]).is_err());
+        assert!(get_cli_overrides(&["plugins..internal=[]".to_string()]).is_err());
+    }
+
+    #[test]
+    fn values() {
+        assert_eq!(parse_value("5559"), Value::Integer(5559));
+        assert_eq!(parse_value("true"), Value::Boolean(true));
+        assert_eq!(parse_value("/data/images"), Value::String("/data/images".to_string()));
+        assert_eq!(parse_value("\"5559\""), Value::String("5559".to_string()));
+        assert_eq!(parse_value("2024-01-01"), Value::String("2024-01-01".to_string()));
+        assert_eq!(parse_value("a = 1"), Value::String("a = 1".to_string()));
+        assert_eq!(format_key(&parse_key("plugins.external.1.id").unwrap()), "plugins.external[1].id");
+    }
+}
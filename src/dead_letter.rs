@@ -0,0 +1,405 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use chrono::Utc;
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::config::{config::Config, errors::Errors};
+use crate::traps_utils::{self, timestamp_str};
+
+use log::{info, error};
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+// Dead letter defaults used when the configuration does not specify a value.
+const DEFAULT_DEAD_LETTER_DIR: &str = "~/camera-traps/dead_letters";
+const DEFAULT_MAX_ENTRIES: usize = 1000;
+const DEFAULT_MAX_PAYLOAD_BYTES: usize = 1024 * 1024;
+
+// Dead letter files have this extension; partially written files do not.
+const DEAD_LETTER_EXT: &str = "json";
+
+// The number of payload bytes shown in each line of the inspect output.
+const HEX_LINE_BYTES: usize = 16;
+
+// The queue used by all plugins, which is only set when dead letters are enabled.
+static DEAD_LETTER_QUEUE: OnceLock<DeadLetterQueue> = OnceLock::new();
+
+// ***************************************************************************
+// DEAD LETTER ENTRY
+// ***************************************************************************
+/** Why a payload was rejected. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeadLetterReason {
+    TooShort,
+    InvalidFlatbuffer,
+    SchemaVersionMismatch,
+    UnknownEventType,
+    PrefixMismatch,
+    NotHandled,
+}
+
+impl fmt::Display for DeadLetterReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/** A rejected payload.  The payload field contains the bytes as they appeared
+ * on the wire encoded in base64, truncated to the configured maximum size.  The
+ * payload_len is always the length of the complete payload.
+ */
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeadLetterEntry {
+    pub recorded: String,
+    pub plugin_name: String,
+    pub reason: DeadLetterReason,
+    pub detail: String,
+    pub payload_len: usize,
+    pub truncated: bool,
+    pub payload: String,
+}
+
+impl DeadLetterEntry {
+    // ----------------------------------------------------------------------
+    // new:
+    // ----------------------------------------------------------------------
+    pub fn new(plugin_name: &str, reason: DeadLetterReason, detail: &str,
+               payload: &[u8], max_payload_bytes: usize) -> Self {
+        let truncated = payload.len() > max_payload_bytes;
+        let kept = if truncated {&payload[..max_payload_bytes]} else {payload};
+        DeadLetterEntry {
+            recorded: timestamp_str(),
+            plugin_name: plugin_name.to_string(),
+            reason,
+            detail: detail.to_string(),
+            payload_len: payload.len(),
+            truncated,
+            payload: STANDARD.encode(kept),
+        }
+    }
+
+    // ----------------------------------------------------------------------
+    // get_payload_bytes:
+    // ----------------------------------------------------------------------
+    /** Decode the base64 payload field back into the captured bytes. */
+    pub fn get_payload_bytes(&self) -> Result<Vec<u8>, String> {
+        STANDARD.decode(&self.payload).map_err(|e| e.to_string())
+    }
+}
+
+// ***************************************************************************
+// DEAD LETTER QUEUE
+// ***************************************************************************
+/** A bounded on-disk queue of rejected payloads.  Each entry is written to its
+ * own JSON file whose name begins with the capture time, so the file names
+ * sort from oldest to newest.  When more than max_entries files exist the
+ * oldest are removed.
+ */
+#[derive(Debug)]
+pub struct DeadLetterQueue {
+    pub dead_letter_dir: String,
+    pub max_entries: usize,
+    pub max_payload_bytes: usize,
+    // Serializes the writes and pruning of plugins running in different threads.
+    lock: Mutex<()>,
+}
+
+impl DeadLetterQueue {
+    // ----------------------------------------------------------------------
+    // new:
+    // ----------------------------------------------------------------------
+    pub fn new(dead_letter_dir: &str, max_entries: usize, max_payload_bytes: usize) -> Self {
+        DeadLetterQueue {
+            dead_letter_dir: traps_utils::get_absolute_path(dead_letter_dir),
+            max_entries: if max_entries < 1 {1} else {max_entries},
+            max_payload_bytes,
+            lock: Mutex::new(()),
+        }
+    }
+
+    // ----------------------------------------------------------------------
+    // new_from_config:
+    // ----------------------------------------------------------------------
+    /** Create a queue using the [dead_letter] section of the configuration.
+     * Capture is opt-in, so None is returned unless enabled is set to true.
+     */
+    pub fn new_from_config(config: &Config) -> Option<Self> {
+        let dconfig = config.dead_letter.clone().unwrap_or_default();
+        if !dconfig.enabled.unwrap_or(false) {
+            return None;
+        }
+        Some(DeadLetterQueue::new(dconfig.dead_letter_dir.as_deref().unwrap_or(DEFAULT_DEAD_LETTER_DIR),
+                                  dconfig.max_entries.unwrap_or(DEFAULT_MAX_ENTRIES),
+                                  dconfig.max_payload_bytes.unwrap_or(DEFAULT_MAX_PAYLOAD_BYTES)))
+    }
+
+    // ----------------------------------------------------------------------
+    // push:
+    // ----------------------------------------------------------------------
+    /** Write the entry to a new file and then remove the oldest entries if
+     * the queue is over its limit.  The file is written under a temporary
+     * name and renamed so that readers never see a partial entry.
+     */
+    pub fn push(&self, entry: &DeadLetterEntry) -> Result<(), Errors> {
+        let json = match serde_json::to_string_pretty(entry) {
+            Ok(s) => s,
+            Err(e) => return Result::Err(Errors::DeadLetterWriteError(self.dead_letter_dir.clone(), e.to_string())),
+        };
+
+        // Plugins capture dead letters concurrently.
+        let _guard = match self.lock.lock() {
+            Ok(g) => g,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        if let Err(e) = fs::create_dir_all(&self.dead_letter_dir) {
+            return Result::Err(Errors::AppDirCreateError(self.dead_letter_dir.clone(), e.to_string()));
+        }
+        let name = format!("{}-{}", Utc::now().format("%Y%m%dT%H%M%S%9fZ"), Uuid::new_v4().simple());
+        let tmp_file = Path::new(&self.dead_letter_dir).join(format!(".{}.tmp", name));
+        let file = Path::new(&self.dead_letter_dir).join(format!("{}.{}", name, DEAD_LETTER_EXT));
+        if let Err(e) = fs::write(&tmp_file, json).and_then(|_| fs::rename(&tmp_file, &file)) {
+            return Result::Err(Errors::DeadLetterWriteError(file.to_string_lossy().to_string(), e.to_string()));
+        }
+
+        // Remove the oldest entries.
+        let files = list_dead_letter_files(&self.dead_letter_dir)?;
+        if files.len() > self.max_entries {
+            for old in &files[..files.len() - self.max_entries] {
+                if let Err(e) = fs::remove_file(old) {
+                    return Result::Err(Errors::DeadLetterWriteError(old.to_string_lossy().to_string(), e.to_string()));
+                }
+            }
+        }
+        Result::Ok(())
+    }
+}
+
+// ***************************************************************************
+// PUBLIC FUNCTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// init:
+// ---------------------------------------------------------------------------
+/** Enable dead letter capture for all plugins using the application
+ * configuration.  This is called once at start up; capture is a no-op
+ * until it is called or unless dead letters are enabled.
+ */
+#[allow(dead_code)]
+pub fn init(config: &Config) {
+    if let Some(queue) = DeadLetterQueue::new_from_config(config) {
+        info!("{}", Errors::DeadLetterEnabled(queue.dead_letter_dir.clone(), queue.max_entries));
+        let _ = DEAD_LETTER_QUEUE.set(queue);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// capture:
+// ---------------------------------------------------------------------------
+/** Record a payload rejected by a plugin along with the error that describes
+ * why.  Failures are logged but not surfaced since the caller has already
+ * given up on the payload.
+ */
+pub fn capture(plugin_name: &str, reason: DeadLetterReason, err: &Errors, payload: &[u8]) {
+    let queue = match DEAD_LETTER_QUEUE.get() {
+        Some(q) => q,
+        None => return,
+    };
+    let entry = DeadLetterEntry::new(plugin_name, reason, &err.to_string(), payload, queue.max_payload_bytes);
+    if let Err(e) = queue.push(&entry) {
+        error!("{}", e);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// read_dead_letters:
+// ---------------------------------------------------------------------------
+/** Read all entries in a dead letter directory from oldest to newest.  A
+ * missing directory has no entries.
+ */
+#[allow(dead_code)]
+pub fn read_dead_letters(dead_letter_dir: &str) -> Result<Vec<DeadLetterEntry>, Errors> {
+    if !Path::new(dead_letter_dir).exists() {
+        return Result::Ok(vec![]);
+    }
+    let mut entries = vec![];
+    for file in list_dead_letter_files(dead_letter_dir)? {
+        let file_str = file.to_string_lossy().to_string();
+        let contents = match fs::read_to_string(&file) {
+            Ok(c) => c,
+            // The entry may have been pruned while we were reading.
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Result::Err(Errors::DeadLetterReadError(file_str, e.to_string())),
+        };
+        match serde_json::from_str::<DeadLetterEntry>(&contents) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => return Result::Err(Errors::DeadLetterReadError(file_str, e.to_string())),
+        }
+    }
+    Result::Ok(entries)
+}
+
+// ***************************************************************************
+// INSPECT
+// ***************************************************************************
+/** List the payloads that plugins rejected.  Entries can be filtered by
+ * plugin name and reason, and the limit keeps only the most recent entries.
+ */
+#[derive(Args, Debug)]
+pub struct DeadLetterParms {
+    /// The dead letter directory
+    #[arg(long = "dir", value_name = "DEAD_LETTER_DIR", default_value = DEFAULT_DEAD_LETTER_DIR,
+          value_parser = traps_utils::parse_absolute_path)]
+    pub dead_letter_dir: String,
+    /// Only list the payloads rejected by this plugin
+    #[arg(long = "plugin", value_name = "NAME")]
+    pub plugin_name: Option<String>,
+    /// Only list the payloads rejected for this reason, such as NotHandled
+    #[arg(long)]
+    pub reason: Option<String>,
+    /// Only list this many of the most recent payloads
+    #[arg(long, value_name = "N")]
+    pub limit: Option<usize>,
+    /// Follow each entry with a hex dump of its captured payload
+    #[arg(long = "payload")]
+    pub show_payload: bool,
+}
+
+// ---------------------------------------------------------------------------
+// inspect:
+// ---------------------------------------------------------------------------
+/** Format the selected dead letters for display.  Each entry is shown on one
+ * line with the start of its payload in hex, or followed by a hex dump of the
+ * whole captured payload if show_payload is set.
+ */
+#[allow(dead_code)]
+pub fn inspect(parms: &DeadLetterParms) -> Result<String, Errors> {
+    let mut entries = read_dead_letters(&parms.dead_letter_dir)?;
+    entries.retain(|e| {
+        parms.plugin_name.as_ref().map(|p| *p == e.plugin_name).unwrap_or(true) &&
+        parms.reason.as_ref().map(|r| r.eq_ignore_ascii_case(&e.reason.to_string())).unwrap_or(true)
+    });
+    if let Some(limit) = parms.limit {
+        if entries.len() > limit {
+            entries.drain(..entries.len() - limit);
+        }
+    }
+
+    let mut out = format!("{} dead letter(s) in {}\n", entries.len(), parms.dead_letter_dir);
+    for entry in &entries {
+        let payload = entry.get_payload_bytes().unwrap_or_default();
+        let preview = &payload[..payload.len().min(HEX_LINE_BYTES)];
+        out.push_str(&format!("\n{}  {}  {}  {} bytes{}\n  {}\n",
+                              entry.recorded, entry.plugin_name, entry.reason, entry.payload_len,
+                              if entry.truncated {" (truncated)"} else {""}, entry.detail));
+        if parms.show_payload {
+            for (i, chunk) in payload.chunks(HEX_LINE_BYTES).enumerate() {
+                out.push_str(&format!("  {:08x}  {}\n", i * HEX_LINE_BYTES, to_hex(chunk)));
+            }
+        } else if !preview.is_empty() {
+            let more = if payload.len() > preview.len() {" ..."} else {""};
+            out.push_str(&format!("  {}{}\n", to_hex(preview), more));
+        }
+    }
+    Result::Ok(out)
+}
+
+// ***************************************************************************
+// PRIVATE FUNCTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// list_dead_letter_files:
+// ---------------------------------------------------------------------------
+/** The dead letter files in a directory sorted from oldest to newest. */
+fn list_dead_letter_files(dead_letter_dir: &str) -> Result<Vec<PathBuf>, Errors> {
+    let dir = match fs::read_dir(dead_letter_dir) {
+        Ok(d) => d,
+        Err(e) => return Result::Err(Errors::DeadLetterReadError(dead_letter_dir.to_string(), e.to_string())),
+    };
+    let mut files: Vec<PathBuf> = dir.filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == DEAD_LETTER_EXT))
+        .collect();
+    files.sort();
+    Result::Ok(files)
+}
+
+// ---------------------------------------------------------------------------
+// to_hex:
+// ---------------------------------------------------------------------------
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect::<Vec<String>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::config::DeadLetterConfig;
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: dead_letter.rs");
+    }
+
+    fn test_dir() -> String {
+        std::env::temp_dir().join(format!("traps-dead-letter-{}", Uuid::new_v4())).to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn push_and_prune() {
+        let dir = test_dir();
+        let queue = DeadLetterQueue::new(&dir, 3, 4);
+        let err = Errors::EventInvalidLen("TestPlugin".to_string(), 1);
+        for i in 0..5u8 {
+            let entry = DeadLetterEntry::new("TestPlugin", DeadLetterReason::TooShort, &err.to_string(), &[i; 6], 4);
+            queue.push(&entry).unwrap();
+        }
+
+        // Only the newest entries are kept and payloads are truncated.
+        let entries = read_dead_letters(&dir).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].get_payload_bytes().unwrap(), vec![2u8; 4]);
+        assert_eq!(entries[2].get_payload_bytes().unwrap(), vec![4u8; 4]);
+        assert_eq!(entries[2].payload_len, 6);
+        assert!(entries[2].truncated);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn opt_in() {
+        // Capture is off unless the configuration enables it.
+        let mut config = Config::default();
+        assert!(DeadLetterQueue::new_from_config(&config).is_none());
+        config.dead_letter = Some(DeadLetterConfig {enabled: Some(false), ..Default::default()});
+        assert!(DeadLetterQueue::new_from_config(&config).is_none());
+        config.dead_letter = Some(DeadLetterConfig {enabled: Some(true), max_entries: Some(5), ..Default::default()});
+        assert_eq!(DeadLetterQueue::new_from_config(&config).unwrap().max_entries, 5);
+    }
+
+    #[test]
+    fn inspect_args() {
+        let dir = test_dir();
+        let queue = DeadLetterQueue::new(&dir, 10, 1024);
+        let err = Errors::EventNotHandledError("ScorePlugin".to_string(), "[1, 0]".to_string());
+        queue.push(&DeadLetterEntry::new("ScorePlugin", DeadLetterReason::NotHandled, &err.to_string(), &[1, 0, 9], 1024)).unwrap();
+        queue.push(&DeadLetterEntry::new("StorePlugin", DeadLetterReason::TooShort, "short", &[1], 1024)).unwrap();
+
+        let parms: DeadLetterParms = traps_utils::parse_args(&["--dir", &dir, "--reason", "nothandled", "--payload"]).unwrap();
+        let out = inspect(&parms).unwrap();
+        assert!(out.starts_with("1 dead letter(s)"), "{}", out);
+        assert!(out.contains("ScorePlugin  NotHandled  3 bytes"), "{}", out);
+        assert!(out.contains("00000000  01 00 09"), "{}", out);
+
+        assert!(traps_utils::parse_args::<DeadLetterParms>(&["--limit", "x"]).is_err());
+        assert!(traps_utils::parse_args::<DeadLetterParms>(&["--bogus"]).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,679 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+use clap::{ArgGroup, Args, ValueEnum};
+
+use event_engine::events::Event;
+use crate::config::errors::Errors;
+use crate::events::{NewImageEvent, EVENT_PREFIX_LEN};
+use crate::journal;
+use crate::traps_utils;
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+// The labels treated as "nothing in the image" unless --negative is specified.
+const DEFAULT_NEGATIVE_LABEL: &str = "empty";
+
+// The suffix of the score files written by the image store's Save actions.
+const SCORE_SUFFIX: &str = ".score";
+
+// The actions recorded for each image.  Save and ReduceSave are the ImageStoredEvent
+// destinations, the others are reported for deleted, abandoned and unprocessed images.
+pub const ACTION_SAVE: &str = "Save";
+pub const ACTION_REDUCE_SAVE: &str = "ReduceSave";
+pub const ACTION_DELETE: &str = "Delete";
+pub const ACTION_ERROR: &str = "Error";
+pub const ACTION_NONE: &str = "None";
+
+// The predicted label of images that were never scored.
+const PREDICTED_NONE: &str = "none";
+
+// ***************************************************************************
+// IMAGE RECORDS
+// ***************************************************************************
+/** Everything known about one image of a simulation run.  The truth comes from
+ * the ground truth file, or else the generator's mapping file.  The scores and
+ * action come from the journal or the score files, and the size of the image
+ * as received comes from the journal's NewImageEvents.
+ */
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct ImageRecord {
+    pub uuid: String,
+    pub image_name: String,
+    pub truth: Option<String>,
+    pub scores: Option<Vec<(String, f32)>>,
+    pub action: Option<String>,
+    pub image_bytes: Option<u64>,
+    pub stored_bytes: Option<u64>,
+}
+
+impl ImageRecord {
+    // ---------------------------------------------------------------------------
+    // predicted_label:
+    // ---------------------------------------------------------------------------
+    /** The highest scoring label, or none if the image was not scored. */
+    pub fn predicted_label(&self) -> String {
+        self.scores.as_ref()
+            .and_then(|s| s.iter().max_by(|a, b| a.1.total_cmp(&b.1)))
+            .map(|s| s.0.clone())
+            .unwrap_or_else(|| PREDICTED_NONE.to_string())
+    }
+
+    // ---------------------------------------------------------------------------
+    // is_kept:
+    // ---------------------------------------------------------------------------
+    pub fn is_kept(&self) -> bool {
+        matches!(self.action.as_deref(), Some(ACTION_SAVE) | Some(ACTION_REDUCE_SAVE))
+    }
+}
+
+/** Where the image store's decisions are read from. */
+#[derive(Debug, Clone)]
+pub enum DecisionSource {
+    Journal(String),
+    ScoreFiles(String),
+}
+
+// ---------------------------------------------------------------------------
+// load_ground_truth:
+// ---------------------------------------------------------------------------
+/** Read the image generator's ground_truth.csv, which has a header line naming
+ * the ground_truth and image_name columns.  Returns image name to label.
+ */
+pub fn load_ground_truth(file: &str) -> Result<HashMap<String, String>, Errors> {
+    let contents = match fs::read_to_string(file) {
+        Ok(c) => c,
+        Err(e) => return Result::Err(Errors::EvaluationReadError(file.to_string(), e.to_string())),
+    };
+    let mut lines = contents.lines().filter(|l| !l.trim().is_empty());
+    let header = split_csv_line(lines.next().unwrap_or_default());
+    let column = |name: &str| header.iter().position(|h| h == name)
+        .ok_or_else(|| Errors::EvaluationReadError(file.to_string(), format!("missing {} column", name)));
+    let (truth_col, name_col) = (column("ground_truth")?, column("image_name")?);
+
+    let mut truth = HashMap::new();
+    for line in lines {
+        let fields = split_csv_line(line);
+        if let (Some(t), Some(n)) = (fields.get(truth_col), fields.get(name_col)) {
+            truth.insert(n.clone(), t.clone());
+        }
+    }
+    Result::Ok(truth)
+}
+
+// ---------------------------------------------------------------------------
+// load_images:
+// ---------------------------------------------------------------------------
+/** Create a record for each image in the generator's uuid mapping file, taking
+ * its truth from the ground truth file when the image is listed there.
+ */
+pub fn load_images(mapping_file: &str, ground_truth: &HashMap<String, String>)
+                   -> Result<BTreeMap<String, ImageRecord>, Errors> {
+    let mapping: Value = match fs::read(mapping_file).map_err(|e| e.to_string())
+                                  .and_then(|b| serde_json::from_slice(&b).map_err(|e| e.to_string())) {
+        Ok(m) => m,
+        Err(e) => return Result::Err(Errors::EvaluationReadError(mapping_file.to_string(), e)),
+    };
+
+    let mut images = BTreeMap::new();
+    for (uuid, entry) in mapping.as_object().into_iter().flatten() {
+        let image_name = match entry["image_name"].as_str() {
+            Some(n) => n.to_string(),
+            None => continue,
+        };
+        let truth = ground_truth.get(&image_name).cloned()
+            .or_else(|| entry["ground_truth"].as_str().map(String::from))
+            .filter(|t| t != "unavailable");
+        images.insert(uuid.clone(), ImageRecord {uuid: uuid.clone(), image_name, truth, ..Default::default()});
+    }
+    Result::Ok(images)
+}
+
+// ---------------------------------------------------------------------------
+// load_decisions:
+// ---------------------------------------------------------------------------
+/** Add the scores and store actions recorded in the journal or score files to
+ * the image records.
+ */
+pub fn load_decisions(images: &mut BTreeMap<String, ImageRecord>, source: &DecisionSource,
+                      image_file_prefix: &str) -> Result<(), Errors> {
+    match source {
+        DecisionSource::Journal(file) => load_journal_decisions(images, file),
+        DecisionSource::ScoreFiles(dir) => load_score_file_decisions(images, dir, image_file_prefix),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// load_stored_bytes:
+// ---------------------------------------------------------------------------
+/** Record the on-disk size of each kept image's files, which are named with
+ * the image file prefix followed by the image uuid.
+ */
+pub fn load_stored_bytes(images: &mut BTreeMap<String, ImageRecord>, image_dir: &str,
+                         image_file_prefix: &str) -> Result<(), Errors> {
+    let entries = match fs::read_dir(image_dir) {
+        Ok(e) => e,
+        Err(e) => return Result::Err(Errors::EvaluationReadError(image_dir.to_string(), e.to_string())),
+    };
+    let mut sizes: HashMap<String, u64> = HashMap::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let uuid = match name.strip_prefix(image_file_prefix).and_then(|n| n.get(..36)) {
+            Some(u) => u.to_string(),
+            None => continue,
+        };
+        if let Ok(meta) = entry.metadata() {
+            *sizes.entry(uuid).or_default() += meta.len();
+        }
+    }
+    for image in images.values_mut().filter(|i| i.is_kept()) {
+        image.stored_bytes = Some(sizes.get(&image.uuid).copied().unwrap_or(0));
+    }
+    Result::Ok(())
+}
+
+// ***************************************************************************
+// EVALUATION
+// ***************************************************************************
+/** Counts and rates for one label or action.  Rates are absent when their
+ * denominator is zero.
+ */
+#[derive(Debug, Serialize, PartialEq)]
+pub struct RateSummary {
+    pub name: String,
+    pub images: usize,
+    pub true_positives: usize,
+    pub precision: Option<f64>,
+    pub recall: Option<f64>,
+}
+
+/** Whether the images the store kept are the ones containing something. */
+#[derive(Debug, Serialize, PartialEq)]
+pub struct KeptSummary {
+    pub true_positives: usize,
+    pub false_positives: usize,
+    pub false_negatives: usize,
+    pub true_negatives: usize,
+    pub precision: Option<f64>,
+    pub recall: Option<f64>,
+    pub images_saved_per_true_positive: Option<f64>,
+    pub storage_bytes: Option<u64>,
+}
+
+/** The evaluation report.  Images without a ground truth are counted but
+ * excluded from the metrics.
+ */
+#[derive(Debug, Serialize)]
+pub struct Evaluation {
+    pub images: usize,
+    pub images_without_truth: usize,
+    pub negative_labels: Vec<String>,
+    pub confusion_matrix: BTreeMap<String, BTreeMap<String, usize>>,
+    pub labels: Vec<RateSummary>,
+    pub actions: Vec<RateSummary>,
+    pub kept: KeptSummary,
+}
+
+// ---------------------------------------------------------------------------
+// evaluate:
+// ---------------------------------------------------------------------------
+/** Compare the scores and store actions to the ground truth.  The confusion
+ * matrix maps each true label to the count of each predicted (highest scoring)
+ * label.  For actions, an image is positive if its true label is not one of
+ * the negative labels, and Save and ReduceSave count as keeping the image.
+ */
+pub fn evaluate(images: &BTreeMap<String, ImageRecord>, negative_labels: &[String]) -> Evaluation {
+    let judged: Vec<&ImageRecord> = images.values().filter(|i| i.truth.is_some()).collect();
+    let is_positive = |i: &ImageRecord| !negative_labels.contains(i.truth.as_ref().unwrap());
+
+    // Labels.
+    let mut confusion_matrix: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+    for image in &judged {
+        *confusion_matrix.entry(image.truth.clone().unwrap_or_default()).or_default()
+                         .entry(image.predicted_label()).or_default() += 1;
+    }
+    let all_labels: BTreeSet<String> = confusion_matrix.iter()
+        .flat_map(|(t, row)| std::iter::once(t.clone()).chain(row.keys().cloned())).collect();
+    let labels = all_labels.into_iter().map(|label| {
+        let tp = confusion_matrix.get(&label).and_then(|r| r.get(&label)).copied().unwrap_or(0);
+        let truth = confusion_matrix.get(&label).map(|r| r.values().sum()).unwrap_or(0);
+        let predicted: usize = confusion_matrix.values().filter_map(|r| r.get(&label)).sum();
+        RateSummary {name: label, images: truth, true_positives: tp, precision: ratio(tp, predicted), recall: ratio(tp, truth)}
+    }).collect();
+
+    // Actions.
+    let positives = judged.iter().filter(|i| is_positive(i)).count();
+    let mut actions = vec![];
+    for action in [ACTION_SAVE, ACTION_REDUCE_SAVE, ACTION_DELETE, ACTION_ERROR, ACTION_NONE] {
+        let taken: Vec<&&ImageRecord> = judged.iter().filter(|i| i.action.as_deref().unwrap_or(ACTION_NONE) == action).collect();
+        if taken.is_empty() && action == ACTION_NONE {
+            continue;
+        }
+        let tp = taken.iter().filter(|i| is_positive(i)).count();
+        actions.push(RateSummary {name: action.to_string(), images: taken.len(), true_positives: tp,
+                                  precision: ratio(tp, taken.len()), recall: ratio(tp, positives)});
+    }
+
+    // Kept images.
+    let count = |kept: bool, positive: bool| judged.iter().filter(|i| i.is_kept() == kept && is_positive(i) == positive).count();
+    let (tp, fp, fneg, tn) = (count(true, true), count(true, false), count(false, true), count(false, false));
+    let kept_images: Vec<&ImageRecord> = images.values().filter(|i| i.is_kept()).collect();
+    let storage_bytes = kept_images.iter().map(|i| i.stored_bytes).sum::<Option<u64>>().filter(|_| !kept_images.is_empty());
+    let kept = KeptSummary {true_positives: tp, false_positives: fp, false_negatives: fneg, true_negatives: tn,
+                            precision: ratio(tp, tp + fp), recall: ratio(tp, tp + fneg),
+                            images_saved_per_true_positive: ratio(tp + fp, tp), storage_bytes};
+
+    Evaluation {images: images.len(), images_without_truth: images.len() - judged.len(),
+                negative_labels: negative_labels.to_vec(), confusion_matrix, labels, actions, kept}
+}
+
+impl Evaluation {
+    // ---------------------------------------------------------------------------
+    // to_markdown:
+    // ---------------------------------------------------------------------------
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# Image Store Evaluation\n\n");
+        out.push_str(&format!("{} images, {} without ground truth.  Negative labels: {}.\n\n",
+                              self.images, self.images_without_truth, self.negative_labels.join(", ")));
+
+        // Kept images.
+        let k = &self.kept;
+        out.push_str("## Kept Images\n\n| Metric | Value |\n|---|---|\n");
+        for (metric, value) in [("True positives", k.true_positives.to_string()),
+                                ("False positives", k.false_positives.to_string()),
+                                ("False negatives", k.false_negatives.to_string()),
+                                ("True negatives", k.true_negatives.to_string()),
+                                ("Precision", fmt_rate(k.precision)),
+                                ("Recall", fmt_rate(k.recall)),
+                                ("Images saved per true positive", fmt_rate(k.images_saved_per_true_positive)),
+                                ("Storage bytes", k.storage_bytes.map_or("-".to_string(), |b| b.to_string()))] {
+            out.push_str(&format!("| {} | {} |\n", metric, value));
+        }
+
+        // Confusion matrix.
+        let predicted: BTreeSet<&String> = self.confusion_matrix.values().flat_map(|r| r.keys()).collect();
+        out.push_str("\n## Confusion Matrix\n\n| Truth \\ Predicted |");
+        for p in &predicted {
+            out.push_str(&format!(" {} |", p));
+        }
+        out.push_str(&format!("\n|---|{}\n", "---|".repeat(predicted.len())));
+        for (truth, row) in &self.confusion_matrix {
+            out.push_str(&format!("| {} |", truth));
+            for p in &predicted {
+                out.push_str(&format!(" {} |", row.get(*p).copied().unwrap_or(0)));
+            }
+            out.push('\n');
+        }
+
+        // Labels and actions.
+        for (title, rows) in [("Labels", &self.labels), ("Actions", &self.actions)] {
+            out.push_str(&format!("\n## {}\n\n| Name | Images | True positives | Precision | Recall |\n|---|---|---|---|---|\n", title));
+            for r in rows {
+                out.push_str(&format!("| {} | {} | {} | {} | {} |\n", r.name, r.images, r.true_positives,
+                                      fmt_rate(r.precision), fmt_rate(r.recall)));
+            }
+        }
+        out
+    }
+}
+
+// ***************************************************************************
+// COMMAND
+// ***************************************************************************
+/** Report a simulation run's image store decisions against the ground truth.
+ * The decisions are read from either a journal or the score files.
+ */
+#[derive(Args, Debug)]
+#[command(group(ArgGroup::new("decisions").required(true).args(["journal", "scores"])))]
+pub struct EvaluateParms {
+    /// The image generator's ground_truth.csv
+    #[arg(long = "ground-truth", value_name = "CSV", value_parser = traps_utils::parse_absolute_path)]
+    pub ground_truth_file: String,
+    /// The image generator's uuid_image_mapping.json
+    #[arg(long = "mapping", value_name = "JSON", value_parser = traps_utils::parse_absolute_path)]
+    pub mapping_file: String,
+    /// Read the scores and decisions from an observer journal; its rotated files are read first
+    #[arg(long, value_name = "FILE", value_parser = traps_utils::parse_absolute_path)]
+    pub journal: Option<String>,
+    /// Read the scores and decisions from the image store's score files in this directory
+    #[arg(long, value_name = "IMAGE_DIR", value_parser = traps_utils::parse_absolute_path)]
+    pub scores: Option<String>,
+    /// Measure the storage of the images kept in this directory [default: the --scores directory]
+    #[arg(long = "images", value_name = "IMAGE_DIR", value_parser = traps_utils::parse_absolute_path)]
+    pub image_dir: Option<String>,
+    /// The image_file_prefix of the image and score files
+    #[arg(long = "prefix", value_name = "IMAGE_FILE_PREFIX", default_value = "")]
+    pub image_file_prefix: String,
+    /// A ground truth label meaning the image has nothing worth keeping; can be repeated
+    #[arg(long = "negative", value_name = "LABEL", default_value = DEFAULT_NEGATIVE_LABEL)]
+    pub negative_labels: Vec<String>,
+    /// The report format
+    #[arg(long, value_enum, default_value_t = ReportFormat::Json)]
+    pub format: ReportFormat,
+    /// Also write the report to this file
+    #[arg(long = "output", value_name = "FILE", value_parser = traps_utils::parse_absolute_path)]
+    pub output_file: Option<String>,
+}
+
+/** The formats of the evaluate and sweep reports. */
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+    Json,
+    #[value(alias = "md")]
+    Markdown,
+}
+
+impl EvaluateParms {
+    // ----------------------------------------------------------------------
+    // get_source:
+    // ----------------------------------------------------------------------
+    /** Where the decisions are read from.  The command line requires exactly
+     * one of --journal and --scores.
+     */
+    pub fn get_source(&self) -> DecisionSource {
+        match (&self.journal, &self.scores) {
+            (Some(file), _) => DecisionSource::Journal(file.clone()),
+            (None, dir) => DecisionSource::ScoreFiles(dir.clone().unwrap_or_default()),
+        }
+    }
+
+    // ----------------------------------------------------------------------
+    // get_image_dir:
+    // ----------------------------------------------------------------------
+    /** The directory whose kept images are measured, which defaults to the
+     * score files' directory.
+     */
+    pub fn get_image_dir(&self) -> Option<&String> {
+        self.image_dir.as_ref().or(self.scores.as_ref())
+    }
+
+    // ----------------------------------------------------------------------
+    // is_markdown:
+    // ----------------------------------------------------------------------
+    pub fn is_markdown(&self) -> bool {self.format == ReportFormat::Markdown}
+
+    // ----------------------------------------------------------------------
+    // load_images:
+    // ----------------------------------------------------------------------
+    /** Load the image records with their truth, scores and decisions. */
+    pub fn load_images(&self) -> Result<BTreeMap<String, ImageRecord>, Errors> {
+        let ground_truth = load_ground_truth(&self.ground_truth_file)?;
+        let mut images = load_images(&self.mapping_file, &ground_truth)?;
+        load_decisions(&mut images, &self.get_source(), &self.image_file_prefix)?;
+        Result::Ok(images)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// run:
+// ---------------------------------------------------------------------------
+/** Evaluate a simulation run and return the report.  The report is also
+ * written to the output file if one was specified.
+ */
+pub fn run(parms: &EvaluateParms) -> Result<String, Errors> {
+    let mut images = parms.load_images()?;
+    if let Some(dir) = parms.get_image_dir() {
+        load_stored_bytes(&mut images, dir, &parms.image_file_prefix)?;
+    }
+
+    let evaluation = evaluate(&images, &parms.negative_labels);
+    let report = if parms.is_markdown() {
+        evaluation.to_markdown()
+    } else {
+        serde_json::to_string_pretty(&evaluation).unwrap_or_default() + "\n"
+    };
+    if let Some(file) = &parms.output_file {
+        if let Err(e) = traps_utils::create_or_replace_file(file, report.as_bytes()) {
+            return Result::Err(Errors::EvaluationWriteError(file.clone(), e.to_string()));
+        }
+    }
+    Result::Ok(report)
+}
+
+// ***************************************************************************
+// PRIVATE FUNCTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// load_journal_decisions:
+// ---------------------------------------------------------------------------
+/** Take the image size from NewImageEvents, the scores from ImageScoredEvents
+ * and the action from ImageStoredEvent, ImageDeletedEvent and
+ * ImageProcessingErrorEvent entries.  An error does not replace a decision the
+ * store already made.  The journal's rotated files are read before the journal
+ * file itself.
+ */
+fn load_journal_decisions(images: &mut BTreeMap<String, ImageRecord>, journal_file: &str) -> Result<(), Errors> {
+    for segment in journal::get_journal_segments(journal_file)? {
+        let segment = segment.to_string_lossy().to_string();
+        for entry in journal::read_journal(&segment)? {
+            let image = match entry.event["image_uuid"].as_str().and_then(|u| images.get_mut(u)) {
+                Some(i) => i,
+                None => continue,
+            };
+            match entry.event_name.as_str() {
+                "NewImageEvent" => {
+                    let raw = entry.get_raw_bytes().unwrap_or_default();
+                    if let Ok(ev) = NewImageEvent::from_bytes(raw.get(EVENT_PREFIX_LEN..).unwrap_or_default().to_vec()) {
+                        image.image_bytes = Some(ev.get_image().len() as u64).filter(|n| *n > 0);
+                    }
+                },
+                "ImageScoredEvent" => image.scores = Some(json_scores(&entry.event)),
+                "ImageStoredEvent" => image.action = entry.event["destination"].as_str().map(String::from),
+                "ImageDeletedEvent" => image.action = Some(ACTION_DELETE.to_string()),
+                "ImageProcessingErrorEvent" => {
+                    image.action.get_or_insert_with(|| ACTION_ERROR.to_string());
+                },
+                _ => (),
+            }
+        }
+    }
+    Result::Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// load_score_file_decisions:
+// ---------------------------------------------------------------------------
+/** Score files are only written when an image is saved, so images with a score
+ * file are recorded as saved and all others as deleted.  Save and ReduceSave
+ * cannot be told apart this way.
+ */
+fn load_score_file_decisions(images: &mut BTreeMap<String, ImageRecord>, dir: &str,
+                             image_file_prefix: &str) -> Result<(), Errors> {
+    let entries = match fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(e) => return Result::Err(Errors::EvaluationReadError(dir.to_string(), e.to_string())),
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.starts_with(image_file_prefix) || !name.ends_with(SCORE_SUFFIX) {
+            continue;
+        }
+        let path = Path::new(dir).join(&name).to_string_lossy().to_string();
+        let event: Value = match fs::read(&path).map_err(|e| e.to_string())
+                                     .and_then(|b| serde_json::from_slice(&b).map_err(|e| e.to_string())) {
+            Ok(v) => v,
+            Err(e) => return Result::Err(Errors::EvaluationReadError(path, e)),
+        };
+        if let Some(image) = event["image_uuid"].as_str().and_then(|u| images.get_mut(u)) {
+            image.scores = Some(json_scores(&event));
+            image.action = Some(ACTION_SAVE.to_string());
+        }
+    }
+    for image in images.values_mut() {
+        image.action.get_or_insert_with(|| ACTION_DELETE.to_string());
+    }
+    Result::Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// json_scores:
+// ---------------------------------------------------------------------------
+/** The label scores of an ImageScoredEvent in JSON form. */
+fn json_scores(event: &Value) -> Vec<(String, f32)> {
+    event["scores"].as_array().into_iter().flatten()
+        .filter_map(|s| Some((s["label"].as_str()?.to_string(), s["probability"].as_f64()? as f32)))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// split_csv_line:
+// ---------------------------------------------------------------------------
+/** Split a CSV line on commas that are not inside double quotes. */
+fn split_csv_line(line: &str) -> Vec<String> {
+    let (mut fields, mut field, mut quoted) = (vec![], String::new(), false);
+    for c in line.trim_end_matches('\r').chars() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields.into_iter().map(|f| f.trim().to_string()).collect()
+}
+
+// ---------------------------------------------------------------------------
+// ratio:
+// ---------------------------------------------------------------------------
+fn ratio(numerator: usize, denominator: usize) -> Option<f64> {
+    if denominator == 0 {None} else {Some(numerator as f64 / denominator as f64)}
+}
+
+// ---------------------------------------------------------------------------
+// fmt_rate:
+// ---------------------------------------------------------------------------
+fn fmt_rate(rate: Option<f64>) -> String {
+    rate.map_or("-".to_string(), |r| format!("{:.3}", r))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: evaluation.rs");
+    }
+
+    fn write(dir: &Path, file: &str, contents: &str) -> String {
+        let path = dir.join(file);
+        fs::write(&path, contents).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn evaluate_score_files() {
+        let dir = std::env::temp_dir().join(format!("traps-evaluation-{}", Uuid::new_v4()));
+        let image_dir = dir.join("images");
+        fs::create_dir_all(&image_dir).unwrap();
+        let uuids: Vec<String> = (0..4).map(|_| Uuid::new_v4().hyphenated().to_string()).collect();
+        let ground_truth = write(&dir, "ground_truth.csv",
+                                 "ground_truth,image_name\nanimal,a.jpg\nanimal,b.jpg\nempty,c.jpg\n\"empty\",\"d, 1.jpg\"\n");
+        let mapping: serde_json::Map<String, Value> = uuids.iter().zip(["a.jpg", "b.jpg", "c.jpg", "d, 1.jpg"])
+            .map(|(u, n)| (u.clone(), serde_json::json!({"UUID": u, "image_name": n, "ground_truth": "unavailable"})))
+            .collect();
+        let mapping = write(&dir, "uuid_image_mapping.json", &Value::Object(mapping).to_string());
+
+        // The first and third images were saved.
+        for (uuid, label) in [(&uuids[0], "animal"), (&uuids[2], "animal")] {
+            write(&image_dir, &format!("pre-{}.score", uuid),
+                  &format!(r#"{{"image_uuid": "{}", "scores": [{{"label": "{}", "probability": 0.9}}]}}"#, uuid, label));
+            write(&image_dir, &format!("pre-{}.jpg", uuid), "0123456789");
+        }
+
+        let stored_bytes: u64 = fs::read_dir(&image_dir).unwrap().map(|e| e.unwrap().metadata().unwrap().len()).sum();
+
+        let parms: EvaluateParms = traps_utils::parse_args(&["--ground-truth", &ground_truth, "--mapping", &mapping,
+                                                             "--scores", &image_dir.to_string_lossy(), "--prefix", "pre-"])
+                                       .unwrap();
+        assert_eq!(parms.get_image_dir().map(String::as_str), Some(image_dir.to_string_lossy().as_ref()));
+        assert_eq!(parms.negative_labels, [DEFAULT_NEGATIVE_LABEL]);
+        let report: Value = serde_json::from_str(&run(&parms).unwrap()).unwrap();
+        assert_eq!(report["images"], 4);
+        assert_eq!(report["confusion_matrix"]["animal"], serde_json::json!({"animal": 1, "none": 1}));
+        assert_eq!(report["confusion_matrix"]["empty"], serde_json::json!({"animal": 1, "none": 1}));
+        assert_eq!(report["kept"], serde_json::json!({"true_positives": 1, "false_positives": 1, "false_negatives": 1,
+                                                      "true_negatives": 1, "precision": 0.5, "recall": 0.5,
+                                                      "images_saved_per_true_positive": 2.0,
+                                                      "storage_bytes": stored_bytes}));
+        assert_eq!(report["actions"][0], serde_json::json!({"name": "Save", "images": 2, "true_positives": 1,
+                                                           "precision": 0.5, "recall": 0.5}));
+
+        // Markdown output.
+        let mut parms = parms;
+        parms.format = ReportFormat::Markdown;
+        let md = run(&parms).unwrap();
+        assert!(md.contains("| Truth \\ Predicted | animal | none |"));
+        assert!(md.contains("| Images saved per true positive | 2.000 |"));
+
+        assert!(traps_utils::parse_args::<EvaluateParms>(&["--ground-truth", &ground_truth, "--mapping", &mapping]).is_err());
+        assert!(traps_utils::parse_args::<EvaluateParms>(&["--ground-truth", &ground_truth, "--mapping", &mapping,
+                                                           "--journal", "x", "--scores", "y"]).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn evaluate_rotated_journal() {
+        let dir = std::env::temp_dir().join(format!("traps-evaluation-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let uuids: Vec<String> = (0..2).map(|_| Uuid::new_v4().hyphenated().to_string()).collect();
+        let ground_truth = write(&dir, "ground_truth.csv", "ground_truth,image_name\nanimal,a.jpg\nempty,b.jpg\n");
+        let mapping: serde_json::Map<String, Value> = uuids.iter().zip(["a.jpg", "b.jpg"])
+            .map(|(u, n)| (u.clone(), serde_json::json!({"UUID": u, "image_name": n, "ground_truth": "unavailable"})))
+            .collect();
+        let mapping = write(&dir, "uuid_image_mapping.json", &Value::Object(mapping).to_string());
+        let line = |name: &str, event: Value| {
+            serde_json::json!({"recorded": "2024-01-01T00:00:00Z", "event_name": name, "event": event, "raw": ""}).to_string()
+        };
+        let scored = |uuid: &str, label: &str| {
+            line("ImageScoredEvent", serde_json::json!({"image_uuid": uuid, "scores": [{"label": label, "probability": 0.9}]}))
+        };
+        let stored = |uuid: &str| line("ImageStoredEvent", serde_json::json!({"image_uuid": uuid, "destination": "Save"}));
+
+        // The first image's entries were all rotated out and the second image's
+        // deletion in events.jsonl.1 is replaced by the save in events.jsonl.
+        write(&dir, "events.jsonl.2", &scored(&uuids[0], "animal"));
+        write(&dir, "events.jsonl.1", &format!("{}\n{}\n{}\n", stored(&uuids[0]), scored(&uuids[1], "animal"),
+                                               line("ImageDeletedEvent", serde_json::json!({"image_uuid": &uuids[1]}))));
+        let journal = write(&dir, "events.jsonl", &stored(&uuids[1]));
+
+        let parms: EvaluateParms = traps_utils::parse_args(&["--ground-truth", &ground_truth, "--mapping", &mapping,
+                                                             "--journal", &journal]).unwrap();
+        let report: Value = serde_json::from_str(&run(&parms).unwrap()).unwrap();
+        assert_eq!(report["confusion_matrix"]["animal"], serde_json::json!({"animal": 1}));
+        assert_eq!(report["confusion_matrix"]["empty"], serde_json::json!({"animal": 1}));
+        assert_eq!(report["actions"][0], serde_json::json!({"name": "Save", "images": 2, "true_positives": 1,
+                                                           "precision": 0.5, "recall": 1.0}));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn evaluate_records() {
+        let record = |uuid: &str, truth: &str, label: Option<&str>, action: &str| ImageRecord {
+            uuid: uuid.to_string(), image_name: uuid.to_string(), truth: Some(truth.to_string()),
+            scores: label.map(|l| vec![(l.to_string(), 0.8), ("other".to_string(), 0.1)]),
+            action: Some(action.to_string()), image_bytes: None, stored_bytes: None};
+        let mut images = BTreeMap::new();
+        for r in [record("1", "deer", Some("deer"), ACTION_SAVE), record("2", "deer", Some("deer"), ACTION_REDUCE_SAVE),
+                  record("3", "blank", Some("deer"), ACTION_DELETE), record("4", "deer", None, ACTION_ERROR)] {
+            images.insert(r.uuid.clone(), r);
+        }
+        images.insert("5".to_string(), ImageRecord {uuid: "5".to_string(), ..Default::default()});
+
+        let eval = evaluate(&images, &["blank".to_string()]);
+        assert_eq!(eval.images_without_truth, 1);
+        assert_eq!(eval.labels[1], RateSummary {name: "deer".to_string(), images: 3, true_positives: 2,
+                                                precision: Some(2.0 / 3.0), recall: Some(2.0 / 3.0)});
+        assert_eq!(eval.actions.iter().map(|a| a.name.as_str()).collect::<Vec<_>>(),
+                   vec![ACTION_SAVE, ACTION_REDUCE_SAVE, ACTION_DELETE, ACTION_ERROR]);
+        assert_eq!(eval.kept.recall, Some(2.0 / 3.0));
+        assert_eq!(eval.kept.precision, Some(1.0));
+        assert_eq!(eval.kept.storage_bytes, None);
+    }
+}
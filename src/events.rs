@@ -3,10 +3,13 @@ use event_engine::events::{Event, EventType};
 use flatbuffers::{FlatBufferBuilder, InvalidFlatbuffer};
 use std::error::Error;
 use uuid::Uuid;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::io::{Read, Write};
 
 // Logging imports.
 use anyhow::Result;
+use log::error;
 
 // Application errors.
 use crate::config::errors::Errors;
@@ -14,1744 +17,354 @@ use crate::events_generated::gen_events;
 use crate::traps_utils::{timestamp_str, timestamp_str_to_datetime};
 
 // ***************************************************************************
-// CONSTANTS
+// EVENT REGISTRY
 // ***************************************************************************
-// Each event is assigned a binary prefix that zqm uses to route incoming
-// binary streams to all of the event's subscribers.
-pub const NEW_IMAGE_PREFIX: [u8; 2] = [0x01, 0x00];
-pub const IMAGE_RECEIVED_PREFIX:      [u8; 2] = [0x02, 0x00];
-pub const IMAGE_SCORED_PREFIX:        [u8; 2] = [0x03, 0x00];
-pub const IMAGE_STORED_PREFIX:        [u8; 2] = [0x04, 0x00];
-pub const IMAGE_DELETED_PREFIX:       [u8; 2] = [0x05, 0x00];
-pub const PLUGIN_STARTED_PREFIX:      [u8; 2] = [0x10, 0x00];
-pub const PLUGIN_TERMINATING_PREFIX:  [u8; 2] = [0x11, 0x00];
-pub const PLUGIN_TERMINATE_PREFIX:    [u8; 2] = [0x12, 0x00];
-pub const MONITOR_POWER_START_PREFIX: [u8; 2] = [0x20, 0x00];
-pub const MONITOR_POWER_STOP_PREFIX:  [u8; 2] = [0x21, 0x00];
-pub const EVENT_PREFIX_LEN: usize = NEW_IMAGE_PREFIX.len();
-
-// ***************************************************************************
-// PUBLIC FUNCTIONS
-// ***************************************************************************
-// ---------------------------------------------------------------------------
-// check_event_prefix:
-// ---------------------------------------------------------------------------
-/** Make sure zqm routing prefix matches the event is supposed to be associated
- * with.  Return true if they match, false otherwise.
- */
-pub fn check_event_prefix(prefix: [u8; 2], event_name: &str) -> bool {
-    match prefix {
-        NEW_IMAGE_PREFIX => {
-            event_name == "NewImageEvent" 
-        }
-        IMAGE_RECEIVED_PREFIX => {
-            event_name == "ImageReceivedEvent" 
-        }
-        IMAGE_SCORED_PREFIX => {
-            event_name == "ImageScoredEvent" 
-        }
-        IMAGE_STORED_PREFIX => {
-            event_name == "ImageStoredEvent" 
-        }
-        IMAGE_DELETED_PREFIX => {
-            event_name == "ImageDeletedEvent" 
-        }
-        PLUGIN_STARTED_PREFIX => {
-            event_name == "PluginStartedEvent" 
-        }
-        PLUGIN_TERMINATING_PREFIX => {
-            event_name == "PluginterminatingEvent" 
-        }
-        PLUGIN_TERMINATE_PREFIX => {
-            event_name == "PluginTerminateEvent" 
-        }
-        MONITOR_POWER_START_PREFIX => {
-            event_name == "MonitorPowerStartEvent" 
-        }
-        MONITOR_POWER_STOP_PREFIX => {
-            event_name == "MonitorPowerStopEvent" 
-        }
-        _ => false,
-    }
-}
-
-// ***************************************************************************
-// EVENTS
-// ***************************************************************************
-// ===========================================================================
-// NewImageEvent:
-// ===========================================================================
-pub struct NewImageEvent {
-    created: String,
-    image_uuid: Uuid,
-    image_format: String,
-    image: Vec<u8>,
-}
-
-// ------------------------------
-// ------ Trait EventType
-// ------------------------------
-impl EventType for NewImageEvent {
-    fn get_name(&self) -> String {
-        String::from("NewImageEvent")
-    }
-
-    fn get_filter(&self) -> Result<Vec<u8>, EngineError> {
-        Result::Ok(NEW_IMAGE_PREFIX.to_vec())
-    }
-}
-
-// ------------------------------
-// ------ Trait Event
-// ------------------------------
-impl Event for NewImageEvent {
-    // ----------------------------------------------------------------------
-    // to_bytes:
-    // ----------------------------------------------------------------------
-    /** Convert the event to a raw byte array (prefix + flatbuffer). */
-    fn to_bytes(&self) -> Result<Vec<u8>, EngineError> {
-        // Create a new flatbuffer.
-        let mut fbuf = FlatBufferBuilder::new();
-
-        // Assign the generated arguments object from our application object.
-        // Create the generated event offset object using the generated arguments.
-        let args = gen_events::NewImageEventArgs {
-            event_create_ts: Some(fbuf.create_string(&self.created)),
-            image_uuid: Some(fbuf.create_string(&self.image_uuid.hyphenated().to_string())),
-            image_format: Some(fbuf.create_string(&self.image_format)),
-            image: Some(fbuf.create_vector(&self.image)),
-        };
-        let event_offset = gen_events::NewImageEvent::create(&mut fbuf, &args);
-
-        // Create generated event arguments which are a union for all possible events.
-        // Create the generated event union offset object using the union arguments.
-        let union_args = gen_events::EventArgs {
-            event_type: gen_events::EventType::NewImageEvent,
-            event: Some(event_offset.as_union_value()),
-        };
-
-        // All event serializations are completed in the same way.
-        Ok(serialize_flatbuffer(NEW_IMAGE_PREFIX, fbuf, union_args))
-    }
-
-    // ----------------------------------------------------------------------
-    // from_bytes:
-    // ----------------------------------------------------------------------
-    /** Get a NewImageEvent from raw event bytes that do NOT include the zqm prefix. */
-    fn from_bytes(bytes: Vec<u8>) -> Result<NewImageEvent, Box<dyn Error>>
-    where
-        Self: Sized,
-    {
-        // Get the union of all possible generated events.
-        let event = bytes_to_gen_event(&bytes)?;
-
-        // Validate that we recieved the expected type of event.
-        let event_type = "NewImageEvent";
-        check_event_type(event_type, &event)?;
-
-        // Create the generated event from the raw flatbuffer.
-        let flatbuf_event = match event.event_as_new_image_event() {
-            Some(ev) => ev,
-            None => {
-                return Err(Box::new(Errors::EventCreateFromFlatbuffer(
-                    event_type.to_string(),
-                )))
-            }
-        };
-
-        // Return a camera-trap event given the flatbuffer generated event.
-        match NewImageEvent::new_from_gen(flatbuf_event) {
-            Ok(ev) => Result::Ok(ev),
-            Err(e) => Result::Err(Box::new(e)),
-        }
-    }
-}
-
-// ------------------------------
-// ------ Associated Functions
-// ------------------------------
-impl NewImageEvent {
-    // ----------------------------------------------------------------------
-    // new:
-    // ----------------------------------------------------------------------
-    #![allow(unused)]
-    pub fn new(image_uuid: Uuid, image_format: String, image: Vec<u8>) -> Self {
-        NewImageEvent {
-            created: timestamp_str(),
-            image_uuid,
-            image_format,
-            image,
-        }
-    }
-
-    // ----------------------------------------------------------------------
-    // new_from_gen:
-    // ----------------------------------------------------------------------
-    /** Construct a new event object from a generated flatbuffer object. */
-    pub fn new_from_gen(ev: gen_events::NewImageEvent) -> Result<Self, Errors> {
-        // Create and populate the image vector.
-        let raw = match ev.image() {
-            Some(raw) => raw,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("image"))),
-        };
-        let mut image = Vec::with_capacity(raw.len());
-        image.extend_from_slice(raw);
-
-        // Get the timestamp.
-        let created = match ev.event_create_ts() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("created"))),
-        };
-
-        // Get the uuid.
-        let u = match ev.image_uuid() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("uuid"))),
-        };
-        let uuid = match Uuid::parse_str(u) {
-            Ok(u) => u,
-            Err(e) => {
-                return Result::Err(Errors::UUIDParseError(
-                    String::from("image_uuid"),
-                    e.to_string(),
-                ))
-            }
-        };
-
-        // Get the image format string.
-        let image_format = match ev.image_format() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("image_format"))),
-        };
-
-        // Finally...
-        Result::Ok(NewImageEvent {
-            created: String::from(created),
-            image_uuid: uuid,
-            image_format: String::from(image_format),
-            image,
-        })
-    }
-}
-
-// ===========================================================================
-// ImageRecievedEvent:
-// ===========================================================================
-pub struct ImageReceivedEvent {
-    created: String,
-    image_uuid: Uuid,
-    image_format: String,
-}
-
-// ------------------------------
-// ------ Trait EventType
-// ------------------------------
-impl EventType for ImageReceivedEvent {
-    fn get_name(&self) -> String {
-        String::from("ImageReceivedEvent")
-    }
-
-    fn get_filter(&self) -> Result<Vec<u8>, EngineError> {
-        Result::Ok(IMAGE_RECEIVED_PREFIX.to_vec())
-    }
-}
-
-// ------------------------------
-// ------ Trait Event
-// ------------------------------
-impl Event for ImageReceivedEvent {
-    // ----------------------------------------------------------------------
-    // to_bytes:
-    // ----------------------------------------------------------------------
-    /** Convert the event to a raw byte array (prefix + flatbuffer). */
-    fn to_bytes(&self) -> Result<Vec<u8>, EngineError> {
-        // Create a new flatbuffer.
-        let mut fbuf = FlatBufferBuilder::new();
-
-        // Assign the generated arguments object from our application object.
-        // Create the generated event offset object using the generated arguments.
-        let args = gen_events::ImageReceivedEventArgs {
-            event_create_ts: Some(fbuf.create_string(&self.created)),
-            image_uuid: Some(fbuf.create_string(&self.image_uuid.hyphenated().to_string())),
-            image_format: Some(fbuf.create_string(&self.image_format)),
-        };
-        let event_offset = gen_events::ImageReceivedEvent::create(&mut fbuf, &args);
-
-        // Create generated event arguments which are a union for all possible events.
-        // Create the generated event union offset object using the union arguments.
-        let union_args = gen_events::EventArgs {
-            event_type: gen_events::EventType::ImageReceivedEvent,
-            event: Some(event_offset.as_union_value()),
-        };
-
-        // All event serializations are completed in the same way.
-        Ok(serialize_flatbuffer(
-            IMAGE_RECEIVED_PREFIX,
-            fbuf,
-            union_args,
-        ))
-    }
-
-    // ----------------------------------------------------------------------
-    // from_bytes:
-    // ----------------------------------------------------------------------
-    /** Get a NewImageEvent from raw event bytes that do NOT include the zqm prefix. */
-    fn from_bytes(bytes: Vec<u8>) -> Result<ImageReceivedEvent, Box<dyn Error>>
-    where
-        Self: Sized,
-    {
-        // Get the union of all possible generated events.
-        let event = bytes_to_gen_event(&bytes)?;
-
-        // Validate that we recieved the expected type of event.
-        let event_type = "ImageReceivedEvent";
-        check_event_type(event_type, &event)?;
-
-        // Create the generated event from the raw flatbuffer.
-        let flatbuf_event = match event.event_as_image_received_event() {
-            Some(ev) => ev,
-            None => {
-                return Err(Box::new(Errors::EventCreateFromFlatbuffer(
-                    event_type.to_string(),
-                )))
-            }
-        };
-
-        // Return a camera-trap event given the flatbuffer generated event.
-        match ImageReceivedEvent::new_from_gen(flatbuf_event) {
-            Ok(ev) => Result::Ok(ev),
-            Err(e) => Result::Err(Box::new(e)),
-        }
-    }
-}
-
-// ------------------------------
-// ------ Associated Functions
-// ------------------------------
-impl ImageReceivedEvent {
-    // ----------------------------------------------------------------------
-    // new:
-    // ----------------------------------------------------------------------
-    #![allow(unused)]
-    pub fn new(image_uuid: Uuid, image_format: String) -> Self {
-        ImageReceivedEvent {
-            created: timestamp_str(),
-            image_uuid,
-            image_format,
-        }
-    }
-
-    // ----------------------------------------------------------------------
-    // new_from_gen:
-    // ----------------------------------------------------------------------
-    /** Construct a new event object from a generated flatbuffer object. */
-    pub fn new_from_gen(ev: gen_events::ImageReceivedEvent) -> Result<Self, Errors> {
-        // Get the timestamp.
-        let created = match ev.event_create_ts() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("created"))),
-        };
-
-        // Get the uuid.
-        let u = match ev.image_uuid() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("uuid"))),
-        };
-        let uuid = match Uuid::parse_str(u) {
-            Ok(u) => u,
-            Err(e) => {
-                return Result::Err(Errors::UUIDParseError(
-                    String::from("image_uuid"),
-                    e.to_string(),
-                ))
-            }
-        };
-
-        // Get the image format.
-        let image_format = match ev.image_format() {
-            Some(f) => f,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("image_format"))),
-        };
-
-        // Finally...
-        Result::Ok(ImageReceivedEvent {
-            created: String::from(created),
-            image_uuid: uuid,
-            image_format: String::from(image_format),
-        })
-    }
-}
-
-// ===========================================================================
-// ImageScoredEvent:
-// ===========================================================================
-// ------------------------------
-// ------ ImageLabelScore
-// ------------------------------
-#[derive(Serialize)]
-pub struct ImageLabelScore {
-    label: String,
-    probability: f32,
-}
-
-impl ImageLabelScore {
-    #![allow(unused)]
-    pub fn new(image_uuid: Uuid, label: String, probability: f32) -> Self {
-        ImageLabelScore {
-            label,
-            probability,
-        }
-    }
-
-    pub fn new_from_gen(ev: gen_events::ImageLabelScore) -> Result<Self, Errors> {
-        // Get the image label string.
-        let label = match ev.label() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("label"))),
-        };
-
-        // Get the label's probability value.
-        let probability = ev.probability();
-
-        // Return the object.
-        Result::Ok(ImageLabelScore {
-            label: String::from(label),
-            probability,
-        })
-    }
-}
-
-// ------------------------------
-// ------ ImageScoredEvent
-// ------------------------------
-#[derive(Serialize)]
-pub struct ImageScoredEvent {
-    created: String,
-    image_uuid: Uuid,
-    image_format: String, 
-    scores: Vec<ImageLabelScore>,
-}
-
-// ------------------------------
-// ------ Trait EventType
-// ------------------------------
-impl EventType for ImageScoredEvent {
-    fn get_name(&self) -> String {
-        String::from("ImageScoredEvent")
-    }
-
-    fn get_filter(&self) -> Result<Vec<u8>, EngineError> {
-        Result::Ok(IMAGE_SCORED_PREFIX.to_vec())
-    }
-}
-
-// ------------------------------
-// ------ Trait Event
-// ------------------------------
-impl Event for ImageScoredEvent {
-    // ----------------------------------------------------------------------
-    // to_bytes:
-    // ----------------------------------------------------------------------
-    /** Convert the event to a raw byte array (prefix + flatbuffer). */
-    fn to_bytes(&self) -> Result<Vec<u8>, EngineError> {
-        // Create a new flatbuffer.
-        let mut fbuf = FlatBufferBuilder::new();
-
-        // Create a vector of gen_events::ImageLabelScores from this object's scores.
-        // First create a vector to hold the gen_events::ImageLabelScore objects.
-        let mut image_label_scores =
-            Vec::<flatbuffers::WIPOffset<gen_events::ImageLabelScore>>::new();
-        for score in &self.scores {
-            // Assign the string fields.
-            let label = Some(fbuf.create_string(&score.label));
-
-            // Create each generated score object
-            let im_score = gen_events::ImageLabelScore::create(
-                &mut fbuf,
-                &gen_events::ImageLabelScoreArgs {
-                    label,
-                    probability: score.probability,
-                },
-            );
-            // Add the current generated score object to the list.
-            image_label_scores.push(im_score);
-        }
-
-        // Assign the generated arguments object from our application object.
-        // Create the generated event offset object using the generated arguments.
-        let args = gen_events::ImageScoredEventArgs {
-            event_create_ts: Some(fbuf.create_string(&self.created)),
-            image_uuid: Some(fbuf.create_string(&self.image_uuid.hyphenated().to_string())),
-            image_format: Some(fbuf.create_string(&self.image_format)),
-            scores: Some(fbuf.create_vector(&image_label_scores)),
-        };
-        let event_offset = gen_events::ImageScoredEvent::create(&mut fbuf, &args);
-
-        // Create generated event arguments which are a union for all possible events.
-        // Create the generated event union offset object using the union arguments.
-        let union_args = gen_events::EventArgs {
-            event_type: gen_events::EventType::ImageScoredEvent,
-            event: Some(event_offset.as_union_value()),
-        };
-
-        // All event serializations are completed in the same way.
-        Ok(serialize_flatbuffer(IMAGE_SCORED_PREFIX, fbuf, union_args))
-    }
-
-    // ----------------------------------------------------------------------
-    // from_bytes:
-    // ----------------------------------------------------------------------
-    /** Get a NewImageEvent from raw event bytes that do NOT include the zqm prefix. */
-    fn from_bytes(bytes: Vec<u8>) -> Result<ImageScoredEvent, Box<dyn Error>>
-    where
-        Self: Sized,
-    {
-        // Get the union of all possible generated events.
-        let event = bytes_to_gen_event(&bytes)?;
-
-        // Validate that we recieved the expected type of event.
-        let event_type = "ImageScoredEvent";
-        check_event_type(event_type, &event)?;
-
-        // Create the generated event from the raw flatbuffer.
-        let flatbuf_event = match event.event_as_image_scored_event() {
-            Some(ev) => ev,
-            None => {
-                return Err(Box::new(Errors::EventCreateFromFlatbuffer(
-                    event_type.to_string(),
-                )))
-            }
-        };
-
-        // Return a camera-trap event given the flatbuffer generated event.
-        match ImageScoredEvent::new_from_gen(flatbuf_event) {
-            Ok(ev) => Result::Ok(ev),
-            Err(e) => Result::Err(Box::new(e)),
-        }
-    }
-}
-
-// ------------------------------
-// ------ Associated Functions
-// ------------------------------
-impl ImageScoredEvent {
-    // ----------------------------------------------------------------------
-    // accessors:
-    // ----------------------------------------------------------------------
-    pub fn get_created(&self) -> &String {
-        &self.created
-    }
-    pub fn get_image_uuid(&self) -> &Uuid {
-        &self.image_uuid
-    }
-    pub fn get_image_format(&self) -> &String {
-        &self.image_format
-    }
-    pub fn get_scores(&self) -> &Vec<ImageLabelScore> {
-        &self.scores
-    }
-
-    // ----------------------------------------------------------------------
-    // new:
-    // ----------------------------------------------------------------------
-    #[allow(unused)]
-    pub fn new(image_uuid: Uuid, image_format: String, scores: Vec<ImageLabelScore>) -> Self {
-        ImageScoredEvent {
-            created: timestamp_str(),
-            image_uuid,
-            image_format,
-            scores,
-        }
-    }
-
-    // ----------------------------------------------------------------------
-    // new_from_gen:
-    // ----------------------------------------------------------------------
-    /** Construct a new event object from a generated flatbuffer object. */
-    pub fn new_from_gen(ev: gen_events::ImageScoredEvent) -> Result<Self, Errors> {
-        // Get the timestamp.
-        let created = match ev.event_create_ts() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("created"))),
-        };
-
-        // Get the uuid.
-        let u = match ev.image_uuid() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("uuid"))),
-        };
-        let uuid = match Uuid::parse_str(u) {
-            Ok(u) => u,
-            Err(e) => {
-                return Result::Err(Errors::UUIDParseError(
-                    String::from("image_uuid"),
-                    e.to_string(),
-                ))
-            }
-        };
-
-        // Get the image format string.
-        let image_format = match ev.image_format() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("image_format"))),
-        };
-
-        // Get the list of scores.
-        let gen_scores = match ev.scores() {
-            Some(v) => v,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("scores"))),
-        };
-
-        // Iterate through each generated score and add to list of event scores.
-        let mut scores: Vec<ImageLabelScore> = Vec::new();
-        for gen_score in gen_scores {
-            // Extract the label.
-            let label = match gen_score.label() {
-                Some(s) => s,
-                None => {
-                    return Result::Err(Errors::EventReadFlatbuffer(String::from(
-                        "ImageLabelScore.label",
-                    )))
-                }
-            };
-
-            // Extract the probability.
-            let probability = gen_score.probability();
-
-            // Create the event imagelabelscore and add it to the vector.
-            let new_score = ImageLabelScore {
-                label: label.to_string(),
-                probability,
-            };
-            scores.push(new_score);
-        }
-
-        // Finally...
-        Result::Ok(ImageScoredEvent {
-            created: String::from(created),
-            image_uuid: uuid,
-            image_format: String::from(image_format),
-            scores,
-        })
-    }
-}
-
-// ===========================================================================
-// ImageStoredEvent:
-// ===========================================================================
-pub struct ImageStoredEvent {
-    created: String,
-    image_uuid: Uuid,
-    image_format: String,
-    destination: String,
-}
-
-// ------------------------------
-// ------ Trait EventType
-// ------------------------------
-impl EventType for ImageStoredEvent {
-    fn get_name(&self) -> String {
-        String::from("ImageStoredEvent")
-    }
-
-    fn get_filter(&self) -> Result<Vec<u8>, EngineError> {
-        Result::Ok(IMAGE_STORED_PREFIX.to_vec())
-    }
-}
-
-// ------------------------------
-// ------ Trait Event
-// ------------------------------
-impl Event for ImageStoredEvent {
-    // ----------------------------------------------------------------------
-    // to_bytes:
-    // ----------------------------------------------------------------------
-    /** Convert the event to a raw byte array (prefix + flatbuffer). */
-    fn to_bytes(&self) -> Result<Vec<u8>, EngineError> {
-        // Create a new flatbuffer.
-        let mut fbuf = FlatBufferBuilder::new();
-
-        // Assign the generated arguments object from our application object.
-        // Create the generated event offset object using the generated arguments.
-        let args = gen_events::ImageStoredEventArgs {
-            event_create_ts: Some(fbuf.create_string(&self.created)),
-            image_uuid: Some(fbuf.create_string(&self.image_uuid.hyphenated().to_string())),
-            image_format: Some(fbuf.create_string(&self.image_format)),
-            destination: Some(fbuf.create_string(&self.destination)),
-        };
-        let event_offset = gen_events::ImageStoredEvent::create(&mut fbuf, &args);
-
-        // Create generated event arguments which are a union for all possible events.
-        // Create the generated event union offset object using the union arguments.
-        let union_args = gen_events::EventArgs {
-            event_type: gen_events::EventType::ImageStoredEvent,
-            event: Some(event_offset.as_union_value()),
-        };
-
-        // All event serializations are completed in the same way.
-        Ok(serialize_flatbuffer(IMAGE_STORED_PREFIX, fbuf, union_args))
-    }
-
-    // ----------------------------------------------------------------------
-    // from_bytes:
-    // ----------------------------------------------------------------------
-    /** Get a NewImageEvent from raw event bytes that do NOT include the zqm prefix. */
-    fn from_bytes(bytes: Vec<u8>) -> Result<ImageStoredEvent, Box<dyn Error>>
-    where
-        Self: Sized,
-    {
-        // Get the union of all possible generated events.
-        let event = bytes_to_gen_event(&bytes)?;
-
-        // Validate that we recieved the expected type of event.
-        let event_type = "ImageStoredEvent";
-        check_event_type(event_type, &event)?;
-
-        // Create the generated event from the raw flatbuffer.
-        let flatbuf_event = match event.event_as_image_stored_event() {
-            Some(ev) => ev,
-            None => {
-                return Err(Box::new(Errors::EventCreateFromFlatbuffer(
-                    event_type.to_string(),
-                )))
-            }
-        };
-
-        // Return a camera-trap event given the flatbuffer generated event.
-        match ImageStoredEvent::new_from_gen(flatbuf_event) {
-            Ok(ev) => Result::Ok(ev),
-            Err(e) => Result::Err(Box::new(e)),
-        }
-    }
-}
-
-// ------------------------------
-// ------ Associated Functions
-// ------------------------------
-impl ImageStoredEvent {
-    // ----------------------------------------------------------------------
-    // new:
-    // ----------------------------------------------------------------------
-    #![allow(unused)]
-    pub fn new(image_uuid: Uuid, image_format: String, destination: String) -> Self {
-        ImageStoredEvent {
-            created: timestamp_str(),
-            image_uuid,
-            image_format,
-            destination,
-        }
-    }
-
-    // ----------------------------------------------------------------------
-    // new_from_gen:
-    // ----------------------------------------------------------------------
-    /** Construct a new event object from a generated flatbuffer object. */
-    pub fn new_from_gen(ev: gen_events::ImageStoredEvent) -> Result<Self, Errors> {
-        // Get the timestamp.
-        let created = match ev.event_create_ts() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("created"))),
-        };
-
-        // Get the uuid.
-        let u = match ev.image_uuid() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("uuid"))),
-        };
-        let uuid = match Uuid::parse_str(u) {
-            Ok(u) => u,
-            Err(e) => {
-                return Result::Err(Errors::UUIDParseError(
-                    String::from("image_uuid"),
-                    e.to_string(),
-                ))
-            }
-        };
-
-        // Get the image format string.
-        let image_format = match ev.image_format() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("image_format"))),
-        };
-
-        // Get the destination.
-        let destination = match ev.destination() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("destination"))),
-        };
-
-        // Finally...
-        Result::Ok(ImageStoredEvent {
-            created: String::from(created),
-            image_uuid: uuid,
-            image_format: String::from(image_format),
-            destination: String::from(destination),
-        })
-    }
-}
-
-// ===========================================================================
-// ImageDeletedEvent:
-// ===========================================================================
-pub struct ImageDeletedEvent {
-    created: String,
-    image_uuid: Uuid,
-    image_format: String,
-}
-
-// ------------------------------
-// ------ Trait EventType
-// ------------------------------
-impl EventType for ImageDeletedEvent {
-    fn get_name(&self) -> String {
-        String::from("ImageDeletedEvent")
-    }
-
-    fn get_filter(&self) -> Result<Vec<u8>, EngineError> {
-        Result::Ok(IMAGE_DELETED_PREFIX.to_vec())
-    }
-}
-
-// ------------------------------
-// ------ Trait Event
-// ------------------------------
-impl Event for ImageDeletedEvent {
-    // ----------------------------------------------------------------------
-    // to_bytes:
-    // ----------------------------------------------------------------------
-    /** Convert the event to a raw byte array (prefix + flatbuffer). */
-    fn to_bytes(&self) -> Result<Vec<u8>, EngineError> {
-        // Create a new flatbuffer.
-        let mut fbuf = FlatBufferBuilder::new();
-
-        // Assign the generated arguments object from our application object.
-        // Create the generated event offset object using the generated arguments.
-        let args = gen_events::ImageDeletedEventArgs {
-            event_create_ts: Some(fbuf.create_string(&self.created)),
-            image_uuid: Some(fbuf.create_string(&self.image_uuid.hyphenated().to_string())),
-            image_format: Some(fbuf.create_string(&self.image_format)),
-        };
-        let event_offset = gen_events::ImageDeletedEvent::create(&mut fbuf, &args);
-
-        // Create generated event arguments which are a union for all possible events.
-        // Create the generated event union offset object using the union arguments.
-        let union_args = gen_events::EventArgs {
-            event_type: gen_events::EventType::ImageDeletedEvent,
-            event: Some(event_offset.as_union_value()),
-        };
-
-        // All event serializations are completed in the same way.
-        Ok(serialize_flatbuffer(IMAGE_DELETED_PREFIX, fbuf, union_args))
-    }
-
-    // ----------------------------------------------------------------------
-    // from_bytes:
-    // ----------------------------------------------------------------------
-    /** Get a NewImageEvent from raw event bytes that do NOT include the zqm prefix. */
-    fn from_bytes(bytes: Vec<u8>) -> Result<ImageDeletedEvent, Box<dyn Error>>
-    where
-        Self: Sized,
-    {
-        // Get the union of all possible generated events.
-        let event = bytes_to_gen_event(&bytes)?;
-
-        // Validate that we recieved the expected type of event.
-        let event_type = "ImageDeletedEvent";
-        check_event_type(event_type, &event)?;
-
-        // Create the generated event from the raw flatbuffer.
-        let flatbuf_event = match event.event_as_image_deleted_event() {
-            Some(ev) => ev,
-            None => {
-                return Err(Box::new(Errors::EventCreateFromFlatbuffer(
-                    event_type.to_string(),
-                )))
-            }
-        };
-
-        // Return a camera-trap event given the flatbuffer generated event.
-        match ImageDeletedEvent::new_from_gen(flatbuf_event) {
-            Ok(ev) => Result::Ok(ev),
-            Err(e) => Result::Err(Box::new(e)),
-        }
-    }
-}
-
-// ------------------------------
-// ------ Associated Functions
-// ------------------------------
-impl ImageDeletedEvent {
-    // ----------------------------------------------------------------------
-    // new:
-    // ----------------------------------------------------------------------
-    #![allow(unused)]
-    pub fn new(image_uuid: Uuid, image_format: String) -> Self {
-        ImageDeletedEvent {
-            created: timestamp_str(),
-            image_uuid,
-            image_format,
-        }
-    }
-
-    // ----------------------------------------------------------------------
-    // new_from_gen:
-    // ----------------------------------------------------------------------
-    /** Construct a new event object from a generated flatbuffer object. */
-    pub fn new_from_gen(ev: gen_events::ImageDeletedEvent) -> Result<Self, Errors> {
-        // Get the timestamp.
-        let created = match ev.event_create_ts() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("created"))),
-        };
-
-        // Get the uuid.
-        let u = match ev.image_uuid() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("uuid"))),
-        };
-        let uuid = match Uuid::parse_str(u) {
-            Ok(u) => u,
-            Err(e) => {
-                return Result::Err(Errors::UUIDParseError(
-                    String::from("image_uuid"),
-                    e.to_string(),
-                ))
-            }
-        };
-
-        // Get the image format string.
-        let image_format = match ev.image_format() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("image_format"))),
-        };
-
-        // Finally...
-        Result::Ok(ImageDeletedEvent {
-            created: String::from(created),
-            image_uuid: uuid,
-            image_format: String::from(image_format),
-        })
-    }
-}
-
-// ===========================================================================
-// PluginStartedEvent:
-// ===========================================================================
-pub struct PluginStartedEvent {
-    created: String,
-    plugin_name: String,
-    plugin_uuid: Uuid,
-}
-
-// ------------------------------
-// ------ Trait EventType
-// ------------------------------
-impl EventType for PluginStartedEvent {
-    fn get_name(&self) -> String {
-        String::from("PluginStartedEvent")
-    }
-
-    fn get_filter(&self) -> Result<Vec<u8>, EngineError> {
-        Result::Ok(PLUGIN_STARTED_PREFIX.to_vec())
-    }
-}
-
-// ------------------------------
-// ------ Trait Event
-// ------------------------------
-impl Event for PluginStartedEvent {
-    // ----------------------------------------------------------------------
-    // to_bytes:
-    // ----------------------------------------------------------------------
-    /** Convert the event to a raw byte array. */
-    fn to_bytes(&self) -> Result<Vec<u8>, EngineError> {
-        // Create a new flatbuffer.
-        let mut fbuf = FlatBufferBuilder::new();
-
-        // Assign the generated arguments object from our application object.
-        // Create the generated event offset object using the generated arguments.
-        let args = gen_events::PluginStartedEventArgs {
-            event_create_ts: Some(fbuf.create_string(&self.created)),
-            plugin_name: Some(fbuf.create_string(&self.plugin_name)),
-            plugin_uuid: Some(fbuf.create_string(&self.plugin_uuid.hyphenated().to_string())),
-        };
-        let event_offset = gen_events::PluginStartedEvent::create(&mut fbuf, &args);
-
-        // Create generated event arguments which are a union for all possible events.
-        // Create the generated event union offset object using the union arguments.
-        let union_args = gen_events::EventArgs {
-            event_type: gen_events::EventType::PluginStartedEvent,
-            event: Some(event_offset.as_union_value()),
-        };
-
-        // All event serializations are completed in the same way.
-        Ok(serialize_flatbuffer(
-            PLUGIN_STARTED_PREFIX,
-            fbuf,
-            union_args,
-        ))
-    }
-
-    // ----------------------------------------------------------------------
-    // from_bytes:
-    // ----------------------------------------------------------------------
-    /** Get a NewImageEvent from raw event bytes that do NOT include the zqm prefix. */
-    fn from_bytes(bytes: Vec<u8>) -> Result<PluginStartedEvent, Box<dyn Error>>
-    where
-        Self: Sized,
-    {
-        // Get the union of all possible generated events.
-        let event = bytes_to_gen_event(&bytes)?;
-
-        // Validate that we recieved the expected type of event.
-        let event_type = "PluginStartedEvent";
-        check_event_type(event_type, &event)?;
-
-        // Create the generated event from the raw flatbuffer.
-        let flatbuf_event = match event.event_as_plugin_started_event() {
-            Some(ev) => ev,
-            None => {
-                return Err(Box::new(Errors::EventCreateFromFlatbuffer(
-                    event_type.to_string(),
-                )))
-            }
-        };
-
-        // Return a camera-trap event given the flatbuffer generated event.
-        match PluginStartedEvent::new_from_gen(flatbuf_event) {
-            Ok(ev) => Result::Ok(ev),
-            Err(e) => Result::Err(Box::new(e)),
-        }
-    }
-}
-
-// ------------------------------
-// ------ Associated Functions
-// ------------------------------
-impl PluginStartedEvent {
-    // ----------------------------------------------------------------------
-    // new:
-    // ----------------------------------------------------------------------
-    #![allow(unused)]
-    pub fn new(plugin_uuid: Uuid, plugin_name: String) -> Self {
-        PluginStartedEvent {
-            created: timestamp_str(),
-            plugin_name,
-            plugin_uuid,
-        }
-    }
-
-    // ----------------------------------------------------------------------
-    // new_from_gen:
-    // ----------------------------------------------------------------------
-    /** Construct a new event object from a generated flatbuffer object. */
-    pub fn new_from_gen(ev: gen_events::PluginStartedEvent) -> Result<Self, Errors> {
-        // Get the timestamp.
-        let created = match ev.event_create_ts() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("created"))),
-        };
-
-        // Get the plugin_name.
-        let plugin_name = match ev.plugin_name() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("plugin_name"))),
-        };
-
-        // Get the uuid.
-        let u = match ev.plugin_uuid() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("uuid"))),
-        };
-        let uuid = match Uuid::parse_str(u) {
-            Ok(u) => u,
-            Err(e) => {
-                return Result::Err(Errors::UUIDParseError(
-                    String::from("plugin_uuid"),
-                    e.to_string(),
-                ))
-            }
-        };
-
-        // Finally...
-        Result::Ok(PluginStartedEvent {
-            created: String::from(created),
-            plugin_name: String::from(plugin_name),
-            plugin_uuid: uuid,
-        })
-    }
-}
-
-// ===========================================================================
-// PluginTerminatingEvent:
-// ===========================================================================
-pub struct PluginTerminatingEvent {
-    created: String,
-    plugin_name: String,
-    plugin_uuid: Uuid,
-}
-
-// ------------------------------
-// ------ Trait EventType
-// ------------------------------
-impl EventType for PluginTerminatingEvent {
-    fn get_name(&self) -> String {
-        String::from("PluginTerminatingEvent")
-    }
-
-    fn get_filter(&self) -> Result<Vec<u8>, EngineError> {
-        Result::Ok(PLUGIN_TERMINATING_PREFIX.to_vec())
-    }
-}
-
-// ------------------------------
-// ------ Trait Event
-// ------------------------------
-impl Event for PluginTerminatingEvent {
-    // ----------------------------------------------------------------------
-    // to_bytes:
-    // ----------------------------------------------------------------------
-    /** Convert the event to a raw byte array (prefix + flatbuffer). */
-    fn to_bytes(&self) -> Result<Vec<u8>, EngineError> {
-        // Create a new flatbuffer.
-        let mut fbuf = FlatBufferBuilder::new();
-
-        // Assign the generated arguments object from our application object.
-        // Create the generated event offset object using the generated arguments.
-        let args = gen_events::PluginTerminatingEventArgs {
-            event_create_ts: Some(fbuf.create_string(&self.created)),
-            plugin_name: Some(fbuf.create_string(&self.plugin_name)),
-            plugin_uuid: Some(fbuf.create_string(&self.plugin_uuid.hyphenated().to_string())),
-        };
-        let event_offset = gen_events::PluginTerminatingEvent::create(&mut fbuf, &args);
-
-        // Create generated event arguments which are a union for all possible events.
-        // Create the generated event union offset object using the union arguments.
-        let union_args = gen_events::EventArgs {
-            event_type: gen_events::EventType::PluginTerminatingEvent,
-            event: Some(event_offset.as_union_value()),
-        };
-
-        // All event serializations are completed in the same way.
-        Ok(serialize_flatbuffer(
-            PLUGIN_TERMINATING_PREFIX,
-            fbuf,
-            union_args,
-        ))
-    }
-
-    // ----------------------------------------------------------------------
-    // from_bytes:
-    // ----------------------------------------------------------------------
-    /** Get a NewImageEvent from raw event bytes that do NOT include the zqm prefix. */
-    fn from_bytes(bytes: Vec<u8>) -> Result<PluginTerminatingEvent, Box<dyn Error>>
-    where
-        Self: Sized,
-    {
-        // Get the union of all possible generated events.
-        let event = bytes_to_gen_event(&bytes)?;
-
-        // Validate that we recieved the expected type of event.
-        let event_type = "PluginTerminatingEvent";
-        check_event_type(event_type, &event)?;
-
-        // Create the generated event from the raw flatbuffer.
-        let flatbuf_event = match event.event_as_plugin_terminating_event() {
-            Some(ev) => ev,
-            None => {
-                return Err(Box::new(Errors::EventCreateFromFlatbuffer(
-                    event_type.to_string(),
-                )))
-            }
-        };
-
-        // Return a camera-trap event given the flatbuffer generated event.
-        match PluginTerminatingEvent::new_from_gen(flatbuf_event) {
-            Ok(ev) => Result::Ok(ev),
-            Err(e) => Result::Err(Box::new(e)),
-        }
-    }
+/** Everything the application needs to know about an event type apart from
+ * its struct definition.  There is exactly one EventInfo for each event in
+ * EVENT_REGISTRY and all prefix/name lookups should go through the registry.
+ */
+pub struct EventInfo {
+    /// The event name, which is also the flatbuffer union variant name.
+    pub name: &'static str,
+    /// The 2 byte zmq routing prefix.
+    pub prefix: [u8; 2],
+    /// Decode a generated event into the application event's fields as JSON.
+    pub gen_to_json: fn(gen_events::Event) -> Result<serde_json::Value, Errors>,
+    /// Create the event from its fields as JSON and convert it to wire format bytes.
+    pub json_to_bytes: fn(serde_json::Value) -> Result<Vec<u8>, Errors>,
 }
 
-// ------------------------------
-// ------ Associated Functions
-// ------------------------------
-impl PluginTerminatingEvent {
-    // ----------------------------------------------------------------------
-    // new:
-    // ----------------------------------------------------------------------
-    #![allow(unused)]
-    pub fn new(plugin_uuid: Uuid, plugin_name: String) -> Self {
-        PluginTerminatingEvent {
-            created: timestamp_str(),
-            plugin_name,
-            plugin_uuid,
-        }
-    }
-
+impl EventInfo {
     // ----------------------------------------------------------------------
-    // new_from_gen:
+    // get_subscription:
     // ----------------------------------------------------------------------
-    /** Construct a new event object from a generated flatbuffer object. */
-    pub fn new_from_gen(ev: gen_events::PluginTerminatingEvent) -> Result<Self, Errors> {
-        // Get the timestamp.
-        let created = match ev.event_create_ts() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("created"))),
-        };
-
-        // Get the plugin_name.
-        let plugin_name = match ev.plugin_name() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("plugin_name"))),
-        };
-
-        // Get the uuid.
-        let u = match ev.plugin_uuid() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("uuid"))),
-        };
-        let uuid = match Uuid::parse_str(u) {
-            Ok(u) => u,
-            Err(e) => {
-                return Result::Err(Errors::UUIDParseError(
-                    String::from("plugin_uuid"),
-                    e.to_string(),
-                ))
-            }
-        };
-
-        // Finally...
-        Result::Ok(PluginTerminatingEvent {
-            created: String::from(created),
-            plugin_name: String::from(plugin_name),
-            plugin_uuid: uuid,
-        })
+    /** Get the EventType used to subscribe to this event. */
+    pub fn get_subscription(&self) -> Box<dyn EventType> {
+        Box::new(EventSubscription { name: self.name, prefix: self.prefix })
     }
 }
 
-// ===========================================================================
-// PluginTerminateEvent:
-// ===========================================================================
-pub struct PluginTerminateEvent {
-    pub created: String,
-    pub target_plugin_name: String,
-    pub target_plugin_uuid: Uuid,
+/** The event engine only needs an event's name and filter prefix to register
+ * a subscription, so subscriptions don't require constructing a real event.
+ */
+pub struct EventSubscription {
+    name: &'static str,
+    prefix: [u8; 2],
 }
 
-// ------------------------------
-// ------ Trait EventType
-// ------------------------------
-impl EventType for PluginTerminateEvent {
+impl EventType for EventSubscription {
     fn get_name(&self) -> String {
-        String::from("PluginTerminateEvent")
+        String::from(self.name)
     }
 
     fn get_filter(&self) -> Result<Vec<u8>, EngineError> {
-        Result::Ok(PLUGIN_TERMINATE_PREFIX.to_vec())
+        Result::Ok(self.prefix.to_vec())
     }
 }
 
-// ------------------------------
-// ------ Trait Event
-// ------------------------------
-impl Event for PluginTerminateEvent {
-    // ----------------------------------------------------------------------
-    // to_bytes:
-    // ----------------------------------------------------------------------
-    /** Convert the event to a raw byte array (prefix + flatbuffer). */
-    fn to_bytes(&self) -> Result<Vec<u8>, EngineError> {
-        // Create a new flatbuffer.
-        let mut fbuf = FlatBufferBuilder::new();
-
-        // Assign the generated arguments object from our application object.
-        // Create the generated event offset object using the generated arguments.
-        let args = gen_events::PluginTerminateEventArgs {
-            event_create_ts: Some(fbuf.create_string(&self.created)),
-            target_plugin_name: Some(fbuf.create_string(&self.target_plugin_name)),
-            target_plugin_uuid: Some(
-                fbuf.create_string(&self.target_plugin_uuid.hyphenated().to_string()),
-            ),
-        };
-        let event_offset = gen_events::PluginTerminateEvent::create(&mut fbuf, &args);
-
-        // Create generated event arguments which are a union for all possible events.
-        // Create the generated event union offset object using the union arguments.
-        let union_args = gen_events::EventArgs {
-            event_type: gen_events::EventType::PluginTerminateEvent,
-            event: Some(event_offset.as_union_value()),
-        };
-
-        // All event serializations are completed in the same way.
-        Ok(serialize_flatbuffer(
-            PLUGIN_TERMINATE_PREFIX,
-            fbuf,
-            union_args,
-        ))
-    }
-
-    // ----------------------------------------------------------------------
-    // from_bytes:
-    // ----------------------------------------------------------------------
-    /** Get a NewImageEvent from raw event bytes that do NOT include the zqm prefix. */
-    fn from_bytes(bytes: Vec<u8>) -> Result<PluginTerminateEvent, Box<dyn Error>>
-    where
-        Self: Sized,
-    {
-        // Get the union of all possible generated events.
-        let event = bytes_to_gen_event(&bytes)?;
-
-        // Validate that we recieved the expected type of event.
-        let event_type = "PluginTerminateEvent";
-        check_event_type(event_type, &event)?;
-
-        // Create the generated event from the raw flatbuffer.
-        let flatbuf_event = match event.event_as_plugin_terminate_event() {
-            Some(ev) => ev,
-            None => {
-                return Err(Box::new(Errors::EventCreateFromFlatbuffer(
-                    event_type.to_string(),
-                )))
-            }
-        };
-
-        // Return a camera-trap event given the flatbuffer generated event.
-        match PluginTerminateEvent::new_from_gen(flatbuf_event) {
-            Ok(ev) => Result::Ok(ev),
-            Err(e) => Result::Err(Box::new(e)),
-        }
-    }
+// ***************************************************************************
+// EVENT TRACING
+// ***************************************************************************
+/** The optional tracing identifiers carried by every event.  The correlation_id
+ * is shared by all events in a chain, the causation_id is the event_uuid of the
+ * event being responded to and the source_plugin names the publisher.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraceIds {
+    pub event_uuid: Option<Uuid>,
+    pub correlation_id: Option<Uuid>,
+    pub causation_id: Option<Uuid>,
+    pub source_plugin: Option<String>,
 }
 
-// ------------------------------
-// ------ Associated Functions
-// ------------------------------
-impl PluginTerminateEvent {
+impl TraceIds {
     // ----------------------------------------------------------------------
-    // new:
+    // caused_by:
     // ----------------------------------------------------------------------
-    #![allow(unused)]
-    pub fn new(target_plugin_uuid: Uuid, target_plugin_name: String) -> Self {
-        PluginTerminateEvent {
-            created: timestamp_str(),
-            target_plugin_name,
-            target_plugin_uuid,
+    /** Create the trace ids of a new event published by source_plugin.  When the
+     * event responds to another event, the cause's correlation id is inherited
+     * and the cause becomes the causation id; otherwise a new chain is started.
+     * Events from publishers that don't set a correlation id are correlated by
+     * their own event_uuid.
+     */
+    pub fn caused_by(cause: Option<&TraceIds>, source_plugin: &str) -> Self {
+        let event_uuid = Uuid::new_v4();
+        let (correlation_id, causation_id) = match cause {
+            Some(c) => (c.correlation_id.or(c.event_uuid).unwrap_or(event_uuid), c.event_uuid),
+            None => (event_uuid, None),
+        };
+        TraceIds {
+            event_uuid: Some(event_uuid),
+            correlation_id: Some(correlation_id),
+            causation_id,
+            source_plugin: Some(source_plugin.to_string()),
         }
     }
 
     // ----------------------------------------------------------------------
-    // new_from_gen:
+    // from_gen:
     // ----------------------------------------------------------------------
-    /** Construct a new event object from a generated flatbuffer object. */
-    pub fn new_from_gen(ev: gen_events::PluginTerminateEvent) -> Result<Self, Errors> {
-        // Get the timestamp.
-        let created = match ev.event_create_ts() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("created"))),
-        };
-
-        // Get the plugin_name.
-        let target_plugin_name = match ev.target_plugin_name() {
-            Some(s) => s,
-            None => {
-                return Result::Err(Errors::EventReadFlatbuffer(String::from(
-                    "target_plugin_name",
-                )))
-            }
-        };
-
-        // Get the uuid.
-        let u = match ev.target_plugin_uuid() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("uuid"))),
-        };
-        let uuid = match Uuid::parse_str(u) {
-            Ok(u) => u,
-            Err(e) => {
-                return Result::Err(Errors::UUIDParseError(
-                    String::from("target_plugin_uuid"),
-                    e.to_string(),
-                ))
-            }
-        };
-
-        // Finally...
-        Result::Ok(PluginTerminateEvent {
-            created: String::from(created),
-            target_plugin_name: String::from(target_plugin_name),
-            target_plugin_uuid: uuid,
+    /** Read the trace ids from a generated event's tracing fields. */
+    fn from_gen(event_uuid: Option<&str>, correlation_id: Option<&str>,
+                causation_id: Option<&str>, source_plugin: Option<&str>) -> Result<Self, Errors> {
+        Result::Ok(TraceIds {
+            event_uuid: event_uuid.map(|v| parse_uuid(v, "event_uuid")).transpose()?,
+            correlation_id: correlation_id.map(|v| parse_uuid(v, "correlation_id")).transpose()?,
+            causation_id: causation_id.map(|v| parse_uuid(v, "causation_id")).transpose()?,
+            source_plugin: source_plugin.map(String::from),
         })
     }
 }
 
-// ===========================================================================
-// MonitorPowerStartEvent:
-// ===========================================================================
-// All possible poew monitoring options.
-#[allow(clippy::upper_case_acronyms)]
-pub enum  MonitorType { ALL = 1, CPU, GPU, DRAM, }
-
-pub struct MonitorPowerStartEvent {
-    created: String,
-    pids: Vec<i32>,
-    monitor_types: Vec<MonitorType>,
-    monitor_start: String,
-    monitor_seconds: u32,
+/** Implemented by every event to get and set its tracing fields. */
+pub trait Traceable {
+    #[allow(dead_code)]
+    fn get_trace_ids(&self) -> TraceIds;
+    fn set_trace_ids(&mut self, ids: TraceIds);
 }
 
-// ------------------------------
-// ------ Trait EventType
-// ------------------------------
-impl EventType for MonitorPowerStartEvent {
-    fn get_name(&self) -> String {
-        String::from("MonitorPowerStartEvent")
-    }
-
-    fn get_filter(&self) -> Result<Vec<u8>, EngineError> {
-        Result::Ok(MONITOR_POWER_START_PREFIX.to_vec())
-    }
+/** Implemented by every event to convert the root generated event into the
+ * application event.
+ */
+pub trait FromGenEvent: Sized {
+    fn from_gen_event(gen_event: gen_events::Event) -> Result<Self, Errors>;
 }
 
-// ------------------------------
-// ------ Trait Event
-// ------------------------------
-impl Event for MonitorPowerStartEvent {
-    // ----------------------------------------------------------------------
-    // to_bytes:
-    // ----------------------------------------------------------------------
-    /** Convert the event to a raw byte array (prefix + flatbuffer). */
-    fn to_bytes(&self) -> Result<Vec<u8>, EngineError> {
-        // Create a new flatbuffer.
-        let mut fbuf = FlatBufferBuilder::new();
-
-        // Populate a list of gen_events monitor types.
-        let mut gen_types: Vec<gen_events::MonitorType> = Vec::new();
-        for mtype in &self.monitor_types {
-            match *mtype {
-                MonitorType::ALL  => gen_types.push(gen_events::MonitorType::ALL),
-                MonitorType::CPU  => gen_types.push(gen_events::MonitorType::CPU),
-                MonitorType::GPU  => gen_types.push(gen_events::MonitorType::GPU),
-                MonitorType::DRAM => gen_types.push(gen_events::MonitorType::DRAM),
-            }
-        }
-
-        // Assign the generated arguments object from our application object.
-        // Create the generated event offset object using the generated arguments.
-        let args = gen_events::MonitorPowerStartEventArgs {
-            event_create_ts: Some(fbuf.create_string(&self.created)),
-            pids: Some(fbuf.create_vector(&self.pids)),
-            monitor_types: Some(fbuf.create_vector(&gen_types)),
-            monitor_start_ts: Some(fbuf.create_string(&self.monitor_start)),
-            monitor_seconds: self.monitor_seconds,
-         };
-        let event_offset = gen_events::MonitorPowerStartEvent::create(&mut fbuf, &args);
-
-        // Create generated event arguments which are a union for all possible events.
-        // Create the generated event union offset object using the union arguments.
-        let union_args = gen_events::EventArgs {
-            event_type: gen_events::EventType::MonitorPowerStartEvent,
-            event: Some(event_offset.as_union_value()),
-        };
-
-        // All event serializations are completed in the same way.
-        Ok(serialize_flatbuffer(
-            MONITOR_POWER_START_PREFIX,
-            fbuf,
-            union_args,
-        ))
-    }
+// ---------------------------------------------------------------------------
+// event_registry:
+// ---------------------------------------------------------------------------
+/** Each line declares one event: its struct, its prefix constant and value,
+ * and the generated accessor used to extract it from the root Event union.
+ * The macro generates the prefix constant, the EventType and FromGenEvent
+ * implementations, and the event's entry in EVENT_REGISTRY.
+ */
+macro_rules! event_registry {
+    ($( $event:ident => $prefix_name:ident: $prefix:expr, $gen_as:ident; )*) => {
+        $(
+            pub const $prefix_name: [u8; 2] = $prefix;
+
+            impl EventType for $event {
+                fn get_name(&self) -> String {
+                    String::from(stringify!($event))
+                }
 
-    // ----------------------------------------------------------------------
-    // from_bytes:
-    // ----------------------------------------------------------------------
-    /** Get a MonitorPowerStartEvent from raw event bytes that do NOT include the zqm prefix. */
-    fn from_bytes(bytes: Vec<u8>) -> Result<MonitorPowerStartEvent, Box<dyn Error>>
-    where
-        Self: Sized,
-    {
-        // Get the union of all possible generated events.
-        let event = bytes_to_gen_event(&bytes)?;
-
-        // Validate that we recieved the expected type of event.
-        let event_type = "MonitorPowerStartEvent";
-        check_event_type(event_type, &event)?;
-
-        // Create the generated event from the raw flatbuffer.
-        let flatbuf_event = match event.event_as_monitor_power_start_event() {
-            Some(ev) => ev,
-            None => {
-                return Err(Box::new(Errors::EventCreateFromFlatbuffer(
-                    event_type.to_string(),
-                )))
+                fn get_filter(&self) -> Result<Vec<u8>, EngineError> {
+                    Result::Ok($prefix_name.to_vec())
+                }
             }
-        };
-
-        // Return a camera-trap event given the flatbuffer generated event.
-        match MonitorPowerStartEvent::new_from_gen(flatbuf_event) {
-            Ok(ev) => Result::Ok(ev),
-            Err(e) => Result::Err(Box::new(e)),
-        }
-    }
-}
-
-// ------------------------------
-// ------ Associated Functions
-// ------------------------------
-impl MonitorPowerStartEvent {
-    // ----------------------------------------------------------------------
-    // new:
-    // ----------------------------------------------------------------------
-    #![allow(unused)]
-    pub fn new(pids: Vec<i32>, monitor_types: Vec<MonitorType>, 
-                monitor_start: String, monitor_seconds: u32) -> Self {
-        MonitorPowerStartEvent {
-            created: timestamp_str(),
-            pids, 
-            monitor_types,
-            monitor_start,
-            monitor_seconds,
-        }
-    }
-
-    // ----------------------------------------------------------------------
-    // new_from_gen:
-    // ----------------------------------------------------------------------
-    /** Construct a new event object from a generated flatbuffer object. 
-     * This function enforces the constraints that the pids and monitor_types
-     * lists must contain at least one element each.
-    */
-    pub fn new_from_gen(ev: gen_events::MonitorPowerStartEvent) -> Result<Self, Errors> {
-        // Get the timestamp.
-        let created = match ev.event_create_ts() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("created"))),
-        };
 
-        // Get pids array.
-        let gen_pids = match ev.pids() {
-            Some(v) => v,
-            None => {
-                return Result::Err(Errors::EventReadFlatbuffer(String::from("pids")))
+            impl FromGenEvent for $event {
+                fn from_gen_event(gen_event: gen_events::Event) -> Result<Self, Errors> {
+                    match gen_event.$gen_as() {
+                        Some(ev) => $event::new_from_gen(ev),
+                        None => Result::Err(Errors::EventCreateFromFlatbuffer(stringify!($event).to_string())),
+                    }
+                }
             }
-        };
-        // Validate there's something to do.
-        if gen_pids.is_empty() {
-            return Result::Err(Errors::EventReceivedEmptyList(
-                "MonitorPowerStartEvent".to_string(), "pids".to_string()))
-        }
-        // Extract the pids into a standard vector.
-        let mut pids: Vec<i32> = Vec::new(); 
-        for pid in gen_pids {
-            pids.push(pid);
-        }
 
-        // Get monitor types array.
-        let gen_monitor_types = match ev.monitor_types() {
-            Some(v) => v,
-            None => {
-                return Result::Err(Errors::EventReadFlatbuffer(String::from("montitor_types")))
-            }
-        };
-        // Validate there's something to do.
-        if gen_monitor_types.is_empty() {
-            return Result::Err(Errors::EventReceivedEmptyList(
-                "MonitorPowerStartEvent".to_string(), "monitor_types".to_string()))
-        }
-        
-        // Extract the monitor types into a standard vector.
-        let mut monitor_types: Vec<MonitorType> = Vec::new();
-        for gen_type in gen_monitor_types {
-            let gen_type_str = match gen_type.variant_name() {
-                Some(s) => s,
-                None => {
-                    return Result::Err(Errors::EventReadFlatbuffer(String::from("montitor_type")))
+            impl Traceable for $event {
+                fn get_trace_ids(&self) -> TraceIds {
+                    TraceIds {
+                        event_uuid: self.event_uuid,
+                        correlation_id: self.correlation_id,
+                        causation_id: self.causation_id,
+                        source_plugin: self.source_plugin.clone(),
+                    }
                 }
-            };
-            // Update this list whenever the there's a change to monitor types.
-            match gen_type_str {
-                "ALL"  => monitor_types.push(MonitorType::ALL),
-                "CPU"  => monitor_types.push(MonitorType::CPU),
-                "GPU"  => monitor_types.push(MonitorType::GPU),
-                "DRAM" => monitor_types.push(MonitorType::DRAM),
-                _ => {
-                    return Result::Err(Errors::EventReadFlatbuffer(
-                        "montitor_type".to_string() + " Unknown -> " + gen_type_str))
-                }
-            };
-        }
 
-        // Get the monitor start timestamp.  It should always be a valid
-        // UTC datetime, but it has no effec if it's in the past.
-        let monitor_start = match ev.monitor_start_ts() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("monitor_start"))),
-        };
-        // Validate that we have a well-formed datetime or the empty string.
-        // The empty string means monitoring should start immediately.
-        if !monitor_start.is_empty() {
-            let dt = match timestamp_str_to_datetime(monitor_start) {
-                Ok(d) => d,
-                Err(e) => {
-                    return Result::Err(Errors::DateParseError(monitor_start.to_string(), e.to_string()))
+                fn set_trace_ids(&mut self, ids: TraceIds) {
+                    self.event_uuid = ids.event_uuid;
+                    self.correlation_id = ids.correlation_id;
+                    self.causation_id = ids.causation_id;
+                    self.source_plugin = ids.source_plugin;
+                }
+            }
+        )*
+
+        /// Read the trace ids of any generated event without decoding the rest of the event.
+        pub fn gen_to_trace_ids(gen_event: gen_events::Event) -> Result<TraceIds, Errors> {
+            $(
+                if let Some(ev) = gen_event.$gen_as() {
+                    return TraceIds::from_gen(ev.event_uuid(), ev.correlation_id(),
+                                              ev.causation_id(), ev.source_plugin());
+                }
+            )*
+            Result::Err(Errors::EventReadFlatbuffer(format!("EventType Unknown -> {}", gen_event.event_type().0)))
+        }
+
+        /// All events known to the application.
+        pub static EVENT_REGISTRY: &[EventInfo] = &[
+            $(
+                EventInfo {
+                    name: stringify!($event),
+                    prefix: $prefix_name,
+                    gen_to_json: |gen_event| {
+                        let ev = $event::from_gen_event(gen_event)?;
+                        serde_json::to_value(ev).map_err(|e| Errors::EventToJsonError(
+                            String::from("EventRegistry"), String::from(stringify!($event)), e.to_string()))
+                    },
+                    json_to_bytes: |json| {
+                        let ev: $event = serde_json::from_value(json).map_err(|e| Errors::EventFromJsonError(
+                            String::from(stringify!($event)), e.to_string()))?;
+                        ev.to_bytes().map_err(|e| Errors::EventToBytesError(
+                            String::from("EventRegistry"), String::from(stringify!($event)), e.to_string()))
+                    },
                 },
-            };
-        }
+            )*
+        ];
+    };
+}
 
-        // Get the monitoring duration in seconds.
-        let monitor_seconds = ev.monitor_seconds();
+// The event structs, their Event and EventType implementations and the
+// event_registry! invocation are generated by build.rs from events.fbs.
+include!(concat!(env!("OUT_DIR"), "/events_wrappers.rs"));
 
-        // Finally...
-        Result::Ok(MonitorPowerStartEvent {
-            created: String::from(created),
-            pids,
-            monitor_types,
-            monitor_start: monitor_start.to_string(),
-            monitor_seconds,
-        })
+pub const EVENT_PREFIX_LEN: usize = NEW_IMAGE_PREFIX.len();
+
+#[allow(dead_code)]
+impl NewImageEvent {
+    // ---------------------------------------------------------------------------
+    // set_image_compression_cutoff:
+    // ---------------------------------------------------------------------------
+    /** Compress the embedded image with the given algorithm when the event is
+     * serialized, unless the image is smaller than min_size bytes.  Small images
+     * are sent uncompressed because compressing them saves little or nothing.
+     */
+    pub fn set_image_compression_cutoff(&mut self, compression: ImageCompression, min_size: usize) {
+        if self.image.len() < min_size {
+            self.image_compression = ImageCompression::Uncompressed;
+        } else {
+            self.image_compression = compression;
+        }
     }
 }
 
-// ===========================================================================
-// MonitorPowerStopEvent:
-// ===========================================================================
-pub struct MonitorPowerStopEvent {
-    created: String,
-    pids: Vec<i32>,
+// ***************************************************************************
+// PUBLIC FUNCTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// get_event_info_by_prefix:
+// ---------------------------------------------------------------------------
+/** Look up an event's registry entry by its zmq prefix. */
+pub fn get_event_info_by_prefix(prefix: [u8; 2]) -> Option<&'static EventInfo> {
+    EVENT_REGISTRY.iter().find(|info| info.prefix == prefix)
 }
 
-// ------------------------------
-// ------ Trait EventType
-// ------------------------------
-impl EventType for MonitorPowerStopEvent {
-    fn get_name(&self) -> String {
-        String::from("MonitorPowerStopEvent")
-    }
+// ---------------------------------------------------------------------------
+// get_event_info_by_name:
+// ---------------------------------------------------------------------------
+/** Look up an event's registry entry by its name. */
+pub fn get_event_info_by_name(event_name: &str) -> Option<&'static EventInfo> {
+    EVENT_REGISTRY.iter().find(|info| info.name == event_name)
+}
 
-    fn get_filter(&self) -> Result<Vec<u8>, EngineError> {
-        Result::Ok(MONITOR_POWER_STOP_PREFIX.to_vec())
+// ---------------------------------------------------------------------------
+// check_event_prefix:
+// ---------------------------------------------------------------------------
+/** Make sure zqm routing prefix matches the event is supposed to be associated
+ * with.  Return true if they match, false otherwise.
+ */
+pub fn check_event_prefix(prefix: [u8; 2], event_name: &str) -> bool {
+    match get_event_info_by_prefix(prefix) {
+        Some(info) => info.name == event_name,
+        None => false,
     }
 }
 
-// ------------------------------
-// ------ Trait Event
-// ------------------------------
-impl Event for MonitorPowerStopEvent {
-    // ----------------------------------------------------------------------
-    // to_bytes:
-    // ----------------------------------------------------------------------
-    /** Convert the event to a raw byte array (prefix + flatbuffer). */
-    fn to_bytes(&self) -> Result<Vec<u8>, EngineError> {
-        // Create a new flatbuffer.
-        let mut fbuf = FlatBufferBuilder::new();
-
-        // Assign the generated arguments object from our application object.
-        // Create the generated event offset object using the generated arguments.
-        let args = gen_events::MonitorPowerStopEventArgs {
-            event_create_ts: Some(fbuf.create_string(&self.created)),
-            pids: Some(fbuf.create_vector(&self.pids)),
-         };
-        let event_offset = gen_events::MonitorPowerStopEvent::create(&mut fbuf, &args);
-
-        // Create generated event arguments which are a union for all possible events.
-        // Create the generated event union offset object using the union arguments.
-        let union_args = gen_events::EventArgs {
-            event_type: gen_events::EventType::MonitorPowerStopEvent,
-            event: Some(event_offset.as_union_value()),
-        };
-
-        // All event serializations are completed in the same way.
-        Ok(serialize_flatbuffer(
-            MONITOR_POWER_STOP_PREFIX,
-            fbuf,
-            union_args,
-        ))
+// ---------------------------------------------------------------------------
+// check_schema_version:
+// ---------------------------------------------------------------------------
+/** Check that an incoming event was written with a compatible schema version.
+ * Events from any minor version of our major version are accepted; the fields
+ * added by newer minor versions are simply not read.  Events from a different
+ * major version are rejected with an error that names the sending plugin, if
+ * the sender identified itself.
+ */
+pub fn check_schema_version(plugin_name: &str, gen_event: gen_events::Event) -> Result<(), Errors> {
+    if gen_event.schema_major() == EVENT_SCHEMA_MAJOR {
+        return Result::Ok(());
     }
+    let sender = match gen_to_trace_ids(gen_event) {
+        Ok(TraceIds { source_plugin: Some(s), .. }) => s,
+        _ => String::from("<unknown>"),
+    };
+    let event_name = gen_event.event_type().variant_name().unwrap_or("<unknown>");
+    Result::Err(Errors::EventSchemaVersionMismatch(
+        plugin_name.to_string(), event_name.to_string(), sender,
+        format!("{}.{}", gen_event.schema_major(), gen_event.schema_minor()),
+        format!("{}.{}", EVENT_SCHEMA_MAJOR, EVENT_SCHEMA_MINOR)))
+}
 
-    // ----------------------------------------------------------------------
-    // from_bytes:
-    // ----------------------------------------------------------------------
-    /** Get a MonitorPowerStopEvent from raw event bytes that do NOT include the zqm prefix. */
-    fn from_bytes(bytes: Vec<u8>) -> Result<MonitorPowerStopEvent, Box<dyn Error>>
-    where
-        Self: Sized,
-    {
-        // Get the union of all possible generated events.
-        let event = bytes_to_gen_event(&bytes)?;
-
-        // Validate that we recieved the expected type of event.
-        let event_type = "MonitorPowerStopEvent";
-        check_event_type(event_type, &event)?;
-
-        // Create the generated event from the raw flatbuffer.
-        let flatbuf_event = match event.event_as_monitor_power_stop_event() {
-            Some(ev) => ev,
-            None => {
-                return Err(Box::new(Errors::EventCreateFromFlatbuffer(
-                    event_type.to_string(),
-                )))
-            }
-        };
-
-        // Return a camera-trap event given the flatbuffer generated event.
-        match MonitorPowerStopEvent::new_from_gen(flatbuf_event) {
-            Ok(ev) => Result::Ok(ev),
-            Err(e) => Result::Err(Box::new(e)),
-        }
-    }
+// ---------------------------------------------------------------------------
+// gen_to_event:
+// ---------------------------------------------------------------------------
+/** Convert the root generated event into the requested application event. */
+pub fn gen_to_event<T: FromGenEvent>(gen_event: gen_events::Event) -> Result<T, Errors> {
+    T::from_gen_event(gen_event)
 }
 
-// ------------------------------
-// ------ Associated Functions
-// ------------------------------
-impl MonitorPowerStopEvent {
-    // ----------------------------------------------------------------------
-    // new:
-    // ----------------------------------------------------------------------
-    #![allow(unused)]
-    pub fn new(pids: Vec<i32>) -> Self {
-        MonitorPowerStopEvent {
-            created: timestamp_str(),
-            pids, 
-        }
+// ---------------------------------------------------------------------------
+// compress_image_bytes:
+// ---------------------------------------------------------------------------
+/** Compress an image with the requested algorithm and return the compressed
+ * bytes and the compression actually used.  If compression fails the error is
+ * logged and the image is returned uncompressed.
+ */
+pub fn compress_image_bytes(image: &[u8], compression: ImageCompression) -> (Cow<'_, [u8]>, ImageCompression) {
+    let result = match compression {
+        ImageCompression::Uncompressed => return (Cow::Borrowed(image), compression),
+        ImageCompression::Zstd => zstd::bulk::compress(image, zstd::DEFAULT_COMPRESSION_LEVEL),
+        ImageCompression::Lz4 => {
+            let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+            encoder.write_all(image)
+                .and_then(|_| encoder.finish().map_err(std::io::Error::from))
+        },
+    };
+    match result {
+        Ok(v) => (Cow::Owned(v), compression),
+        Err(e) => {
+            error!("{}", Errors::ImageCompressionError(format!("{:?}", compression), e.to_string()));
+            (Cow::Borrowed(image), ImageCompression::Uncompressed)
+        },
     }
+}
 
-    // ----------------------------------------------------------------------
-    // new_from_gen:
-    // ----------------------------------------------------------------------
-    /** Construct a new event object from a generated flatbuffer object. 
-     * This function enforces the constraints that the pids list must contain
-     * at least one element.
-    */
-    pub fn new_from_gen(ev: gen_events::MonitorPowerStopEvent) -> Result<Self, Errors> {
-        // Get the timestamp.
-        let created = match ev.event_create_ts() {
-            Some(s) => s,
-            None => return Result::Err(Errors::EventReadFlatbuffer(String::from("created"))),
-        };
+// The largest image that decompress_image_bytes() produces.  Any plugin can
+// publish a NewImageEvent, so a small compressed image mustn't be able to
+// expand without limit.
+pub const MAX_DECOMPRESSED_IMAGE_SIZE: usize = 256 * 1024 * 1024;
 
-        // Get pids array.
-        let gen_pids = match ev.pids() {
-            Some(v) => v,
-            None => {
-                return Result::Err(Errors::EventReadFlatbuffer(String::from("pids")))
-            }
-        };
-        // Validate there's something to do.
-        if gen_pids.is_empty() {
-            return Result::Err(Errors::EventReceivedEmptyList(
-                "MonitorPowerStopEvent".to_string(), "pids".to_string()))
-        }
-        // Extract the pids into a standard vector.
-        let mut pids: Vec<i32> = Vec::new(); 
-        for pid in gen_pids {
-            pids.push(pid);
-        }
+// ---------------------------------------------------------------------------
+// decompress_image_bytes:
+// ---------------------------------------------------------------------------
+/** Decompress an image that was compressed with the given algorithm.  Images
+ * that decompress to more than MAX_DECOMPRESSED_IMAGE_SIZE bytes are rejected.
+ */
+pub fn decompress_image_bytes(image: &[u8], compression: gen_events::ImageCompression) -> Result<Vec<u8>, Errors> {
+    decompress_image_bytes_max(image, compression, MAX_DECOMPRESSED_IMAGE_SIZE)
+}
 
-        // Finally...
-        Result::Ok(MonitorPowerStopEvent {
-            created: String::from(created),
-            pids,
-        })
+// ---------------------------------------------------------------------------
+// decompress_image_bytes_max:
+// ---------------------------------------------------------------------------
+fn decompress_image_bytes_max(image: &[u8], compression: gen_events::ImageCompression, max_size: usize)
+    -> Result<Vec<u8>, Errors> {
+    let compression = ImageCompression::from_gen(compression)?;
+
+    // Read at most one byte more than the limit to detect oversized images.
+    let limit = max_size as u64 + 1;
+    let mut bytes = Vec::new();
+    let result = match compression {
+        ImageCompression::Uncompressed => return Result::Ok(image.to_vec()),
+        ImageCompression::Zstd => zstd::stream::read::Decoder::new(image)
+            .and_then(|d| d.take(limit).read_to_end(&mut bytes)),
+        ImageCompression::Lz4 => lz4_flex::frame::FrameDecoder::new(image).take(limit).read_to_end(&mut bytes),
+    };
+    match result {
+        Ok(_) if bytes.len() > max_size => Result::Err(Errors::ImageCompressionError(format!("{:?}", compression),
+            format!("the image decompresses to more than {} bytes", max_size))),
+        Ok(_) => Result::Ok(bytes),
+        Err(e) => Result::Err(Errors::ImageCompressionError(format!("{:?}", compression), e.to_string())),
     }
 }
 
@@ -1764,8 +377,8 @@ impl MonitorPowerStopEvent {
 /** Given a reference to a byte array containing the serialized flatbuffer
  * event, convert it into an event as defined in the generated flatbuffer code.
  */
-fn bytes_to_gen_event(msg_bytes: &[u8]) -> Result<gen_events::Event, InvalidFlatbuffer> {
-    Result::Ok(gen_events::root_as_event(msg_bytes)?)
+fn bytes_to_gen_event(msg_bytes: &[u8]) -> Result<gen_events::Event<'_>, InvalidFlatbuffer> {
+    gen_events::root_as_event(msg_bytes)
 }
 
 // ---------------------------------------------------------------------------
@@ -1795,6 +408,51 @@ fn check_event_type(expected: &str, event: &gen_events::Event) -> Result<(), Err
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// read_required:
+// ---------------------------------------------------------------------------
+/** Return the value of a flatbuffer field that must be present. */
+fn read_required<T>(value: Option<T>, field: &str) -> Result<T, Errors> {
+    match value {
+        Some(v) => Result::Ok(v),
+        None => Result::Err(Errors::EventReadFlatbuffer(String::from(field))),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// parse_uuid:
+// ---------------------------------------------------------------------------
+fn parse_uuid(s: &str, field: &str) -> Result<Uuid, Errors> {
+    match Uuid::parse_str(s) {
+        Ok(u) => Result::Ok(u),
+        Err(e) => Result::Err(Errors::UUIDParseError(String::from(field), e.to_string())),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// check_not_empty:
+// ---------------------------------------------------------------------------
+/** Some events require their lists to have at least one element. */
+fn check_not_empty<T>(list: &[T], event_name: &str, field: &str) -> Result<(), Errors> {
+    if list.is_empty() {
+        return Result::Err(Errors::EventReceivedEmptyList(event_name.to_string(), field.to_string()));
+    }
+    Result::Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// check_datetime:
+// ---------------------------------------------------------------------------
+/** Validate that we have a well-formed datetime or the empty string. */
+fn check_datetime(s: &str) -> Result<(), Errors> {
+    if !s.is_empty() {
+        if let Err(e) = timestamp_str_to_datetime(s) {
+            return Result::Err(Errors::DateParseError(s.to_string(), e.to_string()));
+        }
+    }
+    Result::Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // serialize_flatbuffer:
 // ---------------------------------------------------------------------------
@@ -1803,6 +461,13 @@ fn serialize_flatbuffer(
     mut fbuf: FlatBufferBuilder,
     union_args: gen_events::EventArgs,
 ) -> Vec<u8> {
+    // Stamp our schema version on the root event.
+    let union_args = gen_events::EventArgs {
+        schema_major: EVENT_SCHEMA_MAJOR,
+        schema_minor: EVENT_SCHEMA_MINOR,
+        ..union_args
+    };
+
     // Get the offset of the particular event already encoded in the union argument.
     let union_offset = gen_events::Event::create(&mut fbuf, &union_args);
 
@@ -1824,25 +489,55 @@ fn serialize_flatbuffer(
 mod tests {
     use event_engine::events::Event;
 
-    use super::{ImageLabelScore, ImageScoredEvent, EVENT_PREFIX_LEN};
+    use super::{ImageLabelScore, ImageScoredEvent, PluginTerminatingEvent, MonitorPowerStartEvent,
+                MonitorPowerStopEvent, MonitorType, EVENT_PREFIX_LEN, 
+                EVENT_REGISTRY, PLUGIN_TERMINATING_PREFIX, check_event_prefix, get_event_info_by_name,
+                get_event_info_by_prefix, ImageReceivedEvent, ImageStoredEvent, ImageDeletedEvent, TraceIds, Traceable,
+                gen_to_trace_ids, check_schema_version, EVENT_SCHEMA_MAJOR, EVENT_SCHEMA_MINOR,
+                NewImageEvent, ImageCompression, compress_image_bytes, decompress_image_bytes,
+                decompress_image_bytes_max};
+    use crate::events_generated::gen_events;
 
     #[test]
     fn here_i_am() {
         println!("file test: events.rs");
     }
 
+    #[test]
+    fn generated_code_in_sync() {
+        // The flatc output for Rust and Python is checked in.  CI compares it
+        // with flatc's output using resources/gen_events.sh --check; here we
+        // make sure every event in the schema has the same union value in both
+        // and that the Python schema version matches events.fbs.
+        let rust = include_str!("events_generated.rs");
+        let python_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/python/ctevents");
+        let ctevents = std::fs::read_to_string(python_dir.join("ctevents.py")).unwrap();
+        let python = std::fs::read_to_string(python_dir.join("gen_events/EventType.py")).unwrap();
+        assert!(ctevents.contains(&format!("EVENT_SCHEMA_MAJOR = {}\n", EVENT_SCHEMA_MAJOR)));
+        assert!(ctevents.contains(&format!("EVENT_SCHEMA_MINOR = {}\n", EVENT_SCHEMA_MINOR)));
+
+        for info in EVENT_REGISTRY {
+            let rust_value = rust.lines()
+                .find_map(|l| l.trim().strip_prefix(&format!("pub const {}: Self = Self(", info.name)))
+                .unwrap_or_else(|| panic!("{} is missing from events_generated.rs", info.name));
+            let python_value = python.lines()
+                .find_map(|l| l.trim().strip_prefix(&format!("{} = ", info.name)))
+                .unwrap_or_else(|| panic!("{} is missing from gen_events/EventType.py", info.name));
+            assert_eq!(rust_value.trim_end_matches(");"), python_value, "{}", info.name);
+            assert!(python_dir.join(format!("gen_events/{}.py", info.name)).exists(), "{}", info.name);
+        }
+    }
+
     #[test]
     fn test_image_scored_event() {
         let image_uuid_1 = uuid::Uuid::new_v4();
-        let image_uuid_2 = uuid::Uuid::new_v4();
-        let image_uuid_3 = uuid::Uuid::new_v4();
         let prob_1: f32 = 0.85;
         let prob_2: f32 = 0.125;
         let prob_3: f32 = 0.025;
         let scores = vec![
-            ImageLabelScore::new(image_uuid_1, "test1".to_string(), prob_1),
-            ImageLabelScore::new(image_uuid_2, "test2".to_string(), prob_2),
-            ImageLabelScore::new(image_uuid_3, "test3".to_string(), prob_3),
+            ImageLabelScore::new("test1".to_string(), prob_1),
+            ImageLabelScore::new("test2".to_string(), prob_2),
+            ImageLabelScore::new("test3".to_string(), prob_3),
         ];
         let image_scored_event = ImageScoredEvent::new(image_uuid_1, "png".to_string(), scores);
         let image_scored_event_bytes = image_scored_event.to_bytes().unwrap();
@@ -1858,4 +553,167 @@ mod tests {
             println!("deserialized score probability: {:?}", s.probability);
         }
     }
+
+    #[test]
+    fn test_event_registry() {
+        // Names and prefixes are unique and consistent with the lookups.
+        for (i, info) in EVENT_REGISTRY.iter().enumerate() {
+            for other in &EVENT_REGISTRY[i + 1..] {
+                assert_ne!(info.name, other.name);
+                assert_ne!(info.prefix, other.prefix);
+            }
+            assert_eq!(get_event_info_by_name(info.name).unwrap().prefix, info.prefix);
+            assert_eq!(get_event_info_by_prefix(info.prefix).unwrap().name, info.name);
+            assert_eq!(info.get_subscription().get_filter().unwrap(), info.prefix.to_vec());
+            assert!(check_event_prefix(info.prefix, info.name));
+        }
+        assert!(get_event_info_by_name("NoSuchEvent").is_none());
+        assert!(!check_event_prefix([0xff, 0xff], "NewImageEvent"));
+
+        // The terminating event's name was once misspelled in the prefix check.
+        assert!(check_event_prefix(PLUGIN_TERMINATING_PREFIX, "PluginTerminatingEvent"));
+    }
+
+    #[test]
+    fn test_event_registry_to_json() {
+        let uuid = uuid::Uuid::new_v4();
+        let bytes = PluginTerminatingEvent::new(uuid, "ImageStorePlugin".to_string()).to_bytes().unwrap();
+        let gen_event = gen_events::root_as_event(&bytes[EVENT_PREFIX_LEN..]).unwrap();
+        let info = get_event_info_by_prefix([bytes[0], bytes[1]]).unwrap();
+        let json = (info.gen_to_json)(gen_event).unwrap();
+        assert_eq!(json["plugin_name"], "ImageStorePlugin");
+        assert_eq!(json["plugin_uuid"], uuid.hyphenated().to_string());
+    }
+
+    #[test]
+    fn test_generated_validation() {
+        // Empty lists and malformed start times are rejected when decoding.
+        let bytes = MonitorPowerStopEvent::new(vec![]).to_bytes().unwrap();
+        assert!(MonitorPowerStopEvent::from_bytes(bytes[EVENT_PREFIX_LEN..].to_vec()).is_err());
+        let bytes = MonitorPowerStartEvent::new(vec![1], vec![], String::new(), 0).to_bytes().unwrap();
+        assert!(MonitorPowerStartEvent::from_bytes(bytes[EVENT_PREFIX_LEN..].to_vec()).is_err());
+        let bytes = MonitorPowerStartEvent::new(vec![1], vec![MonitorType::CPU], "yesterday".to_string(), 0)
+            .to_bytes().unwrap();
+        assert!(MonitorPowerStartEvent::from_bytes(bytes[EVENT_PREFIX_LEN..].to_vec()).is_err());
+
+        // The empty start time means start immediately.
+        let bytes = MonitorPowerStartEvent::new(vec![1], vec![MonitorType::CPU], String::new(), 0)
+            .to_bytes().unwrap();
+        let ev = MonitorPowerStartEvent::from_bytes(bytes[EVENT_PREFIX_LEN..].to_vec()).unwrap();
+        assert_eq!(ev.get_monitor_types(), &vec![MonitorType::CPU]);
+    }
+
+    #[test]
+    fn test_trace_ids() {
+        // A new chain is correlated by its first event.
+        let first = TraceIds::caused_by(None, "ImageGenPlugin");
+        assert_eq!(first.correlation_id, first.event_uuid);
+        assert_eq!(first.causation_id, None);
+
+        // Responses keep the correlation id and point to their cause.
+        let mut ev = ImageReceivedEvent::new(uuid::Uuid::new_v4(), "png".to_string());
+        ev.set_trace_ids(TraceIds::caused_by(Some(&first), "ImageReceivePlugin"));
+        let bytes = ev.to_bytes().unwrap();
+        let gen_event = gen_events::root_as_event(&bytes[EVENT_PREFIX_LEN..]).unwrap();
+        let ids = gen_to_trace_ids(gen_event).unwrap();
+        assert_eq!(ids, ev.get_trace_ids());
+        assert_eq!(ids.correlation_id, first.correlation_id);
+        assert_eq!(ids.causation_id, first.event_uuid);
+        assert_eq!(ids.source_plugin.as_deref(), Some("ImageReceivePlugin"));
+
+        // Events without trace ids start a chain at the response's cause.
+        let untraced = TraceIds { event_uuid: Some(uuid::Uuid::new_v4()), ..Default::default() };
+        let next = TraceIds::caused_by(Some(&untraced), "ImageScorePlugin");
+        assert_eq!(next.correlation_id, untraced.event_uuid);
+        assert_eq!(TraceIds::default(),
+                   ImageDeletedEvent::new(uuid::Uuid::new_v4(), "png".to_string()).get_trace_ids());
+    }
+
+    #[test]
+    fn test_image_stored_location() {
+        let round_trip = |ev: &ImageStoredEvent| {
+            ImageStoredEvent::from_bytes(ev.to_bytes().unwrap()[EVENT_PREFIX_LEN..].to_vec()).unwrap()
+        };
+        let mut ev = ImageStoredEvent::new(uuid::Uuid::new_v4(), "jpg".to_string(), "Save".to_string());
+        assert_eq!(round_trip(&ev).get_location(), None);
+        ev.set_location(Some("/images/x.jpg".to_string()));
+        let ev = round_trip(&ev);
+        assert_eq!(ev.get_location().map(String::as_str), Some("/images/x.jpg"));
+    }
+
+    #[test]
+    fn test_schema_version() {
+        // Encode an event as if it came from a sender with the given schema version.
+        let mut ev = ImageReceivedEvent::new(uuid::Uuid::new_v4(), "png".to_string());
+        ev.set_trace_ids(TraceIds::caused_by(None, "py_plugin"));
+        let encode = |major: u16, minor: u16| {
+            let mut fbuf = flatbuffers::FlatBufferBuilder::new();
+            let event_offset = ev.to_gen(&mut fbuf);
+            let args = gen_events::EventArgs {
+                event_type: gen_events::EventType::ImageReceivedEvent,
+                event: Some(event_offset.as_union_value()),
+                schema_major: major,
+                schema_minor: minor,
+            };
+            let root = gen_events::Event::create(&mut fbuf, &args);
+            fbuf.finish(root, None);
+            fbuf.finished_data().to_vec()
+        };
+
+        // Our own events carry our version.
+        let bytes = ev.to_bytes().unwrap();
+        let gen_event = gen_events::root_as_event(&bytes[EVENT_PREFIX_LEN..]).unwrap();
+        assert_eq!((gen_event.schema_major(), gen_event.schema_minor()), (EVENT_SCHEMA_MAJOR, EVENT_SCHEMA_MINOR));
+        assert!(check_schema_version("test", gen_event).is_ok());
+
+        // Older and newer minor versions, including unversioned senders, are accepted.
+        for (major, minor) in [(1, 0), (EVENT_SCHEMA_MAJOR, EVENT_SCHEMA_MINOR + 1)] {
+            let bytes = encode(major, minor);
+            let gen_event = gen_events::root_as_event(&bytes).unwrap();
+            assert!(check_schema_version("test", gen_event).is_ok());
+            assert_eq!(ImageReceivedEvent::from_bytes(bytes).unwrap().get_trace_ids(), ev.get_trace_ids());
+        }
+
+        // A different major version is rejected and the error names the sender.
+        let bytes = encode(EVENT_SCHEMA_MAJOR + 1, 0);
+        let gen_event = gen_events::root_as_event(&bytes).unwrap();
+        let msg = check_schema_version("test", gen_event).unwrap_err().to_string();
+        assert!(msg.contains("py_plugin"), "{}", msg);
+        assert!(msg.contains(&format!("{}.0", EVENT_SCHEMA_MAJOR + 1)), "{}", msg);
+    }
+
+    #[test]
+    fn test_image_compression() {
+        let image: Vec<u8> = b"camera traps ".iter().cycle().take(4096).cloned().collect();
+        for compression in [ImageCompression::Zstd, ImageCompression::Lz4] {
+            // Compressed images are smaller on the wire and decompressed on read.
+            let mut ev = NewImageEvent::new(uuid::Uuid::new_v4(), "jpg".to_string(), image.clone());
+            let uncompressed_len = ev.to_bytes().unwrap().len();
+            ev.set_image_compression_cutoff(compression, 1024);
+            assert_eq!(ev.get_image_compression(), compression);
+            let bytes = ev.to_bytes().unwrap();
+            assert!(bytes.len() < uncompressed_len);
+            let decoded = NewImageEvent::from_bytes(bytes[EVENT_PREFIX_LEN..].to_vec()).unwrap();
+            assert_eq!(decoded.get_image(), &image);
+            assert_eq!(decoded.get_image_compression(), compression);
+
+            // Images below the cutoff are not compressed.
+            ev.set_image_compression_cutoff(compression, image.len() + 1);
+            assert_eq!(ev.get_image_compression(), ImageCompression::Uncompressed);
+            assert_eq!(ev.to_bytes().unwrap().len(), uncompressed_len);
+        }
+
+        // Corrupt compressed data is an error.
+        assert!(decompress_image_bytes(b"not compressed", gen_events::ImageCompression::Zstd).is_err());
+        assert!(decompress_image_bytes(b"not compressed", gen_events::ImageCompression::Lz4).is_err());
+
+        // Images can't expand past the limit.
+        let image = vec![0u8; 4096];
+        for (compression, gen_compression) in [(ImageCompression::Zstd, gen_events::ImageCompression::Zstd),
+                                               (ImageCompression::Lz4, gen_events::ImageCompression::Lz4)] {
+            let (bytes, _) = compress_image_bytes(&image, compression);
+            assert_eq!(decompress_image_bytes_max(&bytes, gen_compression, 4096).unwrap(), image);
+            assert!(decompress_image_bytes_max(&bytes, gen_compression, 4095).is_err());
+        }
+    }
 }
@@ -1,6 +1,6 @@
 // automatically generated by the FlatBuffers compiler, do not modify
 // ADDED BY HAND TO ELIMINATE NOISE
-#![allow(dead_code, unused_imports, clippy::all)]
+#![allow(unknown_lints, dead_code, unused_imports, mismatched_lifetime_syntaxes, clippy::all)]
 
 
 use core::mem;
@@ -21,10 +21,10 @@ pub mod gen_events {
 #[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
 pub const ENUM_MIN_EVENT_TYPE: u8 = 0;
 #[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
-pub const ENUM_MAX_EVENT_TYPE: u8 = 10;
+pub const ENUM_MAX_EVENT_TYPE: u8 = 13;
 #[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
 #[allow(non_camel_case_types)]
-pub const ENUM_VALUES_EVENT_TYPE: [EventType; 11] = [
+pub const ENUM_VALUES_EVENT_TYPE: [EventType; 14] = [
   EventType::NONE,
   EventType::NewImageEvent,
   EventType::ImageReceivedEvent,
@@ -36,6 +36,9 @@ pub const ENUM_VALUES_EVENT_TYPE: [EventType; 11] = [
   EventType::PluginTerminateEvent,
   EventType::MonitorPowerStartEvent,
   EventType::MonitorPowerStopEvent,
+  EventType::ImageProcessingErrorEvent,
+  EventType::DetectionEvent,
+  EventType::ConfigReloadedEvent,
 ];
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
@@ -54,9 +57,12 @@ impl EventType {
   pub const PluginTerminateEvent: Self = Self(8);
   pub const MonitorPowerStartEvent: Self = Self(9);
   pub const MonitorPowerStopEvent: Self = Self(10);
+  pub const ImageProcessingErrorEvent: Self = Self(11);
+  pub const DetectionEvent: Self = Self(12);
+  pub const ConfigReloadedEvent: Self = Self(13);
 
   pub const ENUM_MIN: u8 = 0;
-  pub const ENUM_MAX: u8 = 10;
+  pub const ENUM_MAX: u8 = 13;
   pub const ENUM_VALUES: &'static [Self] = &[
     Self::NONE,
     Self::NewImageEvent,
@@ -69,6 +75,9 @@ impl EventType {
     Self::PluginTerminateEvent,
     Self::MonitorPowerStartEvent,
     Self::MonitorPowerStopEvent,
+    Self::ImageProcessingErrorEvent,
+    Self::DetectionEvent,
+    Self::ConfigReloadedEvent,
   ];
   /// Returns the variant's name or "" if unknown.
   pub fn variant_name(self) -> Option<&'static str> {
@@ -84,6 +93,9 @@ impl EventType {
       Self::PluginTerminateEvent => Some("PluginTerminateEvent"),
       Self::MonitorPowerStartEvent => Some("MonitorPowerStartEvent"),
       Self::MonitorPowerStopEvent => Some("MonitorPowerStopEvent"),
+      Self::ImageProcessingErrorEvent => Some("ImageProcessingErrorEvent"),
+      Self::DetectionEvent => Some("DetectionEvent"),
+      Self::ConfigReloadedEvent => Some("ConfigReloadedEvent"),
       _ => None,
     }
   }
@@ -143,6 +155,188 @@ impl<'a> flatbuffers::Verifiable for EventType {
 impl flatbuffers::SimpleToVerifyInSlice for EventType {}
 pub struct EventTypeUnionTableOffset {}
 
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_IMAGE_LOCATION_TYPE: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_IMAGE_LOCATION_TYPE: i8 = 2;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_IMAGE_LOCATION_TYPE: [ImageLocationType; 3] = [
+  ImageLocationType::FilePath,
+  ImageLocationType::SharedMemory,
+  ImageLocationType::ContentHash,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct ImageLocationType(pub i8);
+#[allow(non_upper_case_globals)]
+impl ImageLocationType {
+  pub const FilePath: Self = Self(0);
+  pub const SharedMemory: Self = Self(1);
+  pub const ContentHash: Self = Self(2);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 2;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::FilePath,
+    Self::SharedMemory,
+    Self::ContentHash,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::FilePath => Some("FilePath"),
+      Self::SharedMemory => Some("SharedMemory"),
+      Self::ContentHash => Some("ContentHash"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for ImageLocationType {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for ImageLocationType {
+  type Inner = Self;
+  #[inline]
+  fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = unsafe {
+      flatbuffers::read_scalar_at::<i8>(buf, loc)
+    };
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for ImageLocationType {
+    type Output = ImageLocationType;
+    #[inline]
+    fn push(&self, dst: &mut [u8], _rest: &[u8]) {
+        unsafe { flatbuffers::emplace_scalar::<i8>(dst, self.0); }
+    }
+}
+
+impl flatbuffers::EndianScalar for ImageLocationType {
+  #[inline]
+  fn to_little_endian(self) -> Self {
+    let b = i8::to_le(self.0);
+    Self(b)
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(self) -> Self {
+    let b = i8::from_le(self.0);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for ImageLocationType {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for ImageLocationType {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_IMAGE_COMPRESSION: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_IMAGE_COMPRESSION: i8 = 2;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_IMAGE_COMPRESSION: [ImageCompression; 3] = [
+  ImageCompression::Uncompressed,
+  ImageCompression::Zstd,
+  ImageCompression::Lz4,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct ImageCompression(pub i8);
+#[allow(non_upper_case_globals)]
+impl ImageCompression {
+  pub const Uncompressed: Self = Self(0);
+  pub const Zstd: Self = Self(1);
+  pub const Lz4: Self = Self(2);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 2;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Uncompressed,
+    Self::Zstd,
+    Self::Lz4,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Uncompressed => Some("Uncompressed"),
+      Self::Zstd => Some("Zstd"),
+      Self::Lz4 => Some("Lz4"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for ImageCompression {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for ImageCompression {
+  type Inner = Self;
+  #[inline]
+  fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = unsafe {
+      flatbuffers::read_scalar_at::<i8>(buf, loc)
+    };
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for ImageCompression {
+    type Output = ImageCompression;
+    #[inline]
+    fn push(&self, dst: &mut [u8], _rest: &[u8]) {
+        unsafe { flatbuffers::emplace_scalar::<i8>(dst, self.0); }
+    }
+}
+
+impl flatbuffers::EndianScalar for ImageCompression {
+  #[inline]
+  fn to_little_endian(self) -> Self {
+    let b = i8::to_le(self.0);
+    Self(b)
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(self) -> Self {
+    let b = i8::from_le(self.0);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for ImageCompression {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for ImageCompression {}
 #[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
 pub const ENUM_MIN_MONITOR_TYPE: i8 = 1;
 #[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
@@ -238,6 +432,142 @@ impl<'a> flatbuffers::Verifiable for MonitorType {
 }
 
 impl flatbuffers::SimpleToVerifyInSlice for MonitorType {}
+pub enum ImageReferenceOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct ImageReference<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for ImageReference<'a> {
+  type Inner = ImageReference<'a>;
+  #[inline]
+  fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table { buf, loc } }
+  }
+}
+
+impl<'a> ImageReference<'a> {
+  pub const VT_LOCATION_TYPE: flatbuffers::VOffsetT = 4;
+  pub const VT_LOCATION: flatbuffers::VOffsetT = 6;
+  pub const VT_SIZE: flatbuffers::VOffsetT = 8;
+  pub const VT_CHECKSUM: flatbuffers::VOffsetT = 10;
+
+  #[inline]
+  pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    ImageReference { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args ImageReferenceArgs<'args>
+  ) -> flatbuffers::WIPOffset<ImageReference<'bldr>> {
+    let mut builder = ImageReferenceBuilder::new(_fbb);
+    builder.add_size(args.size);
+    if let Some(x) = args.checksum { builder.add_checksum(x); }
+    if let Some(x) = args.location { builder.add_location(x); }
+    builder.add_location_type(args.location_type);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn location_type(&self) -> ImageLocationType {
+    self._tab.get::<ImageLocationType>(ImageReference::VT_LOCATION_TYPE, Some(ImageLocationType::FilePath)).unwrap()
+  }
+  #[inline]
+  pub fn location(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageReference::VT_LOCATION, None)
+  }
+  #[inline]
+  pub fn size(&self) -> u64 {
+    self._tab.get::<u64>(ImageReference::VT_SIZE, Some(0)).unwrap()
+  }
+  #[inline]
+  pub fn checksum(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageReference::VT_CHECKSUM, None)
+  }
+}
+
+impl flatbuffers::Verifiable for ImageReference<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<ImageLocationType>("location_type", Self::VT_LOCATION_TYPE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("location", Self::VT_LOCATION, false)?
+     .visit_field::<u64>("size", Self::VT_SIZE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("checksum", Self::VT_CHECKSUM, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ImageReferenceArgs<'a> {
+    pub location_type: ImageLocationType,
+    pub location: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub size: u64,
+    pub checksum: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for ImageReferenceArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    ImageReferenceArgs {
+      location_type: ImageLocationType::FilePath,
+      location: None,
+      size: 0,
+      checksum: None,
+    }
+  }
+}
+
+pub struct ImageReferenceBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> ImageReferenceBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_location_type(&mut self, location_type: ImageLocationType) {
+    self.fbb_.push_slot::<ImageLocationType>(ImageReference::VT_LOCATION_TYPE, location_type, ImageLocationType::FilePath);
+  }
+  #[inline]
+  pub fn add_location(&mut self, location: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageReference::VT_LOCATION, location);
+  }
+  #[inline]
+  pub fn add_size(&mut self, size: u64) {
+    self.fbb_.push_slot::<u64>(ImageReference::VT_SIZE, size, 0);
+  }
+  #[inline]
+  pub fn add_checksum(&mut self, checksum: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageReference::VT_CHECKSUM, checksum);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ImageReferenceBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    ImageReferenceBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<ImageReference<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for ImageReference<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("ImageReference");
+      ds.field("location_type", &self.location_type());
+      ds.field("location", &self.location());
+      ds.field("size", &self.size());
+      ds.field("checksum", &self.checksum());
+      ds.finish()
+  }
+}
 pub enum NewImageEventOffset {}
 #[derive(Copy, Clone, PartialEq)]
 
@@ -258,6 +588,12 @@ impl<'a> NewImageEvent<'a> {
   pub const VT_IMAGE_UUID: flatbuffers::VOffsetT = 6;
   pub const VT_IMAGE_FORMAT: flatbuffers::VOffsetT = 8;
   pub const VT_IMAGE: flatbuffers::VOffsetT = 10;
+  pub const VT_EVENT_UUID: flatbuffers::VOffsetT = 12;
+  pub const VT_CORRELATION_ID: flatbuffers::VOffsetT = 14;
+  pub const VT_CAUSATION_ID: flatbuffers::VOffsetT = 16;
+  pub const VT_SOURCE_PLUGIN: flatbuffers::VOffsetT = 18;
+  pub const VT_IMAGE_REF: flatbuffers::VOffsetT = 20;
+  pub const VT_IMAGE_COMPRESSION: flatbuffers::VOffsetT = 22;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -269,10 +605,16 @@ impl<'a> NewImageEvent<'a> {
     args: &'args NewImageEventArgs<'args>
   ) -> flatbuffers::WIPOffset<NewImageEvent<'bldr>> {
     let mut builder = NewImageEventBuilder::new(_fbb);
+    if let Some(x) = args.image_ref { builder.add_image_ref(x); }
+    if let Some(x) = args.source_plugin { builder.add_source_plugin(x); }
+    if let Some(x) = args.causation_id { builder.add_causation_id(x); }
+    if let Some(x) = args.correlation_id { builder.add_correlation_id(x); }
+    if let Some(x) = args.event_uuid { builder.add_event_uuid(x); }
     if let Some(x) = args.image { builder.add_image(x); }
     if let Some(x) = args.image_format { builder.add_image_format(x); }
     if let Some(x) = args.image_uuid { builder.add_image_uuid(x); }
     if let Some(x) = args.event_create_ts { builder.add_event_create_ts(x); }
+    builder.add_image_compression(args.image_compression);
     builder.finish()
   }
 
@@ -293,6 +635,30 @@ impl<'a> NewImageEvent<'a> {
   pub fn image(&self) -> Option<&'a [u8]> {
     self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(NewImageEvent::VT_IMAGE, None).map(|v| v.safe_slice())
   }
+  #[inline]
+  pub fn event_uuid(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(NewImageEvent::VT_EVENT_UUID, None)
+  }
+  #[inline]
+  pub fn correlation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(NewImageEvent::VT_CORRELATION_ID, None)
+  }
+  #[inline]
+  pub fn causation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(NewImageEvent::VT_CAUSATION_ID, None)
+  }
+  #[inline]
+  pub fn source_plugin(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(NewImageEvent::VT_SOURCE_PLUGIN, None)
+  }
+  #[inline]
+  pub fn image_ref(&self) -> Option<ImageReference<'a>> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<ImageReference>>(NewImageEvent::VT_IMAGE_REF, None)
+  }
+  #[inline]
+  pub fn image_compression(&self) -> ImageCompression {
+    self._tab.get::<ImageCompression>(NewImageEvent::VT_IMAGE_COMPRESSION, Some(ImageCompression::Uncompressed)).unwrap()
+  }
 }
 
 impl flatbuffers::Verifiable for NewImageEvent<'_> {
@@ -306,6 +672,12 @@ impl flatbuffers::Verifiable for NewImageEvent<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("image_uuid", Self::VT_IMAGE_UUID, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("image_format", Self::VT_IMAGE_FORMAT, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>("image", Self::VT_IMAGE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_uuid", Self::VT_EVENT_UUID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("correlation_id", Self::VT_CORRELATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("causation_id", Self::VT_CAUSATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("source_plugin", Self::VT_SOURCE_PLUGIN, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<ImageReference>>("image_ref", Self::VT_IMAGE_REF, false)?
+     .visit_field::<ImageCompression>("image_compression", Self::VT_IMAGE_COMPRESSION, false)?
      .finish();
     Ok(())
   }
@@ -315,6 +687,12 @@ pub struct NewImageEventArgs<'a> {
     pub image_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
     pub image_format: Option<flatbuffers::WIPOffset<&'a str>>,
     pub image: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+    pub event_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub correlation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub causation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub source_plugin: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub image_ref: Option<flatbuffers::WIPOffset<ImageReference<'a>>>,
+    pub image_compression: ImageCompression,
 }
 impl<'a> Default for NewImageEventArgs<'a> {
   #[inline]
@@ -324,6 +702,12 @@ impl<'a> Default for NewImageEventArgs<'a> {
       image_uuid: None,
       image_format: None,
       image: None,
+      event_uuid: None,
+      correlation_id: None,
+      causation_id: None,
+      source_plugin: None,
+      image_ref: None,
+      image_compression: ImageCompression::Uncompressed,
     }
   }
 }
@@ -350,6 +734,30 @@ impl<'a: 'b, 'b> NewImageEventBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(NewImageEvent::VT_IMAGE, image);
   }
   #[inline]
+  pub fn add_event_uuid(&mut self, event_uuid: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(NewImageEvent::VT_EVENT_UUID, event_uuid);
+  }
+  #[inline]
+  pub fn add_correlation_id(&mut self, correlation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(NewImageEvent::VT_CORRELATION_ID, correlation_id);
+  }
+  #[inline]
+  pub fn add_causation_id(&mut self, causation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(NewImageEvent::VT_CAUSATION_ID, causation_id);
+  }
+  #[inline]
+  pub fn add_source_plugin(&mut self, source_plugin: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(NewImageEvent::VT_SOURCE_PLUGIN, source_plugin);
+  }
+  #[inline]
+  pub fn add_image_ref(&mut self, image_ref: flatbuffers::WIPOffset<ImageReference<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(NewImageEvent::VT_IMAGE_REF, image_ref);
+  }
+  #[inline]
+  pub fn add_image_compression(&mut self, image_compression: ImageCompression) {
+    self.fbb_.push_slot::<ImageCompression>(NewImageEvent::VT_IMAGE_COMPRESSION, image_compression, ImageCompression::Uncompressed);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> NewImageEventBuilder<'a, 'b> {
     let start = _fbb.start_table();
     NewImageEventBuilder {
@@ -371,6 +779,12 @@ impl core::fmt::Debug for NewImageEvent<'_> {
       ds.field("image_uuid", &self.image_uuid());
       ds.field("image_format", &self.image_format());
       ds.field("image", &self.image());
+      ds.field("event_uuid", &self.event_uuid());
+      ds.field("correlation_id", &self.correlation_id());
+      ds.field("causation_id", &self.causation_id());
+      ds.field("source_plugin", &self.source_plugin());
+      ds.field("image_ref", &self.image_ref());
+      ds.field("image_compression", &self.image_compression());
       ds.finish()
   }
 }
@@ -393,6 +807,10 @@ impl<'a> ImageReceivedEvent<'a> {
   pub const VT_EVENT_CREATE_TS: flatbuffers::VOffsetT = 4;
   pub const VT_IMAGE_UUID: flatbuffers::VOffsetT = 6;
   pub const VT_IMAGE_FORMAT: flatbuffers::VOffsetT = 8;
+  pub const VT_EVENT_UUID: flatbuffers::VOffsetT = 10;
+  pub const VT_CORRELATION_ID: flatbuffers::VOffsetT = 12;
+  pub const VT_CAUSATION_ID: flatbuffers::VOffsetT = 14;
+  pub const VT_SOURCE_PLUGIN: flatbuffers::VOffsetT = 16;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -404,6 +822,10 @@ impl<'a> ImageReceivedEvent<'a> {
     args: &'args ImageReceivedEventArgs<'args>
   ) -> flatbuffers::WIPOffset<ImageReceivedEvent<'bldr>> {
     let mut builder = ImageReceivedEventBuilder::new(_fbb);
+    if let Some(x) = args.source_plugin { builder.add_source_plugin(x); }
+    if let Some(x) = args.causation_id { builder.add_causation_id(x); }
+    if let Some(x) = args.correlation_id { builder.add_correlation_id(x); }
+    if let Some(x) = args.event_uuid { builder.add_event_uuid(x); }
     if let Some(x) = args.image_format { builder.add_image_format(x); }
     if let Some(x) = args.image_uuid { builder.add_image_uuid(x); }
     if let Some(x) = args.event_create_ts { builder.add_event_create_ts(x); }
@@ -423,6 +845,22 @@ impl<'a> ImageReceivedEvent<'a> {
   pub fn image_format(&self) -> Option<&'a str> {
     self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageReceivedEvent::VT_IMAGE_FORMAT, None)
   }
+  #[inline]
+  pub fn event_uuid(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageReceivedEvent::VT_EVENT_UUID, None)
+  }
+  #[inline]
+  pub fn correlation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageReceivedEvent::VT_CORRELATION_ID, None)
+  }
+  #[inline]
+  pub fn causation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageReceivedEvent::VT_CAUSATION_ID, None)
+  }
+  #[inline]
+  pub fn source_plugin(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageReceivedEvent::VT_SOURCE_PLUGIN, None)
+  }
 }
 
 impl flatbuffers::Verifiable for ImageReceivedEvent<'_> {
@@ -435,6 +873,10 @@ impl flatbuffers::Verifiable for ImageReceivedEvent<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_create_ts", Self::VT_EVENT_CREATE_TS, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("image_uuid", Self::VT_IMAGE_UUID, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("image_format", Self::VT_IMAGE_FORMAT, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_uuid", Self::VT_EVENT_UUID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("correlation_id", Self::VT_CORRELATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("causation_id", Self::VT_CAUSATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("source_plugin", Self::VT_SOURCE_PLUGIN, false)?
      .finish();
     Ok(())
   }
@@ -443,6 +885,10 @@ pub struct ImageReceivedEventArgs<'a> {
     pub event_create_ts: Option<flatbuffers::WIPOffset<&'a str>>,
     pub image_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
     pub image_format: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub event_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub correlation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub causation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub source_plugin: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for ImageReceivedEventArgs<'a> {
   #[inline]
@@ -451,6 +897,10 @@ impl<'a> Default for ImageReceivedEventArgs<'a> {
       event_create_ts: None,
       image_uuid: None,
       image_format: None,
+      event_uuid: None,
+      correlation_id: None,
+      causation_id: None,
+      source_plugin: None,
     }
   }
 }
@@ -473,6 +923,22 @@ impl<'a: 'b, 'b> ImageReceivedEventBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageReceivedEvent::VT_IMAGE_FORMAT, image_format);
   }
   #[inline]
+  pub fn add_event_uuid(&mut self, event_uuid: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageReceivedEvent::VT_EVENT_UUID, event_uuid);
+  }
+  #[inline]
+  pub fn add_correlation_id(&mut self, correlation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageReceivedEvent::VT_CORRELATION_ID, correlation_id);
+  }
+  #[inline]
+  pub fn add_causation_id(&mut self, causation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageReceivedEvent::VT_CAUSATION_ID, causation_id);
+  }
+  #[inline]
+  pub fn add_source_plugin(&mut self, source_plugin: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageReceivedEvent::VT_SOURCE_PLUGIN, source_plugin);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ImageReceivedEventBuilder<'a, 'b> {
     let start = _fbb.start_table();
     ImageReceivedEventBuilder {
@@ -493,6 +959,10 @@ impl core::fmt::Debug for ImageReceivedEvent<'_> {
       ds.field("event_create_ts", &self.event_create_ts());
       ds.field("image_uuid", &self.image_uuid());
       ds.field("image_format", &self.image_format());
+      ds.field("event_uuid", &self.event_uuid());
+      ds.field("correlation_id", &self.correlation_id());
+      ds.field("causation_id", &self.causation_id());
+      ds.field("source_plugin", &self.source_plugin());
       ds.finish()
   }
 }
@@ -624,6 +1094,10 @@ impl<'a> ImageScoredEvent<'a> {
   pub const VT_IMAGE_UUID: flatbuffers::VOffsetT = 6;
   pub const VT_IMAGE_FORMAT: flatbuffers::VOffsetT = 8;
   pub const VT_SCORES: flatbuffers::VOffsetT = 10;
+  pub const VT_EVENT_UUID: flatbuffers::VOffsetT = 12;
+  pub const VT_CORRELATION_ID: flatbuffers::VOffsetT = 14;
+  pub const VT_CAUSATION_ID: flatbuffers::VOffsetT = 16;
+  pub const VT_SOURCE_PLUGIN: flatbuffers::VOffsetT = 18;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -635,6 +1109,10 @@ impl<'a> ImageScoredEvent<'a> {
     args: &'args ImageScoredEventArgs<'args>
   ) -> flatbuffers::WIPOffset<ImageScoredEvent<'bldr>> {
     let mut builder = ImageScoredEventBuilder::new(_fbb);
+    if let Some(x) = args.source_plugin { builder.add_source_plugin(x); }
+    if let Some(x) = args.causation_id { builder.add_causation_id(x); }
+    if let Some(x) = args.correlation_id { builder.add_correlation_id(x); }
+    if let Some(x) = args.event_uuid { builder.add_event_uuid(x); }
     if let Some(x) = args.scores { builder.add_scores(x); }
     if let Some(x) = args.image_format { builder.add_image_format(x); }
     if let Some(x) = args.image_uuid { builder.add_image_uuid(x); }
@@ -659,6 +1137,22 @@ impl<'a> ImageScoredEvent<'a> {
   pub fn scores(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<ImageLabelScore<'a>>>> {
     self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<ImageLabelScore>>>>(ImageScoredEvent::VT_SCORES, None)
   }
+  #[inline]
+  pub fn event_uuid(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageScoredEvent::VT_EVENT_UUID, None)
+  }
+  #[inline]
+  pub fn correlation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageScoredEvent::VT_CORRELATION_ID, None)
+  }
+  #[inline]
+  pub fn causation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageScoredEvent::VT_CAUSATION_ID, None)
+  }
+  #[inline]
+  pub fn source_plugin(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageScoredEvent::VT_SOURCE_PLUGIN, None)
+  }
 }
 
 impl flatbuffers::Verifiable for ImageScoredEvent<'_> {
@@ -672,6 +1166,10 @@ impl flatbuffers::Verifiable for ImageScoredEvent<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("image_uuid", Self::VT_IMAGE_UUID, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("image_format", Self::VT_IMAGE_FORMAT, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<ImageLabelScore>>>>("scores", Self::VT_SCORES, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_uuid", Self::VT_EVENT_UUID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("correlation_id", Self::VT_CORRELATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("causation_id", Self::VT_CAUSATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("source_plugin", Self::VT_SOURCE_PLUGIN, false)?
      .finish();
     Ok(())
   }
@@ -681,6 +1179,10 @@ pub struct ImageScoredEventArgs<'a> {
     pub image_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
     pub image_format: Option<flatbuffers::WIPOffset<&'a str>>,
     pub scores: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<ImageLabelScore<'a>>>>>,
+    pub event_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub correlation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub causation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub source_plugin: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for ImageScoredEventArgs<'a> {
   #[inline]
@@ -690,6 +1192,10 @@ impl<'a> Default for ImageScoredEventArgs<'a> {
       image_uuid: None,
       image_format: None,
       scores: None,
+      event_uuid: None,
+      correlation_id: None,
+      causation_id: None,
+      source_plugin: None,
     }
   }
 }
@@ -716,8 +1222,24 @@ impl<'a: 'b, 'b> ImageScoredEventBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageScoredEvent::VT_SCORES, scores);
   }
   #[inline]
-  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ImageScoredEventBuilder<'a, 'b> {
-    let start = _fbb.start_table();
+  pub fn add_event_uuid(&mut self, event_uuid: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageScoredEvent::VT_EVENT_UUID, event_uuid);
+  }
+  #[inline]
+  pub fn add_correlation_id(&mut self, correlation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageScoredEvent::VT_CORRELATION_ID, correlation_id);
+  }
+  #[inline]
+  pub fn add_causation_id(&mut self, causation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageScoredEvent::VT_CAUSATION_ID, causation_id);
+  }
+  #[inline]
+  pub fn add_source_plugin(&mut self, source_plugin: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageScoredEvent::VT_SOURCE_PLUGIN, source_plugin);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ImageScoredEventBuilder<'a, 'b> {
+    let start = _fbb.start_table();
     ImageScoredEventBuilder {
       fbb_: _fbb,
       start_: start,
@@ -737,6 +1259,10 @@ impl core::fmt::Debug for ImageScoredEvent<'_> {
       ds.field("image_uuid", &self.image_uuid());
       ds.field("image_format", &self.image_format());
       ds.field("scores", &self.scores());
+      ds.field("event_uuid", &self.event_uuid());
+      ds.field("correlation_id", &self.correlation_id());
+      ds.field("causation_id", &self.causation_id());
+      ds.field("source_plugin", &self.source_plugin());
       ds.finish()
   }
 }
@@ -760,6 +1286,11 @@ impl<'a> ImageStoredEvent<'a> {
   pub const VT_IMAGE_UUID: flatbuffers::VOffsetT = 6;
   pub const VT_IMAGE_FORMAT: flatbuffers::VOffsetT = 8;
   pub const VT_DESTINATION: flatbuffers::VOffsetT = 10;
+  pub const VT_EVENT_UUID: flatbuffers::VOffsetT = 12;
+  pub const VT_CORRELATION_ID: flatbuffers::VOffsetT = 14;
+  pub const VT_CAUSATION_ID: flatbuffers::VOffsetT = 16;
+  pub const VT_SOURCE_PLUGIN: flatbuffers::VOffsetT = 18;
+  pub const VT_LOCATION: flatbuffers::VOffsetT = 20;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -771,6 +1302,11 @@ impl<'a> ImageStoredEvent<'a> {
     args: &'args ImageStoredEventArgs<'args>
   ) -> flatbuffers::WIPOffset<ImageStoredEvent<'bldr>> {
     let mut builder = ImageStoredEventBuilder::new(_fbb);
+    if let Some(x) = args.location { builder.add_location(x); }
+    if let Some(x) = args.source_plugin { builder.add_source_plugin(x); }
+    if let Some(x) = args.causation_id { builder.add_causation_id(x); }
+    if let Some(x) = args.correlation_id { builder.add_correlation_id(x); }
+    if let Some(x) = args.event_uuid { builder.add_event_uuid(x); }
     if let Some(x) = args.destination { builder.add_destination(x); }
     if let Some(x) = args.image_format { builder.add_image_format(x); }
     if let Some(x) = args.image_uuid { builder.add_image_uuid(x); }
@@ -795,6 +1331,26 @@ impl<'a> ImageStoredEvent<'a> {
   pub fn destination(&self) -> Option<&'a str> {
     self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageStoredEvent::VT_DESTINATION, None)
   }
+  #[inline]
+  pub fn event_uuid(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageStoredEvent::VT_EVENT_UUID, None)
+  }
+  #[inline]
+  pub fn correlation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageStoredEvent::VT_CORRELATION_ID, None)
+  }
+  #[inline]
+  pub fn causation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageStoredEvent::VT_CAUSATION_ID, None)
+  }
+  #[inline]
+  pub fn source_plugin(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageStoredEvent::VT_SOURCE_PLUGIN, None)
+  }
+  #[inline]
+  pub fn location(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageStoredEvent::VT_LOCATION, None)
+  }
 }
 
 impl flatbuffers::Verifiable for ImageStoredEvent<'_> {
@@ -808,6 +1364,11 @@ impl flatbuffers::Verifiable for ImageStoredEvent<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("image_uuid", Self::VT_IMAGE_UUID, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("image_format", Self::VT_IMAGE_FORMAT, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("destination", Self::VT_DESTINATION, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_uuid", Self::VT_EVENT_UUID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("correlation_id", Self::VT_CORRELATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("causation_id", Self::VT_CAUSATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("source_plugin", Self::VT_SOURCE_PLUGIN, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("location", Self::VT_LOCATION, false)?
      .finish();
     Ok(())
   }
@@ -817,6 +1378,11 @@ pub struct ImageStoredEventArgs<'a> {
     pub image_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
     pub image_format: Option<flatbuffers::WIPOffset<&'a str>>,
     pub destination: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub event_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub correlation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub causation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub source_plugin: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub location: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for ImageStoredEventArgs<'a> {
   #[inline]
@@ -826,6 +1392,11 @@ impl<'a> Default for ImageStoredEventArgs<'a> {
       image_uuid: None,
       image_format: None,
       destination: None,
+      event_uuid: None,
+      correlation_id: None,
+      causation_id: None,
+      source_plugin: None,
+      location: None,
     }
   }
 }
@@ -852,6 +1423,26 @@ impl<'a: 'b, 'b> ImageStoredEventBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageStoredEvent::VT_DESTINATION, destination);
   }
   #[inline]
+  pub fn add_event_uuid(&mut self, event_uuid: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageStoredEvent::VT_EVENT_UUID, event_uuid);
+  }
+  #[inline]
+  pub fn add_correlation_id(&mut self, correlation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageStoredEvent::VT_CORRELATION_ID, correlation_id);
+  }
+  #[inline]
+  pub fn add_causation_id(&mut self, causation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageStoredEvent::VT_CAUSATION_ID, causation_id);
+  }
+  #[inline]
+  pub fn add_source_plugin(&mut self, source_plugin: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageStoredEvent::VT_SOURCE_PLUGIN, source_plugin);
+  }
+  #[inline]
+  pub fn add_location(&mut self, location: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageStoredEvent::VT_LOCATION, location);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ImageStoredEventBuilder<'a, 'b> {
     let start = _fbb.start_table();
     ImageStoredEventBuilder {
@@ -873,6 +1464,11 @@ impl core::fmt::Debug for ImageStoredEvent<'_> {
       ds.field("image_uuid", &self.image_uuid());
       ds.field("image_format", &self.image_format());
       ds.field("destination", &self.destination());
+      ds.field("event_uuid", &self.event_uuid());
+      ds.field("correlation_id", &self.correlation_id());
+      ds.field("causation_id", &self.causation_id());
+      ds.field("source_plugin", &self.source_plugin());
+      ds.field("location", &self.location());
       ds.finish()
   }
 }
@@ -895,6 +1491,10 @@ impl<'a> ImageDeletedEvent<'a> {
   pub const VT_EVENT_CREATE_TS: flatbuffers::VOffsetT = 4;
   pub const VT_IMAGE_UUID: flatbuffers::VOffsetT = 6;
   pub const VT_IMAGE_FORMAT: flatbuffers::VOffsetT = 8;
+  pub const VT_EVENT_UUID: flatbuffers::VOffsetT = 10;
+  pub const VT_CORRELATION_ID: flatbuffers::VOffsetT = 12;
+  pub const VT_CAUSATION_ID: flatbuffers::VOffsetT = 14;
+  pub const VT_SOURCE_PLUGIN: flatbuffers::VOffsetT = 16;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -906,6 +1506,10 @@ impl<'a> ImageDeletedEvent<'a> {
     args: &'args ImageDeletedEventArgs<'args>
   ) -> flatbuffers::WIPOffset<ImageDeletedEvent<'bldr>> {
     let mut builder = ImageDeletedEventBuilder::new(_fbb);
+    if let Some(x) = args.source_plugin { builder.add_source_plugin(x); }
+    if let Some(x) = args.causation_id { builder.add_causation_id(x); }
+    if let Some(x) = args.correlation_id { builder.add_correlation_id(x); }
+    if let Some(x) = args.event_uuid { builder.add_event_uuid(x); }
     if let Some(x) = args.image_format { builder.add_image_format(x); }
     if let Some(x) = args.image_uuid { builder.add_image_uuid(x); }
     if let Some(x) = args.event_create_ts { builder.add_event_create_ts(x); }
@@ -925,6 +1529,22 @@ impl<'a> ImageDeletedEvent<'a> {
   pub fn image_format(&self) -> Option<&'a str> {
     self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageDeletedEvent::VT_IMAGE_FORMAT, None)
   }
+  #[inline]
+  pub fn event_uuid(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageDeletedEvent::VT_EVENT_UUID, None)
+  }
+  #[inline]
+  pub fn correlation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageDeletedEvent::VT_CORRELATION_ID, None)
+  }
+  #[inline]
+  pub fn causation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageDeletedEvent::VT_CAUSATION_ID, None)
+  }
+  #[inline]
+  pub fn source_plugin(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageDeletedEvent::VT_SOURCE_PLUGIN, None)
+  }
 }
 
 impl flatbuffers::Verifiable for ImageDeletedEvent<'_> {
@@ -937,6 +1557,10 @@ impl flatbuffers::Verifiable for ImageDeletedEvent<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_create_ts", Self::VT_EVENT_CREATE_TS, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("image_uuid", Self::VT_IMAGE_UUID, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("image_format", Self::VT_IMAGE_FORMAT, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_uuid", Self::VT_EVENT_UUID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("correlation_id", Self::VT_CORRELATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("causation_id", Self::VT_CAUSATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("source_plugin", Self::VT_SOURCE_PLUGIN, false)?
      .finish();
     Ok(())
   }
@@ -945,6 +1569,10 @@ pub struct ImageDeletedEventArgs<'a> {
     pub event_create_ts: Option<flatbuffers::WIPOffset<&'a str>>,
     pub image_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
     pub image_format: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub event_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub correlation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub causation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub source_plugin: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for ImageDeletedEventArgs<'a> {
   #[inline]
@@ -953,48 +1581,526 @@ impl<'a> Default for ImageDeletedEventArgs<'a> {
       event_create_ts: None,
       image_uuid: None,
       image_format: None,
+      event_uuid: None,
+      correlation_id: None,
+      causation_id: None,
+      source_plugin: None,
+    }
+  }
+}
+
+pub struct ImageDeletedEventBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> ImageDeletedEventBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_event_create_ts(&mut self, event_create_ts: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageDeletedEvent::VT_EVENT_CREATE_TS, event_create_ts);
+  }
+  #[inline]
+  pub fn add_image_uuid(&mut self, image_uuid: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageDeletedEvent::VT_IMAGE_UUID, image_uuid);
+  }
+  #[inline]
+  pub fn add_image_format(&mut self, image_format: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageDeletedEvent::VT_IMAGE_FORMAT, image_format);
+  }
+  #[inline]
+  pub fn add_event_uuid(&mut self, event_uuid: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageDeletedEvent::VT_EVENT_UUID, event_uuid);
+  }
+  #[inline]
+  pub fn add_correlation_id(&mut self, correlation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageDeletedEvent::VT_CORRELATION_ID, correlation_id);
+  }
+  #[inline]
+  pub fn add_causation_id(&mut self, causation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageDeletedEvent::VT_CAUSATION_ID, causation_id);
+  }
+  #[inline]
+  pub fn add_source_plugin(&mut self, source_plugin: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageDeletedEvent::VT_SOURCE_PLUGIN, source_plugin);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ImageDeletedEventBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    ImageDeletedEventBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<ImageDeletedEvent<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for ImageDeletedEvent<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("ImageDeletedEvent");
+      ds.field("event_create_ts", &self.event_create_ts());
+      ds.field("image_uuid", &self.image_uuid());
+      ds.field("image_format", &self.image_format());
+      ds.field("event_uuid", &self.event_uuid());
+      ds.field("correlation_id", &self.correlation_id());
+      ds.field("causation_id", &self.causation_id());
+      ds.field("source_plugin", &self.source_plugin());
+      ds.finish()
+  }
+}
+pub enum ImageProcessingErrorEventOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct ImageProcessingErrorEvent<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for ImageProcessingErrorEvent<'a> {
+  type Inner = ImageProcessingErrorEvent<'a>;
+  #[inline]
+  fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table { buf, loc } }
+  }
+}
+
+impl<'a> ImageProcessingErrorEvent<'a> {
+  pub const VT_EVENT_CREATE_TS: flatbuffers::VOffsetT = 4;
+  pub const VT_IMAGE_UUID: flatbuffers::VOffsetT = 6;
+  pub const VT_PLUGIN_NAME: flatbuffers::VOffsetT = 8;
+  pub const VT_STAGE: flatbuffers::VOffsetT = 10;
+  pub const VT_ERROR_CODE: flatbuffers::VOffsetT = 12;
+  pub const VT_ERROR_MESSAGE: flatbuffers::VOffsetT = 14;
+  pub const VT_EVENT_UUID: flatbuffers::VOffsetT = 16;
+  pub const VT_CORRELATION_ID: flatbuffers::VOffsetT = 18;
+  pub const VT_CAUSATION_ID: flatbuffers::VOffsetT = 20;
+  pub const VT_SOURCE_PLUGIN: flatbuffers::VOffsetT = 22;
+
+  #[inline]
+  pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    ImageProcessingErrorEvent { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args ImageProcessingErrorEventArgs<'args>
+  ) -> flatbuffers::WIPOffset<ImageProcessingErrorEvent<'bldr>> {
+    let mut builder = ImageProcessingErrorEventBuilder::new(_fbb);
+    if let Some(x) = args.source_plugin { builder.add_source_plugin(x); }
+    if let Some(x) = args.causation_id { builder.add_causation_id(x); }
+    if let Some(x) = args.correlation_id { builder.add_correlation_id(x); }
+    if let Some(x) = args.event_uuid { builder.add_event_uuid(x); }
+    if let Some(x) = args.error_message { builder.add_error_message(x); }
+    if let Some(x) = args.error_code { builder.add_error_code(x); }
+    if let Some(x) = args.stage { builder.add_stage(x); }
+    if let Some(x) = args.plugin_name { builder.add_plugin_name(x); }
+    if let Some(x) = args.image_uuid { builder.add_image_uuid(x); }
+    if let Some(x) = args.event_create_ts { builder.add_event_create_ts(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn event_create_ts(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageProcessingErrorEvent::VT_EVENT_CREATE_TS, None)
+  }
+  #[inline]
+  pub fn image_uuid(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageProcessingErrorEvent::VT_IMAGE_UUID, None)
+  }
+  #[inline]
+  pub fn plugin_name(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageProcessingErrorEvent::VT_PLUGIN_NAME, None)
+  }
+  #[inline]
+  pub fn stage(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageProcessingErrorEvent::VT_STAGE, None)
+  }
+  #[inline]
+  pub fn error_code(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageProcessingErrorEvent::VT_ERROR_CODE, None)
+  }
+  #[inline]
+  pub fn error_message(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageProcessingErrorEvent::VT_ERROR_MESSAGE, None)
+  }
+  #[inline]
+  pub fn event_uuid(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageProcessingErrorEvent::VT_EVENT_UUID, None)
+  }
+  #[inline]
+  pub fn correlation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageProcessingErrorEvent::VT_CORRELATION_ID, None)
+  }
+  #[inline]
+  pub fn causation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageProcessingErrorEvent::VT_CAUSATION_ID, None)
+  }
+  #[inline]
+  pub fn source_plugin(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ImageProcessingErrorEvent::VT_SOURCE_PLUGIN, None)
+  }
+}
+
+impl flatbuffers::Verifiable for ImageProcessingErrorEvent<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_create_ts", Self::VT_EVENT_CREATE_TS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("image_uuid", Self::VT_IMAGE_UUID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("plugin_name", Self::VT_PLUGIN_NAME, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("stage", Self::VT_STAGE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("error_code", Self::VT_ERROR_CODE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("error_message", Self::VT_ERROR_MESSAGE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_uuid", Self::VT_EVENT_UUID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("correlation_id", Self::VT_CORRELATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("causation_id", Self::VT_CAUSATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("source_plugin", Self::VT_SOURCE_PLUGIN, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ImageProcessingErrorEventArgs<'a> {
+    pub event_create_ts: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub image_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub plugin_name: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub stage: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub error_code: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub error_message: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub event_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub correlation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub causation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub source_plugin: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for ImageProcessingErrorEventArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    ImageProcessingErrorEventArgs {
+      event_create_ts: None,
+      image_uuid: None,
+      plugin_name: None,
+      stage: None,
+      error_code: None,
+      error_message: None,
+      event_uuid: None,
+      correlation_id: None,
+      causation_id: None,
+      source_plugin: None,
+    }
+  }
+}
+
+pub struct ImageProcessingErrorEventBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> ImageProcessingErrorEventBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_event_create_ts(&mut self, event_create_ts: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageProcessingErrorEvent::VT_EVENT_CREATE_TS, event_create_ts);
+  }
+  #[inline]
+  pub fn add_image_uuid(&mut self, image_uuid: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageProcessingErrorEvent::VT_IMAGE_UUID, image_uuid);
+  }
+  #[inline]
+  pub fn add_plugin_name(&mut self, plugin_name: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageProcessingErrorEvent::VT_PLUGIN_NAME, plugin_name);
+  }
+  #[inline]
+  pub fn add_stage(&mut self, stage: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageProcessingErrorEvent::VT_STAGE, stage);
+  }
+  #[inline]
+  pub fn add_error_code(&mut self, error_code: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageProcessingErrorEvent::VT_ERROR_CODE, error_code);
+  }
+  #[inline]
+  pub fn add_error_message(&mut self, error_message: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageProcessingErrorEvent::VT_ERROR_MESSAGE, error_message);
+  }
+  #[inline]
+  pub fn add_event_uuid(&mut self, event_uuid: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageProcessingErrorEvent::VT_EVENT_UUID, event_uuid);
+  }
+  #[inline]
+  pub fn add_correlation_id(&mut self, correlation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageProcessingErrorEvent::VT_CORRELATION_ID, correlation_id);
+  }
+  #[inline]
+  pub fn add_causation_id(&mut self, causation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageProcessingErrorEvent::VT_CAUSATION_ID, causation_id);
+  }
+  #[inline]
+  pub fn add_source_plugin(&mut self, source_plugin: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageProcessingErrorEvent::VT_SOURCE_PLUGIN, source_plugin);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ImageProcessingErrorEventBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    ImageProcessingErrorEventBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<ImageProcessingErrorEvent<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for ImageProcessingErrorEvent<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("ImageProcessingErrorEvent");
+      ds.field("event_create_ts", &self.event_create_ts());
+      ds.field("image_uuid", &self.image_uuid());
+      ds.field("plugin_name", &self.plugin_name());
+      ds.field("stage", &self.stage());
+      ds.field("error_code", &self.error_code());
+      ds.field("error_message", &self.error_message());
+      ds.field("event_uuid", &self.event_uuid());
+      ds.field("correlation_id", &self.correlation_id());
+      ds.field("causation_id", &self.causation_id());
+      ds.field("source_plugin", &self.source_plugin());
+      ds.finish()
+  }
+}
+pub enum DetectionEventOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct DetectionEvent<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for DetectionEvent<'a> {
+  type Inner = DetectionEvent<'a>;
+  #[inline]
+  fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table { buf, loc } }
+  }
+}
+
+impl<'a> DetectionEvent<'a> {
+  pub const VT_EVENT_CREATE_TS: flatbuffers::VOffsetT = 4;
+  pub const VT_IMAGE_UUID: flatbuffers::VOffsetT = 6;
+  pub const VT_IMAGE_FORMAT: flatbuffers::VOffsetT = 8;
+  pub const VT_LABEL: flatbuffers::VOffsetT = 10;
+  pub const VT_PROBABILITY: flatbuffers::VOffsetT = 12;
+  pub const VT_THRESHOLD: flatbuffers::VOffsetT = 14;
+  pub const VT_STORED_LOCATION: flatbuffers::VOffsetT = 16;
+  pub const VT_EVENT_UUID: flatbuffers::VOffsetT = 18;
+  pub const VT_CORRELATION_ID: flatbuffers::VOffsetT = 20;
+  pub const VT_CAUSATION_ID: flatbuffers::VOffsetT = 22;
+  pub const VT_SOURCE_PLUGIN: flatbuffers::VOffsetT = 24;
+
+  #[inline]
+  pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    DetectionEvent { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args DetectionEventArgs<'args>
+  ) -> flatbuffers::WIPOffset<DetectionEvent<'bldr>> {
+    let mut builder = DetectionEventBuilder::new(_fbb);
+    if let Some(x) = args.source_plugin { builder.add_source_plugin(x); }
+    if let Some(x) = args.causation_id { builder.add_causation_id(x); }
+    if let Some(x) = args.correlation_id { builder.add_correlation_id(x); }
+    if let Some(x) = args.event_uuid { builder.add_event_uuid(x); }
+    if let Some(x) = args.stored_location { builder.add_stored_location(x); }
+    builder.add_threshold(args.threshold);
+    builder.add_probability(args.probability);
+    if let Some(x) = args.label { builder.add_label(x); }
+    if let Some(x) = args.image_format { builder.add_image_format(x); }
+    if let Some(x) = args.image_uuid { builder.add_image_uuid(x); }
+    if let Some(x) = args.event_create_ts { builder.add_event_create_ts(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn event_create_ts(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(DetectionEvent::VT_EVENT_CREATE_TS, None)
+  }
+  #[inline]
+  pub fn image_uuid(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(DetectionEvent::VT_IMAGE_UUID, None)
+  }
+  #[inline]
+  pub fn image_format(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(DetectionEvent::VT_IMAGE_FORMAT, None)
+  }
+  #[inline]
+  pub fn label(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(DetectionEvent::VT_LABEL, None)
+  }
+  #[inline]
+  pub fn probability(&self) -> f32 {
+    self._tab.get::<f32>(DetectionEvent::VT_PROBABILITY, Some(0.0)).unwrap()
+  }
+  #[inline]
+  pub fn threshold(&self) -> f32 {
+    self._tab.get::<f32>(DetectionEvent::VT_THRESHOLD, Some(0.0)).unwrap()
+  }
+  #[inline]
+  pub fn stored_location(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(DetectionEvent::VT_STORED_LOCATION, None)
+  }
+  #[inline]
+  pub fn event_uuid(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(DetectionEvent::VT_EVENT_UUID, None)
+  }
+  #[inline]
+  pub fn correlation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(DetectionEvent::VT_CORRELATION_ID, None)
+  }
+  #[inline]
+  pub fn causation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(DetectionEvent::VT_CAUSATION_ID, None)
+  }
+  #[inline]
+  pub fn source_plugin(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(DetectionEvent::VT_SOURCE_PLUGIN, None)
+  }
+}
+
+impl flatbuffers::Verifiable for DetectionEvent<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_create_ts", Self::VT_EVENT_CREATE_TS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("image_uuid", Self::VT_IMAGE_UUID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("image_format", Self::VT_IMAGE_FORMAT, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("label", Self::VT_LABEL, false)?
+     .visit_field::<f32>("probability", Self::VT_PROBABILITY, false)?
+     .visit_field::<f32>("threshold", Self::VT_THRESHOLD, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("stored_location", Self::VT_STORED_LOCATION, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_uuid", Self::VT_EVENT_UUID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("correlation_id", Self::VT_CORRELATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("causation_id", Self::VT_CAUSATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("source_plugin", Self::VT_SOURCE_PLUGIN, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct DetectionEventArgs<'a> {
+    pub event_create_ts: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub image_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub image_format: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub label: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub probability: f32,
+    pub threshold: f32,
+    pub stored_location: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub event_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub correlation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub causation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub source_plugin: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for DetectionEventArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    DetectionEventArgs {
+      event_create_ts: None,
+      image_uuid: None,
+      image_format: None,
+      label: None,
+      probability: 0.0,
+      threshold: 0.0,
+      stored_location: None,
+      event_uuid: None,
+      correlation_id: None,
+      causation_id: None,
+      source_plugin: None,
     }
   }
 }
 
-pub struct ImageDeletedEventBuilder<'a: 'b, 'b> {
+pub struct DetectionEventBuilder<'a: 'b, 'b> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
 }
-impl<'a: 'b, 'b> ImageDeletedEventBuilder<'a, 'b> {
+impl<'a: 'b, 'b> DetectionEventBuilder<'a, 'b> {
   #[inline]
   pub fn add_event_create_ts(&mut self, event_create_ts: flatbuffers::WIPOffset<&'b  str>) {
-    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageDeletedEvent::VT_EVENT_CREATE_TS, event_create_ts);
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(DetectionEvent::VT_EVENT_CREATE_TS, event_create_ts);
   }
   #[inline]
   pub fn add_image_uuid(&mut self, image_uuid: flatbuffers::WIPOffset<&'b  str>) {
-    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageDeletedEvent::VT_IMAGE_UUID, image_uuid);
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(DetectionEvent::VT_IMAGE_UUID, image_uuid);
   }
   #[inline]
   pub fn add_image_format(&mut self, image_format: flatbuffers::WIPOffset<&'b  str>) {
-    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ImageDeletedEvent::VT_IMAGE_FORMAT, image_format);
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(DetectionEvent::VT_IMAGE_FORMAT, image_format);
   }
   #[inline]
-  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ImageDeletedEventBuilder<'a, 'b> {
+  pub fn add_label(&mut self, label: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(DetectionEvent::VT_LABEL, label);
+  }
+  #[inline]
+  pub fn add_probability(&mut self, probability: f32) {
+    self.fbb_.push_slot::<f32>(DetectionEvent::VT_PROBABILITY, probability, 0.0);
+  }
+  #[inline]
+  pub fn add_threshold(&mut self, threshold: f32) {
+    self.fbb_.push_slot::<f32>(DetectionEvent::VT_THRESHOLD, threshold, 0.0);
+  }
+  #[inline]
+  pub fn add_stored_location(&mut self, stored_location: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(DetectionEvent::VT_STORED_LOCATION, stored_location);
+  }
+  #[inline]
+  pub fn add_event_uuid(&mut self, event_uuid: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(DetectionEvent::VT_EVENT_UUID, event_uuid);
+  }
+  #[inline]
+  pub fn add_correlation_id(&mut self, correlation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(DetectionEvent::VT_CORRELATION_ID, correlation_id);
+  }
+  #[inline]
+  pub fn add_causation_id(&mut self, causation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(DetectionEvent::VT_CAUSATION_ID, causation_id);
+  }
+  #[inline]
+  pub fn add_source_plugin(&mut self, source_plugin: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(DetectionEvent::VT_SOURCE_PLUGIN, source_plugin);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> DetectionEventBuilder<'a, 'b> {
     let start = _fbb.start_table();
-    ImageDeletedEventBuilder {
+    DetectionEventBuilder {
       fbb_: _fbb,
       start_: start,
     }
   }
   #[inline]
-  pub fn finish(self) -> flatbuffers::WIPOffset<ImageDeletedEvent<'a>> {
+  pub fn finish(self) -> flatbuffers::WIPOffset<DetectionEvent<'a>> {
     let o = self.fbb_.end_table(self.start_);
     flatbuffers::WIPOffset::new(o.value())
   }
 }
 
-impl core::fmt::Debug for ImageDeletedEvent<'_> {
+impl core::fmt::Debug for DetectionEvent<'_> {
   fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-    let mut ds = f.debug_struct("ImageDeletedEvent");
+    let mut ds = f.debug_struct("DetectionEvent");
       ds.field("event_create_ts", &self.event_create_ts());
       ds.field("image_uuid", &self.image_uuid());
       ds.field("image_format", &self.image_format());
+      ds.field("label", &self.label());
+      ds.field("probability", &self.probability());
+      ds.field("threshold", &self.threshold());
+      ds.field("stored_location", &self.stored_location());
+      ds.field("event_uuid", &self.event_uuid());
+      ds.field("correlation_id", &self.correlation_id());
+      ds.field("causation_id", &self.causation_id());
+      ds.field("source_plugin", &self.source_plugin());
       ds.finish()
   }
 }
@@ -1017,6 +2123,10 @@ impl<'a> PluginStartedEvent<'a> {
   pub const VT_EVENT_CREATE_TS: flatbuffers::VOffsetT = 4;
   pub const VT_PLUGIN_NAME: flatbuffers::VOffsetT = 6;
   pub const VT_PLUGIN_UUID: flatbuffers::VOffsetT = 8;
+  pub const VT_EVENT_UUID: flatbuffers::VOffsetT = 10;
+  pub const VT_CORRELATION_ID: flatbuffers::VOffsetT = 12;
+  pub const VT_CAUSATION_ID: flatbuffers::VOffsetT = 14;
+  pub const VT_SOURCE_PLUGIN: flatbuffers::VOffsetT = 16;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -1028,6 +2138,10 @@ impl<'a> PluginStartedEvent<'a> {
     args: &'args PluginStartedEventArgs<'args>
   ) -> flatbuffers::WIPOffset<PluginStartedEvent<'bldr>> {
     let mut builder = PluginStartedEventBuilder::new(_fbb);
+    if let Some(x) = args.source_plugin { builder.add_source_plugin(x); }
+    if let Some(x) = args.causation_id { builder.add_causation_id(x); }
+    if let Some(x) = args.correlation_id { builder.add_correlation_id(x); }
+    if let Some(x) = args.event_uuid { builder.add_event_uuid(x); }
     if let Some(x) = args.plugin_uuid { builder.add_plugin_uuid(x); }
     if let Some(x) = args.plugin_name { builder.add_plugin_name(x); }
     if let Some(x) = args.event_create_ts { builder.add_event_create_ts(x); }
@@ -1047,6 +2161,22 @@ impl<'a> PluginStartedEvent<'a> {
   pub fn plugin_uuid(&self) -> Option<&'a str> {
     self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PluginStartedEvent::VT_PLUGIN_UUID, None)
   }
+  #[inline]
+  pub fn event_uuid(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PluginStartedEvent::VT_EVENT_UUID, None)
+  }
+  #[inline]
+  pub fn correlation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PluginStartedEvent::VT_CORRELATION_ID, None)
+  }
+  #[inline]
+  pub fn causation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PluginStartedEvent::VT_CAUSATION_ID, None)
+  }
+  #[inline]
+  pub fn source_plugin(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PluginStartedEvent::VT_SOURCE_PLUGIN, None)
+  }
 }
 
 impl flatbuffers::Verifiable for PluginStartedEvent<'_> {
@@ -1059,6 +2189,10 @@ impl flatbuffers::Verifiable for PluginStartedEvent<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_create_ts", Self::VT_EVENT_CREATE_TS, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("plugin_name", Self::VT_PLUGIN_NAME, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("plugin_uuid", Self::VT_PLUGIN_UUID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_uuid", Self::VT_EVENT_UUID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("correlation_id", Self::VT_CORRELATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("causation_id", Self::VT_CAUSATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("source_plugin", Self::VT_SOURCE_PLUGIN, false)?
      .finish();
     Ok(())
   }
@@ -1067,6 +2201,10 @@ pub struct PluginStartedEventArgs<'a> {
     pub event_create_ts: Option<flatbuffers::WIPOffset<&'a str>>,
     pub plugin_name: Option<flatbuffers::WIPOffset<&'a str>>,
     pub plugin_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub event_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub correlation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub causation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub source_plugin: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for PluginStartedEventArgs<'a> {
   #[inline]
@@ -1075,6 +2213,10 @@ impl<'a> Default for PluginStartedEventArgs<'a> {
       event_create_ts: None,
       plugin_name: None,
       plugin_uuid: None,
+      event_uuid: None,
+      correlation_id: None,
+      causation_id: None,
+      source_plugin: None,
     }
   }
 }
@@ -1097,6 +2239,22 @@ impl<'a: 'b, 'b> PluginStartedEventBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PluginStartedEvent::VT_PLUGIN_UUID, plugin_uuid);
   }
   #[inline]
+  pub fn add_event_uuid(&mut self, event_uuid: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PluginStartedEvent::VT_EVENT_UUID, event_uuid);
+  }
+  #[inline]
+  pub fn add_correlation_id(&mut self, correlation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PluginStartedEvent::VT_CORRELATION_ID, correlation_id);
+  }
+  #[inline]
+  pub fn add_causation_id(&mut self, causation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PluginStartedEvent::VT_CAUSATION_ID, causation_id);
+  }
+  #[inline]
+  pub fn add_source_plugin(&mut self, source_plugin: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PluginStartedEvent::VT_SOURCE_PLUGIN, source_plugin);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> PluginStartedEventBuilder<'a, 'b> {
     let start = _fbb.start_table();
     PluginStartedEventBuilder {
@@ -1117,6 +2275,10 @@ impl core::fmt::Debug for PluginStartedEvent<'_> {
       ds.field("event_create_ts", &self.event_create_ts());
       ds.field("plugin_name", &self.plugin_name());
       ds.field("plugin_uuid", &self.plugin_uuid());
+      ds.field("event_uuid", &self.event_uuid());
+      ds.field("correlation_id", &self.correlation_id());
+      ds.field("causation_id", &self.causation_id());
+      ds.field("source_plugin", &self.source_plugin());
       ds.finish()
   }
 }
@@ -1139,6 +2301,10 @@ impl<'a> PluginTerminatingEvent<'a> {
   pub const VT_EVENT_CREATE_TS: flatbuffers::VOffsetT = 4;
   pub const VT_PLUGIN_NAME: flatbuffers::VOffsetT = 6;
   pub const VT_PLUGIN_UUID: flatbuffers::VOffsetT = 8;
+  pub const VT_EVENT_UUID: flatbuffers::VOffsetT = 10;
+  pub const VT_CORRELATION_ID: flatbuffers::VOffsetT = 12;
+  pub const VT_CAUSATION_ID: flatbuffers::VOffsetT = 14;
+  pub const VT_SOURCE_PLUGIN: flatbuffers::VOffsetT = 16;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -1150,6 +2316,10 @@ impl<'a> PluginTerminatingEvent<'a> {
     args: &'args PluginTerminatingEventArgs<'args>
   ) -> flatbuffers::WIPOffset<PluginTerminatingEvent<'bldr>> {
     let mut builder = PluginTerminatingEventBuilder::new(_fbb);
+    if let Some(x) = args.source_plugin { builder.add_source_plugin(x); }
+    if let Some(x) = args.causation_id { builder.add_causation_id(x); }
+    if let Some(x) = args.correlation_id { builder.add_correlation_id(x); }
+    if let Some(x) = args.event_uuid { builder.add_event_uuid(x); }
     if let Some(x) = args.plugin_uuid { builder.add_plugin_uuid(x); }
     if let Some(x) = args.plugin_name { builder.add_plugin_name(x); }
     if let Some(x) = args.event_create_ts { builder.add_event_create_ts(x); }
@@ -1169,6 +2339,22 @@ impl<'a> PluginTerminatingEvent<'a> {
   pub fn plugin_uuid(&self) -> Option<&'a str> {
     self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PluginTerminatingEvent::VT_PLUGIN_UUID, None)
   }
+  #[inline]
+  pub fn event_uuid(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PluginTerminatingEvent::VT_EVENT_UUID, None)
+  }
+  #[inline]
+  pub fn correlation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PluginTerminatingEvent::VT_CORRELATION_ID, None)
+  }
+  #[inline]
+  pub fn causation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PluginTerminatingEvent::VT_CAUSATION_ID, None)
+  }
+  #[inline]
+  pub fn source_plugin(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PluginTerminatingEvent::VT_SOURCE_PLUGIN, None)
+  }
 }
 
 impl flatbuffers::Verifiable for PluginTerminatingEvent<'_> {
@@ -1181,6 +2367,10 @@ impl flatbuffers::Verifiable for PluginTerminatingEvent<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_create_ts", Self::VT_EVENT_CREATE_TS, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("plugin_name", Self::VT_PLUGIN_NAME, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("plugin_uuid", Self::VT_PLUGIN_UUID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_uuid", Self::VT_EVENT_UUID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("correlation_id", Self::VT_CORRELATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("causation_id", Self::VT_CAUSATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("source_plugin", Self::VT_SOURCE_PLUGIN, false)?
      .finish();
     Ok(())
   }
@@ -1189,6 +2379,10 @@ pub struct PluginTerminatingEventArgs<'a> {
     pub event_create_ts: Option<flatbuffers::WIPOffset<&'a str>>,
     pub plugin_name: Option<flatbuffers::WIPOffset<&'a str>>,
     pub plugin_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub event_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub correlation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub causation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub source_plugin: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for PluginTerminatingEventArgs<'a> {
   #[inline]
@@ -1197,6 +2391,10 @@ impl<'a> Default for PluginTerminatingEventArgs<'a> {
       event_create_ts: None,
       plugin_name: None,
       plugin_uuid: None,
+      event_uuid: None,
+      correlation_id: None,
+      causation_id: None,
+      source_plugin: None,
     }
   }
 }
@@ -1219,6 +2417,22 @@ impl<'a: 'b, 'b> PluginTerminatingEventBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PluginTerminatingEvent::VT_PLUGIN_UUID, plugin_uuid);
   }
   #[inline]
+  pub fn add_event_uuid(&mut self, event_uuid: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PluginTerminatingEvent::VT_EVENT_UUID, event_uuid);
+  }
+  #[inline]
+  pub fn add_correlation_id(&mut self, correlation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PluginTerminatingEvent::VT_CORRELATION_ID, correlation_id);
+  }
+  #[inline]
+  pub fn add_causation_id(&mut self, causation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PluginTerminatingEvent::VT_CAUSATION_ID, causation_id);
+  }
+  #[inline]
+  pub fn add_source_plugin(&mut self, source_plugin: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PluginTerminatingEvent::VT_SOURCE_PLUGIN, source_plugin);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> PluginTerminatingEventBuilder<'a, 'b> {
     let start = _fbb.start_table();
     PluginTerminatingEventBuilder {
@@ -1239,6 +2453,10 @@ impl core::fmt::Debug for PluginTerminatingEvent<'_> {
       ds.field("event_create_ts", &self.event_create_ts());
       ds.field("plugin_name", &self.plugin_name());
       ds.field("plugin_uuid", &self.plugin_uuid());
+      ds.field("event_uuid", &self.event_uuid());
+      ds.field("correlation_id", &self.correlation_id());
+      ds.field("causation_id", &self.causation_id());
+      ds.field("source_plugin", &self.source_plugin());
       ds.finish()
   }
 }
@@ -1261,6 +2479,10 @@ impl<'a> PluginTerminateEvent<'a> {
   pub const VT_EVENT_CREATE_TS: flatbuffers::VOffsetT = 4;
   pub const VT_TARGET_PLUGIN_NAME: flatbuffers::VOffsetT = 6;
   pub const VT_TARGET_PLUGIN_UUID: flatbuffers::VOffsetT = 8;
+  pub const VT_EVENT_UUID: flatbuffers::VOffsetT = 10;
+  pub const VT_CORRELATION_ID: flatbuffers::VOffsetT = 12;
+  pub const VT_CAUSATION_ID: flatbuffers::VOffsetT = 14;
+  pub const VT_SOURCE_PLUGIN: flatbuffers::VOffsetT = 16;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -1272,6 +2494,10 @@ impl<'a> PluginTerminateEvent<'a> {
     args: &'args PluginTerminateEventArgs<'args>
   ) -> flatbuffers::WIPOffset<PluginTerminateEvent<'bldr>> {
     let mut builder = PluginTerminateEventBuilder::new(_fbb);
+    if let Some(x) = args.source_plugin { builder.add_source_plugin(x); }
+    if let Some(x) = args.causation_id { builder.add_causation_id(x); }
+    if let Some(x) = args.correlation_id { builder.add_correlation_id(x); }
+    if let Some(x) = args.event_uuid { builder.add_event_uuid(x); }
     if let Some(x) = args.target_plugin_uuid { builder.add_target_plugin_uuid(x); }
     if let Some(x) = args.target_plugin_name { builder.add_target_plugin_name(x); }
     if let Some(x) = args.event_create_ts { builder.add_event_create_ts(x); }
@@ -1291,6 +2517,22 @@ impl<'a> PluginTerminateEvent<'a> {
   pub fn target_plugin_uuid(&self) -> Option<&'a str> {
     self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PluginTerminateEvent::VT_TARGET_PLUGIN_UUID, None)
   }
+  #[inline]
+  pub fn event_uuid(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PluginTerminateEvent::VT_EVENT_UUID, None)
+  }
+  #[inline]
+  pub fn correlation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PluginTerminateEvent::VT_CORRELATION_ID, None)
+  }
+  #[inline]
+  pub fn causation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PluginTerminateEvent::VT_CAUSATION_ID, None)
+  }
+  #[inline]
+  pub fn source_plugin(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(PluginTerminateEvent::VT_SOURCE_PLUGIN, None)
+  }
 }
 
 impl flatbuffers::Verifiable for PluginTerminateEvent<'_> {
@@ -1303,6 +2545,10 @@ impl flatbuffers::Verifiable for PluginTerminateEvent<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_create_ts", Self::VT_EVENT_CREATE_TS, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("target_plugin_name", Self::VT_TARGET_PLUGIN_NAME, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("target_plugin_uuid", Self::VT_TARGET_PLUGIN_UUID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_uuid", Self::VT_EVENT_UUID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("correlation_id", Self::VT_CORRELATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("causation_id", Self::VT_CAUSATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("source_plugin", Self::VT_SOURCE_PLUGIN, false)?
      .finish();
     Ok(())
   }
@@ -1311,6 +2557,10 @@ pub struct PluginTerminateEventArgs<'a> {
     pub event_create_ts: Option<flatbuffers::WIPOffset<&'a str>>,
     pub target_plugin_name: Option<flatbuffers::WIPOffset<&'a str>>,
     pub target_plugin_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub event_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub correlation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub causation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub source_plugin: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for PluginTerminateEventArgs<'a> {
   #[inline]
@@ -1319,6 +2569,10 @@ impl<'a> Default for PluginTerminateEventArgs<'a> {
       event_create_ts: None,
       target_plugin_name: None,
       target_plugin_uuid: None,
+      event_uuid: None,
+      correlation_id: None,
+      causation_id: None,
+      source_plugin: None,
     }
   }
 }
@@ -1341,6 +2595,22 @@ impl<'a: 'b, 'b> PluginTerminateEventBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PluginTerminateEvent::VT_TARGET_PLUGIN_UUID, target_plugin_uuid);
   }
   #[inline]
+  pub fn add_event_uuid(&mut self, event_uuid: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PluginTerminateEvent::VT_EVENT_UUID, event_uuid);
+  }
+  #[inline]
+  pub fn add_correlation_id(&mut self, correlation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PluginTerminateEvent::VT_CORRELATION_ID, correlation_id);
+  }
+  #[inline]
+  pub fn add_causation_id(&mut self, causation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PluginTerminateEvent::VT_CAUSATION_ID, causation_id);
+  }
+  #[inline]
+  pub fn add_source_plugin(&mut self, source_plugin: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(PluginTerminateEvent::VT_SOURCE_PLUGIN, source_plugin);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> PluginTerminateEventBuilder<'a, 'b> {
     let start = _fbb.start_table();
     PluginTerminateEventBuilder {
@@ -1361,6 +2631,202 @@ impl core::fmt::Debug for PluginTerminateEvent<'_> {
       ds.field("event_create_ts", &self.event_create_ts());
       ds.field("target_plugin_name", &self.target_plugin_name());
       ds.field("target_plugin_uuid", &self.target_plugin_uuid());
+      ds.field("event_uuid", &self.event_uuid());
+      ds.field("correlation_id", &self.correlation_id());
+      ds.field("causation_id", &self.causation_id());
+      ds.field("source_plugin", &self.source_plugin());
+      ds.finish()
+  }
+}
+pub enum ConfigReloadedEventOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct ConfigReloadedEvent<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for ConfigReloadedEvent<'a> {
+  type Inner = ConfigReloadedEvent<'a>;
+  #[inline]
+  fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table { buf, loc } }
+  }
+}
+
+impl<'a> ConfigReloadedEvent<'a> {
+  pub const VT_EVENT_CREATE_TS: flatbuffers::VOffsetT = 4;
+  pub const VT_CONFIG_FILE: flatbuffers::VOffsetT = 6;
+  pub const VT_APPLIED: flatbuffers::VOffsetT = 8;
+  pub const VT_REQUIRES_RESTART: flatbuffers::VOffsetT = 10;
+  pub const VT_EVENT_UUID: flatbuffers::VOffsetT = 12;
+  pub const VT_CORRELATION_ID: flatbuffers::VOffsetT = 14;
+  pub const VT_CAUSATION_ID: flatbuffers::VOffsetT = 16;
+  pub const VT_SOURCE_PLUGIN: flatbuffers::VOffsetT = 18;
+
+  #[inline]
+  pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    ConfigReloadedEvent { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args ConfigReloadedEventArgs<'args>
+  ) -> flatbuffers::WIPOffset<ConfigReloadedEvent<'bldr>> {
+    let mut builder = ConfigReloadedEventBuilder::new(_fbb);
+    if let Some(x) = args.source_plugin { builder.add_source_plugin(x); }
+    if let Some(x) = args.causation_id { builder.add_causation_id(x); }
+    if let Some(x) = args.correlation_id { builder.add_correlation_id(x); }
+    if let Some(x) = args.event_uuid { builder.add_event_uuid(x); }
+    if let Some(x) = args.requires_restart { builder.add_requires_restart(x); }
+    if let Some(x) = args.applied { builder.add_applied(x); }
+    if let Some(x) = args.config_file { builder.add_config_file(x); }
+    if let Some(x) = args.event_create_ts { builder.add_event_create_ts(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn event_create_ts(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ConfigReloadedEvent::VT_EVENT_CREATE_TS, None)
+  }
+  #[inline]
+  pub fn config_file(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ConfigReloadedEvent::VT_CONFIG_FILE, None)
+  }
+  #[inline]
+  pub fn applied(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>>(ConfigReloadedEvent::VT_APPLIED, None)
+  }
+  #[inline]
+  pub fn requires_restart(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>>(ConfigReloadedEvent::VT_REQUIRES_RESTART, None)
+  }
+  #[inline]
+  pub fn event_uuid(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ConfigReloadedEvent::VT_EVENT_UUID, None)
+  }
+  #[inline]
+  pub fn correlation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ConfigReloadedEvent::VT_CORRELATION_ID, None)
+  }
+  #[inline]
+  pub fn causation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ConfigReloadedEvent::VT_CAUSATION_ID, None)
+  }
+  #[inline]
+  pub fn source_plugin(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ConfigReloadedEvent::VT_SOURCE_PLUGIN, None)
+  }
+}
+
+impl flatbuffers::Verifiable for ConfigReloadedEvent<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_create_ts", Self::VT_EVENT_CREATE_TS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("config_file", Self::VT_CONFIG_FILE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<&'_ str>>>>("applied", Self::VT_APPLIED, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<&'_ str>>>>("requires_restart", Self::VT_REQUIRES_RESTART, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_uuid", Self::VT_EVENT_UUID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("correlation_id", Self::VT_CORRELATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("causation_id", Self::VT_CAUSATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("source_plugin", Self::VT_SOURCE_PLUGIN, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ConfigReloadedEventArgs<'a> {
+    pub event_create_ts: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub config_file: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub applied: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>>,
+    pub requires_restart: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>>,
+    pub event_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub correlation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub causation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub source_plugin: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for ConfigReloadedEventArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    ConfigReloadedEventArgs {
+      event_create_ts: None,
+      config_file: None,
+      applied: None,
+      requires_restart: None,
+      event_uuid: None,
+      correlation_id: None,
+      causation_id: None,
+      source_plugin: None,
+    }
+  }
+}
+
+pub struct ConfigReloadedEventBuilder<'a: 'b, 'b> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b> ConfigReloadedEventBuilder<'a, 'b> {
+  #[inline]
+  pub fn add_event_create_ts(&mut self, event_create_ts: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ConfigReloadedEvent::VT_EVENT_CREATE_TS, event_create_ts);
+  }
+  #[inline]
+  pub fn add_config_file(&mut self, config_file: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ConfigReloadedEvent::VT_CONFIG_FILE, config_file);
+  }
+  #[inline]
+  pub fn add_applied(&mut self, applied: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<&'b  str>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ConfigReloadedEvent::VT_APPLIED, applied);
+  }
+  #[inline]
+  pub fn add_requires_restart(&mut self, requires_restart: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<&'b  str>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ConfigReloadedEvent::VT_REQUIRES_RESTART, requires_restart);
+  }
+  #[inline]
+  pub fn add_event_uuid(&mut self, event_uuid: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ConfigReloadedEvent::VT_EVENT_UUID, event_uuid);
+  }
+  #[inline]
+  pub fn add_correlation_id(&mut self, correlation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ConfigReloadedEvent::VT_CORRELATION_ID, correlation_id);
+  }
+  #[inline]
+  pub fn add_causation_id(&mut self, causation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ConfigReloadedEvent::VT_CAUSATION_ID, causation_id);
+  }
+  #[inline]
+  pub fn add_source_plugin(&mut self, source_plugin: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ConfigReloadedEvent::VT_SOURCE_PLUGIN, source_plugin);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> ConfigReloadedEventBuilder<'a, 'b> {
+    let start = _fbb.start_table();
+    ConfigReloadedEventBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<ConfigReloadedEvent<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for ConfigReloadedEvent<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("ConfigReloadedEvent");
+      ds.field("event_create_ts", &self.event_create_ts());
+      ds.field("config_file", &self.config_file());
+      ds.field("applied", &self.applied());
+      ds.field("requires_restart", &self.requires_restart());
+      ds.field("event_uuid", &self.event_uuid());
+      ds.field("correlation_id", &self.correlation_id());
+      ds.field("causation_id", &self.causation_id());
+      ds.field("source_plugin", &self.source_plugin());
       ds.finish()
   }
 }
@@ -1385,6 +2851,10 @@ impl<'a> MonitorPowerStartEvent<'a> {
   pub const VT_MONITOR_TYPES: flatbuffers::VOffsetT = 8;
   pub const VT_MONITOR_START_TS: flatbuffers::VOffsetT = 10;
   pub const VT_MONITOR_SECONDS: flatbuffers::VOffsetT = 12;
+  pub const VT_EVENT_UUID: flatbuffers::VOffsetT = 14;
+  pub const VT_CORRELATION_ID: flatbuffers::VOffsetT = 16;
+  pub const VT_CAUSATION_ID: flatbuffers::VOffsetT = 18;
+  pub const VT_SOURCE_PLUGIN: flatbuffers::VOffsetT = 20;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -1396,6 +2866,10 @@ impl<'a> MonitorPowerStartEvent<'a> {
     args: &'args MonitorPowerStartEventArgs<'args>
   ) -> flatbuffers::WIPOffset<MonitorPowerStartEvent<'bldr>> {
     let mut builder = MonitorPowerStartEventBuilder::new(_fbb);
+    if let Some(x) = args.source_plugin { builder.add_source_plugin(x); }
+    if let Some(x) = args.causation_id { builder.add_causation_id(x); }
+    if let Some(x) = args.correlation_id { builder.add_correlation_id(x); }
+    if let Some(x) = args.event_uuid { builder.add_event_uuid(x); }
     builder.add_monitor_seconds(args.monitor_seconds);
     if let Some(x) = args.monitor_start_ts { builder.add_monitor_start_ts(x); }
     if let Some(x) = args.monitor_types { builder.add_monitor_types(x); }
@@ -1425,6 +2899,22 @@ impl<'a> MonitorPowerStartEvent<'a> {
   pub fn monitor_seconds(&self) -> u32 {
     self._tab.get::<u32>(MonitorPowerStartEvent::VT_MONITOR_SECONDS, Some(0)).unwrap()
   }
+  #[inline]
+  pub fn event_uuid(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(MonitorPowerStartEvent::VT_EVENT_UUID, None)
+  }
+  #[inline]
+  pub fn correlation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(MonitorPowerStartEvent::VT_CORRELATION_ID, None)
+  }
+  #[inline]
+  pub fn causation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(MonitorPowerStartEvent::VT_CAUSATION_ID, None)
+  }
+  #[inline]
+  pub fn source_plugin(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(MonitorPowerStartEvent::VT_SOURCE_PLUGIN, None)
+  }
 }
 
 impl flatbuffers::Verifiable for MonitorPowerStartEvent<'_> {
@@ -1439,6 +2929,10 @@ impl flatbuffers::Verifiable for MonitorPowerStartEvent<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, MonitorType>>>("monitor_types", Self::VT_MONITOR_TYPES, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("monitor_start_ts", Self::VT_MONITOR_START_TS, false)?
      .visit_field::<u32>("monitor_seconds", Self::VT_MONITOR_SECONDS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_uuid", Self::VT_EVENT_UUID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("correlation_id", Self::VT_CORRELATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("causation_id", Self::VT_CAUSATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("source_plugin", Self::VT_SOURCE_PLUGIN, false)?
      .finish();
     Ok(())
   }
@@ -1449,6 +2943,10 @@ pub struct MonitorPowerStartEventArgs<'a> {
     pub monitor_types: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, MonitorType>>>,
     pub monitor_start_ts: Option<flatbuffers::WIPOffset<&'a str>>,
     pub monitor_seconds: u32,
+    pub event_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub correlation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub causation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub source_plugin: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for MonitorPowerStartEventArgs<'a> {
   #[inline]
@@ -1459,6 +2957,10 @@ impl<'a> Default for MonitorPowerStartEventArgs<'a> {
       monitor_types: None,
       monitor_start_ts: None,
       monitor_seconds: 0,
+      event_uuid: None,
+      correlation_id: None,
+      causation_id: None,
+      source_plugin: None,
     }
   }
 }
@@ -1489,6 +2991,22 @@ impl<'a: 'b, 'b> MonitorPowerStartEventBuilder<'a, 'b> {
     self.fbb_.push_slot::<u32>(MonitorPowerStartEvent::VT_MONITOR_SECONDS, monitor_seconds, 0);
   }
   #[inline]
+  pub fn add_event_uuid(&mut self, event_uuid: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(MonitorPowerStartEvent::VT_EVENT_UUID, event_uuid);
+  }
+  #[inline]
+  pub fn add_correlation_id(&mut self, correlation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(MonitorPowerStartEvent::VT_CORRELATION_ID, correlation_id);
+  }
+  #[inline]
+  pub fn add_causation_id(&mut self, causation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(MonitorPowerStartEvent::VT_CAUSATION_ID, causation_id);
+  }
+  #[inline]
+  pub fn add_source_plugin(&mut self, source_plugin: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(MonitorPowerStartEvent::VT_SOURCE_PLUGIN, source_plugin);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> MonitorPowerStartEventBuilder<'a, 'b> {
     let start = _fbb.start_table();
     MonitorPowerStartEventBuilder {
@@ -1511,6 +3029,10 @@ impl core::fmt::Debug for MonitorPowerStartEvent<'_> {
       ds.field("monitor_types", &self.monitor_types());
       ds.field("monitor_start_ts", &self.monitor_start_ts());
       ds.field("monitor_seconds", &self.monitor_seconds());
+      ds.field("event_uuid", &self.event_uuid());
+      ds.field("correlation_id", &self.correlation_id());
+      ds.field("causation_id", &self.causation_id());
+      ds.field("source_plugin", &self.source_plugin());
       ds.finish()
   }
 }
@@ -1532,6 +3054,10 @@ impl<'a> flatbuffers::Follow<'a> for MonitorPowerStopEvent<'a> {
 impl<'a> MonitorPowerStopEvent<'a> {
   pub const VT_EVENT_CREATE_TS: flatbuffers::VOffsetT = 4;
   pub const VT_PIDS: flatbuffers::VOffsetT = 6;
+  pub const VT_EVENT_UUID: flatbuffers::VOffsetT = 8;
+  pub const VT_CORRELATION_ID: flatbuffers::VOffsetT = 10;
+  pub const VT_CAUSATION_ID: flatbuffers::VOffsetT = 12;
+  pub const VT_SOURCE_PLUGIN: flatbuffers::VOffsetT = 14;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -1543,6 +3069,10 @@ impl<'a> MonitorPowerStopEvent<'a> {
     args: &'args MonitorPowerStopEventArgs<'args>
   ) -> flatbuffers::WIPOffset<MonitorPowerStopEvent<'bldr>> {
     let mut builder = MonitorPowerStopEventBuilder::new(_fbb);
+    if let Some(x) = args.source_plugin { builder.add_source_plugin(x); }
+    if let Some(x) = args.causation_id { builder.add_causation_id(x); }
+    if let Some(x) = args.correlation_id { builder.add_correlation_id(x); }
+    if let Some(x) = args.event_uuid { builder.add_event_uuid(x); }
     if let Some(x) = args.pids { builder.add_pids(x); }
     if let Some(x) = args.event_create_ts { builder.add_event_create_ts(x); }
     builder.finish()
@@ -1557,6 +3087,22 @@ impl<'a> MonitorPowerStopEvent<'a> {
   pub fn pids(&self) -> Option<flatbuffers::Vector<'a, i32>> {
     self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, i32>>>(MonitorPowerStopEvent::VT_PIDS, None)
   }
+  #[inline]
+  pub fn event_uuid(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(MonitorPowerStopEvent::VT_EVENT_UUID, None)
+  }
+  #[inline]
+  pub fn correlation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(MonitorPowerStopEvent::VT_CORRELATION_ID, None)
+  }
+  #[inline]
+  pub fn causation_id(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(MonitorPowerStopEvent::VT_CAUSATION_ID, None)
+  }
+  #[inline]
+  pub fn source_plugin(&self) -> Option<&'a str> {
+    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(MonitorPowerStopEvent::VT_SOURCE_PLUGIN, None)
+  }
 }
 
 impl flatbuffers::Verifiable for MonitorPowerStopEvent<'_> {
@@ -1568,6 +3114,10 @@ impl flatbuffers::Verifiable for MonitorPowerStopEvent<'_> {
     v.visit_table(pos)?
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_create_ts", Self::VT_EVENT_CREATE_TS, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, i32>>>("pids", Self::VT_PIDS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("event_uuid", Self::VT_EVENT_UUID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("correlation_id", Self::VT_CORRELATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("causation_id", Self::VT_CAUSATION_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("source_plugin", Self::VT_SOURCE_PLUGIN, false)?
      .finish();
     Ok(())
   }
@@ -1575,6 +3125,10 @@ impl flatbuffers::Verifiable for MonitorPowerStopEvent<'_> {
 pub struct MonitorPowerStopEventArgs<'a> {
     pub event_create_ts: Option<flatbuffers::WIPOffset<&'a str>>,
     pub pids: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, i32>>>,
+    pub event_uuid: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub correlation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub causation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub source_plugin: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for MonitorPowerStopEventArgs<'a> {
   #[inline]
@@ -1582,6 +3136,10 @@ impl<'a> Default for MonitorPowerStopEventArgs<'a> {
     MonitorPowerStopEventArgs {
       event_create_ts: None,
       pids: None,
+      event_uuid: None,
+      correlation_id: None,
+      causation_id: None,
+      source_plugin: None,
     }
   }
 }
@@ -1600,6 +3158,22 @@ impl<'a: 'b, 'b> MonitorPowerStopEventBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(MonitorPowerStopEvent::VT_PIDS, pids);
   }
   #[inline]
+  pub fn add_event_uuid(&mut self, event_uuid: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(MonitorPowerStopEvent::VT_EVENT_UUID, event_uuid);
+  }
+  #[inline]
+  pub fn add_correlation_id(&mut self, correlation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(MonitorPowerStopEvent::VT_CORRELATION_ID, correlation_id);
+  }
+  #[inline]
+  pub fn add_causation_id(&mut self, causation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(MonitorPowerStopEvent::VT_CAUSATION_ID, causation_id);
+  }
+  #[inline]
+  pub fn add_source_plugin(&mut self, source_plugin: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(MonitorPowerStopEvent::VT_SOURCE_PLUGIN, source_plugin);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> MonitorPowerStopEventBuilder<'a, 'b> {
     let start = _fbb.start_table();
     MonitorPowerStopEventBuilder {
@@ -1619,6 +3193,10 @@ impl core::fmt::Debug for MonitorPowerStopEvent<'_> {
     let mut ds = f.debug_struct("MonitorPowerStopEvent");
       ds.field("event_create_ts", &self.event_create_ts());
       ds.field("pids", &self.pids());
+      ds.field("event_uuid", &self.event_uuid());
+      ds.field("correlation_id", &self.correlation_id());
+      ds.field("causation_id", &self.causation_id());
+      ds.field("source_plugin", &self.source_plugin());
       ds.finish()
   }
 }
@@ -1640,6 +3218,8 @@ impl<'a> flatbuffers::Follow<'a> for Event<'a> {
 impl<'a> Event<'a> {
   pub const VT_EVENT_TYPE: flatbuffers::VOffsetT = 4;
   pub const VT_EVENT: flatbuffers::VOffsetT = 6;
+  pub const VT_SCHEMA_MAJOR: flatbuffers::VOffsetT = 8;
+  pub const VT_SCHEMA_MINOR: flatbuffers::VOffsetT = 10;
 
   #[inline]
   pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -1652,6 +3232,8 @@ impl<'a> Event<'a> {
   ) -> flatbuffers::WIPOffset<Event<'bldr>> {
     let mut builder = EventBuilder::new(_fbb);
     if let Some(x) = args.event { builder.add_event(x); }
+    builder.add_schema_minor(args.schema_minor);
+    builder.add_schema_major(args.schema_major);
     builder.add_event_type(args.event_type);
     builder.finish()
   }
@@ -1666,6 +3248,14 @@ impl<'a> Event<'a> {
     self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Table<'a>>>(Event::VT_EVENT, None)
   }
   #[inline]
+  pub fn schema_major(&self) -> u16 {
+    self._tab.get::<u16>(Event::VT_SCHEMA_MAJOR, Some(1)).unwrap()
+  }
+  #[inline]
+  pub fn schema_minor(&self) -> u16 {
+    self._tab.get::<u16>(Event::VT_SCHEMA_MINOR, Some(0)).unwrap()
+  }
+  #[inline]
   #[allow(non_snake_case)]
   pub fn event_as_new_image_event(&self) -> Option<NewImageEvent<'a>> {
     if self.event_type() == EventType::NewImageEvent {
@@ -1765,6 +3355,36 @@ impl<'a> Event<'a> {
     }
   }
 
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn event_as_image_processing_error_event(&self) -> Option<ImageProcessingErrorEvent<'a>> {
+    if self.event_type() == EventType::ImageProcessingErrorEvent {
+      self.event().map(ImageProcessingErrorEvent::init_from_table)
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn event_as_detection_event(&self) -> Option<DetectionEvent<'a>> {
+    if self.event_type() == EventType::DetectionEvent {
+      self.event().map(DetectionEvent::init_from_table)
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  #[allow(non_snake_case)]
+  pub fn event_as_config_reloaded_event(&self) -> Option<ConfigReloadedEvent<'a>> {
+    if self.event_type() == EventType::ConfigReloadedEvent {
+      self.event().map(ConfigReloadedEvent::init_from_table)
+    } else {
+      None
+    }
+  }
+
 }
 
 impl flatbuffers::Verifiable for Event<'_> {
@@ -1786,9 +3406,14 @@ impl flatbuffers::Verifiable for Event<'_> {
           EventType::PluginTerminateEvent => v.verify_union_variant::<flatbuffers::ForwardsUOffset<PluginTerminateEvent>>("EventType::PluginTerminateEvent", pos),
           EventType::MonitorPowerStartEvent => v.verify_union_variant::<flatbuffers::ForwardsUOffset<MonitorPowerStartEvent>>("EventType::MonitorPowerStartEvent", pos),
           EventType::MonitorPowerStopEvent => v.verify_union_variant::<flatbuffers::ForwardsUOffset<MonitorPowerStopEvent>>("EventType::MonitorPowerStopEvent", pos),
+          EventType::ImageProcessingErrorEvent => v.verify_union_variant::<flatbuffers::ForwardsUOffset<ImageProcessingErrorEvent>>("EventType::ImageProcessingErrorEvent", pos),
+          EventType::DetectionEvent => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DetectionEvent>>("EventType::DetectionEvent", pos),
+          EventType::ConfigReloadedEvent => v.verify_union_variant::<flatbuffers::ForwardsUOffset<ConfigReloadedEvent>>("EventType::ConfigReloadedEvent", pos),
           _ => Ok(()),
         }
      })?
+     .visit_field::<u16>("schema_major", Self::VT_SCHEMA_MAJOR, false)?
+     .visit_field::<u16>("schema_minor", Self::VT_SCHEMA_MINOR, false)?
      .finish();
     Ok(())
   }
@@ -1796,6 +3421,8 @@ impl flatbuffers::Verifiable for Event<'_> {
 pub struct EventArgs {
     pub event_type: EventType,
     pub event: Option<flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>>,
+    pub schema_major: u16,
+    pub schema_minor: u16,
 }
 impl<'a> Default for EventArgs {
   #[inline]
@@ -1803,6 +3430,8 @@ impl<'a> Default for EventArgs {
     EventArgs {
       event_type: EventType::NONE,
       event: None,
+      schema_major: 1,
+      schema_minor: 0,
     }
   }
 }
@@ -1821,6 +3450,14 @@ impl<'a: 'b, 'b> EventBuilder<'a, 'b> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Event::VT_EVENT, event);
   }
   #[inline]
+  pub fn add_schema_major(&mut self, schema_major: u16) {
+    self.fbb_.push_slot::<u16>(Event::VT_SCHEMA_MAJOR, schema_major, 1);
+  }
+  #[inline]
+  pub fn add_schema_minor(&mut self, schema_minor: u16) {
+    self.fbb_.push_slot::<u16>(Event::VT_SCHEMA_MINOR, schema_minor, 0);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a>) -> EventBuilder<'a, 'b> {
     let start = _fbb.start_table();
     EventBuilder {
@@ -1910,11 +3547,34 @@ impl core::fmt::Debug for Event<'_> {
             ds.field("event", &"InvalidFlatbuffer: Union discriminant does not match value.")
           }
         },
+        EventType::ImageProcessingErrorEvent => {
+          if let Some(x) = self.event_as_image_processing_error_event() {
+            ds.field("event", &x)
+          } else {
+            ds.field("event", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        EventType::DetectionEvent => {
+          if let Some(x) = self.event_as_detection_event() {
+            ds.field("event", &x)
+          } else {
+            ds.field("event", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        EventType::ConfigReloadedEvent => {
+          if let Some(x) = self.event_as_config_reloaded_event() {
+            ds.field("event", &x)
+          } else {
+            ds.field("event", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
         _ => {
           let x: Option<()> = None;
           ds.field("event", &x)
         },
       };
+      ds.field("schema_major", &self.schema_major());
+      ds.field("schema_minor", &self.schema_minor());
       ds.finish()
   }
 }
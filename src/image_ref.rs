@@ -0,0 +1,279 @@
+use std::borrow::Cow;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use crate::config::{config::Config, errors::Errors};
+use crate::events::{decompress_image_bytes, ImageLocationType, ImageReference};
+use crate::events_generated::gen_events;
+use crate::traps_utils;
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+// POSIX shared memory objects are visible as files in this directory on Linux.
+pub const SHARED_MEMORY_DIR: &str = "/dev/shm";
+
+// ***************************************************************************
+// REFERENCE DIRECTORIES
+// ***************************************************************************
+/** The directories that referenced images are read from.  FilePath references
+ * must name a file in file_dir and ContentHash references name a file in
+ * content_dir; references of either type are rejected when their directory
+ * isn't configured.  The directories are absolute paths.
+ */
+#[derive(Debug, Default, Clone)]
+pub struct ImageRefDirs {
+    pub file_dir: Option<String>,
+    pub content_dir: Option<String>,
+}
+
+impl ImageRefDirs {
+    // ----------------------------------------------------------------------
+    // new_from_config:
+    // ----------------------------------------------------------------------
+    pub fn new_from_config(config: &Config) -> Self {
+        ImageRefDirs {
+            file_dir: config.image_ref_dir.as_deref().map(traps_utils::get_absolute_path),
+            content_dir: config.image_content_dir.as_deref().map(traps_utils::get_absolute_path),
+        }
+    }
+}
+
+// ***************************************************************************
+// PUBLIC FUNCTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// resolve_image:
+// ---------------------------------------------------------------------------
+/** Get the image carried by a NewImageEvent.  If the event has an image
+ * reference, the image is read from the referenced location and its size and
+ * checksum are verified.  Otherwise, the embedded image is decompressed if
+ * necessary or returned without copying.
+ */
+pub fn resolve_image<'a>(event: &gen_events::NewImageEvent<'a>, dirs: &ImageRefDirs)
+    -> Result<Cow<'a, [u8]>, Errors> {
+    if let Some(image_ref) = event.image_ref() {
+        let image_ref = ImageReference::new_from_gen(image_ref)?;
+        return Result::Ok(Cow::Owned(read_reference(&image_ref, dirs)?));
+    }
+    let bytes = match event.image() {
+        Some(bytes) => bytes,
+        None => return Result::Err(Errors::EventReadFlatbuffer("image".to_string())),
+    };
+    match event.image_compression() {
+        gen_events::ImageCompression::Uncompressed => Result::Ok(Cow::Borrowed(bytes)),
+        compression => Result::Ok(Cow::Owned(decompress_image_bytes(bytes, compression)?)),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// read_reference:
+// ---------------------------------------------------------------------------
+/** Read the referenced image and verify its size and checksum. */
+pub fn read_reference(image_ref: &ImageReference, dirs: &ImageRefDirs) -> Result<Vec<u8>, Errors> {
+    let location = image_ref.get_location();
+    let path = reference_path(image_ref.get_location_type(), location, dirs)?;
+    let bytes = match fs::read(&path) {
+        Ok(b) => b,
+        Err(e) => return Result::Err(Errors::ImageReferenceError(location.clone(),
+                                         format!("unable to read {}: {}", path.display(), e))),
+    };
+
+    // Make sure we got the image that was sent.
+    if bytes.len() as u64 != image_ref.get_size() {
+        return Result::Err(Errors::ImageReferenceError(location.clone(),
+            format!("expected {} bytes but read {}", image_ref.get_size(), bytes.len())));
+    }
+    let actual = checksum(&bytes);
+    if !actual.eq_ignore_ascii_case(image_ref.get_checksum()) {
+        return Result::Err(Errors::ImageReferenceError(location.clone(),
+            format!("expected checksum {} but calculated {}", image_ref.get_checksum(), actual)));
+    }
+    Result::Ok(bytes)
+}
+
+// ---------------------------------------------------------------------------
+// reference_path:
+// ---------------------------------------------------------------------------
+/** Get the file path of a referenced image.  File paths must be absolute and
+ * inside the configured file directory.  Shared memory and content hash
+ * locations are simple names that are looked up in the shared memory
+ * directory and the content directory, respectively.  Since any process that
+ * can publish on the bus can send a reference, the path is canonicalized and
+ * rejected if it resolves, for example through a symbolic link, to a file
+ * outside of its directory.
+ */
+pub fn reference_path(location_type: ImageLocationType, location: &str, dirs: &ImageRefDirs)
+    -> Result<PathBuf, Errors> {
+    let (dir, path) = match location_type {
+        ImageLocationType::FilePath => {
+            let path = PathBuf::from(location);
+            if !path.is_absolute() {
+                return Result::Err(Errors::ImageReferenceError(location.to_string(),
+                                       "file paths must be absolute".to_string()));
+            }
+            match &dirs.file_dir {
+                Some(dir) => (dir.as_str(), path),
+                None => return Result::Err(Errors::ImageReferenceError(location.to_string(),
+                                               "no image_ref_dir is configured".to_string())),
+            }
+        },
+        ImageLocationType::SharedMemory => {
+            check_simple_name(location)?;
+            (SHARED_MEMORY_DIR, Path::new(SHARED_MEMORY_DIR).join(location))
+        },
+        ImageLocationType::ContentHash => {
+            check_simple_name(location)?;
+            match &dirs.content_dir {
+                Some(dir) => (dir.as_str(), Path::new(dir).join(location)),
+                None => return Result::Err(Errors::ImageReferenceError(location.to_string(),
+                                               "no image_content_dir is configured".to_string())),
+            }
+        },
+    };
+    check_inside_dir(location, &path, dir)
+}
+
+// ---------------------------------------------------------------------------
+// file_reference:
+// ---------------------------------------------------------------------------
+/** Create a FilePath reference to an existing image file. */
+pub fn file_reference(path: &str) -> Result<ImageReference, Errors> {
+    let bytes = match fs::read(path) {
+        Ok(b) => b,
+        Err(e) => return Result::Err(Errors::ImageReferenceError(path.to_string(), e.to_string())),
+    };
+    Result::Ok(ImageReference::new(ImageLocationType::FilePath, path.to_string(),
+                                   bytes.len() as u64, checksum(&bytes)))
+}
+
+// ---------------------------------------------------------------------------
+// checksum:
+// ---------------------------------------------------------------------------
+/** The lowercase hex SHA-256 digest used as an image reference's checksum. */
+pub fn checksum(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+// ***************************************************************************
+// PRIVATE FUNCTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// check_inside_dir:
+// ---------------------------------------------------------------------------
+/** Canonicalize the path and make sure it's inside the canonicalized directory. */
+fn check_inside_dir(location: &str, path: &Path, dir: &str) -> Result<PathBuf, Errors> {
+    let canonical_dir = match fs::canonicalize(dir) {
+        Ok(d) => d,
+        Err(e) => return Result::Err(Errors::ImageReferenceError(location.to_string(),
+                                         format!("unable to access directory {}: {}", dir, e))),
+    };
+    let canonical_path = match fs::canonicalize(path) {
+        Ok(p) => p,
+        Err(e) => return Result::Err(Errors::ImageReferenceError(location.to_string(),
+                                         format!("unable to read {}: {}", path.display(), e))),
+    };
+    if !canonical_path.starts_with(&canonical_dir) {
+        return Result::Err(Errors::ImageReferenceError(location.to_string(),
+                               format!("the image is not in directory {}", dir)));
+    }
+    Result::Ok(canonical_path)
+}
+
+// ---------------------------------------------------------------------------
+// check_simple_name:
+// ---------------------------------------------------------------------------
+/** Names resolved against a directory cannot navigate out of the directory. */
+fn check_simple_name(name: &str) -> Result<(), Errors> {
+    if name.is_empty() || name.contains('/') || name == "." || name == ".." {
+        return Result::Err(Errors::ImageReferenceError(name.to_string(),
+                               "the location must be a simple name".to_string()));
+    }
+    Result::Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use event_engine::events::Event;
+    use crate::events::{ImageCompression, NewImageEvent, EVENT_PREFIX_LEN};
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: image_ref.rs");
+    }
+
+    #[test]
+    fn test_resolve_image() {
+        let dir = std::env::temp_dir().join(format!("image_ref_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let image = vec![1u8, 2, 3, 4, 5];
+        let sum = checksum(&image);
+        fs::write(dir.join(&sum), &image).unwrap();
+        let path = dir.join(&sum).to_string_lossy().to_string();
+        let dir_str = dir.to_string_lossy().to_string();
+        let dirs = ImageRefDirs {file_dir: Some(dir_str.clone()), content_dir: Some(dir_str.clone())};
+        let no_dirs = ImageRefDirs::default();
+
+        // Decode an event and resolve its image.
+        let resolve = |ev: NewImageEvent, dirs: &ImageRefDirs| {
+            let bytes = ev.to_bytes().unwrap();
+            let gen_event = gen_events::root_as_event(&bytes[EVENT_PREFIX_LEN..]).unwrap();
+            resolve_image(&gen_event.event_as_new_image_event().unwrap(), dirs).map(|c| c.to_vec())
+        };
+        let by_ref = |image_ref: ImageReference| {
+            let mut ev = NewImageEvent::new(uuid::Uuid::new_v4(), "jpg".to_string(), vec![]);
+            ev.set_image_ref(Some(image_ref));
+            ev
+        };
+
+        // Embedded images and references by file path and content hash.
+        let embedded = NewImageEvent::new(uuid::Uuid::new_v4(), "jpg".to_string(), image.clone());
+        assert_eq!(resolve(embedded, &no_dirs).unwrap(), image);
+        for compression in [ImageCompression::Zstd, ImageCompression::Lz4] {
+            let mut compressed = NewImageEvent::new(uuid::Uuid::new_v4(), "jpg".to_string(), image.clone());
+            compressed.set_image_compression(compression);
+            assert_eq!(resolve(compressed, &no_dirs).unwrap(), image);
+        }
+        assert_eq!(resolve(by_ref(file_reference(&path).unwrap()), &dirs).unwrap(), image);
+        let hash_ref = ImageReference::new(ImageLocationType::ContentHash, sum.clone(), 5, sum.clone());
+        assert_eq!(resolve(by_ref(hash_ref), &dirs).unwrap(), image);
+
+        // Bad references are rejected.
+        let bad_size = ImageReference::new(ImageLocationType::FilePath, path.clone(), 4, sum.clone());
+        assert!(resolve(by_ref(bad_size), &dirs).is_err());
+        let bad_sum = ImageReference::new(ImageLocationType::FilePath, path.clone(), 5, checksum(b"x"));
+        assert!(resolve(by_ref(bad_sum), &dirs).is_err());
+        let no_dir = ImageReference::new(ImageLocationType::ContentHash, sum.clone(), 5, sum.clone());
+        assert!(resolve(by_ref(no_dir), &no_dirs).is_err());
+        let no_file_dir = ImageReference::new(ImageLocationType::FilePath, path.clone(), 5, sum.clone());
+        assert!(resolve(by_ref(no_file_dir), &no_dirs).is_err());
+        let escape = ImageReference::new(ImageLocationType::ContentHash, format!("../{}", sum), 5, sum.clone());
+        assert!(resolve(by_ref(escape), &dirs).is_err());
+        let relative = ImageReference::new(ImageLocationType::FilePath, sum.clone(), 5, sum.clone());
+        assert!(resolve(by_ref(relative), &dirs).is_err());
+
+        // File paths outside of the file directory are rejected, including
+        // ones that navigate out of it or are reached through a symbolic link.
+        let outside_dir = std::env::temp_dir().join(format!("image_ref_outside_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&outside_dir).unwrap();
+        let outside = outside_dir.join("image.jpg");
+        fs::write(&outside, &image).unwrap();
+        let outside_str = outside.to_string_lossy().to_string();
+        let outside_ref = ImageReference::new(ImageLocationType::FilePath, outside_str.clone(), 5, sum.clone());
+        assert!(resolve(by_ref(outside_ref), &dirs).is_err());
+        let dotdot = format!("{}/../{}/image.jpg", dir_str,
+                             outside_dir.file_name().unwrap().to_string_lossy());
+        let dotdot_ref = ImageReference::new(ImageLocationType::FilePath, dotdot, 5, sum.clone());
+        assert!(resolve(by_ref(dotdot_ref), &dirs).is_err());
+        std::os::unix::fs::symlink(&outside, dir.join("link.jpg")).unwrap();
+        let link = dir.join("link.jpg").to_string_lossy().to_string();
+        let link_ref = ImageReference::new(ImageLocationType::FilePath, link, 5, sum.clone());
+        assert!(resolve(by_ref(link_ref), &dirs).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_dir_all(&outside_dir).unwrap();
+    }
+}
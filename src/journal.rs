@@ -0,0 +1,519 @@
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::{thread, time};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use chrono::{DateTime, FixedOffset};
+use clap::Args;
+
+use crate::config::{config::{Config, JournalConfig}, errors::Errors};
+use crate::events;
+use crate::events_generated::gen_events;
+use crate::traps_utils::{self, timestamp_str, timestamp_str_to_datetime};
+
+use log::{info, warn, error};
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+// Journal defaults used when the configuration does not specify a value.
+const DEFAULT_JOURNAL_DIR: &str = "~/camera-traps/journal";
+const DEFAULT_MAX_FILE_BYTES: u64 = 100 * 1024 * 1024;
+const DEFAULT_MAX_FILES: u32 = 10;
+
+// The [plugins.config] table of the action that writes the journal.
+const CONFIG_NAME: &str = "observer_journal_action";
+
+// The active journal file name.  Rotated files have a numeric suffix
+// appended, with .1 being the most recently rotated file.
+pub const JOURNAL_FILE_NAME: &str = "events.jsonl";
+
+// Replay defaults.
+const DEFAULT_REPLAY_ENDPOINT: &str = "tcp://localhost:5559";
+const DEFAULT_REPLAY_SPEED: f64 = 1.0;
+
+// Time given to zmq to establish the publisher's connection before sending.
+const REPLAY_CONNECT_WAIT_MILLIS: u64 = 1000;
+
+// ***************************************************************************
+// JOURNAL ENTRY
+// ***************************************************************************
+/** A single line in the journal.  The event field contains the decoded event
+ * fields as JSON, the raw field contains the complete event as it appeared on
+ * the wire (2 byte prefix + flatbuffer) encoded in base64.
+ */
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub recorded: String,
+    pub event_name: String,
+    pub event: Value,
+    pub raw: String,
+}
+
+impl JournalEntry {
+    // ----------------------------------------------------------------------
+    // new:
+    // ----------------------------------------------------------------------
+    /** Create a journal entry from the marshalled incoming event.  The generated
+     * event is decoded into its application event using new_from_gen.
+     */
+    pub fn new(prefix: [u8; 2], gen_event: gen_events::Event, raw_bytes: &[u8]) -> Result<Self, Errors> {
+        let (event_name, event) = gen_event_to_json(prefix, gen_event)?;
+        Result::Ok(JournalEntry {
+            recorded: timestamp_str(),
+            event_name: event_name.to_string(),
+            event,
+            raw: STANDARD.encode(raw_bytes),
+        })
+    }
+
+    // ----------------------------------------------------------------------
+    // get_raw_bytes:
+    // ----------------------------------------------------------------------
+    /** Decode the base64 raw field back into wire format bytes. */
+    pub fn get_raw_bytes(&self) -> Result<Vec<u8>, String> {
+        STANDARD.decode(&self.raw).map_err(|e| e.to_string())
+    }
+}
+
+// ***************************************************************************
+// JOURNAL
+// ***************************************************************************
+/** The journal writer.  Each append opens the active file, so no file handles
+ * are held between events.  When the active file reaches max_file_bytes it is
+ * rotated and at most max_files journal files are kept.
+ */
+#[derive(Debug, Clone)]
+pub struct Journal {
+    pub journal_dir: String,
+    pub max_file_bytes: u64,
+    pub max_files: u32,
+}
+
+impl Journal {
+    // ----------------------------------------------------------------------
+    // new:
+    // ----------------------------------------------------------------------
+    pub fn new(journal_dir: &str, max_file_bytes: u64, max_files: u32) -> Self {
+        Journal {
+            journal_dir: traps_utils::get_absolute_path(journal_dir),
+            max_file_bytes,
+            max_files: if max_files < 1 {1} else {max_files},
+        }
+    }
+
+    // ----------------------------------------------------------------------
+    // new_from_config:
+    // ----------------------------------------------------------------------
+    /** Create a journal using the [plugins.config.observer_journal_action] table
+     * of the configuration.
+     */
+    pub fn new_from_config(config: &Config) -> Result<Self, Errors> {
+        let jconfig: JournalConfig = config.get_plugin_config(CONFIG_NAME)?;
+        Result::Ok(Journal::new(jconfig.journal_dir.as_deref().unwrap_or(DEFAULT_JOURNAL_DIR),
+                                jconfig.max_file_bytes.unwrap_or(DEFAULT_MAX_FILE_BYTES),
+                                jconfig.max_files.unwrap_or(DEFAULT_MAX_FILES)))
+    }
+
+    // ----------------------------------------------------------------------
+    // get_journal_file:
+    // ----------------------------------------------------------------------
+    /** The absolute path of the active journal file. */
+    pub fn get_journal_file(&self) -> PathBuf {
+        Path::new(&self.journal_dir).join(JOURNAL_FILE_NAME)
+    }
+
+    // ----------------------------------------------------------------------
+    // append:
+    // ----------------------------------------------------------------------
+    /** Write the entry as a single JSON line to the active journal file,
+     * rotating the file first if it has reached its maximum size.
+     */
+    pub fn append(&self, entry: &JournalEntry) -> Result<(), Errors> {
+        // Serialize the entry.
+        let mut line = match serde_json::to_string(entry) {
+            Ok(s) => s,
+            Err(e) => {
+                return Result::Err(Errors::EventToJsonError("ObserverPlugin".to_string(),
+                                                            entry.event_name.clone(), e.to_string()));
+            }
+        };
+        line.push('\n');
+
+        // Make sure the directory exists.
+        let journal_file = self.get_journal_file();
+        let journal_str = journal_file.to_string_lossy().to_string();
+        if let Err(e) = fs::create_dir_all(&self.journal_dir) {
+            return Result::Err(Errors::AppDirCreateError(self.journal_dir.clone(), e.to_string()));
+        }
+
+        // Rotate if the next write would exceed the limit.
+        if let Ok(meta) = fs::metadata(&journal_file) {
+            if meta.len() > 0 && meta.len() + line.len() as u64 > self.max_file_bytes {
+                self.rotate()?;
+            }
+        }
+
+        // Append the entry.
+        let mut file = match OpenOptions::new().create(true).append(true).open(&journal_file) {
+            Ok(f) => f,
+            Err(e) => return Result::Err(Errors::JournalWriteError(journal_str, e.to_string())),
+        };
+        match file.write_all(line.as_bytes()) {
+            Ok(_) => Result::Ok(()),
+            Err(e) => Result::Err(Errors::JournalWriteError(journal_str, e.to_string())),
+        }
+    }
+
+    // ----------------------------------------------------------------------
+    // rotate:
+    // ----------------------------------------------------------------------
+    /** Shift each rotated file's numeric suffix up by one, discarding the
+     * oldest file, and then move the active file to suffix 1.
+     */
+    fn rotate(&self) -> Result<(), Errors> {
+        let journal_file = self.get_journal_file();
+        let journal_str = journal_file.to_string_lossy().to_string();
+
+        // Keeping a single file means just starting over.
+        if self.max_files == 1 {
+            return match fs::remove_file(&journal_file) {
+                Ok(_) => Result::Ok(()),
+                Err(e) => Result::Err(Errors::JournalRotateError(journal_str, e.to_string())),
+            };
+        }
+
+        // Remove the oldest file and shift the others.
+        let oldest = self.get_rotated_file(self.max_files - 1);
+        if oldest.exists() {
+            if let Err(e) = fs::remove_file(&oldest) {
+                return Result::Err(Errors::JournalRotateError(journal_str, e.to_string()));
+            }
+        }
+        for n in (1..self.max_files - 1).rev() {
+            let from = self.get_rotated_file(n);
+            if from.exists() {
+                if let Err(e) = fs::rename(&from, self.get_rotated_file(n + 1)) {
+                    return Result::Err(Errors::JournalRotateError(journal_str, e.to_string()));
+                }
+            }
+        }
+        match fs::rename(&journal_file, self.get_rotated_file(1)) {
+            Ok(_) => Result::Ok(()),
+            Err(e) => Result::Err(Errors::JournalRotateError(journal_str, e.to_string())),
+        }
+    }
+
+    // ----------------------------------------------------------------------
+    // get_rotated_file:
+    // ----------------------------------------------------------------------
+    fn get_rotated_file(&self, n: u32) -> PathBuf {
+        Path::new(&self.journal_dir).join(format!("{}.{}", JOURNAL_FILE_NAME, n))
+    }
+}
+
+// ***************************************************************************
+// PUBLIC FUNCTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// gen_event_to_json:
+// ---------------------------------------------------------------------------
+/** Decode a generated event into its application event and return the event
+ * name and the event's fields as JSON.
+ */
+pub fn gen_event_to_json(prefix: [u8; 2], gen_event: gen_events::Event) -> Result<(&'static str, Value), Errors> {
+    match events::get_event_info_by_prefix(prefix) {
+        Some(info) => Result::Ok((info.name, (info.gen_to_json)(gen_event)?)),
+        None => Result::Err(Errors::EventNotHandledError("ObserverPlugin".to_string(), format!("{:?}", prefix))),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// read_journal:
+// ---------------------------------------------------------------------------
+/** Read all entries from a journal file.  Blank lines are skipped and any
+ * malformed line causes an error naming the line number.
+ */
+pub fn read_journal(journal_file: &str) -> Result<Vec<JournalEntry>, Errors> {
+    let file = match fs::File::open(journal_file) {
+        Ok(f) => f,
+        Err(e) => return Result::Err(Errors::JournalReadError(journal_file.to_string(), e.to_string())),
+    };
+
+    let mut entries = vec![];
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = match line {
+            Ok(l) => l,
+            Err(e) => return Result::Err(Errors::JournalReadError(journal_file.to_string(), e.to_string())),
+        };
+        if line.trim().is_empty() {continue;}
+        match serde_json::from_str::<JournalEntry>(&line) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => return Result::Err(Errors::JournalParseError(journal_file.to_string(), i + 1, e.to_string())),
+        }
+    }
+
+    Result::Ok(entries)
+}
+
+// ***************************************************************************
+// REPLAY
+// ***************************************************************************
+/** Republish the events in an observer journal to a running application.  A
+ * speed of 1.0 replays the journal with its original timing, 10.0 replays it
+ * 10 times faster and 0.0 sends events as fast as possible.  Plugin lifecycle
+ * events are skipped unless all_events is set, since replaying a
+ * PluginTerminateEvent would shut down the running application.
+ */
+#[derive(Args, Debug)]
+pub struct ReplayParms {
+    /// The journal file; its rotated files are replayed first
+    #[arg(value_name = "JOURNAL_FILE", value_parser = traps_utils::parse_absolute_path)]
+    pub journal_file: String,
+    /// The zmq endpoint of the application's publish_port
+    #[arg(long, default_value = DEFAULT_REPLAY_ENDPOINT)]
+    pub endpoint: String,
+    /// The replay speed relative to the original timing; 0 sends events as fast as possible
+    #[arg(long, value_name = "FACTOR", default_value_t = DEFAULT_REPLAY_SPEED, value_parser = parse_speed)]
+    pub speed: f64,
+    /// Also replay the plugin lifecycle events
+    #[arg(long = "all")]
+    pub all_events: bool,
+}
+
+// ---------------------------------------------------------------------------
+// parse_speed:
+// ---------------------------------------------------------------------------
+fn parse_speed(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(speed) if speed >= 0.0 => Result::Ok(speed),
+        _ => Result::Err(format!("invalid speed {}", s)),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// get_journal_segments:
+// ---------------------------------------------------------------------------
+/** Return the files that make up the journal in the order they were written.
+ * Rotated files have the journal file's name with a numeric suffix and the
+ * highest suffix is the oldest, so the rotated files come first in descending
+ * suffix order followed by the journal file itself.  Missing files are skipped.
+ */
+pub fn get_journal_segments(journal_file: &str) -> Result<Vec<PathBuf>, Errors> {
+    let path = Path::new(journal_file);
+    let (dir, file_name) = match (path.parent(), path.file_name()) {
+        (Some(d), Some(f)) => (d, f.to_string_lossy().to_string()),
+        _ => return Result::Err(Errors::JournalReadError(journal_file.to_string(), "invalid journal path".to_string())),
+    };
+
+    // Collect the rotated files' suffixes.
+    let dir_entries = match fs::read_dir(dir) {
+        Ok(d) => d,
+        Err(e) => return Result::Err(Errors::JournalReadError(journal_file.to_string(), e.to_string())),
+    };
+    let rotated_prefix = format!("{}.", file_name);
+    let mut suffixes: Vec<u32> = dir_entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.strip_prefix(&rotated_prefix).and_then(|n| n.parse::<u32>().ok())
+        })
+        .collect();
+    suffixes.sort_unstable_by(|a, b| b.cmp(a));
+
+    // Oldest first, active file last.
+    let mut segments: Vec<PathBuf> = suffixes.iter().map(|n| dir.join(format!("{}.{}", file_name, n))).collect();
+    if path.exists() {
+        segments.push(path.to_path_buf());
+    }
+    if segments.is_empty() {
+        return Result::Err(Errors::JournalReadError(journal_file.to_string(), "no journal files found".to_string()));
+    }
+
+    Result::Ok(segments)
+}
+
+// ---------------------------------------------------------------------------
+// replay:
+// ---------------------------------------------------------------------------
+/** Republish the events in a journal to a running camera-traps application.
+ * The journal's rotated files are replayed before the journal file itself so
+ * that events are sent in the order they were recorded.  The endpoint is the
+ * application's publish_port, which is where the event engine receives all
+ * events.  The delay between consecutive events is the difference in their
+ * recorded timestamps divided by the speed factor.
+ *
+ * Returns the number of events sent.
+ */
+pub fn replay(parms: &ReplayParms) -> Result<usize, Errors> {
+    // Read the whole journal before connecting.
+    let mut segments = vec![];
+    for segment in get_journal_segments(&parms.journal_file)? {
+        let segment = segment.to_string_lossy().to_string();
+        let entries = read_journal(&segment)?;
+        segments.push((segment, entries));
+    }
+    let total: usize = segments.iter().map(|(_, entries)| entries.len()).sum();
+
+    // Connect a publisher to the application.
+    let context = zmq::Context::new();
+    let socket = match context.socket(zmq::PUB) {
+        Ok(s) => s,
+        Err(e) => return Result::Err(Errors::SocketConnectError(parms.endpoint.clone(), e.to_string())),
+    };
+    if let Err(e) = socket.connect(&parms.endpoint) {
+        return Result::Err(Errors::SocketConnectError(parms.endpoint.clone(), e.to_string()));
+    }
+    thread::sleep(time::Duration::from_millis(REPLAY_CONNECT_WAIT_MILLIS));
+
+    // Send each event honoring the original spacing.
+    let mut sent = 0;
+    let mut last_recorded: Option<DateTime<FixedOffset>> = None;
+    for (segment, entries) in &segments {
+        for (i, entry) in entries.iter().enumerate() {
+            if !parms.all_events && is_lifecycle_event(&entry.event_name) {continue;}
+
+            // Wait the scaled interval between this event and the previous one sent.
+            let recorded = match timestamp_str_to_datetime(&entry.recorded) {
+                Ok(ts) => Some(ts),
+                Err(e) => {
+                    warn!("{}", Errors::DateParseError(entry.recorded.clone(), e.to_string()));
+                    None
+                }
+            };
+            if let (Some(prev), Some(cur)) = (last_recorded, recorded) {
+                if parms.speed > 0.0 {
+                    if let Ok(delta) = (cur - prev).to_std() {
+                        thread::sleep(delta.div_f64(parms.speed));
+                    }
+                }
+            }
+            if recorded.is_some() {last_recorded = recorded;}
+
+            // Send the raw event.
+            let bytes = match entry.get_raw_bytes() {
+                Ok(b) => b,
+                Err(e) => {
+                    error!("{}", Errors::JournalParseError(segment.clone(), i + 1, e));
+                    continue;
+                }
+            };
+            match socket.send(bytes, 0) {
+                Ok(_) => sent += 1,
+                Err(e) => error!("{}", Errors::SocketSendError("replay".to_string(), entry.event_name.clone(), e.to_string())),
+            }
+        }
+    }
+
+    info!("{}", Errors::ReplayCompleted(sent, total, parms.journal_file.clone(), parms.endpoint.clone()));
+    Result::Ok(sent)
+}
+
+// ***************************************************************************
+// PRIVATE FUNCTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// is_lifecycle_event:
+// ---------------------------------------------------------------------------
+fn is_lifecycle_event(event_name: &str) -> bool {
+    matches!(event_name, "PluginStartedEvent" | "PluginTerminatingEvent" | "PluginTerminateEvent")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use event_engine::events::Event;
+    use crate::events::{ImageLabelScore, ImageScoredEvent, EVENT_PREFIX_LEN, IMAGE_SCORED_PREFIX};
+    use uuid::Uuid;
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: journal.rs");
+    }
+
+    fn test_dir() -> String {
+        std::env::temp_dir().join(format!("traps-journal-{}", Uuid::new_v4())).to_string_lossy().to_string()
+    }
+
+    fn scored_entry() -> JournalEntry {
+        let uuid = Uuid::new_v4();
+        let scores = vec![ImageLabelScore::new("cow".to_string(), 0.8)];
+        let bytes = ImageScoredEvent::new(uuid, "png".to_string(), scores).to_bytes().unwrap();
+        let gen_event = gen_events::root_as_event(&bytes[EVENT_PREFIX_LEN..]).unwrap();
+        JournalEntry::new(IMAGE_SCORED_PREFIX, gen_event, &bytes).unwrap()
+    }
+
+    #[test]
+    fn entry_round_trip() {
+        let entry = scored_entry();
+        assert_eq!(entry.event_name, "ImageScoredEvent");
+        assert_eq!(entry.event["scores"][0]["label"], "cow");
+
+        // The raw bytes decode back into the same event.
+        let bytes = entry.get_raw_bytes().unwrap();
+        assert_eq!(bytes[0..EVENT_PREFIX_LEN], IMAGE_SCORED_PREFIX);
+        let ev = ImageScoredEvent::from_bytes(bytes[EVENT_PREFIX_LEN..].to_vec()).unwrap();
+        assert_eq!(ev.get_image_uuid().hyphenated().to_string(), entry.event["image_uuid"]);
+    }
+
+    #[test]
+    fn append_and_rotate() {
+        let dir = test_dir();
+        let entry = scored_entry();
+        let line_len = serde_json::to_string(&entry).unwrap().len() as u64 + 1;
+
+        // Room for 2 entries per file and 3 files in total.
+        let journal = Journal::new(&dir, 2 * line_len, 3);
+        for _ in 0..7 {
+            journal.append(&entry).unwrap();
+        }
+
+        let active = journal.get_journal_file();
+        assert_eq!(read_journal(active.to_str().unwrap()).unwrap().len(), 1);
+        assert_eq!(read_journal(journal.get_rotated_file(1).to_str().unwrap()).unwrap().len(), 2);
+        assert_eq!(read_journal(journal.get_rotated_file(2).to_str().unwrap()).unwrap().len(), 2);
+        assert!(!journal.get_rotated_file(3).exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn journal_segments() {
+        let dir = test_dir();
+        let entry = scored_entry();
+        let line_len = serde_json::to_string(&entry).unwrap().len() as u64 + 1;
+
+        // One entry per file leaves the active file and 3 rotated files.
+        let journal = Journal::new(&dir, line_len, 4);
+        for _ in 0..4 {
+            journal.append(&entry).unwrap();
+        }
+
+        let active = journal.get_journal_file();
+        let segments = get_journal_segments(active.to_str().unwrap()).unwrap();
+        assert_eq!(segments, vec![journal.get_rotated_file(3), journal.get_rotated_file(2),
+                                  journal.get_rotated_file(1), active]);
+
+        // Nothing to replay.
+        fs::remove_dir_all(&dir).unwrap();
+        fs::create_dir_all(&dir).unwrap();
+        assert!(get_journal_segments(journal.get_journal_file().to_str().unwrap()).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn replay_args() {
+        let parms: ReplayParms = traps_utils::parse_args(&["/tmp/events.jsonl", "--speed", "10", "--all"]).unwrap();
+        assert_eq!(parms.journal_file, "/tmp/events.jsonl");
+        assert_eq!(parms.speed, 10.0);
+        assert_eq!(parms.endpoint, DEFAULT_REPLAY_ENDPOINT);
+        assert!(parms.all_events);
+
+        assert!(traps_utils::parse_args::<ReplayParms>(&[]).is_err());
+        assert!(traps_utils::parse_args::<ReplayParms>(&["x", "--speed", "-1"]).is_err());
+    }
+}
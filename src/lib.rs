@@ -1,4 +1,769 @@
+// Stardard imports.
+use std::{env, fs, io, thread, sync::{Arc, Mutex, OnceLock}};
+use lazy_static::lazy_static;
+
+// Logging imports.
+use log::{error, warn, info, LevelFilter};
+use anyhow::{Context, Result, anyhow};
+
+// Application modules.
+pub mod plugins;
+pub mod action_registry;
+mod bus;
+pub mod cli;
+pub mod config;
+mod dead_letter;
+mod evaluation;
 pub mod events;
 mod events_generated;
-mod config;
-mod traps_utils;
\ No newline at end of file
+mod image_ref;
+mod journal;
+mod plugin_registry;
+mod reload;
+mod shutdown;
+mod sweep;
+mod validate;
+mod traps_utils;
+use cli::{Cli, Command};
+use config::config::{Config};
+use config::errors::{Errors};
+use config::layers::{self, ConfigSources, Override};
+use event_engine::plugins::{Plugin, ExternalPlugin};
+use plugins::{image_gen_plugin::ImageGenPlugin, image_recv_plugin::ImageReceivePlugin,
+              image_score_plugin::ImageScorePlugin, image_store_plugin::ImageStorePlugin,
+              observer_plugin::ObserverPlugin, external_app_plugin::ExternalAppPlugin,
+              plugin_monitor_plugin::PluginMonitorPlugin,
+              detection_reporter_plugin::DetectionReporterPlugin,
+              mqtt_publisher_plugin::MqttPublisherPlugin, oracle_plugin::OraclePlugin,
+              power_monitor_plugin::PowerMonitorPlugin};
+use plugin_registry::PluginRegistry;
+use shutdown::ImageTracker;
+
+// Event engine imports.
+use event_engine::App;
+use event_engine::events::{Event, EventType};
+use events::Traceable;
+
+// Signal imports.
+use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM};
+use signal_hook::iterator::Signals;
+
+// ***************************************************************************
+//                                Constants
+// ***************************************************************************
+// Constants.
+const ENV_LOG4RS_FILE_KEY : &str = "TRAPS_LOG4RS_CONFIG_FILE";
+const LOG4RS_CONFIG_FILE  : &str = "resources/log4rs.yml";
+const ENV_CONFIG_FILE_KEY : &str = "TRAPS_CONFIG_FILE";
+const DEFAULT_CONFIG_FILE : &str = "~/traps.toml";
+
+// The source recorded in the trace ids of ConfigReloadedEvents.
+const RELOAD_EVENT_SOURCE : &str = "camera-traps reload";
+
+// ***************************************************************************
+//                             Static Variables 
+// ***************************************************************************
+// Lazily initialize the parameters variable so that is has a 'static lifetime.
+// We exit if we can't read our parameters.
+lazy_static! {
+    static ref RUNTIME_CTX: RuntimeCtx = init_runtime_context();
+}
+
+// The configuration file given on the command line and the configuration
+// overrides, which must be set before the runtime context is first accessed.
+static CONFIG_ARGS: OnceLock<(Option<String>, Vec<Override>)> = OnceLock::new();
+
+// The log4rs handle, the log configuration file and the command line log
+// level, which are used to change the log level when the configuration is reloaded.
+static LOG_STATE: OnceLock<(log4rs::Handle, String, Option<LevelFilter>)> = OnceLock::new();
+
+// ***************************************************************************
+//                                Functions
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// run_command:
+// ---------------------------------------------------------------------------
+/** Run the application or one of its tools as directed by the parsed command
+ * line.  This is what the camera-traps binary does.  A binary that adds its
+ * own actions registers them with action_registry::register_action() and then
+ * calls this function, so that its actions can be configured, listed and
+ * validated along with the built-in ones.
+ */
+pub fn run_command(cli: Cli) -> Result<()> {
+    // Initialize log4rs logging.
+    init_logging(&cli)?;
+
+    // Make the built-in actions available to the configuration and plugins.
+    plugins::actions::register_builtin_actions();
+
+    // Run the application or one of the tools that don't start plugins.
+    match cli.command {
+        None => run(cli.config_file, &cli.settings),
+        Some(Command::Run {config_file}) => run(config_file, &cli.settings),
+        Some(Command::Validate(parms)) => run_validate(parms, &cli.settings),
+        Some(Command::PrintConfig {config_file}) => run_print_config(config_file, &cli.settings),
+        Some(Command::ListEvents) => {
+            print!("{}", cli::format_event_list());
+            Ok(())
+        },
+        Some(Command::ListActions) => {
+            print!("{}", cli::format_action_list());
+            Ok(())
+        },
+        Some(Command::SendEvent(parms)) => run_send_event(&parms),
+        Some(Command::Tail(parms)) => run_tail(&parms),
+        Some(Command::Replay(parms)) => run_replay(&parms),
+        Some(Command::DeadLetters(parms)) => run_dead_letters(&parms),
+        Some(Command::Evaluate(parms)) => run_evaluate(&parms),
+        Some(Command::Sweep(parms)) => run_sweep(&parms),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// run:
+// ---------------------------------------------------------------------------
+/** Start the configured plugins and run the event engine until it shuts down.
+ * The configuration file and settings are those of the run command.  The
+ * built-in actions are registered here if they haven't been, so any other
+ * actions must be registered before calling this function, which can only be
+ * called once per process.
+ */
+pub fn run(config_file: Option<String>, settings: &[String]) -> Result<()> {
+    // Write to stdout.
+    println!("Starting camera-traps!");
+
+    // Make the built-in actions available when run_command didn't.
+    plugins::actions::register_builtin_actions();
+
+    // Record the command line configuration file and overrides for the runtime context.
+    let overrides = get_config_overrides(settings)?;
+    let _ = CONFIG_ARGS.set((config_file, overrides));
+
+    // Force the reading of input parameters and initialization of runtime context.
+    info!("{}", Errors::InputParms(format!("{:#?}", *RUNTIME_CTX)));
+
+    // Apply the configured log level, reload the configuration on SIGHUP and
+    // shut down gracefully on SIGINT or SIGTERM.
+    apply_log_level(&RUNTIME_CTX.parms.config.log_level);
+    reload::set_config(RUNTIME_CTX.parms.config.clone());
+    start_signal_listener()?;
+
+    // Capture rejected event payloads.
+    dead_letter::init(&RUNTIME_CTX.parms.config);
+
+    // A run to completion needs the plugin whose events finish each image.
+    if shutdown::get_completion_config(&RUNTIME_CTX.parms.config).is_some() {
+        if let Err(e) = shutdown::check_completion_stages(&RUNTIME_CTX.parms.config) {
+            error!("{}", e);
+            return Result::Err(anyhow!(e));
+        }
+    }
+
+    // File/dir creation and checking for the actions that use the local image directory.
+    if plugins::actions::uses_local_image_dir(&RUNTIME_CTX.parms.config) {
+        traps_utils::validate_image_dir(&RUNTIME_CTX.abs_image_dir)?;
+    }
+
+    // Configure plugins.  The images are tracked through the pipeline by the
+    // plugin monitor.
+    let images = Arc::new(Mutex::new(ImageTracker::new()));
+    let app = init_app(&RUNTIME_CTX.parms, images.clone())?;
+
+    // Run the event engine.
+    match app.run() {
+        Ok(_) => (),
+        Err(e) => {
+            let msg = Errors::AppErrorShutdown(e.to_string());
+            error!("{}", msg);
+            return Result::Err(anyhow!(msg));
+        },
+    };
+
+    // A run to completion reports the images' outcomes in its exit status.
+    if shutdown::get_completion_config(&RUNTIME_CTX.parms.config).is_some() {
+        report_completion(&images.lock().unwrap());
+    }
+
+    // We're done.
+    info!("{}", Errors::AppNormalShutdown());
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// report_completion:
+// ---------------------------------------------------------------------------
+/** Log the outcomes of the images in a run to completion and exit with a
+ * failure status if any image failed or did not finish.  Images rejected
+ * because the run was shutting down are not failures.
+ */
+fn report_completion(images: &ImageTracker) {
+    info!("{}", Errors::CompletionSummary(images.new_images, images.stored, images.deleted, images.failed,
+                                          images.rejected, images.in_flight()));
+    if images.failed > 0 || images.in_flight() > 0 {
+        let code = shutdown::COMPLETION_FAILURE_EXIT_CODE;
+        error!("{}", Errors::CompletionImagesFailed(images.failed, images.in_flight(), code));
+        std::process::exit(code);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// run_replay:
+// ---------------------------------------------------------------------------
+/** Republish the events recorded in an observer journal to a running 
+ * camera-traps application.  No plugins are started in this mode.
+ */
+fn run_replay(parms: &journal::ReplayParms) -> Result<()> {
+    info!("{}", Errors::InputParms(format!("{:#?}", parms)));
+
+    match journal::replay(parms) {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            error!("{}", e);
+            Result::Err(anyhow!(e))
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// run_dead_letters:
+// ---------------------------------------------------------------------------
+/** Print the payloads that plugins rejected and captured as dead letters.
+ * No plugins are started in this mode.
+ */
+fn run_dead_letters(parms: &dead_letter::DeadLetterParms) -> Result<()> {
+    match dead_letter::inspect(parms) {
+        Ok(s) => {
+            print!("{}", s);
+            Ok(())
+        },
+        Err(e) => {
+            error!("{}", e);
+            Result::Err(anyhow!(e))
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// run_evaluate:
+// ---------------------------------------------------------------------------
+/** Print a report comparing the image store's decisions to the ground truth.
+ * No plugins are started in this mode.
+ */
+fn run_evaluate(parms: &evaluation::EvaluateParms) -> Result<()> {
+    match evaluation::run(parms) {
+        Ok(s) => {
+            print!("{}", s);
+            Ok(())
+        },
+        Err(e) => {
+            error!("{}", e);
+            Result::Err(anyhow!(e))
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// run_sweep:
+// ---------------------------------------------------------------------------
+/** Print the storage and recall of many image store threshold combinations
+ * applied to a recorded run.  No plugins are started in this mode.
+ */
+fn run_sweep(parms: &sweep::SweepParms) -> Result<()> {
+    match sweep::run(parms) {
+        Ok(s) => {
+            print!("{}", s);
+            Ok(())
+        },
+        Err(e) => {
+            error!("{}", e);
+            Result::Err(anyhow!(e))
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// run_validate:
+// ---------------------------------------------------------------------------
+/** Print every problem found in a configuration and its image store
+ * configuration.  An error is returned if there are any problems so that the
+ * application exits with a nonzero status.  No plugins are started in this mode.
+ */
+fn run_validate(mut parms: validate::ValidateParms, settings: &[String]) -> Result<()> {
+    parms.overrides = get_config_overrides(settings)?;
+
+    let problems = validate::validate(&parms);
+    if problems.is_empty() {
+        println!("{}", Errors::ValidateConfigValid(parms.config_file));
+        return Ok(());
+    }
+    print!("{}", validate::format_problems(&parms, &problems));
+    let err = Errors::ValidateConfigInvalid(problems.len(), parms.config_file);
+    error!("{}", err);
+    Result::Err(anyhow!(err))
+}
+
+// ---------------------------------------------------------------------------
+// run_print_config:
+// ---------------------------------------------------------------------------
+/** Print the configuration the application would run with, preceded by the
+ * settings that override the configuration file.  No plugins are started in
+ * this mode.
+ */
+fn run_print_config(config_file: Option<String>, settings: &[String]) -> Result<()> {
+    let overrides = get_config_overrides(settings)?;
+    let parms = read_parms(&get_config_file(config_file), &overrides)?;
+    match cli::format_config(&parms.config) {
+        Ok(s) => {
+            println!("# {}", parms.config_file);
+            for (key, layer) in parms.sources.get_overridden() {
+                println!("# {} is set by {}", key, layer);
+            }
+            print!("{}", s);
+            Ok(())
+        },
+        Err(e) => {
+            let msg = format!("{}\n   {}", Errors::TOMLParseError(parms.config_file), e);
+            error!("{}", msg);
+            Result::Err(anyhow!(msg))
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// run_send_event:
+// ---------------------------------------------------------------------------
+/** Publish one event described in JSON to a running camera-traps application.
+ * No plugins are started in this mode.
+ */
+fn run_send_event(parms: &bus::SendEventParms) -> Result<()> {
+    match bus::send_event(parms) {
+        Ok(_) => {
+            println!("{}", Errors::EventSent(parms.event_name.clone(), parms.endpoint.clone()));
+            Ok(())
+        },
+        Err(e) => {
+            error!("{}", e);
+            Result::Err(anyhow!(e))
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// run_tail:
+// ---------------------------------------------------------------------------
+/** Print the events published in a running camera-traps application.  No
+ * plugins are started in this mode.
+ */
+fn run_tail(parms: &bus::TailParms) -> Result<()> {
+    match bus::tail(parms, &mut io::stdout()) {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            error!("{}", e);
+            Result::Err(anyhow!(e))
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// initApp:
+// ---------------------------------------------------------------------------
+fn init_app(parms: &'static Parms, images: Arc<Mutex<ImageTracker>>) -> Result<App, Errors>{
+    
+    // Create the app on the specified
+    let mut app: App = App::new(parms.config.publish_port as i32, parms.config.subscribe_port as i32);
+
+    // The registry of all configured plugins used to monitor their liveness.
+    let mut registry = PluginRegistry::new();
+
+    // Internal plugins are optional.
+    let int_plugins = parms.config.plugins.internal.clone().unwrap_or_default();
+
+    // Help make the log more readable.
+    let delimiter = "\n".to_string() + "-".repeat(70).as_str();
+    info!("{}", delimiter.clone() + 
+           (format!("{}",Errors::RegisteringNumInternalPlugins(int_plugins.len())) + delimiter.as_str()).as_str());
+
+    // Register internal plugins if any are defined. 
+    for plugin_name in &int_plugins {
+        match plugin_name.as_str() {
+            "image_gen_plugin" => {
+                let plugin = ImageGenPlugin::new(&RUNTIME_CTX);
+                let uuid = plugin.get_id();
+                registry.add_expected("image_gen_plugin", &plugin.get_name(), uuid, false);
+                info!("{}",Errors::RegisteringInternalPlugin("image_gen_plugin".to_string(), uuid.hyphenated().to_string()));
+                app = app.register_plugin(Arc::new(Box::new(plugin)));
+            },
+            "image_recv_plugin" => {
+                let plugin = ImageReceivePlugin::new(&RUNTIME_CTX);
+                let uuid = plugin.get_id();
+                registry.add_expected("image_recv_plugin", &plugin.get_name(), uuid, false);
+                info!("{}",Errors::RegisteringInternalPlugin("image_recv_plugin".to_string(), uuid.hyphenated().to_string()));
+                app = app.register_plugin(Arc::new(Box::new(plugin)));
+            },
+            "image_score_plugin" => {
+                let plugin = ImageScorePlugin::new(&RUNTIME_CTX);
+                let uuid = plugin.get_id();
+                registry.add_expected("image_score_plugin", &plugin.get_name(), uuid, false);
+                info!("{}",Errors::RegisteringInternalPlugin("image_score_plugin".to_string(), uuid.hyphenated().to_string()));
+                app = app.register_plugin(Arc::new(Box::new(plugin)));
+            },
+            "image_store_plugin" => {
+                let plugin = ImageStorePlugin::new(&RUNTIME_CTX)?;
+                let uuid = plugin.get_id();
+                registry.add_expected("image_store_plugin", &plugin.get_name(), uuid, false);
+                info!("{}",Errors::RegisteringInternalPlugin("image_store_plugin".to_string(), uuid.hyphenated().to_string()));
+                app = app.register_plugin(Arc::new(Box::new(plugin)));
+            },
+            "observer_plugin" => {
+                let plugin = ObserverPlugin::new(&RUNTIME_CTX)?;
+                let uuid = plugin.get_id();
+                registry.add_expected("observer_plugin", &plugin.get_name(), uuid, false);
+                info!("{}", Errors::RegisteringInternalPlugin("observer_plugin".to_string(), uuid.hyphenated().to_string()));
+                app = app.register_plugin(Arc::new(Box::new(plugin)));
+            },
+            "detection_reporter_plugin" => {
+                let plugin = DetectionReporterPlugin::new(&RUNTIME_CTX)?;
+                let uuid = plugin.get_id();
+                registry.add_expected("detection_reporter_plugin", &plugin.get_name(), uuid, false);
+                info!("{}", Errors::RegisteringInternalPlugin("detection_reporter_plugin".to_string(), uuid.hyphenated().to_string()));
+                app = app.register_plugin(Arc::new(Box::new(plugin)));
+            },
+            "mqtt_publisher_plugin" => {
+                let plugin = MqttPublisherPlugin::new(&RUNTIME_CTX)?;
+                let uuid = plugin.get_id();
+                registry.add_expected("mqtt_publisher_plugin", &plugin.get_name(), uuid, false);
+                info!("{}", Errors::RegisteringInternalPlugin("mqtt_publisher_plugin".to_string(), uuid.hyphenated().to_string()));
+                app = app.register_plugin(Arc::new(Box::new(plugin)));
+            },
+            "oracle_plugin" => {
+                let plugin = OraclePlugin::new(&RUNTIME_CTX)?;
+                let uuid = plugin.get_id();
+                registry.add_expected("oracle_plugin", &plugin.get_name(), uuid, false);
+                info!("{}", Errors::RegisteringInternalPlugin("oracle_plugin".to_string(), uuid.hyphenated().to_string()));
+                app = app.register_plugin(Arc::new(Box::new(plugin)));
+            },
+            "power_monitor_plugin" => {
+                let plugin = PowerMonitorPlugin::new(&RUNTIME_CTX)?;
+                let uuid = plugin.get_id();
+                registry.add_expected("power_monitor_plugin", &plugin.get_name(), uuid, false);
+                info!("{}", Errors::RegisteringInternalPlugin("power_monitor_plugin".to_string(), uuid.hyphenated().to_string()));
+                app = app.register_plugin(Arc::new(Box::new(plugin)));
+            },
+           other => {
+                // Aborting.
+                let err = Errors::PluginUnknown(other.to_string());
+                error!("{}", err);
+                return Result::Err(err);
+            },
+        }
+    }
+
+    // External plugins are optional.
+    let ext_plugins = parms.config.plugins.external.clone().unwrap_or_default();
+
+    // End internal plugin registration.
+    info!("{}", delimiter.clone() + 
+           (format!("{}",Errors::RegisteringNumExternalPlugins(ext_plugins.len())) + delimiter.as_str()).as_str());
+
+
+    // Register external plugins if any are defined.
+    for ext_plugin in &ext_plugins {
+        let app_plugin = ExternalAppPlugin::new(ext_plugin)?;
+
+        // Register the external plugin
+        let cnt = app_plugin.get_subscriptions().unwrap().len();
+        let id = app_plugin.get_id().hyphenated().to_string();
+        info!("{}", Errors::RegisteringExternalPlugin(app_plugin.get_name(), id, app_plugin.get_tcp_port(), cnt));
+        registry.add_expected(&ext_plugin.plugin_name, &app_plugin.get_name(), app_plugin.get_id(), true);
+        app = app.register_external_plugin(Arc::new(Box::new(app_plugin)));
+    }
+
+    // End plugin registration.
+    if !ext_plugins.is_empty() {info!("{}", delimiter);}
+
+    // Issue a warning if no plugins are configured, otherwise monitor them.
+    if int_plugins.is_empty() && ext_plugins.is_empty() {
+        warn!("{}", Errors::PluginNone());
+    } else {
+        let plugin = PluginMonitorPlugin::new(&RUNTIME_CTX, Arc::new(Mutex::new(registry)), images);
+        let uuid = plugin.get_id();
+        info!("{}", Errors::RegisteringInternalPlugin("plugin_monitor_plugin".to_string(), uuid.hyphenated().to_string()));
+        app = app.register_plugin(Arc::new(Box::new(plugin)));
+        shutdown::set_coordinated();
+    }
+
+    // Return the app.
+    Result::Ok(app)
+}
+
+// ---------------------------------------------------------------------------
+// init_runtime_context:
+// ---------------------------------------------------------------------------
+fn init_runtime_context() -> RuntimeCtx {
+    // If either of these fail the application aborts.
+    let parms = get_parms().expect("FAILED to read configuration file.");
+    let abs_image_dir = init_image_dir(&parms.config.images_output_dir)
+                                        .expect("FAILED to initialize image directory.");
+    
+    // Return the context.
+    RuntimeCtx {parms, abs_image_dir}
+}
+
+// ---------------------------------------------------------------------------
+// get_parms:
+// ---------------------------------------------------------------------------
+/** Retrieve the application parameters from the configuration file specified
+ * either through an environment variable or on the command line.  If neither
+ * are provided, an attempt is made to use the default file path.
+ */
+fn get_parms() -> Result<Parms> {
+    let (config_file, overrides) = CONFIG_ARGS.get().cloned().unwrap_or_default();
+    read_parms(&get_config_file(config_file), &overrides)
+}
+
+// ---------------------------------------------------------------------------
+// get_config_overrides:
+// ---------------------------------------------------------------------------
+/** The configuration overrides from TRAPS_ environment variables followed by
+ * those from the command line, which take precedence.
+ */
+fn get_config_overrides(settings: &[String]) -> Result<Vec<Override>> {
+    let mut overrides = match layers::get_env_overrides(env::vars()) {
+        Ok(o) => o,
+        Err(e) => {
+            error!("{}", e);
+            return Result::Err(anyhow!(e));
+        }
+    };
+    match layers::get_cli_overrides(settings) {
+        Ok(o) => overrides.extend(o),
+        Err(e) => {
+            error!("{}", e);
+            return Result::Err(anyhow!(e));
+        }
+    };
+    Result::Ok(overrides)
+}
+
+// ---------------------------------------------------------------------------
+// get_config_file:
+// ---------------------------------------------------------------------------
+/** The configuration file path from the environment, the command line or the
+ * default, in that order.
+ */
+fn get_config_file(arg: Option<String>) -> String {
+    env::var(ENV_CONFIG_FILE_KEY).unwrap_or_else(|_| arg.unwrap_or_else(|| DEFAULT_CONFIG_FILE.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// read_parms:
+// ---------------------------------------------------------------------------
+/** Read the configuration file and apply the overrides to it. */
+fn read_parms(config_file: &str, overrides: &[Override]) -> Result<Parms> {
+    // Read the cofiguration file.
+    let config_file_abs = traps_utils::get_absolute_path(config_file);
+    info!("{}", Errors::ReadingConfigFile(config_file_abs.clone()));
+    let contents = match fs::read_to_string(&config_file_abs) {
+        Ok(c) => c,
+        Err(e) => {
+            let msg = format!("{}\n   {}", Errors::FileIOError(config_file_abs), e);
+            error!("{}", msg);
+            return Result::Err(anyhow!(msg));
+        }
+    };
+
+    // Parse the toml configuration and apply the overrides.
+    let (config, sources) = match layers::load_config(&config_file_abs, &contents, overrides) {
+        Ok(c)  => c,
+        Err(e) => {
+            error!("{}", e);
+            return Result::Err(anyhow!(e));
+        }
+    };
+
+    Result::Ok(Parms { config_file: config_file_abs, config, sources})
+}
+
+// ---------------------------------------------------------------------------
+// init_image_dir:
+// ---------------------------------------------------------------------------
+fn init_image_dir(dir: &str) -> Result<String> {
+    // Get the absolute filepath to the images directory.
+    let abs_dir = traps_utils::get_absolute_path(dir);
+    Result::Ok(abs_dir)
+}
+
+// ---------------------------------------------------------------------------
+// init_logging:
+// ---------------------------------------------------------------------------
+/** Initialize log4rs from the command line's log configuration file or the
+ * default.  A log level given on the command line replaces the file's root
+ * level.  The file is read again, rather than watched, when the application
+ * configuration is reloaded.
+ */
+fn init_logging(cli: &Cli) -> Result<()> {
+    let log_config = cli.log_config.clone().unwrap_or_else(init_log_config);
+    let context = || format!("{}", Errors::Log4rsInitialization(log_config.clone()));
+    let mut config = log4rs::config::load_config_file(&log_config, Default::default()).with_context(context)?;
+    if let Some(level) = cli.log_level {
+        config.root_mut().set_level(level);
+    }
+    let handle = log4rs::init_config(config).with_context(context)?;
+    let _ = LOG_STATE.set((handle, log_config, cli.log_level));
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// apply_log_level:
+// ---------------------------------------------------------------------------
+/** Re-read the log configuration file and set its root level to the command
+ * line level or else the application configuration's log_level, if either is
+ * given.  Logging is unchanged if the file can't be read.
+ */
+fn apply_log_level(config_level: &Option<String>) {
+    let (handle, log_config, cli_level) = match LOG_STATE.get() {
+        Some(s) => s,
+        None => return,
+    };
+    let mut config = match log4rs::config::load_config_file(log_config, Default::default()) {
+        Ok(c) => c,
+        Err(e) => {
+            error!("{}: {}", Errors::Log4rsInitialization(log_config.clone()), e);
+            return;
+        }
+    };
+    let level = cli_level.or_else(|| config_level.as_ref().and_then(|l| l.parse::<LevelFilter>().ok()));
+    if let Some(level) = level {
+        config.root_mut().set_level(level);
+    }
+    handle.set_config(config);
+}
+
+// ---------------------------------------------------------------------------
+// start_signal_listener:
+// ---------------------------------------------------------------------------
+/** Start the thread that reloads the configuration file each time the
+ * application receives SIGHUP and that requests a graceful shutdown on the
+ * first SIGINT or SIGTERM.  The plugin monitor carries out the shutdown; when
+ * no plugins are configured there's no monitor and nothing to drain, so the
+ * application exits right away.  A second SIGINT or SIGTERM exits immediately.
+ *
+ * The publisher for the ConfigReloadedEvents is created here and reused by
+ * every reload.  The event engine's ports can't change on reload.
+ */
+fn start_signal_listener() -> Result<()> {
+    let mut signals = Signals::new([SIGHUP, SIGINT, SIGTERM]).context("Unable to register the signal handlers")?;
+    let endpoint = format!("tcp://localhost:{}", RUNTIME_CTX.parms.config.publish_port);
+    let publisher = bus::open_publisher(&endpoint)?;
+    thread::Builder::new().name("signals".to_string()).spawn(move || {
+        for signal in signals.forever() {
+            let name = signal_hook::low_level::signal_name(signal).unwrap_or("signal").to_string();
+            match signal {
+                SIGHUP => reload_config(&publisher),
+                _ if shutdown::request_shutdown() => {
+                    warn!("{}", Errors::ShutdownRequested(name));
+                    if !shutdown::is_coordinated() {
+                        info!("{}", Errors::ShutdownNoPlugins());
+                        info!("{}", Errors::AppNormalShutdown());
+                        std::process::exit(0);
+                    }
+                },
+                _ => {
+                    error!("{}", Errors::ShutdownForced(name));
+                    std::process::exit(128 + signal);
+                },
+            }
+        }
+    }).context("Unable to start the signal handling thread")?;
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// reload_config:
+// ---------------------------------------------------------------------------
+/** Validate the configuration file and, if it has no problems, apply its live
+ * settings to the running application.  Settings that can only change on
+ * restart are reported and ignored.  The plugins learn about the new settings
+ * from the ConfigReloadedEvent sent to the event engine on the publisher.
+ */
+fn reload_config(publisher: &zmq::Socket) {
+    let config_file = RUNTIME_CTX.parms.config_file.clone();
+    let overrides = CONFIG_ARGS.get().map(|(_, o)| o.clone()).unwrap_or_default();
+
+    // Reject configurations with problems as a whole.
+    let problems = validate::validate(&validate::ValidateParms {config_file: config_file.clone(), image_store_file: None,
+                                                                 overrides: overrides.clone()});
+    if !problems.is_empty() {
+        let list: Vec<String> = problems.iter().map(|p| format!("  {}: {}", p.location, p.message)).collect();
+        error!("{}", Errors::ConfigReloadRejected(config_file, list.join("\n")));
+        return;
+    }
+    let new = match read_parms(&config_file, &overrides) {
+        Ok(p) => p.config,
+        Err(_) => return,
+    };
+    let running = reload::get_config().unwrap_or_else(|| Arc::new(RUNTIME_CTX.parms.config.clone()));
+    let plan = match reload::plan_reload(&RUNTIME_CTX.parms.config, &running, &new) {
+        Ok(p) => p,
+        Err(e) => {
+            error!("{}", e);
+            return;
+        }
+    };
+
+    // Put the live settings into effect.
+    apply_log_level(&plan.config.log_level);
+    reload::set_config(plan.config);
+    let applied = if plan.applied.is_empty() {"none".to_string()} else {plan.applied.join(", ")};
+    info!("{}", Errors::ConfigReloadApplied(config_file.clone(), applied));
+    if !plan.requires_restart.is_empty() {
+        warn!("{}", Errors::ConfigReloadRestartRequired(plan.requires_restart.join(", ")));
+    }
+
+    // Tell the internal and external plugins.
+    let mut ev = events::ConfigReloadedEvent::new(config_file, plan.applied, plan.requires_restart);
+    ev.set_trace_ids(events::TraceIds::caused_by(None, RELOAD_EVENT_SOURCE));
+    let sent = ev.to_bytes().map_err(|e| Errors::EventToBytesError(RELOAD_EVENT_SOURCE.to_string(), ev.get_name(), e.to_string()))
+        .and_then(|bytes| publisher.send(bytes, 0)
+            .map_err(|e| Errors::SocketSendError(RELOAD_EVENT_SOURCE.to_string(), ev.get_name(), e.to_string())));
+    if let Err(e) = sent {
+        error!("{}", e);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// init_log_config:
+// ---------------------------------------------------------------------------
+fn init_log_config() -> String {
+    env::var(ENV_LOG4RS_FILE_KEY).unwrap_or_else(|_| LOG4RS_CONFIG_FILE.to_string())
+}
+
+// ***************************************************************************
+//                                  Structs
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// Parms:
+// ---------------------------------------------------------------------------
+#[derive(Debug)]
+pub struct Parms {
+    pub config_file: String,
+    pub config: Config,
+    pub sources: ConfigSources,
+}
+
+// ---------------------------------------------------------------------------
+// RuntimeCtx:
+// ---------------------------------------------------------------------------
+#[derive(Debug)]
+pub struct RuntimeCtx {
+    pub parms: Parms,
+    pub abs_image_dir: String,
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn here_i_am() {
+        println!("file test: lib.rs");
+    }
+}
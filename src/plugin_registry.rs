@@ -0,0 +1,277 @@
+use std::fmt;
+use std::time::Duration;
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::traps_utils::timestamp_str;
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+// Used to match terminate events that target all plugins.
+const PLUGIN_NAME_WILDCARD: &str = "*";
+
+// ***************************************************************************
+// PLUGIN STATE
+// ***************************************************************************
+/** The lifecycle states of a registered plugin.  Plugins start out Expected,
+ * move to Started when their PluginStartedEvent arrives and to Terminated
+ * when their PluginTerminatingEvent arrives.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum PluginState {
+    Expected,
+    Started,
+    Terminated,
+}
+
+impl fmt::Display for PluginState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/** What the registry knows about one configured plugin.  The config_name is
+ * the name used in traps.toml; the plugin_name is the name the plugin uses
+ * in its lifecycle events.
+ */
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginEntry {
+    pub config_name: String,
+    pub plugin_name: String,
+    pub plugin_uuid: Uuid,
+    pub external: bool,
+    pub state: PluginState,
+    pub started: Option<String>,
+    pub terminated: Option<String>,
+    pub terminate_requested: bool,
+    pub alarmed: bool,
+}
+
+/** The alarms the registry can raise. */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginAlarm {
+    StartTimeout(String, Uuid),
+    UnexpectedTermination(String, Uuid),
+}
+
+/** What a PluginTerminatingEvent meant to the registry.  A plugin that was
+ * requested to terminate did so as expected, a plugin that wasn't raises an
+ * alarm and a plugin that isn't registered is only reported.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginTermination {
+    Requested,
+    Unexpected(PluginAlarm),
+    Unregistered,
+}
+
+// ***************************************************************************
+// PLUGIN REGISTRY
+// ***************************************************************************
+/** Registry of the internal and external plugins that the configuration says
+ * should be running.  The registry is fed lifecycle events and reports plugins
+ * that never start or that terminate without being asked to.
+ */
+#[derive(Debug, Default, Serialize)]
+pub struct PluginRegistry {
+    plugins: Vec<PluginEntry>,
+}
+
+impl PluginRegistry {
+    // ----------------------------------------------------------------------
+    // new:
+    // ----------------------------------------------------------------------
+    pub fn new() -> Self {
+        PluginRegistry::default()
+    }
+
+    // ----------------------------------------------------------------------
+    // add_expected:
+    // ----------------------------------------------------------------------
+    /** Register a plugin that is expected to start. */
+    pub fn add_expected(&mut self, config_name: &str, plugin_name: &str, plugin_uuid: Uuid, external: bool) {
+        self.plugins.push(PluginEntry {
+            config_name: config_name.to_string(),
+            plugin_name: plugin_name.to_string(),
+            plugin_uuid,
+            external,
+            state: PluginState::Expected,
+            started: None,
+            terminated: None,
+            terminate_requested: false,
+            alarmed: false,
+        });
+    }
+
+    // ----------------------------------------------------------------------
+    // get_plugins:
+    // ----------------------------------------------------------------------
+    #[allow(dead_code)]
+    pub fn get_plugins(&self) -> &Vec<PluginEntry> {
+        &self.plugins
+    }
+
+    // ----------------------------------------------------------------------
+    // record_started:
+    // ----------------------------------------------------------------------
+    /** Record a PluginStartedEvent.  Returns false if the plugin is not registered. */
+    pub fn record_started(&mut self, plugin_name: &str, plugin_uuid: &Uuid) -> bool {
+        match self.find_mut(plugin_name, plugin_uuid) {
+            Some(entry) => {
+                entry.state = PluginState::Started;
+                entry.started = Some(timestamp_str());
+                true
+            },
+            None => false,
+        }
+    }
+
+    // ----------------------------------------------------------------------
+    // record_terminate_request:
+    // ----------------------------------------------------------------------
+    /** Record a PluginTerminateEvent by marking each targeted plugin. */
+    pub fn record_terminate_request(&mut self, target_name: &str, target_uuid: &Uuid) {
+        for entry in self.plugins.iter_mut() {
+            if target_name == PLUGIN_NAME_WILDCARD || entry.plugin_name == target_name ||
+               entry.config_name == target_name || entry.plugin_uuid == *target_uuid {
+                entry.terminate_requested = true;
+            }
+        }
+    }
+
+    // ----------------------------------------------------------------------
+    // record_terminating:
+    // ----------------------------------------------------------------------
+    /** Record a PluginTerminatingEvent.  A plugin that was not requested to
+     * terminate is marked as alarmed and its alarm is returned.
+     */
+    pub fn record_terminating(&mut self, plugin_name: &str, plugin_uuid: &Uuid) -> PluginTermination {
+        let entry = match self.find_mut(plugin_name, plugin_uuid) {
+            Some(e) => e,
+            None => return PluginTermination::Unregistered,
+        };
+        entry.state = PluginState::Terminated;
+        entry.terminated = Some(timestamp_str());
+        if entry.terminate_requested {
+            PluginTermination::Requested
+        } else {
+            entry.alarmed = true;
+            PluginTermination::Unexpected(PluginAlarm::UnexpectedTermination(entry.plugin_name.clone(), entry.plugin_uuid))
+        }
+    }
+
+    // ----------------------------------------------------------------------
+    // check_start_timeouts:
+    // ----------------------------------------------------------------------
+    /** Return an alarm for each plugin that has not started within the timeout.
+     * The elapsed time is measured from application start.  Each plugin is
+     * reported at most once.
+     */
+    pub fn check_start_timeouts(&mut self, elapsed: Duration, timeout: Duration) -> Vec<PluginAlarm> {
+        let mut alarms = vec![];
+        if elapsed < timeout {return alarms;}
+        for entry in self.plugins.iter_mut() {
+            if entry.state == PluginState::Expected && !entry.alarmed {
+                entry.alarmed = true;
+                alarms.push(PluginAlarm::StartTimeout(entry.plugin_name.clone(), entry.plugin_uuid));
+            }
+        }
+        alarms
+    }
+
+    // ----------------------------------------------------------------------
+    // all_terminated:
+    // ----------------------------------------------------------------------
+    /** True when every plugin that started has also terminated. */
+    pub fn all_terminated(&self) -> bool {
+        self.plugins.iter().all(|p| p.state != PluginState::Started)
+    }
+
+    // ----------------------------------------------------------------------
+    // summary:
+    // ----------------------------------------------------------------------
+    /** A human readable, one line per plugin status report. */
+    pub fn summary(&self) -> String {
+        let mut s = String::new();
+        for p in &self.plugins {
+            let kind = if p.external {"external"} else {"internal"};
+            s.push_str(&format!("  {:<32} {:<8} {:<10} {}\n", p.config_name, kind, p.state.to_string(), p.plugin_uuid.hyphenated()));
+        }
+        s
+    }
+
+    // ----------------------------------------------------------------------
+    // get_config_name:
+    // ----------------------------------------------------------------------
+    /** The traps.toml name of the plugin that sent a lifecycle event, if the
+     * plugin is registered.
+     */
+    pub fn get_config_name(&self, plugin_name: &str, plugin_uuid: &Uuid) -> Option<&str> {
+        self.plugins.iter().find(|p| p.plugin_uuid == *plugin_uuid)
+            .or_else(|| self.plugins.iter().find(|p| p.plugin_name == plugin_name))
+            .map(|p| p.config_name.as_str())
+    }
+
+    // ----------------------------------------------------------------------
+    // find_mut:
+    // ----------------------------------------------------------------------
+    // Match on uuid first since names may not be unique across plugin types.
+    fn find_mut(&mut self, plugin_name: &str, plugin_uuid: &Uuid) -> Option<&mut PluginEntry> {
+        match self.plugins.iter().position(|p| p.plugin_uuid == *plugin_uuid) {
+            Some(i) => self.plugins.get_mut(i),
+            None => self.plugins.iter_mut().find(|p| p.plugin_name == plugin_name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: plugin_registry.rs");
+    }
+
+    #[test]
+    fn lifecycle() {
+        let recv = Uuid::new_v4();
+        let ext = Uuid::new_v4();
+        let mut reg = PluginRegistry::new();
+        reg.add_expected("image_recv_plugin", "ImageReceivePlugin", recv, false);
+        reg.add_expected("ext_image_score_plugin", "ext_image_score_plugin", ext, true);
+
+        // Internal plugins are matched by uuid, external plugins may report a different name.
+        assert!(reg.record_started("ImageReceivePlugin", &recv));
+        assert!(reg.record_started("image_scoring_plugin", &ext));
+        assert!(!reg.record_started("unknown", &Uuid::new_v4()));
+        assert_eq!(reg.get_config_name("image_scoring_plugin", &ext), Some("ext_image_score_plugin"));
+        assert_eq!(reg.get_config_name("ImageReceivePlugin", &Uuid::nil()), Some("image_recv_plugin"));
+        assert_eq!(reg.get_config_name("unknown", &Uuid::nil()), None);
+        assert!(reg.check_start_timeouts(Duration::from_secs(100), Duration::from_secs(10)).is_empty());
+
+        // Requested termination raises no alarm, unrequested termination does.
+        reg.record_terminate_request("ImageReceivePlugin", &Uuid::nil());
+        assert_eq!(reg.record_terminating("ImageReceivePlugin", &recv), PluginTermination::Requested);
+        assert!(!reg.all_terminated());
+        assert_eq!(reg.record_terminating("x", &ext),
+                   PluginTermination::Unexpected(PluginAlarm::UnexpectedTermination("ext_image_score_plugin".to_string(), ext)));
+        assert!(reg.all_terminated());
+        assert_eq!(reg.record_terminating("unknown", &Uuid::new_v4()), PluginTermination::Unregistered);
+    }
+
+    #[test]
+    fn start_timeout() {
+        let id = Uuid::new_v4();
+        let mut reg = PluginRegistry::new();
+        reg.add_expected("image_store_plugin", "ImageStorePlugin", id, false);
+
+        // No alarm before the timeout and only one alarm after it.
+        assert!(reg.check_start_timeouts(Duration::from_secs(5), Duration::from_secs(10)).is_empty());
+        assert_eq!(reg.check_start_timeouts(Duration::from_secs(11), Duration::from_secs(10)),
+                   vec![PluginAlarm::StartTimeout("ImageStorePlugin".to_string(), id)]);
+        assert!(reg.check_start_timeouts(Duration::from_secs(12), Duration::from_secs(10)).is_empty());
+    }
+}
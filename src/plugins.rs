@@ -3,9 +3,44 @@ pub mod image_recv_plugin;
 pub mod image_score_plugin;
 pub mod image_store_plugin;
 pub mod observer_plugin;
+pub mod plugin_monitor_plugin;
+pub mod detection_reporter_plugin;
+pub mod mqtt_publisher_plugin;
+pub mod oracle_plugin;
+pub mod power_monitor_plugin;
 pub mod external_app_plugin;
 pub mod actions;
 
+use crate::config::config::{Config, DetectionReporterConfig, ImageStoreConfig, MqttPublisherConfig,
+                            OracleConfig, PowerMonitorConfig};
+use crate::config::errors::Errors;
+
+// The names of the internal plugins that can be listed in the [plugins]
+// internal configuration.
+pub const INTERNAL_PLUGIN_NAMES: &[&str] = &[
+    "image_gen_plugin", "image_recv_plugin", "image_score_plugin", "image_store_plugin",
+    "observer_plugin", "detection_reporter_plugin", "mqtt_publisher_plugin", "oracle_plugin",
+    "power_monitor_plugin",
+];
+
+// ---------------------------------------------------------------------------
+// check_plugin_config:
+// ---------------------------------------------------------------------------
+/** Deserialize the [plugins.config.<name>] table of an internal plugin or
+ * action into the settings type the plugin or action uses.  Plugins and
+ * actions without settings accept any table.
+ */
+pub fn check_plugin_config(config: &Config, name: &str) -> Result<(), Errors> {
+    match name {
+        "image_store_plugin" => config.get_plugin_config::<ImageStoreConfig>(name).map(|_| ()),
+        "detection_reporter_plugin" => config.get_plugin_config::<DetectionReporterConfig>(name).map(|_| ()),
+        "mqtt_publisher_plugin" => config.get_plugin_config::<MqttPublisherConfig>(name).map(|_| ()),
+        "oracle_plugin" => config.get_plugin_config::<OracleConfig>(name).map(|_| ()),
+        "power_monitor_plugin" => config.get_plugin_config::<PowerMonitorConfig>(name).map(|_| ()),
+        _ => Result::Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
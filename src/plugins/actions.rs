@@ -2,4 +2,261 @@ pub mod image_recv_actions;
 pub mod image_gen_actions;
 pub mod image_score_actions;
 pub mod image_store_actions;
-pub mod observer_actions;
\ No newline at end of file
+pub mod observer_actions;
+
+use std::sync::{Arc, Once};
+
+use crate::Config;
+use crate::config::errors::Errors;
+use crate::events_generated::gen_events;
+use crate::reload;
+use crate::traps_utils;
+
+pub use crate::action_registry::{Action, ActionContext, ActionInput, ActionOutcome, ActionRequirements,
+                                 ConfigSetting, check_action_config, get_action, get_action_names,
+                                 get_action_plugin, register_action};
+
+use log::{info, error};
+
+// Registers the built-in actions once.
+static BUILTIN_ACTIONS: Once = Once::new();
+
+// ***************************************************************************
+// BUILT-IN ACTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// register_builtin_actions:
+// ---------------------------------------------------------------------------
+/** Add each action module's built-in actions to the action registry.  This is
+ * called when the application starts, before the configuration is checked or
+ * the plugins start, and does nothing when called again.
+ */
+pub fn register_builtin_actions() {
+    BUILTIN_ACTIONS.call_once(|| {
+        let mut actions = vec![];
+        actions.extend(image_gen_actions::builtin_actions());
+        actions.extend(image_recv_actions::builtin_actions());
+        actions.extend(image_score_actions::builtin_actions());
+        actions.extend(image_store_actions::builtin_actions());
+        actions.extend(observer_actions::builtin_actions());
+        for action in actions {
+            if let Err(e) = register_action(action) {
+                error!("{}", e);
+            }
+        }
+    });
+}
+
+// ---------------------------------------------------------------------------
+// get_plugin:
+// ---------------------------------------------------------------------------
+/** The plugin running a built-in action's chain, or the input mismatch error
+ * when the action is run by a plugin it doesn't belong to.
+ */
+pub fn get_plugin<'a, P: 'static>(input: &ActionInput<'a>, action: &str) -> Result<&'a P, Errors> {
+    input.context.as_any().downcast_ref::<P>().ok_or_else(|| input.unexpected(action))
+}
+
+// ---------------------------------------------------------------------------
+// get_event:
+// ---------------------------------------------------------------------------
+/** The generated event of an action's input, or the input mismatch error when
+ * the plugin ran its chain without an event.
+ */
+pub fn get_event<'a>(input: &ActionInput<'a>, action: &str) -> Result<gen_events::Event<'a>, Errors> {
+    if input.event.len() <= crate::events::EVENT_PREFIX_LEN {
+        return Result::Err(input.unexpected(action));
+    }
+    traps_utils::bytes_to_gen_event(&input.event[crate::events::EVENT_PREFIX_LEN..])
+}
+
+// ---------------------------------------------------------------------------
+// uses_local_image_dir:
+// ---------------------------------------------------------------------------
+/** Check if any of the configured plugin actions requires a local image
+ * directory for I/O.
+ */
+pub fn uses_local_image_dir(config: &Config) -> bool {
+    config.plugins.internal_actions.clone().unwrap_or_default().iter()
+        .filter_map(|name| get_action(name))
+        .any(|action| action.requirements().local_image_dir)
+}
+
+// ***************************************************************************
+// ACTION CHAINS
+// ***************************************************************************
+/** An action in a plugin's chain.  The config is the action's
+ * [plugins.config.<name>] table, which is empty when the table is missing.
+ */
+#[derive(Clone)]
+pub struct ChainedAction {
+    pub action: Arc<dyn Action>,
+    pub config: toml::Value,
+}
+
+/** The ordered actions an internal plugin runs, which are all the entries in
+ * the internal_actions configuration that belong to the plugin, in the order
+ * listed.  A plugin without configured actions runs its no-op action.
+ */
+#[derive(Clone)]
+pub struct ActionChain {
+    pub plugin_name: String,
+    pub actions: Vec<ChainedAction>,
+}
+
+impl ActionChain {
+    // ---------------------------------------------------------------------------
+    // select:
+    // ---------------------------------------------------------------------------
+    /** Called one time by each internal plugin to select its chain of actions
+     * from the registry.  The plugin is the internal plugin's file name, such
+     * as image_recv_plugin, and the plugin name is used in messages.  An error
+     * is returned if any configured action for the plugin is not registered.
+     */
+    pub fn select(config: &Config, plugin: &str, plugin_name: &str) -> Result<Self, Errors> {
+        let int_actions = config.plugins.internal_actions.clone().unwrap_or_default();
+        let tables = config.plugins.config.clone().unwrap_or_default();
+
+        // Chain the configured actions that target this plugin.
+        let noop_name = format!("{}noop_action", plugin.trim_end_matches("plugin"));
+        let mut names: Vec<&str> = int_actions.iter().map(|a| a.as_str())
+                                              .filter(|a| get_action_plugin(a) == Some(plugin)).collect();
+        if names.is_empty() {
+            names.push(&noop_name);
+        }
+        let mut actions = vec![];
+        for name in names {
+            let action = match get_action(name) {
+                Some(a) => a,
+                None => {
+                    let err = Errors::ActionNotFound(plugin_name.to_string(), name.to_string());
+                    error!("{}", err);
+                    return Result::Err(err);
+                }
+            };
+            info!("{}", Errors::ActionConfigured(plugin_name.to_string(), name.to_string()));
+            let config = tables.get(name).cloned().unwrap_or_else(|| toml::Value::Table(Default::default()));
+            actions.push(ChainedAction {action, config});
+        }
+
+        Result::Ok(ActionChain {plugin_name: plugin_name.to_string(), actions})
+    }
+
+    // ---------------------------------------------------------------------------
+    // reload:
+    // ---------------------------------------------------------------------------
+    /** Called when the configuration is reloaded to pick up new action
+     * settings from the configuration in effect.  The chain is unchanged if
+     * its actions can't be selected.
+     */
+    pub fn reload(&mut self, plugin: &str) {
+        let config = match reload::get_config() {
+            Some(c) => c,
+            None => return,
+        };
+        match ActionChain::select(&config, plugin, &self.plugin_name) {
+            Ok(chain) => *self = chain,
+            Err(e) => error!("{}", Errors::ConfigReloadPluginError(self.plugin_name.clone(), e.to_string())),
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // run:
+    // ---------------------------------------------------------------------------
+    /** Execute the actions in order and return their outcomes.  Each action's
+     * success lets the chain continue; the first failure stops the chain and
+     * its error is returned.
+     */
+    pub fn run(&self, input: &ActionInput) -> Result<Vec<ActionOutcome>, Errors> {
+        let mut outcomes = vec![];
+        for chained in &self.actions {
+            match chained.action.execute(input, &chained.config) {
+                Ok(outcome) => outcomes.push(outcome),
+                Err(e) => {
+                    let err = Errors::ActionChainStopped(self.plugin_name.clone(), chained.action.name().to_string(),
+                                                         e.to_string());
+                    error!("{}", err);
+                    return Result::Err(e);
+                }
+            }
+        }
+        Result::Ok(outcomes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: actions.rs");
+    }
+
+    // An action that fails when its table says so.
+    struct TestAction(&'static str);
+    impl Action for TestAction {
+        fn name(&self) -> &str {self.0}
+        fn config_schema(&self) -> Vec<ConfigSetting> {
+            vec![ConfigSetting {key: "fail", kind: "boolean", description: "Fail every time"}]
+        }
+        fn requirements(&self) -> ActionRequirements {ActionRequirements {local_image_dir: true}}
+        fn execute(&self, _input: &ActionInput, config: &toml::Value) -> Result<ActionOutcome, Errors> {
+            match config.get("fail").and_then(|v| v.as_bool()) {
+                Some(true) => Result::Err(Errors::PluginNone()),
+                _ => Result::Ok(ActionOutcome::Done),
+            }
+        }
+    }
+
+    #[test]
+    fn builtin_actions() {
+        // The built-in actions belong to their plugins.
+        register_builtin_actions();
+        let names = get_action_names();
+        assert!(names.iter().any(|n| n == "image_recv_write_file_action"));
+        for name in &names {
+            assert!(get_action_plugin(name).is_some(), "{}", name);
+        }
+        assert!(get_action("image_store_file_action").unwrap().requirements().local_image_dir);
+        assert!(!get_action("observer_journal_action").unwrap().requirements().local_image_dir);
+    }
+
+    #[test]
+    fn action_chains() {
+        register_builtin_actions();
+        register_action(Arc::new(TestAction("image_recv_chain_test_action"))).unwrap();
+        let config: Config = toml::from_str(r#"
+            publish_port = 5559
+            subscribe_port = 5560
+            images_output_dir = "/tmp/images"
+            [plugins]
+            internal_actions = ["image_recv_noop_action", "observer_journal_action", "image_recv_chain_test_action"]
+            [plugins.config.image_recv_chain_test_action]
+            fail = true
+        "#).unwrap();
+
+        // The plugin's actions are chained in the order listed with their tables.
+        let chain = ActionChain::select(&config, "image_recv_plugin", "ImageReceivePlugin").unwrap();
+        let names: Vec<&str> = chain.actions.iter().map(|a| a.action.name()).collect();
+        assert_eq!(names, ["image_recv_noop_action", "image_recv_chain_test_action"]);
+        assert!(chain.actions[0].config.as_table().unwrap().is_empty());
+        assert_eq!(chain.actions[1].config["fail"].as_bool(), Some(true));
+        assert!(uses_local_image_dir(&config));
+
+        // Plugins without actions run their no-op action and unknown actions are errors.
+        let chain = ActionChain::select(&config, "image_store_plugin", "ImageStorePlugin").unwrap();
+        let names: Vec<&str> = chain.actions.iter().map(|a| a.action.name()).collect();
+        assert_eq!(names, ["image_store_noop_action"]);
+        let config: Config = toml::from_str(r#"
+            publish_port = 5559
+            subscribe_port = 5560
+            images_output_dir = "/tmp/images"
+            [plugins]
+            internal_actions = ["observer_shred_action"]
+        "#).unwrap();
+        assert!(matches!(ActionChain::select(&config, "observer_plugin", "ObserverPlugin"),
+                         Err(Errors::ActionNotFound(..))));
+        assert!(!uses_local_image_dir(&config));
+    }
+}
@@ -1,112 +1,99 @@
 use std::fs::OpenOptions;
 use std::io::Write;
 
-use crate::{Config, traps_utils};
+use std::sync::Arc;
+
+use crate::traps_utils;
 use crate::plugins::image_recv_plugin::ImageReceivePlugin;
+use crate::plugins::actions::{self, Action, ActionInput, ActionOutcome, ActionRequirements};
 use event_engine::{plugins::Plugin};
 use crate::{config::errors::Errors};
 use crate::events_generated::gen_events::NewImageEvent;
-use anyhow::{Result, anyhow};
-
-use log::{info, error};
 
-// The search string prefix for this plugin.
-const PREFIX: &str  = "image_recv_";
+use log::error;
 
-// ---------------------------------------------------------------------------
-// select_action:
-// ---------------------------------------------------------------------------
-/** Called one time by each internal plugin to select their single action function. 
- * The internal_actions array component of the plugins configuration object lists
- * zero or more function names.  Each function name is associated with one of the 
- * defined plugins using a convention.  The convention is that each function begins
- * with a prefix derived from the plugin name by dropping the "plugin.rs" portion
- * of the plugin file's name.
- * 
- * This function returns the first function named in internal_actions that matches
- * the plugin's prefix string.  If no such entry is found, the no-op action is 
- * returned.  If a matching entry does not correspond to an action function defined
- * in this file, then an error is returned.
- * 
- * Each action function associated with this plugin requires an arm in the match 
- * statement, which requires maintenance when new action functions are developed. 
+// ***************************************************************************
+// ACTIONS
+// ***************************************************************************
+/** The actions this plugin provides, which are added to the action registry
+ * when the application starts.
  */
-pub fn select_action(config: &'static Config) -> Result<fn(&ImageReceivePlugin, &NewImageEvent) -> bool > {
-    
-    // Internal plugins are optional.
-    let int_actions = match config.plugins.internal_actions.clone() {
-        Some(v) => v,
-        None => vec![]
-    };
+pub fn builtin_actions() -> Vec<Arc<dyn Action>> {
+    vec![Arc::new(ImageRecvNoopAction), Arc::new(ImageRecvWriteFileAction)]
+}
 
-    // Iterate through all configured actions looking for the one
-    // that targets this plugin.  The convention is that a plugin's
-    // actions start with a prefix of plugin's file name (i.e., the
-    // text preceeding 'plugin.rs').
-    for action in &int_actions {
-        // See if the current action is for this plugin.
-        if !(*action).starts_with(PREFIX) {continue;}
-
-        // We expect an implemented function to match the name.
-        match action.as_str() {
-            "image_recv_noop_action" => {
-                info!("{}", Errors::ActionConfigured("ImageReceivePlugin".to_string(), action.to_string()));
-                return Result::Ok(image_recv_noop_action);
-            },
-            "image_recv_write_file_action" => {
-                info!("{}", Errors::ActionConfigured("ImageReceivePlugin".to_string(), action.to_string()));
-                return Result::Ok(image_recv_write_file_action);
-            },
-            unknown => {
-                let msg = Errors::ActionNotFound("ImageReceivePlugin".to_string(), unknown.to_string());
-                error!("{}", msg);
-                return Result::Err(anyhow!(msg));
-            }
-        };
+/** Registered as image_recv_noop_action. */
+pub struct ImageRecvNoopAction;
+impl Action for ImageRecvNoopAction {
+    fn name(&self) -> &str {"image_recv_noop_action"}
+    fn execute(&self, input: &ActionInput, config: &toml::Value) -> Result<ActionOutcome, Errors> {
+        let (plugin, event) = get_recv_input(input, self.name())?;
+        image_recv_noop_action(plugin, &event, input.image.unwrap_or_default(), config).map(|_| ActionOutcome::Done)
     }
-    
-    // Default is to take no action is specified for this plugin.
-    Result::Ok(image_recv_noop_action)
+}
+
+/** Registered as image_recv_write_file_action, which writes into the local
+ * image directory.
+ */
+pub struct ImageRecvWriteFileAction;
+impl Action for ImageRecvWriteFileAction {
+    fn name(&self) -> &str {"image_recv_write_file_action"}
+    fn requirements(&self) -> ActionRequirements {ActionRequirements {local_image_dir: true}}
+    fn execute(&self, input: &ActionInput, config: &toml::Value) -> Result<ActionOutcome, Errors> {
+        let (plugin, event) = get_recv_input(input, self.name())?;
+        image_recv_write_file_action(plugin, &event, input.image.unwrap_or_default(), config).map(|_| ActionOutcome::Done)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// get_recv_input:
+// ---------------------------------------------------------------------------
+/** The receive plugin and NewImageEvent of an action's input. */
+fn get_recv_input<'a>(input: &ActionInput<'a>, action: &str) -> Result<(&'a ImageReceivePlugin, NewImageEvent<'a>), Errors> {
+    let plugin = actions::get_plugin::<ImageReceivePlugin>(input, action)?;
+    let event = actions::get_event(input, action)?.event_as_new_image_event()
+                       .ok_or_else(|| input.unexpected(action))?;
+    Result::Ok((plugin, event))
 }
 
 // ---------------------------------------------------------------------------
 // image_recv_noop_action:
 // ---------------------------------------------------------------------------
-/** No-op action always returns true to allow processing to continue. */
+/** No-op action always succeeds to allow processing to continue. */
 #[allow(unused)]
-pub fn image_recv_noop_action(plugin: &ImageReceivePlugin, event: &NewImageEvent) -> bool 
-{true}
+pub fn image_recv_noop_action(plugin: &ImageReceivePlugin, event: &NewImageEvent, image: &[u8],
+                              config: &toml::Value) -> Result<(), Errors>
+{Result::Ok(())}
 
 // ---------------------------------------------------------------------------
 // image_recv_write_file_action:
 // ---------------------------------------------------------------------------
-/** Write image to file.  Return true if task complete successfully, otherwise
- * return false to abort processing for this image.
+/** Write image to file.  The image is either the one embedded in the event or 
+ * the one it references.  Return Ok if task complete successfully, otherwise
+ * return the error that aborts processing for this image.
 */
-pub fn image_recv_write_file_action(plugin: &ImageReceivePlugin, event: &NewImageEvent) -> bool {
+#[allow(unused)]
+pub fn image_recv_write_file_action(plugin: &ImageReceivePlugin, event: &NewImageEvent, image: &[u8],
+                                    config: &toml::Value) -> Result<(), Errors> {
 
-    // There's no point in moving on if we can't access the image data.
-    let bytes = match event.image() {
-        Some(b) => b,
-        None => {
-            let msg = format!("{}", Errors::ActionNoImageError(
-                                      plugin.get_name(), "image_recv_write_file_action".to_string(),
-                                      "NewImageEvent".to_string(),
-                                    ));
-            error!("{}", msg);
-            return false;
-        } 
-    };
+    // There's no point in moving on if we don't have any image data.
+    if image.is_empty() {
+        let err = Errors::ActionNoImageError(
+                                  plugin.get_name(), "image_recv_write_file_action".to_string(),
+                                  "NewImageEvent".to_string());
+        error!("{}", err);
+        return Result::Err(err);
+    }
     
     // Get the uuid string for use in the file name.
     let uuid_str = match event.image_uuid() {
         Some(s) => s,
         None => {
             // Log the error and just return.
-            let msg = format!("{}", Errors::PluginEventAccessUuidError(
-                                      plugin.get_name(), "NewImageEvent".to_string()));
-            error!("{}", msg);
-            return false;
+            let err = Errors::PluginEventAccessUuidError(
+                                      plugin.get_name(), "NewImageEvent".to_string());
+            error!("{}", err);
+            return Result::Err(err);
         }
     };
 
@@ -115,10 +102,10 @@ pub fn image_recv_write_file_action(plugin: &ImageReceivePlugin, event: &NewImag
         Some(s) => s.to_string().to_lowercase(),
         None => {
             // Log the error and just return.
-            let msg = format!("{}", Errors::ActionImageFormatTypeError(
-                                      plugin.get_name(), "NewImageEvent".to_string()));
-            error!("{}", msg);
-            return false;
+            let err = Errors::ActionImageFormatTypeError(
+                                      plugin.get_name(), "NewImageEvent".to_string());
+            error!("{}", err);
+            return Result::Err(err);
         } 
     };
 
@@ -133,28 +120,28 @@ pub fn image_recv_write_file_action(plugin: &ImageReceivePlugin, event: &NewImag
                         .open(&filepath) {
                             Ok(f) => f,
                             Err(e) => {
-                                let msg = format!("{}", Errors::ActionOpenFileError(
+                                let err = Errors::ActionOpenFileError(
                                     plugin.get_name(), "image_recv_write_file_action".to_string(), 
-                                    filepath, e.to_string()));
-                                error!("{}", msg);
-                                return false;
+                                    filepath, e.to_string());
+                                error!("{}", err);
+                                return Result::Err(err);
                             }
     };
 
     // Write the image bytes to file.  We always return ().
-    match file.write_all(bytes) {
+    match file.write_all(image) {
         Ok(_) => (),
         Err(e) => {
-            let msg = format!("{}", Errors::ActionWriteFileError(
+            let err = Errors::ActionWriteFileError(
                 plugin.get_name(), "image_recv_write_file_action".to_string(), 
-                filepath, e.to_string()));
-            error!("{}", msg);
-            return false;
+                filepath, e.to_string());
+            error!("{}", err);
+            return Result::Err(err);
         }
     }
 
     // Success
-    true
+    Result::Ok(())
 }
 
 // ---------------------------------------------------------------------------
@@ -162,8 +149,8 @@ pub fn image_recv_write_file_action(plugin: &ImageReceivePlugin, event: &NewImag
 // ---------------------------------------------------------------------------
 /** Create absolute file path for the image. */
 fn create_image_filepath(plugin: &ImageReceivePlugin, uuid_str: &str, suffix: &str) -> String {
-    return traps_utils::create_image_filepath(&plugin.get_runctx().abs_image_dir, 
-                                              &plugin.get_runctx().parms.config.image_file_prefix, 
-                                              uuid_str, 
-                                              suffix);
+    traps_utils::create_image_filepath(&plugin.get_runctx().abs_image_dir, 
+                                       &plugin.get_runctx().parms.config.image_file_prefix, 
+                                       uuid_str, 
+                                       suffix)
 }
@@ -2,19 +2,18 @@
 #[allow(unused_imports)]
 use crate::{Config, traps_utils};
 use crate::plugins::image_store_plugin::{ImageStorePlugin, StoreAction, StoreParms};
+use crate::plugins::actions::{self, Action, ActionInput, ActionOutcome, ActionRequirements};
 use crate::events_generated::gen_events::ImageScoredEvent;
 use crate::{events, config::errors::Errors};
 use event_engine::{plugins::Plugin};
 
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 use std::fs;
+use std::sync::Arc;
 use serde_json;
 use glob::glob;
 
-use log::{info, error, warn, debug};
-
-// The search string prefix for this plugin.
-const PREFIX: &str  = "image_store_";
+use log::{error, warn, debug};
 
 // The score file suffix.
 const SCORE_SUFFIX: &str = "score";
@@ -23,59 +22,47 @@ const SCORE_SUFFIX: &str = "score";
 //                            PUBLIC FUNCTIONS
 // ***************************************************************************
 // ---------------------------------------------------------------------------
-// select_action:
+// builtin_actions:
 // ---------------------------------------------------------------------------
-/** Called one time by each internal plugin to select their single action function. 
- * The internal_actions array component of the plugins configuration object lists
- * zero or more function names.  Each function name is associated with one of the 
- * defined plugins using a convention.  The convention is that each function begins
- * with a prefix derived from the plugin name by dropping the "plugin.rs" portion
- * of the plugin file's name.
- * 
- * This function returns the first function named in internal_actions that matches
- * the plugin's prefix string.  If no such entry is found, the no-op action is 
- * returned.  If a matching entry does not correspond to an action function defined
- * in this file, then an error is returned.
- * 
- * Each action function associated with this plugin requires an arm in the match 
- * statement, which requires maintenance when new action functions are developed. 
+/** The actions this plugin provides, which are added to the action registry
+ * when the application starts.
  */
-pub fn select_action(config: &'static Config) -> Result<fn(&ImageStorePlugin, &ImageScoredEvent, &StoreParms)->StoreAction> {
-    
-    // Internal plugins are optional.
-    let int_actions = match config.plugins.internal_actions.clone() {
-        Some(v) => v,
-         None => vec![]
-    };
-            
-    // Iterate through all configured actions looking for the one
-    // that targets this plugin.  The convention is that a plugin's
-    // actions start with a prefix of plugin's file name (i.e., the
-    // text preceeding 'plugin.rs').
-    for action in &int_actions {
-        // See if the current action is for this plugin.
-        if !(*action).starts_with(PREFIX) {continue;}
-
-        // We expect an implemented function to match the name.
-        match action.as_str() {
-            "image_store_noop_action" => {
-                info!("{}", Errors::ActionConfigured("ImageStorePlugin".to_string(), action.to_string()));
-                return Result::Ok(image_store_noop_action);
-            },
-            "image_store_file_action" => {
-                info!("{}", Errors::ActionConfigured("ImageStorePlugin".to_string(), action.to_string()));
-                return Result::Ok(image_store_file_action);
-            },
-            unknown => {
-                let msg = Errors::ActionNotFound("ImageStorePlugin".to_string(), unknown.to_string());
-                error!("{}", msg);
-                return Result::Err(anyhow!(msg));
-            }
-        };
+pub fn builtin_actions() -> Vec<Arc<dyn Action>> {
+    vec![Arc::new(ImageStoreNoopAction), Arc::new(ImageStoreFileAction)]
+}
+
+/** Registered as image_store_noop_action. */
+pub struct ImageStoreNoopAction;
+impl Action for ImageStoreNoopAction {
+    fn name(&self) -> &str {"image_store_noop_action"}
+    fn execute(&self, input: &ActionInput, config: &toml::Value) -> Result<ActionOutcome, Errors> {
+        let (plugin, event) = get_store_input(input, self.name())?;
+        get_outcome(plugin, &event, image_store_noop_action(plugin, &event, &plugin.get_store_parms(), config))
+    }
+}
+
+/** Registered as image_store_file_action, which manages the images in the
+ * local image directory.
+ */
+pub struct ImageStoreFileAction;
+impl Action for ImageStoreFileAction {
+    fn name(&self) -> &str {"image_store_file_action"}
+    fn requirements(&self) -> ActionRequirements {ActionRequirements {local_image_dir: true}}
+    fn execute(&self, input: &ActionInput, config: &toml::Value) -> Result<ActionOutcome, Errors> {
+        let (plugin, event) = get_store_input(input, self.name())?;
+        get_outcome(plugin, &event, image_store_file_action(plugin, &event, &plugin.get_store_parms(), config))
     }
-    
-    // Default is to take no action is specified for this plugin.
-    Result::Ok(image_store_noop_action)
+}
+
+// ---------------------------------------------------------------------------
+// get_store_input:
+// ---------------------------------------------------------------------------
+/** The store plugin and ImageScoredEvent of an action's input. */
+fn get_store_input<'a>(input: &ActionInput<'a>, action: &str) -> Result<(&'a ImageStorePlugin, ImageScoredEvent<'a>), Errors> {
+    let plugin = actions::get_plugin::<ImageStorePlugin>(input, action)?;
+    let event = actions::get_event(input, action)?.event_as_image_scored_event()
+                       .ok_or_else(|| input.unexpected(action))?;
+    Result::Ok((plugin, event))
 }
 
 // ---------------------------------------------------------------------------
@@ -83,8 +70,8 @@ pub fn select_action(config: &'static Config) -> Result<fn(&ImageStorePlugin, &I
 // ---------------------------------------------------------------------------
 /** No-op action. */
 #[allow(unused)]
-pub fn image_store_noop_action(plugin: &ImageStorePlugin, event: &ImageScoredEvent, 
-                               store_parms_ref: &StoreParms) -> StoreAction {
+pub fn image_store_noop_action(plugin: &ImageStorePlugin, event: &ImageScoredEvent,
+                               store_parms_ref: &StoreParms, config: &toml::Value) -> StoreAction {
     StoreAction::Noop
 }
 
@@ -92,8 +79,8 @@ pub fn image_store_noop_action(plugin: &ImageStorePlugin, event: &ImageScoredEve
 // image_store_file_action:
 // ---------------------------------------------------------------------------
 #[allow(unused)]
-pub fn image_store_file_action(plugin: &ImageStorePlugin, event: &ImageScoredEvent, 
-                               store_parms_ref: &StoreParms) -> StoreAction {
+pub fn image_store_file_action(plugin: &ImageStorePlugin, event: &ImageScoredEvent,
+                               store_parms_ref: &StoreParms, config: &toml::Value) -> StoreAction {
 
     // Find highest the score reported in the event.
     let highest_score = get_highest_score(event);
@@ -111,29 +98,22 @@ pub fn image_store_file_action(plugin: &ImageStorePlugin, event: &ImageScoredEve
     }
 }
 
-// ***************************************************************************
-//                            PRIVATE FUNCTIONS
-// ***************************************************************************
 // ---------------------------------------------------------------------------
-// get_highest_score:
+// get_outcome:
 // ---------------------------------------------------------------------------
-/** Return the highest score in the list of scores.  If no scores are present,
- * return 0.
+/** Convert the store action taken into the action's outcome.  ErrorOut fails
+ * the action.  Images that are saved stay in the local image directory, which
+ * is reported as their location.
  */
-fn get_highest_score(event: &ImageScoredEvent) -> f32 {
-    // Get a reference to the score vector.
-    let scores = match event.scores() {
-        Some(v) => v,
-        None => return 0f32,
-    };
-
-    // Get the highest proabability of any score in the vector.
-    let mut highest: f32 = 0f32; 
-    for score in scores {
-        highest = highest.max(score.probability());
-    };
-
-    highest
+fn get_outcome(plugin: &ImageStorePlugin, event: &ImageScoredEvent, taken: StoreAction) -> Result<ActionOutcome, Errors> {
+    match taken {
+        StoreAction::ErrorOut => Result::Err(Errors::PluginEventActionError(
+                                     plugin.get_name(), "ImageScoredEvent".to_string(),
+                                     event.image_uuid().unwrap_or_default().to_string())),
+        StoreAction::Save | StoreAction::ReduceSave =>
+            Result::Ok(ActionOutcome::Stored(taken, make_image_filepath(plugin, event))),
+        taken => Result::Ok(ActionOutcome::Stored(taken, None)),
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -142,7 +122,7 @@ fn get_highest_score(event: &ImageScoredEvent) -> f32 {
 /** Compare the highest score for all against the configured thresholds.  The
  * highest threshold met determines the action.
  */
-fn get_action_for_score(store_parms_ref: &StoreParms, score: f32) -> StoreAction {
+pub fn get_action_for_score(store_parms_ref: &StoreParms, score: f32) -> StoreAction {
 
     // Iterate through the threshold parameters configured at startup.
     // The alogorithm is find the first range that the highest event score
@@ -160,6 +140,41 @@ fn get_action_for_score(store_parms_ref: &StoreParms, score: f32) -> StoreAction
     StoreAction::Delete
 }
 
+// ---------------------------------------------------------------------------
+// highest_score:
+// ---------------------------------------------------------------------------
+/** Return the highest of the label probabilities, or 0 if there are none.  This
+ * is the score that selects the store action.
+ */
+pub fn highest_score(probabilities: impl IntoIterator<Item = f32>) -> f32 {
+    let mut highest: f32 = 0f32; 
+    for probability in probabilities {
+        highest = highest.max(probability);
+    };
+
+    highest
+}
+
+// ***************************************************************************
+//                            PRIVATE FUNCTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// get_highest_score:
+// ---------------------------------------------------------------------------
+/** Return the highest score in the list of scores.  If no scores are present,
+ * return 0.
+ */
+fn get_highest_score(event: &ImageScoredEvent) -> f32 {
+    // Get a reference to the score vector.
+    let scores = match event.scores() {
+        Some(v) => v,
+        None => return 0f32,
+    };
+
+    // Get the highest proabability of any score in the vector.
+    highest_score(scores.iter().map(|score| score.probability()))
+}
+
 // ---------------------------------------------------------------------------
 // make_image_filepath:
 // ---------------------------------------------------------------------------
@@ -171,7 +186,6 @@ fn get_action_for_score(store_parms_ref: &StoreParms, score: f32) -> StoreAction
  * the empty string.  Both of these values are part of the application configuration.
  * The image uuid and format are returned in the NewImageEvent. 
  */
-#[allow(dead_code)]
 fn make_image_filepath(plugin: &ImageStorePlugin, event: &ImageScoredEvent) -> Option<String> {
     // Get the uuid string for use in the file name.
     let uuid_str = match event.image_uuid() {
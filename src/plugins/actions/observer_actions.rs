@@ -1,69 +1,94 @@
-#[allow(unused_imports)]
-use crate::Config;
+use std::sync::Arc;
+
 use crate::plugins::observer_plugin::ObserverPlugin;
+use crate::plugins::actions::{self, Action, ActionInput, ActionOutcome, ConfigSetting};
 use crate::{config::errors::Errors};
-use anyhow::{Result, anyhow};
-
-use log::{info, error};
+use crate::journal::JournalEntry;
+use crate::traps_utils::IncomingEvent;
 
-// The search string prefix for this plugin.
-const PREFIX: &str  = "observer_";
+use log::error;
 
-// ---------------------------------------------------------------------------
-// select_action:
-// ---------------------------------------------------------------------------
-/** Called one time by each internal plugin to select their single action function. 
- * The internal_actions array component of the plugins configuration object lists
- * zero or more function names.  Each function name is associated with one of the 
- * defined plugins using a convention.  The convention is that each function begins
- * with a prefix derived from the plugin name by dropping the "plugin.rs" portion
- * of the plugin file's name.
- * 
- * This function returns the first function named in internal_actions that matches
- * the plugin's prefix string.  If no such entry is found, the no-op action is 
- * returned.  If a matching entry does not correspond to an action function defined
- * in this file, then an error is returned.
- * 
- * Each action function associated with this plugin requires an arm in the match 
- * statement, which requires maintenance when new action functions are developed. 
+// ***************************************************************************
+// ACTIONS
+// ***************************************************************************
+/** The actions this plugin provides, which are added to the action registry
+ * when the application starts.
  */
-pub fn select_action(config: &'static Config) -> Result<fn(&ObserverPlugin)> {
-    
-    // Internal plugins are optional.
-    let int_actions = match config.plugins.internal_actions.clone() {
-        Some(v) => v,
-         None => vec![]
-    };
-            
-    // Iterate through all configured actions looking for the one
-    // that targets this plugin.  The convention is that a plugin's
-    // actions start with a prefix of plugin's file name (i.e., the
-    // text preceeding 'plugin.rs').
-    for action in &int_actions {
-        // See if the current action is for this plugin.
-        if !(*action).starts_with(PREFIX) {continue;}
+pub fn builtin_actions() -> Vec<Arc<dyn Action>> {
+    vec![Arc::new(ObserverNoopAction), Arc::new(ObserverJournalAction)]
+}
 
-        // We expect an implemented function to match the name.
-        match action.as_str() {
-            "observer_noop_action" => {
-                info!("{}", Errors::ActionConfigured("ObserverPlugin".to_string(), action.to_string()));
-                return Result::Ok(observer_noop_action);
-            },
-            unknown => {
-                let msg = Errors::ActionNotFound("ObserverPlugin".to_string(), unknown.to_string());
-                error!("{}", msg);
-                return Result::Err(anyhow!(msg));
-            }
-        };
+/** Registered as observer_noop_action. */
+pub struct ObserverNoopAction;
+impl Action for ObserverNoopAction {
+    fn name(&self) -> &str {"observer_noop_action"}
+    fn execute(&self, input: &ActionInput, config: &toml::Value) -> Result<ActionOutcome, Errors> {
+        let (plugin, event) = get_observe_input(input, self.name())?;
+        observer_noop_action(plugin, &event, config).map(|_| ActionOutcome::Done)
     }
-    
-    // Default is to take no action is specified for this plugin.
-    Result::Ok(observer_noop_action)
+}
+
+/** Registered as observer_journal_action.  Its table holds the journal
+ * settings.
+ */
+pub struct ObserverJournalAction;
+impl Action for ObserverJournalAction {
+    fn name(&self) -> &str {"observer_journal_action"}
+    fn config_schema(&self) -> Vec<ConfigSetting> {
+        vec![
+            ConfigSetting {key: "journal_dir", kind: "string", description: "The directory of the journal files"},
+            ConfigSetting {key: "max_file_bytes", kind: "integer", description: "The size at which a journal file is rotated"},
+            ConfigSetting {key: "max_files", kind: "integer", description: "The number of rotated journal files kept"},
+        ]
+    }
+    fn execute(&self, input: &ActionInput, config: &toml::Value) -> Result<ActionOutcome, Errors> {
+        let (plugin, event) = get_observe_input(input, self.name())?;
+        observer_journal_action(plugin, &event, config).map(|_| ActionOutcome::Done)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// get_observe_input:
+// ---------------------------------------------------------------------------
+/** The observer plugin and incoming event of an action's input. */
+fn get_observe_input<'a>(input: &ActionInput<'a>, action: &str) -> Result<(&'a ObserverPlugin, IncomingEvent<'a>), Errors> {
+    let plugin = actions::get_plugin::<ObserverPlugin>(input, action)?;
+    let gen_event = actions::get_event(input, action)?;
+    Result::Ok((plugin, IncomingEvent {prefix_array: [input.event[0], input.event[1]], gen_event, raw_bytes: input.event}))
 }
 
 // ---------------------------------------------------------------------------
 // observer_noop_action:
 // ---------------------------------------------------------------------------
-/** No-op action. */
+/** No-op action always succeeds to allow processing to continue. */
+#[allow(unused)]
+pub fn observer_noop_action(plugin: &ObserverPlugin, event: &IncomingEvent,
+                            config: &toml::Value) -> Result<(), Errors> {Result::Ok(())}
+
+// ---------------------------------------------------------------------------
+// observer_journal_action:
+// ---------------------------------------------------------------------------
+/** Append the event to the plugin's rotating JSONL journal.  Each entry contains
+ * the decoded event fields as JSON and the raw event bytes in base64 so that
+ * the journal can later be replayed.  The journal's settings are applied when
+ * the plugin is created.  Failures are logged and the event is not journaled.
+ */
 #[allow(unused)]
-pub fn observer_noop_action(plugin: &ObserverPlugin) {}
+pub fn observer_journal_action(plugin: &ObserverPlugin, event: &IncomingEvent,
+                               config: &toml::Value) -> Result<(), Errors> {
+    // Decode the event into a journal entry.
+    let entry = match JournalEntry::new(event.prefix_array, event.gen_event, event.raw_bytes) {
+        Ok(e) => e,
+        Err(e) => {
+            error!("{}", e);
+            return Result::Err(e);
+        }
+    };
+
+    // Write the entry.
+    if let Err(e) = plugin.get_journal().append(&entry) {
+        error!("{}", e);
+        return Result::Err(e);
+    }
+    Result::Ok(())
+}
@@ -0,0 +1,481 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use uuid::Uuid;
+use zmq::Socket;
+use serde::Deserialize;
+use event_engine::plugins::Plugin;
+use event_engine::errors::EngineError;
+use event_engine::events::{Event, EventType};
+use crate::events_generated::gen_events;
+use crate::{events, config::errors::Errors};
+use crate::{reload, traps_utils, RuntimeCtx};
+use crate::config::config::DetectionReporterConfig;
+use crate::dead_letter::{self, DeadLetterReason};
+use crate::events::{CONFIG_RELOADED_PREFIX, IMAGE_DELETED_PREFIX, IMAGE_PROCESSING_ERROR_PREFIX, IMAGE_SCORED_PREFIX, IMAGE_STORED_PREFIX,
+                    PLUGIN_TERMINATE_PREFIX, ConfigReloadedEvent, DetectionEvent, ImageDeletedEvent,
+                    ImageProcessingErrorEvent, ImageScoredEvent, ImageStoredEvent, Traceable};
+
+use log::{info, error, debug};
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+// Defaults used when the [plugins.config.detection_reporter_plugin] configuration does not specify a value.
+// These match the defaults of the original Python detection reporter where possible.
+const DEFAULT_DETECTION_FILE: &str = "~/traps-detection.toml";
+const DEFAULT_OUTPUT_DIR: &str = "~/camera-traps/output";
+const DEFAULT_EVENTS_FILE: &str = "detections.csv";
+
+// The name of this plugin's [plugins.config] table.
+const CONFIG_NAME: &str = "detection_reporter_plugin";
+
+// The categories of lines written to the events file.
+const CSV_DETECTION: &str = "DETECTION";
+const CSV_STORING: &str = "STORING";
+
+// ***************************************************************************
+// STRUCTS
+// ***************************************************************************
+/** A label whose score exceeded the label's detection threshold. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Detection {
+    pub label: String,
+    pub probability: f32,
+    pub threshold: f32,
+}
+
+/** The detections of a scored image that are reported once the image is
+ * stored, deleted or abandoned.
+ */
+#[derive(Debug)]
+struct PendingImage {
+    image_format: String,
+    detections: Vec<Detection>,
+}
+
+/** The contents of the detection file. */
+#[derive(Debug, Deserialize)]
+struct DetectionInput {
+    thresholds: BTreeMap<String, f32>,
+}
+
+/** The reporter's runtime parameters. */
+#[derive(Debug)]
+pub struct DetectionParms {
+    pub thresholds: BTreeMap<String, f32>,
+    pub events_file: String,
+}
+
+pub struct DetectionReporterPlugin {
+    name: String,
+    id: Uuid,
+    config: DetectionReporterConfig,
+}
+impl Plugin for DetectionReporterPlugin {
+    // ---------------------------------------------------------------------------
+    // start:
+    // ---------------------------------------------------------------------------
+    /// The entry point for the plugin. The engine will start the plugin in its own
+    /// thread and execute this function.  The pub_socket is used by the plugin to
+    /// publish new events.  The sub_socket is used by the plugin to get events
+    /// published by other plugins.
+    fn start(
+        &self,
+        pub_socket: Socket,
+        sub_socket: Socket,
+    ) -> Result<(), EngineError> {
+
+        // Announce our arrival.
+        info!("{}", format!("{}", Errors::PluginStarted(self.name.clone(), self.get_id().hyphenated().to_string())));
+
+        // Read the thresholds and start a new events file.
+        let mut parms = match self.init_detection_parms() {
+            Ok(p) => p,
+            Err(e) => {
+                return Err(EngineError::PluginExecutionError(self.name.clone(),
+                                                             self.get_id().hyphenated().to_string(),
+                                                             e.to_string()));
+            }
+        };
+
+        // Send the plugin start up event.
+        match traps_utils::send_started_event(self, &pub_socket) {
+            Ok(_) => (),
+            Err(e) => return Err(e),
+        };
+
+        // Detected images waiting to be stored, deleted or abandoned.
+        let mut pending: HashMap<Uuid, PendingImage> = HashMap::new();
+
+        // Enter our infinite work loop.
+        loop {
+            // ----------------- Wait on the Next Event -----------------------
+            // The bytes vector is an output parameter populated by the marshalling function
+            // with raw event bytes. The ev_in.gen_event field references these raw bytes
+            // so the bytes vector must be of a lifetime at least as long as ev_in.
+            let mut bytes: Vec<u8> = vec![];
+            let ev_in = match traps_utils::marshal_next_event(self, &sub_socket, &mut bytes) {
+                Some(ev) => ev,
+                None => continue,
+            };
+
+            // ----------------- Process Subscription Events ------------------
+            // Process events we expect; log and disregard all others.
+            let terminate = match ev_in.prefix_array {
+                IMAGE_SCORED_PREFIX => {
+                    // Images scored while a reload has removed this plugin are not reported.
+                    debug!("\n  -> {} received event {}", self.name, String::from("ImageScoredEvent"));
+                    if reload::is_plugin_enabled(CONFIG_NAME) {
+                        self.process_scored_event(ev_in.gen_event, &parms, &mut pending);
+                    }
+                    false
+                },
+                IMAGE_STORED_PREFIX => {
+                    debug!("\n  -> {} received event {}", self.name, String::from("ImageStoredEvent"));
+                    self.process_stored_event(ev_in.gen_event, &pub_socket, &parms, &mut pending);
+                    false
+                },
+                IMAGE_DELETED_PREFIX => {
+                    debug!("\n  -> {} received event {}", self.name, String::from("ImageDeletedEvent"));
+                    if let Ok(ev) = self.read_event::<ImageDeletedEvent>(ev_in.gen_event) {
+                        self.report_detections(ev_in.gen_event, &pub_socket, ev.get_image_uuid(), None, &mut pending);
+                    }
+                    false
+                },
+                IMAGE_PROCESSING_ERROR_PREFIX => {
+                    debug!("\n  -> {} received event {}", self.name, String::from("ImageProcessingErrorEvent"));
+                    if let Ok(ev) = self.read_event::<ImageProcessingErrorEvent>(ev_in.gen_event) {
+                        self.report_detections(ev_in.gen_event, &pub_socket, ev.get_image_uuid(), None, &mut pending);
+                    }
+                    false
+                },
+                CONFIG_RELOADED_PREFIX => {
+                    info!("\n  -> {} received event {}", self.name, String::from("ConfigReloadedEvent"));
+                    if let Some(thresholds) = self.reload_thresholds() {
+                        parms.thresholds = thresholds;
+                    }
+                    false
+                },
+                PLUGIN_TERMINATE_PREFIX => {
+                    // Determine whether we are the target of this terminate event. The called method
+                    // will return true if this plugin should shutdown.
+                    info!("\n  -> {} received event {}", self.name, String::from("PluginTerminateEvent"));
+                    traps_utils::process_plugin_terminate_event(ev_in.gen_event, &self.id, &self.name)
+                },
+                unexpected => {
+                    // This should only happen for valid events to which we are not subscribed.
+                    // Completely invalid event prefixes are detected above in check_event_prefix().
+                    let pre = format!("{:?}", unexpected);
+                    let err = Errors::EventNotHandledError(self.name.clone(), pre);
+                    error!("{}", err);
+                    dead_letter::capture(&self.name, DeadLetterReason::NotHandled, &err, ev_in.raw_bytes);
+                    false
+                }
+            };
+
+            // Determine if we should terminate our event read loop.
+            if terminate {
+                // Clean up and send the terminating event.
+                traps_utils::send_terminating_event(&self.name, self.id, &pub_socket);
+                break;
+            }
+        }
+
+        // Shutting down.
+        Ok(())
+    }
+
+    /// Return the event subscriptions, as a vector of strings, that this plugin is interested in.
+    fn get_subscriptions(&self) -> Result<Vec<Box<dyn EventType>>, EngineError> {
+        Ok(vec![
+            Box::new(ImageScoredEvent::new(Uuid::new_v4(), "fake".to_string(), vec![])),
+            Box::new(ImageStoredEvent::new(Uuid::new_v4(), "fake".to_string(), "fake".to_string())),
+            Box::new(ImageDeletedEvent::new(Uuid::new_v4(), "fake".to_string())),
+            Box::new(ImageProcessingErrorEvent::new(Uuid::new_v4(), "fake".to_string(), "fake".to_string(),
+                                                    "fake".to_string(), "fake".to_string())),
+            Box::new(ConfigReloadedEvent::new("fake".to_string(), vec![], vec![])),
+            Box::new(events::PluginTerminateEvent::new(Uuid::new_v4(), String::from("*"))),
+        ])
+    }
+
+    /// Simple accessors for this plugin.
+    fn get_name(&self) -> String {self.name.clone()}
+    fn get_id(&self) -> Uuid {self.id}
+}
+
+impl DetectionReporterPlugin {
+    // ---------------------------------------------------------------------------
+    // new:
+    // ---------------------------------------------------------------------------
+    /** Create the plugin with its [plugins.config.detection_reporter_plugin]
+     * table.
+     */
+    pub fn new(runctx: &'static RuntimeCtx) -> Result<Self, Errors> {
+        let config = &runctx.parms.config;
+        Result::Ok(DetectionReporterPlugin {
+            name: "DetectionReporterPlugin".to_string(),
+            id: Uuid::new_v4(),
+            config: config.get_plugin_config(CONFIG_NAME)?,
+        })
+    }
+
+    // ---------------------------------------------------------------------------
+    // init_detection_parms:
+    // ---------------------------------------------------------------------------
+    /** Get the per-label thresholds from the configuration or else the detection
+     * file and truncate the events file, creating the output directory if
+     * necessary.
+     */
+    pub fn init_detection_parms(&self) -> Result<DetectionParms, Errors> {
+        let config = &self.config;
+        let thresholds = get_thresholds(config)?;
+
+        // Start with an empty events file like the Python reporter.
+        let output_dir = traps_utils::get_absolute_path(config.output_dir.as_deref().unwrap_or(DEFAULT_OUTPUT_DIR));
+        let events_file = Path::new(&output_dir).join(config.events_file.as_deref().unwrap_or(DEFAULT_EVENTS_FILE))
+                              .to_string_lossy().to_string();
+        if let Err(e) = fs::create_dir_all(&output_dir) {
+            return Result::Err(Errors::AppDirCreateError(output_dir, e.to_string()));
+        }
+        if let Err(e) = traps_utils::create_or_replace_file(&events_file, &[]) {
+            return Result::Err(Errors::DetectionWriteError(events_file, e.to_string()));
+        }
+
+        Result::Ok(DetectionParms {thresholds, events_file})
+    }
+
+    // ---------------------------------------------------------------------------
+    // reload_thresholds:
+    // ---------------------------------------------------------------------------
+    /** Get the per-label thresholds from the configuration in effect after a
+     * reload.  None is returned, and the current thresholds are kept, if they
+     * can't be read.
+     */
+    fn reload_thresholds(&self) -> Option<BTreeMap<String, f32>> {
+        let config = reload::get_config()?;
+        let result = config.get_plugin_config(CONFIG_NAME)
+                           .and_then(|c| get_thresholds(&c));
+        match result {
+            Ok(thresholds) => Some(thresholds),
+            Err(e) => {
+                error!("{}", Errors::ConfigReloadPluginError(self.name.clone(), e.to_string()));
+                None
+            }
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // read_event:
+    // ---------------------------------------------------------------------------
+    fn read_event<T: events::FromGenEvent>(&self, gen_event: gen_events::Event) -> Result<T, Errors> {
+        let result = events::gen_to_event::<T>(gen_event);
+        if let Err(e) = &result {
+            error!("{}", e);
+        }
+        result
+    }
+
+    // ---------------------------------------------------------------------------
+    // process_scored_event:
+    // ---------------------------------------------------------------------------
+    /** Record the image's detections, if any, and append them to the events file. */
+    fn process_scored_event(&self, gen_event: gen_events::Event, parms: &DetectionParms,
+                            pending: &mut HashMap<Uuid, PendingImage>) {
+        let ev = match self.read_event::<ImageScoredEvent>(gen_event) {
+            Ok(ev) => ev,
+            Err(_) => return,
+        };
+        let scores: Vec<(String, f32)> = ev.get_scores().iter()
+            .map(|s| (s.get_label().clone(), s.get_probability())).collect();
+        let detections = find_detections(&parms.thresholds, &scores);
+        if detections.is_empty() {
+            return;
+        }
+
+        let uuid_str = ev.get_image_uuid().hyphenated().to_string();
+        info!("{}", Errors::DetectionReported(uuid_str.clone(), format_model_result(&detections)));
+        append_line(&parms.events_file, &format_detection_line(&uuid_str, &detections));
+        pending.insert(*ev.get_image_uuid(),
+                       PendingImage {image_format: ev.get_image_format().clone(), detections});
+    }
+
+    // ---------------------------------------------------------------------------
+    // process_stored_event:
+    // ---------------------------------------------------------------------------
+    /** Append a stored detected image to the events file and report its detections. */
+    fn process_stored_event(&self, gen_event: gen_events::Event, pub_socket: &Socket, parms: &DetectionParms,
+                            pending: &mut HashMap<Uuid, PendingImage>) {
+        let ev = match self.read_event::<ImageStoredEvent>(gen_event) {
+            Ok(ev) => ev,
+            Err(_) => return,
+        };
+        if !pending.contains_key(ev.get_image_uuid()) {
+            return;
+        }
+
+        // The events file records the image's file name and the store plugin's decision.
+        let uuid_str = ev.get_image_uuid().hyphenated().to_string();
+        let image_path = format!("{}.{}", uuid_str, ev.get_image_format());
+        append_line(&parms.events_file, &format_storing_line(&uuid_str, &image_path, ev.get_destination()));
+
+        // The store action reports where it stored the image, if anywhere.
+        self.report_detections(gen_event, pub_socket, ev.get_image_uuid(), ev.get_location().cloned(), pending);
+    }
+
+    // ---------------------------------------------------------------------------
+    // report_detections:
+    // ---------------------------------------------------------------------------
+    /** Publish a DetectionEvent for each of an image's pending detections. */
+    fn report_detections(&self, gen_event: gen_events::Event, pub_socket: &Socket, image_uuid: &Uuid,
+                         stored_location: Option<String>, pending: &mut HashMap<Uuid, PendingImage>) {
+        let image = match pending.remove(image_uuid) {
+            Some(p) => p,
+            None => return,
+        };
+
+        for detection in image.detections {
+            let mut ev = DetectionEvent::new(*image_uuid, image.image_format.clone(), detection.label,
+                                             detection.probability, detection.threshold);
+            ev.set_stored_location(stored_location.clone());
+            ev.set_trace_ids(traps_utils::response_trace_ids(&self.name, gen_event));
+            let bytes = match ev.to_bytes() {
+                Ok(v) => v,
+                Err(e) => {
+                    error!("{}", Errors::EventToBytesError(self.get_name(), ev.get_name(), e.to_string()));
+                    continue;
+                }
+            };
+            if let Err(e) = pub_socket.send(bytes, 0) {
+                error!("{}", Errors::SocketSendError(self.get_name(), ev.get_name(), e.to_string()));
+            }
+        }
+    }
+}
+
+// ***************************************************************************
+// PRIVATE FUNCTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// get_thresholds:
+// ---------------------------------------------------------------------------
+/** Get the per-label thresholds from the thresholds table or else the detection file. */
+fn get_thresholds(config: &DetectionReporterConfig) -> Result<BTreeMap<String, f32>, Errors> {
+    match &config.thresholds {
+        Some(t) => Result::Ok(t.clone()),
+        None => read_thresholds(&traps_utils::get_absolute_path(
+                    config.detection_file.as_deref().unwrap_or(DEFAULT_DETECTION_FILE))),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// read_thresholds:
+// ---------------------------------------------------------------------------
+/** Read the [thresholds] table of label/threshold pairs from the detection file. */
+fn read_thresholds(detection_file: &str) -> Result<BTreeMap<String, f32>, Errors> {
+    let contents = match fs::read_to_string(detection_file) {
+        Ok(c) => c,
+        Err(e) => return Result::Err(Errors::DetectionThresholdsError(detection_file.to_string(), e.to_string())),
+    };
+    match toml::from_str::<DetectionInput>(&contents) {
+        Ok(input) => Result::Ok(input.thresholds),
+        Err(e) => Result::Err(Errors::DetectionThresholdsError(detection_file.to_string(), e.to_string())),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// find_detections:
+// ---------------------------------------------------------------------------
+/** A label is detected when its probability is greater than its threshold.
+ * Labels without a threshold are never detected.
+ */
+fn find_detections(thresholds: &BTreeMap<String, f32>, scores: &[(String, f32)]) -> Vec<Detection> {
+    scores.iter()
+        .filter_map(|(label, probability)| match thresholds.get(label) {
+            Some(threshold) if probability > threshold =>
+                Some(Detection {label: label.clone(), probability: *probability, threshold: *threshold}),
+            _ => None,
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// format_model_result:
+// ---------------------------------------------------------------------------
+/** Format the detections the way the Python reporter prints its list of
+ * label and probability dictionaries.
+ */
+fn format_model_result(detections: &[Detection]) -> String {
+    let entries: Vec<String> = detections.iter()
+        .map(|d| format!("{{'label': '{}', 'probability': {:?}}}", d.label, d.probability as f64))
+        .collect();
+    format!("[{}]", entries.join(", "))
+}
+
+// ---------------------------------------------------------------------------
+// format_detection_line:
+// ---------------------------------------------------------------------------
+fn format_detection_line(uuid: &str, detections: &[Detection]) -> String {
+    format!("{}, {}, {}\n", CSV_DETECTION, uuid, format_model_result(detections))
+}
+
+// ---------------------------------------------------------------------------
+// format_storing_line:
+// ---------------------------------------------------------------------------
+fn format_storing_line(uuid: &str, image_path: &str, decision: &str) -> String {
+    format!("{}, {}, {}, {}\n", CSV_STORING, uuid, image_path, decision)
+}
+
+// ---------------------------------------------------------------------------
+// append_line:
+// ---------------------------------------------------------------------------
+/** Append a line to the events file, logging any error. */
+fn append_line(events_file: &str, line: &str) {
+    let result = OpenOptions::new().create(true).append(true).open(events_file)
+                     .and_then(|mut f| f.write_all(line.as_bytes()));
+    if let Err(e) = result {
+        error!("{}", Errors::DetectionWriteError(events_file.to_string(), e.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: detection_reporter_plugin.rs");
+    }
+
+    #[test]
+    fn detections_and_csv_lines() {
+        let mut thresholds = BTreeMap::new();
+        thresholds.insert("animal".to_string(), 0.5);
+        thresholds.insert("person".to_string(), 0.8);
+        let scores = vec![("animal".to_string(), 0.75), ("person".to_string(), 0.8), ("cow".to_string(), 0.99)];
+
+        // Only labels strictly above their threshold are detected.
+        let detections = find_detections(&thresholds, &scores);
+        assert_eq!(detections, vec![Detection {label: "animal".to_string(), probability: 0.75, threshold: 0.5}]);
+
+        // The lines match those written by the Python detection reporter.
+        let uuid = "7d6c4e5a-0b3f-4c8a-9d2e-1f0a2b3c4d5e";
+        assert_eq!(format_detection_line(uuid, &detections),
+                   format!("DETECTION, {}, [{{'label': 'animal', 'probability': 0.75}}]\n", uuid));
+        assert_eq!(format_storing_line(uuid, &format!("{}.jpg", uuid), "Save"),
+                   format!("STORING, {}, {}.jpg, Save\n", uuid, uuid));
+    }
+
+    #[test]
+    fn thresholds_file() {
+        let path = std::env::temp_dir().join(format!("traps-detection-{}.toml", Uuid::new_v4()));
+        fs::write(&path, "[thresholds]\nanimal = 0.5\n").unwrap();
+        let thresholds = read_thresholds(&path.to_string_lossy()).unwrap();
+        assert_eq!(thresholds.get("animal"), Some(&0.5));
+        fs::write(&path, "animal = 0.5\n").unwrap();
+        assert!(read_thresholds(&path.to_string_lossy()).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
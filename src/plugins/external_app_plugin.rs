@@ -1,6 +1,6 @@
 use uuid::Uuid;
 use crate::config::config::ExtPluginConfig;
-use crate::{events, events::MonitorType, config::errors::Errors};
+use crate::{events, config::errors::Errors};
 use event_engine::{plugins::ExternalPlugin};
 use event_engine::errors::EngineError;
 use event_engine::events::EventType;
@@ -31,41 +31,12 @@ impl ExternalPlugin for ExternalAppPlugin {
         // Create the result vector.
         let mut event_types: Vec<Box<dyn EventType>> = vec![];
 
-        // Populate the vector each
+        // Look up each configured event name in the event registry.
         for event_name in &self.subscriptions {
-            match event_name.as_str() {
-                "NewImageEvent" => {
-                    event_types.push(Box::new(events::NewImageEvent::new(Uuid::new_v4(), String::from("fake"), vec![])),);
-                },
-                "ImageReceivedEvent" => {
-                    event_types.push(Box::new(events::ImageReceivedEvent::new(Uuid::new_v4(), String::from("fake"))));
-                },
-                "ImageScoredEvent" => {
-                    event_types.push(Box::new(events::ImageScoredEvent::new(Uuid::new_v4(), String::from("fake"), vec![])));
-                },
-                "ImageStoredEvent" => {
-                    event_types.push(Box::new(events::ImageStoredEvent::new(Uuid::new_v4(), String::from("fake"), String::from("path"))));
-                },
-                "ImageDeletedEvent" => {
-                    event_types.push(Box::new(events::ImageDeletedEvent::new(Uuid::new_v4(), String::from("fake"))));
-                },
-                "PluginTerminateEvent" => {
-                    event_types.push(Box::new(events::PluginTerminateEvent::new(Uuid::new_v4(), String::from("*"))));
-                },
-                "PluginStartedEvent" => {
-                    event_types.push(Box::new(events::PluginStartedEvent::new(Uuid::new_v4(), String::from("ObserverPlugin"))));
-                },
-                "PluginTerminatingEvent" => {
-                    event_types.push(Box::new(events::PluginTerminatingEvent::new(Uuid::new_v4(), String::from("ObserverPlugin"))));
-                },
-                "MonitorPowerStartEvent" => {
-                    event_types.push(Box::new(events::MonitorPowerStartEvent::new(vec!(1), vec!(MonitorType::ALL), String::from("2023-01-01T00:00:01.000000001+00:00"), 100)));
-                },
-                "MonitorPowerStopEvent" => {
-                    event_types.push(Box::new(events::MonitorPowerStopEvent::new(vec!(1))));
-                },
-                other => {
-                    let msg = format!("{}", Errors::EventNotHandledError(self.plugin_name.clone(), String::from(other)));
+            match events::get_event_info_by_name(event_name) {
+                Some(info) => event_types.push(info.get_subscription()),
+                None => {
+                    let msg = format!("{}", Errors::EventNotHandledError(self.plugin_name.clone(), event_name.clone()));
                     error!("{}", msg);
                     return Result::Err(EngineError::PluginExecutionError(self.plugin_name.clone(), self.id.to_string(), msg));
                 },
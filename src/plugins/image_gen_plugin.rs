@@ -1,3 +1,4 @@
+use std::any::Any;
 use uuid::Uuid;
 use zmq::Socket;
 use event_engine::{plugins::Plugin};
@@ -5,9 +6,10 @@ use event_engine::errors::EngineError;
 use event_engine::events::EventType;
 use crate::{events, config::errors::Errors};
 use crate::traps_utils;
+use crate::dead_letter::{self, DeadLetterReason};
 use crate::events::{PLUGIN_TERMINATE_PREFIX};
-use crate::plugins::actions::image_gen_actions::select_action;
-use crate::RuntimeCtx;
+use crate::plugins::actions::{ActionChain, ActionContext, ActionInput};
+use crate::{Config, RuntimeCtx};
 
 use log::{info, error};
 
@@ -34,9 +36,8 @@ impl Plugin for ImageGenPlugin {
         // Announce our arrival.
         info!("{}", format!("{}", Errors::PluginStarted(self.name.clone(), self.get_id().hyphenated().to_string())));
 
-        // Get this plugin's required action function pointer.
-        #[allow(unused_variables)]
-        let action = match select_action(&self.runctx.parms.config) {
+        // Get this plugin's chain of actions.
+        let actions = match ActionChain::select(&self.runctx.parms.config, "image_gen_plugin", "ImageGenPlugin") {
             Ok(a) => a,
             Err(e) => {
                 return Err(EngineError::PluginExecutionError(self.name.clone(), 
@@ -51,6 +52,10 @@ impl Plugin for ImageGenPlugin {
             Err(e) => return Err(e),
         };
 
+        // Run the action chain once the plugin has started.  Failures are logged
+        // by the chain.
+        let _ = actions.run(&ActionInput {event: &[], image: None, context: self});
+
 
         // Enter our infinite work loop.
         loop {
@@ -79,7 +84,9 @@ impl Plugin for ImageGenPlugin {
                     // This should only happen for valid events to which we are not subscribed.
                     // Completely invalid event prefixes are detected above in check_event_prefix().
                     let pre = format!("{:?}", unexpected);
-                    error!("{}", Errors::EventNotHandledError(self.name.clone(), pre));
+                    let err = Errors::EventNotHandledError(self.name.clone(), pre);
+                    error!("{}", err);
+                    dead_letter::capture(&self.name, DeadLetterReason::NotHandled, &err, ev_in.raw_bytes);
                     false
                 }
             };
@@ -106,6 +113,12 @@ impl Plugin for ImageGenPlugin {
     fn get_id(&self) -> Uuid {self.id}
 }
 
+impl ActionContext for ImageGenPlugin {
+    fn get_plugin_name(&self) -> &str {"image_gen_plugin"}
+    fn get_config(&self) -> &Config {&self.runctx.parms.config}
+    fn as_any(&self) -> &dyn Any {self}
+}
+
 impl ImageGenPlugin {
     
     // ---------------------------------------------------------------------------
@@ -1,3 +1,4 @@
+use std::any::Any;
 use uuid::Uuid;
 use zmq::Socket;
 use event_engine::{plugins::Plugin};
@@ -6,17 +7,20 @@ use event_engine::events::EventType;
 use event_engine::events::Event;
 use crate::events_generated::gen_events;
 use crate::{events, config::errors::Errors};
-use crate::{traps_utils, RuntimeCtx};
-use crate::events::{NEW_IMAGE_PREFIX, PLUGIN_TERMINATE_PREFIX};
-use crate::plugins::actions::image_recv_actions::select_action;
+use crate::{image_ref, shutdown, traps_utils, Config, RuntimeCtx};
+use crate::image_ref::ImageRefDirs;
+use crate::dead_letter::{self, DeadLetterReason};
+use crate::events::{CONFIG_RELOADED_PREFIX, NEW_IMAGE_PREFIX, PLUGIN_TERMINATE_PREFIX, Traceable};
+use crate::plugins::actions::{ActionChain, ActionContext, ActionInput};
 
-use log::{info, error, debug};
+use log::{info, warn, error, debug};
 
 
 pub struct ImageReceivePlugin {
     name: String,
     id: Uuid,
     runctx: &'static RuntimeCtx,
+    image_ref_dirs: ImageRefDirs,
 }
 
 impl Plugin for ImageReceivePlugin {
@@ -36,8 +40,8 @@ impl Plugin for ImageReceivePlugin {
         // Announce our arrival.
         info!("{}", format!("{}", Errors::PluginStarted(self.name.clone(), self.get_id().hyphenated().to_string())));
 
-        // Get this plugin's required action function pointer.
-        let action = match select_action(&self.runctx.parms.config) {
+        // Get this plugin's chain of actions.
+        let mut actions = match ActionChain::select(&self.runctx.parms.config, "image_recv_plugin", "ImageReceivePlugin") {
             Ok(a) => a,
             Err(e) => {
                 return Err(EngineError::PluginExecutionError(self.name.clone(), 
@@ -73,7 +77,13 @@ impl Plugin for ImageReceivePlugin {
             let terminate = match ev_in.prefix_array {
                 NEW_IMAGE_PREFIX => {
                     debug!("\n  -> {} received event {}", self.name, String::from("NewImageEvent"));
-                    self.send_event(ev_in.gen_event, &pub_socket, action);
+                    self.send_event(ev_in.gen_event, ev_in.raw_bytes, &pub_socket, &actions);
+                    false
+                },
+                CONFIG_RELOADED_PREFIX => {
+                    // Pick up the new settings of our actions.
+                    info!("{}", format!("{}", Errors::EventProcessing(self.name.clone(), "ConfigReloadedEvent")));
+                    actions.reload("image_recv_plugin");
                     false
                 },
                 PLUGIN_TERMINATE_PREFIX => {
@@ -86,7 +96,9 @@ impl Plugin for ImageReceivePlugin {
                     // This should only happen for valid events to which we are not subscribed.
                     // Completely invalid event prefixes are detected above in check_event_prefix().
                     let pre = format!("{:?}", unexpected);
-                    error!("{}", Errors::EventNotHandledError(self.name.clone(), pre));
+                    let err = Errors::EventNotHandledError(self.name.clone(), pre);
+                    error!("{}", err);
+                    dead_letter::capture(&self.name, DeadLetterReason::NotHandled, &err, ev_in.raw_bytes);
                     false
                 }
             };
@@ -107,6 +119,7 @@ impl Plugin for ImageReceivePlugin {
     fn get_subscriptions(&self) -> Result<Vec<Box<dyn EventType>>, EngineError> {
         Ok(vec![
             Box::new(events::NewImageEvent::new(Uuid::new_v4(), String::from("fake"), vec![])),
+            Box::new(events::ConfigReloadedEvent::new("fake".to_string(), vec![], vec![])),
             Box::new(events::PluginTerminateEvent::new(Uuid::new_v4(), String::from("*"))),
         ])
     }
@@ -116,6 +129,12 @@ impl Plugin for ImageReceivePlugin {
     fn get_id(&self) -> Uuid {self.id}
 }
 
+impl ActionContext for ImageReceivePlugin {
+    fn get_plugin_name(&self) -> &str {"image_recv_plugin"}
+    fn get_config(&self) -> &Config {&self.runctx.parms.config}
+    fn as_any(&self) -> &dyn Any {self}
+}
+
 impl ImageReceivePlugin {
 
     // ---------------------------------------------------------------------------
@@ -126,6 +145,7 @@ impl ImageReceivePlugin {
             name: "ImageReceivePlugin".to_string(),
             id: Uuid::new_v4(),
             runctx,
+            image_ref_dirs: ImageRefDirs::new_from_config(&runctx.parms.config),
         }
     }
 
@@ -137,8 +157,8 @@ impl ImageReceivePlugin {
     // ---------------------------------------------------------------------------
     // send_event:
     // ---------------------------------------------------------------------------
-    fn send_event(&self, event: gen_events::Event, pub_socket: &Socket, 
-                  action: fn(&ImageReceivePlugin, &gen_events::NewImageEvent) -> bool) {
+    fn send_event(&self, event: gen_events::Event, raw_bytes: &[u8], pub_socket: &Socket, 
+                  actions: &ActionChain) {
         // Extract the image uuid from the new image event.
         let new_image_event = match event.event_as_new_image_event() {
             Some(ev) => ev,
@@ -171,33 +191,64 @@ impl ImageReceivePlugin {
             }
         };
 
+        // Once we know the image, each failure is also published as an error event.
+        let report = |stage: &str, err: &Errors| {
+            traps_utils::send_image_processing_error_event(&self.name, uuid, stage, err, event, pub_socket);
+        };
+
+        // Images that arrive once the application starts shutting down are abandoned.
+        if shutdown::is_draining() {
+            let err = Errors::ShutdownImageRejected(self.get_name(), uuid_str.to_string());
+            warn!("{}", err);
+            report(traps_utils::STAGE_SHUTDOWN, &err);
+            return
+        }
+
         let image_format = match new_image_event.image_format() {
             Some(s) => s,
             None => {
                 // Log the error and just return.
-                let msg = format!("{}", Errors::PluginEventAccessUuidError(
-                                          self.get_name(), "NewImageEvent".to_string()));
+                let err = Errors::PluginEventAccessUuidError(self.get_name(), "NewImageEvent".to_string());
+                error!("{}", err);
+                report(traps_utils::STAGE_READ_EVENT, &err);
+                return
+            }
+        };
+
+        // Get the image, which is either embedded in the event or referenced by it.
+        let image = match image_ref::resolve_image(&new_image_event, &self.image_ref_dirs) {
+            Ok(image) => image,
+            Err(e) => {
+                // Log the error and just return.
+                error!("{}", e);
+                let msg = format!("{}", Errors::ActionNoImageError(
+                                          self.get_name(), "image resolution".to_string(), "NewImageEvent".to_string()));
                 error!("{}", msg);
+                report(traps_utils::STAGE_RESOLVE_IMAGE, &e);
                 return
             }
         };
 
-        // Execute the action function.  False is returned by actions if they are 
-        // unable to complete their tasks and processing for this event should abort. 
-        if !action(self, &new_image_event) {
+        // Execute the action chain.  Actions return an error if they are unable
+        // to complete their tasks and processing for this event should abort.
+        if let Err(e) = actions.run(&ActionInput {event: raw_bytes, image: Some(&image), context: self}) {
             let msg = format!("{}", Errors::PluginEventActionError(
                                       self.get_name(), "NewImageEvent".to_string(), uuid_str.to_string()));
             error!("{}", msg);
+            report(traps_utils::STAGE_ACTION, &e);
             return
         }
 
         // Create the image received event and serialize it.
-        let ev = events::ImageReceivedEvent::new(uuid, image_format.to_string());
+        let mut ev = events::ImageReceivedEvent::new(uuid, image_format.to_string());
+        ev.set_trace_ids(traps_utils::response_trace_ids(&self.name, event));
         let bytes = match ev.to_bytes() {
             Ok(v) => v,
             Err(e) => {
                 // Log the error and just return.
-                error!("{}", e.to_string());
+                let err = Errors::EventToBytesError(self.get_name(), ev.get_name(), e.to_string());
+                error!("{}", err);
+                report(traps_utils::STAGE_PUBLISH, &err);
                 return
             } 
         };
@@ -208,8 +259,9 @@ impl ImageReceivePlugin {
             Ok(_) => (),
             Err(e) => {
                 // Log the error and return if we can't send the message.
-                let msg = format!("{}", Errors::SocketSendError(self.get_name(), ev.get_name(), e.to_string()));
-                error!("{}", msg);
+                let err = Errors::SocketSendError(self.get_name(), ev.get_name(), e.to_string());
+                error!("{}", err);
+                report(traps_utils::STAGE_PUBLISH, &err);
             }
         };
     }
@@ -1,3 +1,4 @@
+use std::any::Any;
 use uuid::Uuid;
 use zmq::Socket;
 use rand::Rng;
@@ -7,9 +8,10 @@ use event_engine::events::EventType;
 use event_engine::events::Event;
 use crate::events_generated::gen_events;
 use crate::{events, config::errors::Errors};
-use crate::{traps_utils, RuntimeCtx};
-use crate::events::{IMAGE_RECEIVED_PREFIX, PLUGIN_TERMINATE_PREFIX};
-use crate::plugins::actions::image_score_actions::select_action;
+use crate::{traps_utils, Config, RuntimeCtx};
+use crate::dead_letter::{self, DeadLetterReason};
+use crate::events::{CONFIG_RELOADED_PREFIX, IMAGE_RECEIVED_PREFIX, PLUGIN_TERMINATE_PREFIX, Traceable};
+use crate::plugins::actions::{ActionChain, ActionContext, ActionInput};
 
 use log::{info, error, debug};
 
@@ -36,9 +38,8 @@ impl Plugin for ImageScorePlugin {
         // Announce our arrival.
         info!("{}", format!("{}", Errors::PluginStarted(self.name.clone(), self.get_id().hyphenated().to_string())));
 
-        // Get this plugin's required action function pointer.
-        #[allow(unused_variables)]
-        let action = match select_action(&self.runctx.parms.config) {
+        // Get this plugin's chain of actions.
+        let mut actions = match ActionChain::select(&self.runctx.parms.config, "image_score_plugin", "ImageScorePlugin") {
             Ok(a) => a,
             Err(e) => {
                 return Err(EngineError::PluginExecutionError(self.name.clone(), 
@@ -74,7 +75,13 @@ impl Plugin for ImageScorePlugin {
             let terminate = match ev_in.prefix_array {
                 IMAGE_RECEIVED_PREFIX => {
                     debug!("\n  -> {} received event {}", self.name, String::from("ImageReceivedEvent"));
-                    self.send_event(ev_in.gen_event, &pub_socket);
+                    self.send_event(ev_in.gen_event, ev_in.raw_bytes, &pub_socket, &actions);
+                    false
+                },
+                CONFIG_RELOADED_PREFIX => {
+                    // Pick up the new settings of our actions.
+                    info!("{}", format!("{}", Errors::EventProcessing(self.name.clone(), "ConfigReloadedEvent")));
+                    actions.reload("image_score_plugin");
                     false
                 },
                 PLUGIN_TERMINATE_PREFIX => {
@@ -87,7 +94,9 @@ impl Plugin for ImageScorePlugin {
                     // This should only happen for valid events to which we are not subscribed.
                     // Completely invalid event prefixes are detected above in check_event_prefix().
                     let pre = format!("{:?}", unexpected);
-                    error!("{}", Errors::EventNotHandledError(self.name.clone(), pre));
+                    let err = Errors::EventNotHandledError(self.name.clone(), pre);
+                    error!("{}", err);
+                    dead_letter::capture(&self.name, DeadLetterReason::NotHandled, &err, ev_in.raw_bytes);
                     false
                 }
             };
@@ -108,6 +117,7 @@ impl Plugin for ImageScorePlugin {
     fn get_subscriptions(&self) -> Result<Vec<Box<dyn EventType>>, EngineError> {
         Ok(vec![
             Box::new(events::ImageReceivedEvent::new(Uuid::new_v4(), "fake".to_string())),
+            Box::new(events::ConfigReloadedEvent::new("fake".to_string(), vec![], vec![])),
             Box::new(events::PluginTerminateEvent::new(Uuid::new_v4(), String::from("*"))),
         ])
     }
@@ -117,6 +127,12 @@ impl Plugin for ImageScorePlugin {
     fn get_id(&self) -> Uuid {self.id}
 }
 
+impl ActionContext for ImageScorePlugin {
+    fn get_plugin_name(&self) -> &str {"image_score_plugin"}
+    fn get_config(&self) -> &Config {&self.runctx.parms.config}
+    fn as_any(&self) -> &dyn Any {self}
+}
+
 impl ImageScorePlugin {
     // ---------------------------------------------------------------------------
     // new:
@@ -138,7 +154,8 @@ impl ImageScorePlugin {
     // ---------------------------------------------------------------------------
     // send_event:
     // ---------------------------------------------------------------------------
-    fn send_event(&self, event: gen_events::Event, pub_socket: &Socket) {
+    fn send_event(&self, event: gen_events::Event, raw_bytes: &[u8], pub_socket: &Socket,
+                  actions: &ActionChain) {
         // Extract the image uuid from the new image event.
         let image_recv_event = match event.event_as_image_received_event() {
             Some(ev) => ev,
@@ -165,33 +182,50 @@ impl ImageScorePlugin {
             }
         };
 
+        // Once we know the image, each failure is also published as an error event.
+        let report = |stage: &str, err: &Errors| {
+            traps_utils::send_image_processing_error_event(&self.name, uuid, stage, err, event, pub_socket);
+        };
+
         let image_format = match image_recv_event.image_format() {
             Some(s) => s,
             None => {
                 // Log the error and just return.
-                let msg = format!("{}", Errors::PluginEventAccessUuidError(
-                                          self.get_name(), "ImageReceivedEvent".to_string()));
-                error!("{}", msg);
+                let err = Errors::PluginEventAccessUuidError(self.get_name(), "ImageReceivedEvent".to_string());
+                error!("{}", err);
+                report(traps_utils::STAGE_READ_EVENT, &err);
                 return
             }
         };
 
+        // Execute the action chain and abort the image on error.
+        if let Err(e) = actions.run(&ActionInput {event: raw_bytes, image: None, context: self}) {
+            let msg = format!("{}", Errors::PluginEventActionError(
+                                      self.get_name(), "ImageReceivedEvent".to_string(), uuid_str.to_string()));
+            error!("{}", msg);
+            report(traps_utils::STAGE_ACTION, &e);
+            return
+        }
+
         // *** SIMULATED SCORING - USE PYTHON SCORING PLUGIN FOR REAL SCORING ***
         // 
         // Create the image label with a random score and put it in a vector.
         let mut rng = rand::thread_rng();
         let prob = rng.gen_range(0.0..=1.0); // inclusive range
         let image_label = 
-            events::ImageLabelScore::new(uuid, "cow".to_string(), prob);
+            events::ImageLabelScore::new("cow".to_string(), prob);
         let labels = vec![image_label];
 
         // Create the image received event and serialize it.
-        let ev = events::ImageScoredEvent::new(uuid, image_format.to_string(), labels);
+        let mut ev = events::ImageScoredEvent::new(uuid, image_format.to_string(), labels);
+        ev.set_trace_ids(traps_utils::response_trace_ids(&self.name, event));
         let bytes = match ev.to_bytes() {
             Ok(v) => v,
             Err(e) => {
                 // Log the error and just return.
-                error!("{}", e.to_string());
+                let err = Errors::EventToBytesError(self.get_name(), ev.get_name(), e.to_string());
+                error!("{}", err);
+                report(traps_utils::STAGE_PUBLISH, &err);
                 return
             } 
         };
@@ -203,8 +237,9 @@ impl ImageScorePlugin {
             Err(e) => {
                 // Log the error and abort if we can't send our start up message.
                 //let msg = format!("{}", Errors::SocketSendError(plugin.get_name().clone(), ev.get_name(), e.to_string()));
-                let msg = format!("{}", Errors::SocketSendError(self.get_name(), ev.get_name(), e.to_string()));
-                error!("{}", msg);
+                let err = Errors::SocketSendError(self.get_name(), ev.get_name(), e.to_string());
+                error!("{}", err);
+                report(traps_utils::STAGE_PUBLISH, &err);
             }
         };
     }
@@ -3,6 +3,8 @@ use uuid::Uuid;
 use zmq::Socket;
 use serde::Deserialize;
 use std::{env, fs};
+use std::any::Any;
+use std::sync::{RwLock, RwLockReadGuard};
 use anyhow::{Result, anyhow};
 use event_engine::{plugins::Plugin};
 use event_engine::errors::EngineError;
@@ -10,11 +12,12 @@ use event_engine::events::EventType;
 use event_engine::events::Event;
 use crate::events_generated::gen_events;
 use crate::{events, config::errors::Errors};
-use crate::{traps_utils, RuntimeCtx};
-#[allow(unused_imports)]
+use crate::{reload, traps_utils, RuntimeCtx};
+use crate::dead_letter::{self, DeadLetterReason};
 use crate::Config;
-use crate::events::{IMAGE_SCORED_PREFIX, PLUGIN_TERMINATE_PREFIX};
-use crate::plugins::actions::image_store_actions::select_action;
+use crate::config::config::ImageStoreConfig;
+use crate::events::{CONFIG_RELOADED_PREFIX, IMAGE_SCORED_PREFIX, PLUGIN_TERMINATE_PREFIX, Traceable};
+use crate::plugins::actions::{ActionChain, ActionContext, ActionInput, ActionOutcome};
 
 use log::{info, error, debug};
 
@@ -22,31 +25,24 @@ use log::{info, error, debug};
 //                                Constants
 // ***************************************************************************
 // Constants.
-#[allow(dead_code)]
 const ENV_CONFIG_FILE_KEY : &str = "TRAPS_IMAGE_STORE_FILE";
-#[allow(dead_code)]
 const DEFAULT_CONFIG_FILE : &str = "~/traps-image-store.toml";
 
+// The name of this plugin's [plugins.config] table.
+pub const CONFIG_NAME : &str = "image_store_plugin";
+
 // ***************************************************************************
 //                            Structs and Enums
 // ***************************************************************************
-#[allow(dead_code)]
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub enum StoreAction {
-    Delete,
-    ErrorOut,
-    Noop,
-    ReduceSave,
-    Save,
-}
+pub use crate::action_registry::StoreAction;
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct StoreParms {
     pub config_file: String,
     pub config: StoreConfig,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct StoreConfig {
     pub action_thresholds: Vec<(f32, StoreAction)>,
 }
@@ -60,6 +56,8 @@ pub struct ImageStorePlugin {
     name: String,
     id: Uuid,
     runctx: &'static RuntimeCtx,
+    config: ImageStoreConfig,
+    store_parms: RwLock<StoreParms>,
 }
 
 // ***************************************************************************
@@ -91,10 +89,11 @@ impl Plugin for ImageStorePlugin {
                                                              e.to_string()));
             }
         };
+        info!("{}", Errors::StoreThresholdsRead(self.name.clone(), store_parms.config_file.clone()));
+        self.set_store_parms(store_parms);
 
-        // Get this plugin's required action function pointer.
-        #[allow(unused_variables)]
-        let action = match select_action(&self.runctx.parms.config) {
+        // Get this plugin's chain of actions.
+        let mut actions = match ActionChain::select(&self.runctx.parms.config, "image_store_plugin", "ImageStorePlugin") {
             Ok(a) => a,
             Err(e) => {
                 return Err(EngineError::PluginExecutionError(self.name.clone(), 
@@ -130,7 +129,16 @@ impl Plugin for ImageStorePlugin {
             let terminate = match ev_in.prefix_array {
                 IMAGE_SCORED_PREFIX => {
                     debug!("\n  -> {} received event {}", self.name, String::from("ImageScoredEvent"));
-                    self.send_event(ev_in.gen_event, &pub_socket, action, &store_parms);
+                    self.send_event(ev_in.gen_event, ev_in.raw_bytes, &pub_socket, &actions);
+                    false
+                },
+                CONFIG_RELOADED_PREFIX => {
+                    // Pick up the new settings of our actions.
+                    info!("{}", format!("{}", Errors::EventProcessing(self.name.clone(), "ConfigReloadedEvent")));
+                    actions.reload("image_store_plugin");
+                    if let Some(parms) = self.reload_store_parms() {
+                        self.set_store_parms(parms);
+                    }
                     false
                 },
                 PLUGIN_TERMINATE_PREFIX => {
@@ -143,7 +151,9 @@ impl Plugin for ImageStorePlugin {
                     // This should only happen for valid events to which we are not subscribed.
                     // Completely invalid event prefixes are detected above in check_event_prefix().
                     let pre = format!("{:?}", unexpected);
-                    error!("{}", Errors::EventNotHandledError(self.name.clone(), pre));
+                    let err = Errors::EventNotHandledError(self.name.clone(), pre);
+                    error!("{}", err);
+                    dead_letter::capture(&self.name, DeadLetterReason::NotHandled, &err, ev_in.raw_bytes);
                     false
                 }
             };
@@ -164,6 +174,7 @@ impl Plugin for ImageStorePlugin {
     fn get_subscriptions(&self) -> Result<Vec<Box<dyn EventType>>, EngineError> {
         Ok(vec![
             Box::new(events::ImageScoredEvent::new(Uuid::new_v4(), "fake".to_string(), vec![])),
+            Box::new(events::ConfigReloadedEvent::new("fake".to_string(), vec![], vec![])),
             Box::new(events::PluginTerminateEvent::new(Uuid::new_v4(), String::from("*"))),
         ])
     }
@@ -173,16 +184,25 @@ impl Plugin for ImageStorePlugin {
     fn get_id(&self) -> Uuid {self.id}
 }
 
+impl ActionContext for ImageStorePlugin {
+    fn get_plugin_name(&self) -> &str {CONFIG_NAME}
+    fn get_config(&self) -> &Config {&self.runctx.parms.config}
+    fn as_any(&self) -> &dyn Any {self}
+}
+
 impl ImageStorePlugin {
     // ---------------------------------------------------------------------------
     // new:
     // ---------------------------------------------------------------------------
-    pub fn new(runctx: &'static RuntimeCtx) -> Self {
-        ImageStorePlugin {
+    /** Create the plugin with its [plugins.config.image_store_plugin] table. */
+    pub fn new(runctx: &'static RuntimeCtx) -> Result<Self, Errors> {
+        Result::Ok(ImageStorePlugin {
             name: "ImageStorePlugin".to_string(),
             id: Uuid::new_v4(),
             runctx,
-        }
+            config: runctx.parms.config.get_plugin_config(CONFIG_NAME)?,
+            store_parms: RwLock::new(StoreParms::default()),
+        })
     }
 
     // ---------------------------------------------------------------------------
@@ -194,9 +214,8 @@ impl ImageStorePlugin {
     // ---------------------------------------------------------------------------
     // send_event:
     // ---------------------------------------------------------------------------
-    fn send_event(&self, event: gen_events::Event, pub_socket: &Socket,
-                  action: fn(&ImageStorePlugin, &gen_events::ImageScoredEvent, &StoreParms)->StoreAction,
-                  store_parms_ref: &StoreParms) {
+    fn send_event(&self, event: gen_events::Event, raw_bytes: &[u8], pub_socket: &Socket,
+                  actions: &ActionChain) {
         // Extract the image uuid from the new image event.
         let image_scored_event = match event.event_as_image_scored_event() {
             Some(ev) => ev,
@@ -223,12 +242,19 @@ impl ImageStorePlugin {
             }
         };
 
+        // Once we know the image, each failure is also published as an error event.
+        let report = |stage: &str, err: &Errors| {
+            traps_utils::send_image_processing_error_event(&self.name, uuid, stage, err, event, pub_socket);
+        };
+
         // Determine the destination based on the first score.
         let labels= match image_scored_event.scores() {
             Some(v) => v,
             None => {
                 // Log the error and just return.
-                error!("{}", "uuid access error".to_string());
+                let err = Errors::EventReadFlatbuffer("scores".to_string());
+                error!("{}", err);
+                report(traps_utils::STAGE_READ_EVENT, &err);
                 return
             }
         };
@@ -237,40 +263,53 @@ impl ImageStorePlugin {
             Some(s) => s,
             None => {
                 // Log the error and just return.
-                let msg = format!("{}", Errors::PluginEventAccessUuidError(
-                                          self.get_name(), "ImageScoredEvent".to_string()));
-                error!("{}", msg);
+                let err = Errors::PluginEventAccessUuidError(self.get_name(), "ImageScoredEvent".to_string());
+                error!("{}", err);
+                report(traps_utils::STAGE_READ_EVENT, &err);
                 return
             }
         };
 
         // Make sure we got at least one score.
         if labels.is_empty() {
-            error!("{}", "No scores received".to_string());
+            let err = Errors::EventReceivedEmptyList("ImageScoredEvent".to_string(), "scores".to_string());
+            error!("{}", err);
+            report(traps_utils::STAGE_READ_EVENT, &err);
             return
         }
 
-        // Execute the action function and abort image on error.
-        let action_taken = action(self, &image_scored_event, store_parms_ref);
-        if action_taken == StoreAction::ErrorOut {
-            let msg = format!("{}", Errors::PluginEventActionError(
-                                      self.get_name(), "NewImageEvent".to_string(), uuid_str.to_string()));
-            error!("{}", msg);
-            return
-        }
+        // Execute the action chain and abort image on error.  The store action
+        // taken, and where it stored the image, is the last one other than Noop.
+        let input = ActionInput {event: raw_bytes, image: None, context: self};
+        let (action_taken, location) = match actions.run(&input) {
+            Ok(outcomes) => outcomes.into_iter().rev().find_map(|o| match o {
+                                ActionOutcome::Stored(StoreAction::Noop, _) | ActionOutcome::Done => None,
+                                ActionOutcome::Stored(taken, location) => Some((taken, location)),
+                            }).unwrap_or((StoreAction::Noop, None)),
+            Err(err) => {
+                report(traps_utils::STAGE_ACTION, &err);
+                return
+            }
+        };
 
         // Did we decide to delete or store this image?
+        let trace_ids = traps_utils::response_trace_ids(&self.name, event);
         let ev: Box<dyn Event>;
         let ev_name: &str;
         if action_taken == StoreAction::Delete {
             // Send an image delete event.
             ev_name = "ImageDeletedEvent";
-            ev = Box::new(events::ImageDeletedEvent::new(uuid, image_format.to_string()));
+            let mut del_ev = events::ImageDeletedEvent::new(uuid, image_format.to_string());
+            del_ev.set_trace_ids(trace_ids);
+            ev = Box::new(del_ev);
         } else {
             // Create the image stored event and serialize it.
             let dest = format!("{:?}", action_taken);
             ev_name = "ImageStoredEvent";
-            ev = Box::new(events::ImageStoredEvent::new(uuid, image_format.to_lowercase().to_string(), dest));
+            let mut store_ev = events::ImageStoredEvent::new(uuid, image_format.to_lowercase().to_string(), dest);
+            store_ev.set_location(location);
+            store_ev.set_trace_ids(trace_ids);
+            ev = Box::new(store_ev);
         }
 
         // Convert to a byte stream.
@@ -278,7 +317,9 @@ impl ImageStorePlugin {
             Ok(v) => v,
             Err(e) => {
                 // Log the error and just return.
-                error!("{}", e.to_string());
+                let err = Errors::EventToBytesError(self.get_name(), ev_name.to_string(), e.to_string());
+                error!("{}", err);
+                report(traps_utils::STAGE_PUBLISH, &err);
                 return
             } 
         };
@@ -289,28 +330,70 @@ impl ImageStorePlugin {
             Ok(_) => (),
             Err(e) => {
                 // Log the error and abort if we can't send our start up message.
-                let msg = format!("{}", Errors::SocketSendError(self.get_name(), ev_name.to_string(), e.to_string()));
-                error!("{}", msg);
+                let err = Errors::SocketSendError(self.get_name(), ev_name.to_string(), e.to_string());
+                error!("{}", err);
+                report(traps_utils::STAGE_PUBLISH, &err);
             }
         };
     }
 
+    // ---------------------------------------------------------------------------
+    // get_store_parms:
+    // ---------------------------------------------------------------------------
+    /** The store thresholds in effect, which the store actions use. */
+    pub fn get_store_parms(&self) -> RwLockReadGuard<'_, StoreParms> {
+        self.store_parms.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // ---------------------------------------------------------------------------
+    // set_store_parms:
+    // ---------------------------------------------------------------------------
+    fn set_store_parms(&self, parms: StoreParms) {
+        *self.store_parms.write().unwrap_or_else(|e| e.into_inner()) = parms;
+    }
+
     // ---------------------------------------------------------------------------
     // init_store_parms:
     // ---------------------------------------------------------------------------
-    /** Retrieve the application parameters from the configuration file specified
-     * either through an environment variable or as the first (and only) command
-     * line argument.  If neither are provided, an attempt is made to use the
-     * default file path.
+    /** Retrieve the store thresholds from the plugin's configuration table or,
+     * if the table doesn't assign them, from the image store configuration file.
      */
     pub fn init_store_parms(&self) -> Result<StoreParms> {
-        // Get the config file path from the environment, command line or default.
-        let config_file = env::var(ENV_CONFIG_FILE_KEY).unwrap_or_else(|_| DEFAULT_CONFIG_FILE.to_string());
+        get_store_parms(&self.config, &self.runctx.parms.config_file)
+    }
 
-        // Read the cofiguration file.
-        let config_file_abs = traps_utils::get_absolute_path(&config_file);
-        // println!("{}", Errors::ReadingConfigFile(config_file_abs.clone()));
-        let contents = match fs::read_to_string(&config_file_abs) {
+    // ---------------------------------------------------------------------------
+    // reload_store_parms:
+    // ---------------------------------------------------------------------------
+    /** Retrieve the store thresholds from the configuration in effect after a
+     * reload.  None is returned, and the current thresholds are kept, if they
+     * can't be read.
+     */
+    fn reload_store_parms(&self) -> Option<StoreParms> {
+        let config = reload::get_config()?;
+        let result = config.get_plugin_config::<ImageStoreConfig>(CONFIG_NAME)
+            .map_err(|e| e.to_string())
+            .and_then(|c| get_store_parms(&c, &self.runctx.parms.config_file).map_err(|e| e.to_string()));
+        match result {
+            Ok(parms) => Some(parms),
+            Err(e) => {
+                error!("{}", Errors::ConfigReloadPluginError(self.name.clone(), e));
+                None
+            }
+        }
+    }
+
+}
+
+impl StoreConfig {
+    // ---------------------------------------------------------------------------
+    // new_from_file:
+    // ---------------------------------------------------------------------------
+    /** Read and parse an image store configuration file such as
+     * traps-image-store.toml.
+     */
+    pub fn new_from_file(config_file_abs: &str) -> Result<Self> {
+        let contents = match fs::read_to_string(config_file_abs) {
             Ok(c) => c,
             Err(e) => return Result::Err(anyhow!(e.to_string())),
         };
@@ -321,12 +404,24 @@ impl ImageStorePlugin {
             Err(e) => return Result::Err(anyhow!(e.to_string())),
         };
 
+        // Order the thresholds.
+        StoreConfig::new_from_thresholds(&raw_input.action_thresholds)
+    }
+
+    // ---------------------------------------------------------------------------
+    // new_from_thresholds:
+    // ---------------------------------------------------------------------------
+    /** Create the list of thresholds that get_action_for_score() searches from
+     * the action_thresholds table of the image store configuration.  The list is
+     * ordered from highest to lowest threshold and always ends with a 0.0 entry.
+     */
+    pub fn new_from_thresholds(action_thresholds: &BTreeMap<String, f32>) -> Result<Self> {
         // Create the mutable list into which we'll write the threshold tuples.
         let mut list: Vec<(f32, StoreAction)> = Vec::new();
-        if !raw_input.action_thresholds.is_empty() {
+        if !action_thresholds.is_empty() {
             // Iterator through all configured thresholds allowing for
             // some case insensitivity.
-            for entry in &raw_input.action_thresholds {
+            for entry in action_thresholds {
                 let act = match entry.0.as_str() {
                     "Delete"     => StoreAction::Delete,
                     "Save"       => StoreAction::Save,
@@ -375,23 +470,56 @@ impl ImageStorePlugin {
         // For some reason, clippy says (&b.0) is an unnecessary borrow.
         list.sort_by(|a, b| (b.0).partial_cmp(&a.0).expect("failed f32 compare!"));
 
-        // Convert the u8 element to f32 to match the scoring type.
+        // Copy the sorted elements into the result list.
         let mut listf32: Vec<(f32, StoreAction)> = Vec::new();
-        let it = list.iter().map(|cur| (cur.0 as f32, cur.1.clone()));
+        let it = list.iter().map(|cur| (cur.0, cur.1.clone()));
         for item in it {
             listf32.push(item);
         }
 
-        // Return a newly constructed storage parms object.
-        Result::Ok(StoreParms { config_file: config_file_abs, config: StoreConfig {action_thresholds: listf32} })
+        Result::Ok(StoreConfig {action_thresholds: listf32})
     }
+}
+
 
+// ---------------------------------------------------------------------------
+// get_store_parms:
+// ---------------------------------------------------------------------------
+/** Create the store parameters from the action_thresholds of the plugin's
+ * configuration table, which is part of the application configuration file.
+ * If the table doesn't assign thresholds, the image store configuration file
+ * specified either through an environment variable or the default file path
+ * is read instead.  The parameters record the file the thresholds came from.
+ */
+pub fn get_store_parms(store_config: &ImageStoreConfig, app_config_file: &str) -> Result<StoreParms> {
+    match &store_config.action_thresholds {
+        Some(thresholds) => {
+            let config = StoreConfig::new_from_thresholds(thresholds)?;
+            Result::Ok(StoreParms { config_file: app_config_file.to_string(), config })
+        },
+        None => {
+            let config_file_abs = get_store_config_file();
+            let config = StoreConfig::new_from_file(&config_file_abs)?;
+            Result::Ok(StoreParms { config_file: config_file_abs, config })
+        }
+    }
 }
 
+// ---------------------------------------------------------------------------
+// get_store_config_file:
+// ---------------------------------------------------------------------------
+/** The absolute path of the image store configuration file, which is specified
+ * through an environment variable or else is the default file path.
+ */
+pub fn get_store_config_file() -> String {
+    let config_file = env::var(ENV_CONFIG_FILE_KEY).unwrap_or_else(|_| DEFAULT_CONFIG_FILE.to_string());
+    traps_utils::get_absolute_path(&config_file)
+}
 
 #[cfg(test)]
 mod tests {
-    use crate::plugins::image_store_plugin::StoreAction;
+    use crate::plugins::image_store_plugin::{get_store_parms, StoreAction};
+    use crate::config::config::ImageStoreConfig;
     use crate::events::{ImageLabelScore, ImageScoredEvent};
     use uuid::Uuid;
     use serde_json;
@@ -444,14 +572,25 @@ mod tests {
         assert_eq!(&list[6], &(0.0,    StoreAction::Delete));
     }
 
+    #[test]
+    fn store_parms_from_config() {
+        // Thresholds in the plugin's table are used instead of the image store file.
+        let mut thresholds = std::collections::BTreeMap::new();
+        thresholds.insert("Save".to_string(), 0.8f32);
+        let store_config = ImageStoreConfig {action_thresholds: Some(thresholds)};
+        let parms = get_store_parms(&store_config, "/tmp/traps.toml").unwrap();
+        assert_eq!(parms.config_file, "/tmp/traps.toml");
+        assert_eq!(parms.config.action_thresholds, vec![(0.8, StoreAction::Save), (0.0, StoreAction::Delete)]);
+    }
+
     #[test]
     fn sertest() {
         // Image uuid.
         let uuid = Uuid::new_v4();
         
         // Create label vector.
-        let label1 = ImageLabelScore::new(uuid.clone(), "cow".to_string(), 0.8); 
-        let label2 = ImageLabelScore::new(uuid.clone(), "dog".to_string(), 0.3); 
+        let label1 = ImageLabelScore::new("cow".to_string(), 0.8); 
+        let label2 = ImageLabelScore::new("dog".to_string(), 0.3); 
         let labels:Vec<ImageLabelScore> = vec!(label1, label2);
 
         // Create the event.
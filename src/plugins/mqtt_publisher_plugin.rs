@@ -0,0 +1,582 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use rumqttc::{Client, Connection, MqttOptions, Packet, QoS};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+use zmq::Socket;
+use event_engine::plugins::Plugin;
+use event_engine::errors::EngineError;
+use event_engine::events::EventType;
+use crate::{events, config::errors::Errors};
+use crate::config::config::MqttPublisherConfig;
+use crate::{reload, traps_utils, RuntimeCtx};
+use crate::dead_letter::{self, DeadLetterReason};
+use crate::events::PLUGIN_TERMINATE_PREFIX;
+
+use log::{info, warn, error};
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+// Defaults used when the [plugins.config.mqtt_publisher_plugin] configuration does not specify a value.
+const DEFAULT_BROKER_HOST: &str = "localhost";
+const DEFAULT_BROKER_PORT: u16 = 1883;
+const DEFAULT_CLIENT_ID: &str = "camera-traps-mqtt-publisher";
+const DEFAULT_KEEP_ALIVE_SECS: u64 = 30;
+const DEFAULT_CAMERA_ID: &str = "camera-traps";
+const DEFAULT_EVENTS: [&str; 4] = ["DetectionEvent", "ImageStoredEvent", "PluginStartedEvent", "PluginTerminatingEvent"];
+const DEFAULT_TOPIC_TEMPLATE: &str = "camera-traps/{camera_id}/{event_type}";
+const DEFAULT_QOS: u8 = 1;
+const DEFAULT_RETAIN: bool = false;
+const DEFAULT_OFFLINE_QUEUE_SIZE: usize = 1000;
+
+// The name of this plugin's [plugins.config] table.
+const CONFIG_NAME: &str = "mqtt_publisher_plugin";
+
+// The number of requests the MQTT client buffers before try_publish fails.
+const CLIENT_CHANNEL_CAPACITY: usize = 64;
+
+// How long to wait for an event before retrying queued messages.
+const POLL_TIMEOUT_MILLIS: i64 = 1000;
+
+// How long the connection thread waits between attempts to reach the broker.
+const RECONNECT_DELAY_MILLIS: u64 = 1000;
+
+// Topic placeholders without a value in the event are replaced with this value.
+const MISSING_TOPIC_VALUE: &str = "unknown";
+
+// ***************************************************************************
+// STRUCTS
+// ***************************************************************************
+/** How messages for one event type are published. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct MqttEventSettings {
+    pub topic_template: String,
+    pub qos: QoS,
+    pub retain: bool,
+    pub fields: Option<Vec<String>>,
+}
+
+/** The publisher's runtime parameters, keyed by event type name. */
+#[derive(Debug)]
+pub struct MqttParms {
+    pub broker_host: String,
+    pub broker_port: u16,
+    pub client_id: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub keep_alive_secs: u64,
+    pub camera_id: String,
+    pub events: BTreeMap<String, MqttEventSettings>,
+    pub offline_queue_size: usize,
+}
+
+/** A message ready to be published. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct MqttMessage {
+    pub topic: String,
+    pub payload: Vec<u8>,
+    pub qos: QoS,
+    pub retain: bool,
+}
+
+/** Messages waiting for the broker to become reachable.  The queue is bounded
+ * and the oldest messages are dropped when it is full.
+ */
+#[derive(Debug)]
+pub struct OfflineQueue {
+    messages: VecDeque<MqttMessage>,
+    capacity: usize,
+}
+
+pub struct MqttPublisherPlugin {
+    name: String,
+    id: Uuid,
+    parms: MqttParms,
+}
+impl Plugin for MqttPublisherPlugin {
+    // ---------------------------------------------------------------------------
+    // start:
+    // ---------------------------------------------------------------------------
+    /// The entry point for the plugin. The engine will start the plugin in its own
+    /// thread and execute this function.  The pub_socket is used by the plugin to
+    /// publish new events.  The sub_socket is used by the plugin to get events
+    /// published by other plugins.
+    ///
+    /// The MQTT connection is driven by a separate thread that reconnects to the
+    /// broker after outages.  This plugin polls its subscription socket so that
+    /// messages queued during an outage are sent soon after the broker returns.
+    fn start(
+        &self,
+        pub_socket: Socket,
+        sub_socket: Socket,
+    ) -> Result<(), EngineError> {
+
+        // Announce our arrival.
+        info!("{}", format!("{}", Errors::PluginStarted(self.name.clone(), self.get_id().hyphenated().to_string())));
+
+        // Connect to the broker in the background.
+        let (client, connection) = Client::new(self.mqtt_options(), CLIENT_CHANNEL_CAPACITY);
+        let connected = Arc::new(AtomicBool::new(false));
+        let stopping = Arc::new(AtomicBool::new(false));
+        let broker = format!("{}:{}", self.parms.broker_host, self.parms.broker_port);
+        let (thread_connected, thread_stopping) = (connected.clone(), stopping.clone());
+        thread::spawn(move || drive_connection(connection, broker, thread_connected, thread_stopping));
+
+        // Send the plugin start up event.
+        match traps_utils::send_started_event(self, &pub_socket) {
+            Ok(_) => (),
+            Err(e) => return Err(e),
+        };
+
+        // Enter our infinite work loop.
+        let mut queue = OfflineQueue::new(self.parms.offline_queue_size);
+        loop {
+            // ----------------- Send Queued Messages -------------------------
+            self.flush_queue(&client, &connected, &mut queue);
+
+            // ----------------- Wait on the Next Event -----------------------
+            match sub_socket.poll(zmq::POLLIN, POLL_TIMEOUT_MILLIS) {
+                Ok(0) => continue,
+                Ok(_) => (),
+                Err(e) => {
+                    error!("{}", Errors::SocketRecvError(self.name.clone(), e.to_string()));
+                    continue;
+                }
+            }
+
+            // The bytes vector is an output parameter populated by the marshalling function
+            // with raw event bytes. The ev_in.gen_event field references these raw bytes
+            // so the bytes vector must be of a lifetime at least as long as ev_in.
+            let mut bytes: Vec<u8> = vec![];
+            let ev_in = match traps_utils::marshal_next_event(self, &sub_socket, &mut bytes) {
+                Some(ev) => ev,
+                None => continue,
+            };
+
+            // ----------------- Process Subscription Events ------------------
+            // Publish the configured events; log and disregard all others.
+            let info = events::get_event_info_by_prefix(ev_in.prefix_array);
+            match info.and_then(|i| self.parms.events.get(i.name).map(|s| (i, s))) {
+                // A reload removed this plugin from the configuration.
+                Some(_) if !reload::is_plugin_enabled(CONFIG_NAME) => (),
+                Some((info, settings)) => {
+                    match (info.gen_to_json)(ev_in.gen_event) {
+                        Ok(json) => {
+                            match build_message(&self.parms.camera_id, info.name, &json, settings) {
+                                Ok(msg) => self.publish(&client, &connected, &mut queue, msg),
+                                Err(e) => error!("{}", e),
+                            }
+                        },
+                        Err(e) => error!("{}", e),
+                    }
+                },
+                None if ev_in.prefix_array == PLUGIN_TERMINATE_PREFIX => (),
+                None => {
+                    // This should only happen for valid events to which we are not subscribed.
+                    // Completely invalid event prefixes are detected above in check_event_prefix().
+                    let pre = format!("{:?}", ev_in.prefix_array);
+                    let err = Errors::EventNotHandledError(self.name.clone(), pre);
+                    error!("{}", err);
+                    dead_letter::capture(&self.name, DeadLetterReason::NotHandled, &err, ev_in.raw_bytes);
+                },
+            }
+
+            // Determine whether we are the target of a terminate event.
+            if ev_in.prefix_array == PLUGIN_TERMINATE_PREFIX {
+                info!("\n  -> {} received event {}", self.name, String::from("PluginTerminateEvent"));
+                if traps_utils::process_plugin_terminate_event(ev_in.gen_event, &self.id, &self.name) {
+                    break;
+                }
+            }
+        }
+
+        // Hand any queued messages to the client before disconnecting.
+        self.flush_queue(&client, &connected, &mut queue);
+        if !queue.is_empty() {
+            warn!("{}", Errors::MqttPublishError(format!("{} queued topics", queue.len()),
+                                                 "the broker was unreachable at shutdown".to_string()));
+        }
+        stopping.store(true, Ordering::SeqCst);
+        let _ = client.disconnect();
+
+        // Clean up and send the terminating event.
+        traps_utils::send_terminating_event(&self.name, self.id, &pub_socket);
+
+        // Shutting down.
+        Ok(())
+    }
+
+    /// Return the event subscriptions, which are the configured event types and
+    /// the PluginTerminateEvent.
+    fn get_subscriptions(&self) -> Result<Vec<Box<dyn EventType>>, EngineError> {
+        let mut subscriptions: Vec<Box<dyn EventType>> = vec![];
+        for name in self.parms.events.keys() {
+            match events::get_event_info_by_name(name) {
+                Some(info) => subscriptions.push(info.get_subscription()),
+                None => return Err(EngineError::PluginExecutionError(self.name.clone(),
+                                        self.get_id().hyphenated().to_string(),
+                                        Errors::MqttInvalidConfig(format!("unknown event type {}", name)).to_string())),
+            }
+        }
+        if !self.parms.events.contains_key("PluginTerminateEvent") {
+            subscriptions.push(Box::new(events::PluginTerminateEvent::new(Uuid::new_v4(), String::from("*"))));
+        }
+        Ok(subscriptions)
+    }
+
+    /// Simple accessors for this plugin.
+    fn get_name(&self) -> String {self.name.clone()}
+    fn get_id(&self) -> Uuid {self.id}
+}
+
+impl MqttPublisherPlugin {
+    // ---------------------------------------------------------------------------
+    // new:
+    // ---------------------------------------------------------------------------
+    /** Create the plugin with its [plugins.config.mqtt_publisher_plugin] table,
+     * failing if the configuration is invalid.
+     */
+    pub fn new(runctx: &'static RuntimeCtx) -> Result<Self, Errors> {
+        let config = &runctx.parms.config;
+        let config = config.get_plugin_config(CONFIG_NAME)?;
+        Result::Ok(MqttPublisherPlugin {
+            name: "MqttPublisherPlugin".to_string(),
+            id: Uuid::new_v4(),
+            parms: MqttParms::new_from_config(&config)?,
+        })
+    }
+
+    // ---------------------------------------------------------------------------
+    // mqtt_options:
+    // ---------------------------------------------------------------------------
+    fn mqtt_options(&self) -> MqttOptions {
+        let mut options = MqttOptions::new(self.parms.client_id.clone(), self.parms.broker_host.clone(),
+                                           self.parms.broker_port);
+        options.set_keep_alive(Duration::from_secs(self.parms.keep_alive_secs));
+        if let Some(username) = &self.parms.username {
+            options.set_credentials(username.clone(), self.parms.password.clone().unwrap_or_default());
+        }
+        options
+    }
+
+    // ---------------------------------------------------------------------------
+    // publish:
+    // ---------------------------------------------------------------------------
+    /** Publish the message if the broker is connected and nothing is queued ahead
+     * of it, otherwise queue it.
+     */
+    fn publish(&self, client: &Client, connected: &AtomicBool, queue: &mut OfflineQueue, msg: MqttMessage) {
+        if connected.load(Ordering::SeqCst) && queue.is_empty() {
+            match client.try_publish(msg.topic.clone(), msg.qos, msg.retain, msg.payload.clone()) {
+                Ok(_) => return,
+                Err(e) => warn!("{}", Errors::MqttPublishError(msg.topic.clone(), e.to_string())),
+            }
+        }
+        if let Some(dropped) = queue.push(msg) {
+            warn!("{}", Errors::MqttQueueFull(queue.capacity, dropped.topic));
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // flush_queue:
+    // ---------------------------------------------------------------------------
+    /** Publish queued messages in order while the broker is connected. */
+    fn flush_queue(&self, client: &Client, connected: &AtomicBool, queue: &mut OfflineQueue) {
+        while connected.load(Ordering::SeqCst) {
+            let msg = match queue.pop() {
+                Some(m) => m,
+                None => return,
+            };
+            if let Err(e) = client.try_publish(msg.topic.clone(), msg.qos, msg.retain, msg.payload.clone()) {
+                warn!("{}", Errors::MqttPublishError(msg.topic.clone(), e.to_string()));
+                queue.requeue(msg);
+                return;
+            }
+        }
+    }
+}
+
+impl MqttParms {
+    // ---------------------------------------------------------------------------
+    // new_from_config:
+    // ---------------------------------------------------------------------------
+    /** Apply the defaults to the configuration and validate it. */
+    pub fn new_from_config(config: &MqttPublisherConfig) -> Result<Self, Errors> {
+        let default_names: Vec<String> = DEFAULT_EVENTS.iter().map(|s| s.to_string()).collect();
+        let names = config.events.clone().unwrap_or(default_names);
+        let overrides = config.overrides.clone().unwrap_or_default();
+        for name in overrides.keys() {
+            if !names.contains(name) {
+                return Result::Err(Errors::MqttInvalidConfig(
+                    format!("overrides are configured for event type {} which is not in the events list", name)));
+            }
+        }
+
+        // Each event type starts with the default settings.
+        let default_template = config.topic_template.clone().unwrap_or(DEFAULT_TOPIC_TEMPLATE.to_string());
+        let default_qos = config.qos.unwrap_or(DEFAULT_QOS);
+        let default_retain = config.retain.unwrap_or(DEFAULT_RETAIN);
+        let mut events = BTreeMap::new();
+        for name in names {
+            if events::get_event_info_by_name(&name).is_none() {
+                return Result::Err(Errors::MqttInvalidConfig(format!("unknown event type {}", name)));
+            }
+            let o = overrides.get(&name).cloned().unwrap_or_default();
+            let settings = MqttEventSettings {
+                topic_template: o.topic_template.unwrap_or(default_template.clone()),
+                qos: parse_qos(o.qos.unwrap_or(default_qos))?,
+                retain: o.retain.unwrap_or(default_retain),
+                fields: o.fields,
+            };
+            events.insert(name, settings);
+        }
+
+        Result::Ok(MqttParms {
+            broker_host: config.broker_host.clone().unwrap_or(DEFAULT_BROKER_HOST.to_string()),
+            broker_port: config.broker_port.unwrap_or(DEFAULT_BROKER_PORT),
+            client_id: config.client_id.clone().unwrap_or(DEFAULT_CLIENT_ID.to_string()),
+            username: config.username.clone(),
+            password: config.password.clone(),
+            keep_alive_secs: config.keep_alive_secs.unwrap_or(DEFAULT_KEEP_ALIVE_SECS),
+            camera_id: config.camera_id.clone().unwrap_or(DEFAULT_CAMERA_ID.to_string()),
+            events,
+            offline_queue_size: config.offline_queue_size.unwrap_or(DEFAULT_OFFLINE_QUEUE_SIZE),
+        })
+    }
+}
+
+impl OfflineQueue {
+    // ---------------------------------------------------------------------------
+    // new:
+    // ---------------------------------------------------------------------------
+    pub fn new(capacity: usize) -> Self {
+        OfflineQueue { messages: VecDeque::new(), capacity }
+    }
+
+    // ---------------------------------------------------------------------------
+    // push:
+    // ---------------------------------------------------------------------------
+    /** Add a message to the end of the queue and return the message dropped to
+     * make room for it, if any.  A queue with no capacity drops every message.
+     */
+    pub fn push(&mut self, msg: MqttMessage) -> Option<MqttMessage> {
+        if self.capacity == 0 {
+            return Some(msg);
+        }
+        let dropped = if self.messages.len() >= self.capacity {self.messages.pop_front()} else {None};
+        self.messages.push_back(msg);
+        dropped
+    }
+
+    // ---------------------------------------------------------------------------
+    // pop:
+    // ---------------------------------------------------------------------------
+    pub fn pop(&mut self) -> Option<MqttMessage> {
+        self.messages.pop_front()
+    }
+
+    // ---------------------------------------------------------------------------
+    // requeue:
+    // ---------------------------------------------------------------------------
+    /** Put a message that could not be sent back at the front of the queue. */
+    pub fn requeue(&mut self, msg: MqttMessage) {
+        self.messages.push_front(msg);
+    }
+
+    pub fn len(&self) -> usize {self.messages.len()}
+    pub fn is_empty(&self) -> bool {self.messages.is_empty()}
+}
+
+// ***************************************************************************
+// PRIVATE FUNCTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// drive_connection:
+// ---------------------------------------------------------------------------
+/** Run the MQTT event loop, which connects to the broker and reconnects after
+ * errors, until the plugin stops.  Connection failures are logged once until
+ * the connection is restored.
+ */
+fn drive_connection(mut connection: Connection, broker: String, connected: Arc<AtomicBool>,
+                    stopping: Arc<AtomicBool>) {
+    let mut reported = false;
+    for notification in connection.iter() {
+        match notification {
+            Ok(rumqttc::Event::Incoming(Packet::ConnAck(_))) => {
+                info!("{}", Errors::MqttConnected(broker.clone()));
+                connected.store(true, Ordering::SeqCst);
+                reported = false;
+            },
+            Ok(_) => (),
+            Err(e) => {
+                connected.store(false, Ordering::SeqCst);
+                if stopping.load(Ordering::SeqCst) {
+                    break;
+                }
+                if !reported {
+                    error!("{}", Errors::MqttConnectionError(broker.clone(), e.to_string()));
+                    reported = true;
+                }
+                thread::sleep(Duration::from_millis(RECONNECT_DELAY_MILLIS));
+            },
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// parse_qos:
+// ---------------------------------------------------------------------------
+fn parse_qos(qos: u8) -> Result<QoS, Errors> {
+    match qos {
+        0 => Result::Ok(QoS::AtMostOnce),
+        1 => Result::Ok(QoS::AtLeastOnce),
+        2 => Result::Ok(QoS::ExactlyOnce),
+        other => Result::Err(Errors::MqttInvalidConfig(format!("qos must be 0, 1 or 2, not {}", other))),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// build_message:
+// ---------------------------------------------------------------------------
+/** Create the message for an event from its JSON representation. */
+fn build_message(camera_id: &str, event_type: &str, event: &Value, settings: &MqttEventSettings)
+    -> Result<MqttMessage, Errors> {
+    let topic = render_topic(&settings.topic_template, camera_id, event_type, event);
+    let payload = build_payload(camera_id, event_type, event, settings.fields.as_deref());
+    match serde_json::to_vec(&payload) {
+        Ok(p) => Result::Ok(MqttMessage {topic, payload: p, qos: settings.qos, retain: settings.retain}),
+        Err(e) => Result::Err(Errors::EventToJsonError("MqttPublisherPlugin".to_string(), event_type.to_string(),
+                                                       e.to_string())),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// build_payload:
+// ---------------------------------------------------------------------------
+/** The payload is a JSON object with the camera id, the event type and either
+ * all of the event's fields or only the selected fields.
+ */
+fn build_payload(camera_id: &str, event_type: &str, event: &Value, fields: Option<&[String]>) -> Value {
+    let mut payload = Map::new();
+    payload.insert("camera_id".to_string(), Value::String(camera_id.to_string()));
+    payload.insert("event_type".to_string(), Value::String(event_type.to_string()));
+    if let Value::Object(event_fields) = event {
+        for (key, value) in event_fields {
+            if fields.map(|f| f.contains(key)).unwrap_or(true) {
+                payload.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    Value::Object(payload)
+}
+
+// ---------------------------------------------------------------------------
+// render_topic:
+// ---------------------------------------------------------------------------
+/** Replace the {camera_id} and {event_type} placeholders and any {<field>}
+ * placeholder naming a top level event field, such as {label}.  Values are
+ * made safe for use as a single topic level.
+ */
+fn render_topic(template: &str, camera_id: &str, event_type: &str, event: &Value) -> String {
+    let mut topic = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(e) => start + e,
+            None => break,
+        };
+        topic.push_str(&rest[..start]);
+        let name = &rest[start + 1..end];
+        let value = match name {
+            "camera_id" => camera_id.to_string(),
+            "event_type" => event_type.to_string(),
+            field => match event.get(field) {
+                Some(Value::String(s)) => s.clone(),
+                Some(Value::Null) | None => MISSING_TOPIC_VALUE.to_string(),
+                Some(v) => v.to_string(),
+            },
+        };
+        topic.push_str(&value.replace(['/', '+', '#'], "_"));
+        rest = &rest[end + 1..];
+    }
+    topic.push_str(rest);
+    topic
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use serde_json::json;
+    use crate::config::config::MqttEventConfig;
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: mqtt_publisher_plugin.rs");
+    }
+
+    #[test]
+    fn parms_and_messages() {
+        // Defaults with a detection override.
+        let mut overrides = HashMap::new();
+        overrides.insert("DetectionEvent".to_string(), MqttEventConfig {
+            topic_template: Some("traps/{camera_id}/detections/{label}".to_string()),
+            qos: Some(2),
+            retain: Some(true),
+            fields: Some(vec!["image_uuid".to_string(), "label".to_string()]),
+        });
+        let config = MqttPublisherConfig {camera_id: Some("cam1".to_string()), overrides: Some(overrides),
+                                          ..Default::default()};
+        let parms = MqttParms::new_from_config(&config).unwrap();
+        assert_eq!(parms.events.len(), DEFAULT_EVENTS.len());
+        assert_eq!(parms.events["ImageStoredEvent"].qos, QoS::AtLeastOnce);
+
+        // The detection topic and payload use the override.
+        let event = json!({"image_uuid": "abc", "label": "animal/bird", "probability": 0.75});
+        let msg = build_message(&parms.camera_id, "DetectionEvent", &event, &parms.events["DetectionEvent"]).unwrap();
+        assert_eq!(msg.topic, "traps/cam1/detections/animal_bird");
+        assert_eq!(msg.qos, QoS::ExactlyOnce);
+        assert!(msg.retain);
+        let payload: Value = serde_json::from_slice(&msg.payload).unwrap();
+        assert_eq!(payload, json!({"camera_id": "cam1", "event_type": "DetectionEvent",
+                                   "image_uuid": "abc", "label": "animal/bird"}));
+
+        // Other events use the defaults.
+        let msg = build_message(&parms.camera_id, "ImageStoredEvent", &event, &parms.events["ImageStoredEvent"]).unwrap();
+        assert_eq!(msg.topic, "camera-traps/cam1/ImageStoredEvent");
+        assert_eq!(render_topic("a/{missing}/{probability}", "c", "e", &event), "a/unknown/0.75");
+
+        // Invalid configurations.
+        let bad = MqttPublisherConfig {qos: Some(3), ..Default::default()};
+        assert!(MqttParms::new_from_config(&bad).is_err());
+        let bad = MqttPublisherConfig {events: Some(vec!["NoSuchEvent".to_string()]), ..Default::default()};
+        assert!(MqttParms::new_from_config(&bad).is_err());
+        let mut overrides = HashMap::new();
+        overrides.insert("ImageDeletedEvent".to_string(), MqttEventConfig::default());
+        let bad = MqttPublisherConfig {overrides: Some(overrides), ..Default::default()};
+        assert!(MqttParms::new_from_config(&bad).is_err());
+    }
+
+    #[test]
+    fn offline_queue() {
+        let msg = |topic: &str| MqttMessage {topic: topic.to_string(), payload: vec![], qos: QoS::AtMostOnce,
+                                             retain: false};
+        let mut queue = OfflineQueue::new(2);
+        assert!(queue.push(msg("a")).is_none());
+        assert!(queue.push(msg("b")).is_none());
+        assert_eq!(queue.push(msg("c")).unwrap().topic, "a");
+        let first = queue.pop().unwrap();
+        assert_eq!(first.topic, "b");
+        queue.requeue(first);
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.pop().unwrap().topic, "b");
+        assert_eq!(queue.pop().unwrap().topic, "c");
+        assert!(queue.is_empty());
+        assert!(OfflineQueue::new(0).push(msg("d")).is_some());
+    }
+}
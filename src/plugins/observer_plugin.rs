@@ -1,24 +1,27 @@
+use std::any::Any;
 use uuid::Uuid;
 use zmq::Socket;
 use event_engine::plugins::Plugin;
 use event_engine::errors::EngineError;
 use event_engine::events::{EventType};
 use crate::{events, config::errors::Errors};
-use crate::{traps_utils, RuntimeCtx};
+use crate::{reload, traps_utils, RuntimeCtx};
 #[allow(unused_imports)]
 use crate::Config;
-use crate::events::{NEW_IMAGE_PREFIX, IMAGE_RECEIVED_PREFIX, IMAGE_SCORED_PREFIX, 
-                    IMAGE_STORED_PREFIX, IMAGE_DELETED_PREFIX, PLUGIN_STARTED_PREFIX,
-                    PLUGIN_TERMINATING_PREFIX, PLUGIN_TERMINATE_PREFIX, 
-                    MONITOR_POWER_START_PREFIX, MONITOR_POWER_STOP_PREFIX};
-use crate::plugins::actions::observer_actions::select_action;                    
+use crate::events::{CONFIG_RELOADED_PREFIX, PLUGIN_TERMINATE_PREFIX};
+use crate::plugins::actions::{ActionChain, ActionContext, ActionInput};
+use crate::journal::Journal;
 
 use log::{info, error};
 
+// The plugin's name in the [plugins] internal list.
+const PLUGIN_NAME: &str = "observer_plugin";
+
 pub struct ObserverPlugin {
     name: String,
     id: Uuid,
     runctx: &'static RuntimeCtx,
+    journal: Journal,
 }
 impl Plugin for ObserverPlugin {
     // ---------------------------------------------------------------------------
@@ -37,9 +40,8 @@ impl Plugin for ObserverPlugin {
         // Announce our arrival.
         info!("{}", format!("{}", Errors::PluginStarted(self.name.clone(), self.get_id().hyphenated().to_string())));
 
-        // Get this plugin's required action function pointer.
-        #[allow(unused_variables)]
-        let action = match select_action(&self.runctx.parms.config) {
+        // Get this plugin's chain of actions.
+        let mut actions = match ActionChain::select(&self.runctx.parms.config, "observer_plugin", "ObserverPlugin") {
             Ok(a) => a,
             Err(e) => {
                 return Err(EngineError::PluginExecutionError(self.name.clone(), 
@@ -73,32 +75,10 @@ impl Plugin for ObserverPlugin {
             // ----------------- Process Subscription Events ------------------
             // Process events we expect; log and disregard all others.
             let terminate = match ev_in.prefix_array {
-                NEW_IMAGE_PREFIX => {
-                    self.record_event("NewImageEvent");
-                    false
-                },
-                IMAGE_RECEIVED_PREFIX => {
-                    self.record_event("ImageReceivedEvent");
-                    false
-                },
-                IMAGE_SCORED_PREFIX => {
-                    self.record_event("ImageScoredEvent");
-                    false
-                },
-                IMAGE_STORED_PREFIX => {
-                    self.record_event("ImageStoredEvent");
-                    false
-                },
-                IMAGE_DELETED_PREFIX => {
-                    self.record_event("ImageDeletedEvent");
-                    false
-                },
-                PLUGIN_STARTED_PREFIX => {
-                    self.record_event("PluginStartedEvent");
-                    false
-                },
-                PLUGIN_TERMINATING_PREFIX => {
-                    self.record_event("PluginTerminatingEvent");
+                CONFIG_RELOADED_PREFIX => {
+                    // Pick up the new settings of our actions.
+                    self.record_event("ConfigReloadedEvent");
+                    actions.reload("observer_plugin");
                     false
                 },
                 PLUGIN_TERMINATE_PREFIX => {
@@ -107,22 +87,26 @@ impl Plugin for ObserverPlugin {
                     self.record_event("PluginTerminateEvent");
                     traps_utils::process_plugin_terminate_event(ev_in.gen_event, &self.id, &self.name)
                 },
-                MONITOR_POWER_START_PREFIX => {
-                    self.record_event("MonitorPowerStartEvent");
-                    false
-                },
-                MONITOR_POWER_STOP_PREFIX => {
-                    self.record_event("MonitorPowerStopEvent");
-                    false
-                },
-                unexpected => {
-                    // This should only happen for valid events to which we are not subscribed.
-                    // Completely invalid event prefixes are detected above in check_event_prefix().
-                    let pre = format!("{:?}", unexpected);
-                    error!("{}", Errors::EventNotHandledError(self.name.clone(), pre));
+                prefix => {
+                    // We subscribe to every registered event, so an unknown prefix should 
+                    // not happen.  Completely invalid event prefixes are detected above in
+                    // check_event_prefix().
+                    match events::get_event_info_by_prefix(prefix) {
+                        Some(info) => self.record_event(info.name),
+                        None => {
+                            let pre = format!("{:?}", prefix);
+                            error!("{}", Errors::EventNotHandledError(self.name.clone(), pre));
+                        }
+                    }
                     false
                 }
             };
+
+            // Every event is passed to the configured actions unless a reload removed
+            // this plugin from the configuration.  Failures are logged by the chain.
+            if reload::is_plugin_enabled(PLUGIN_NAME) {
+                let _ = actions.run(&ActionInput {event: ev_in.raw_bytes, image: None, context: self});
+            }
         
             // Determine if we should terminate our event read loop.
             if terminate {
@@ -138,19 +122,8 @@ impl Plugin for ObserverPlugin {
 
     /// Return the event subscriptions as a vector of event types that this plugin is interested in.
     fn get_subscriptions(&self) -> Result<Vec<Box<dyn EventType>>, EngineError> {
-        // This plugin subscribes to all events.  When events change so must this list.
-        Ok(vec![
-            Box::new(events::NewImageEvent::new(Uuid::new_v4(), String::from("fake"), vec![])),
-            Box::new(events::ImageReceivedEvent::new(Uuid::new_v4(), "fake".to_string())),
-            Box::new(events::ImageScoredEvent::new(Uuid::new_v4(), "fake".to_string(), vec![])),
-            Box::new(events::ImageStoredEvent::new(Uuid::new_v4(), "fake".to_string(), String::from("path"))),
-            Box::new(events::ImageDeletedEvent::new(Uuid::new_v4(), "fake".to_string())),
-            Box::new(events::PluginTerminateEvent::new(Uuid::new_v4(), String::from("*"))),
-            Box::new(events::PluginTerminatingEvent::new(Uuid::new_v4(), String::from("ObserverPlugin"))), 
-            Box::new(events::PluginStartedEvent::new(Uuid::new_v4(), String::from("ObserverPlugin"))),
-            Box::new(events::MonitorPowerStartEvent::new(vec![1], vec![events::MonitorType::ALL], "fake".to_string(), 1)),
-            Box::new(events::MonitorPowerStopEvent::new(vec![1])),
-        ])
+        // This plugin subscribes to all registered events.
+        Ok(events::EVENT_REGISTRY.iter().map(|info| info.get_subscription()).collect())
     }
 
     /// Simple accessors for this plugin.
@@ -158,16 +131,23 @@ impl Plugin for ObserverPlugin {
     fn get_id(&self) -> Uuid {self.id}
 }
 
+impl ActionContext for ObserverPlugin {
+    fn get_plugin_name(&self) -> &str {PLUGIN_NAME}
+    fn get_config(&self) -> &Config {&self.runctx.parms.config}
+    fn as_any(&self) -> &dyn Any {self}
+}
+
 impl ObserverPlugin {
     // ---------------------------------------------------------------------------
     // new:
     // ---------------------------------------------------------------------------
-    pub fn new(runctx: &'static RuntimeCtx) -> Self {
-        ObserverPlugin {
+    pub fn new(runctx: &'static RuntimeCtx) -> Result<Self, Errors> {
+        Result::Ok(ObserverPlugin {
             name: "ObserverPlugin".to_string(),
             id: Uuid::new_v4(),
             runctx,
-        }
+            journal: Journal::new_from_config(&runctx.parms.config)?,
+        })
     }
 
     // ---------------------------------------------------------------------------
@@ -176,6 +156,11 @@ impl ObserverPlugin {
     #[allow(unused)]
     pub fn get_runctx(&self) -> &RuntimeCtx {self.runctx}
 
+    // ---------------------------------------------------------------------------
+    // get_journal:
+    // ---------------------------------------------------------------------------
+    pub fn get_journal(&self) -> &Journal {&self.journal}
+
     // ---------------------------------------------------------------------------
     // record_event:
     // ---------------------------------------------------------------------------
@@ -0,0 +1,594 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime};
+
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+use zmq::Socket;
+use event_engine::plugins::Plugin;
+use event_engine::errors::EngineError;
+use event_engine::events::EventType;
+use crate::events_generated::gen_events;
+use crate::{events, config::errors::Errors};
+use crate::{traps_utils, RuntimeCtx};
+use crate::config::config::OracleConfig;
+use crate::dead_letter::{self, DeadLetterReason};
+use crate::events::{IMAGE_DELETED_PREFIX, IMAGE_PROCESSING_ERROR_PREFIX, IMAGE_RECEIVED_PREFIX, IMAGE_SCORED_PREFIX,
+                    IMAGE_STORED_PREFIX, PLUGIN_TERMINATE_PREFIX, ImageDeletedEvent, ImageProcessingErrorEvent,
+                    ImageReceivedEvent, ImageScoredEvent, ImageStoredEvent};
+
+use log::{info, error, debug};
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+// Defaults used when the [plugins.config.oracle_plugin] configuration does not
+// specify a value.  The file names are those used by the Python oracle and the
+// CKN daemon.
+const DEFAULT_OUTPUT_DIR: &str = "~/camera-traps/output";
+const DEFAULT_MAPPING_FILE: &str = "uuid_image_mapping.json";
+const DEFAULT_FINAL_FILE: &str = "image_mapping_final.json";
+const DEFAULT_FLUSH_INTERVAL_SECS: u64 = 5;
+
+// The name of this plugin's [plugins.config] table.
+const CONFIG_NAME: &str = "oracle_plugin";
+
+// The CKN daemon exits when this entry appears in the final mapping file.
+pub const TERMINATING_UUID: &str = "6e153711-9823-4ee6-b608-58e2e801db51";
+
+// The decisions recorded for deleted and abandoned images.
+const DECISION_DELETED: &str = "Deleted";
+const DECISION_ERROR: &str = "Error";
+
+// How long to wait for an event before checking whether to flush.
+const POLL_TIMEOUT_MILLIS: i64 = 1000;
+
+// ***************************************************************************
+// IMAGE MAPPING
+// ***************************************************************************
+/** The fields recorded for one image.  The joined flag is set once the fields
+ * written by the image generator have been added.
+ */
+#[derive(Debug, Default)]
+struct ImageEntry {
+    fields: Map<String, Value>,
+    joined: bool,
+}
+
+/** The in-memory form of image_mapping_final.json.  Each image's lifecycle
+ * timestamps, scores and decision are joined with the information the image
+ * generator wrote to its uuid mapping file.
+ *
+ * Each flush replaces the final file with the complete mapping, so the CKN
+ * daemon can follow a run in progress.  Rewriting the whole mapping on every
+ * event would make long runs quadratic, so the plugin flushes at most once per
+ * flush interval.  Flushes also append the images that changed to the
+ * optional updates file, one JSON object per line, where a later line for an
+ * image replaces the earlier ones.
+ */
+#[derive(Debug)]
+pub struct ImageMapping {
+    mapping_path: String,
+    final_path: String,
+    updates_path: Option<String>,
+    images: BTreeMap<String, ImageEntry>,
+    changed: BTreeSet<String>,
+    unjoined: BTreeSet<String>,
+    generator_mapping: Map<String, Value>,
+    generator_mtime: Option<SystemTime>,
+}
+
+impl ImageMapping {
+    // ---------------------------------------------------------------------------
+    // new:
+    // ---------------------------------------------------------------------------
+    pub fn new(mapping_path: String, final_path: String, updates_path: Option<String>) -> Self {
+        ImageMapping {mapping_path, final_path, updates_path, images: BTreeMap::new(), changed: BTreeSet::new(),
+                      unjoined: BTreeSet::new(), generator_mapping: Map::new(), generator_mtime: None}
+    }
+
+    // ---------------------------------------------------------------------------
+    // update:
+    // ---------------------------------------------------------------------------
+    /** Set fields of an image, adding the image if necessary.  Fields already
+     * set by earlier events are replaced.
+     */
+    pub fn update(&mut self, uuid: &str, fields: Vec<(&str, Value)>) {
+        if !self.images.contains_key(uuid) {
+            if !self.generator_mapping.contains_key(uuid) {
+                self.reload_generator_mapping();
+            }
+            self.images.insert(uuid.to_string(), ImageEntry::default());
+            self.join(uuid);
+        }
+        let entry = self.images.get_mut(uuid).expect("image entry was just added");
+        for (key, value) in fields {
+            entry.fields.insert(key.to_string(), value);
+        }
+        self.changed.insert(uuid.to_string());
+    }
+
+    // ---------------------------------------------------------------------------
+    // set_decision_if_absent:
+    // ---------------------------------------------------------------------------
+    /** Record a decision unless the image already has one. */
+    pub fn set_decision_if_absent(&mut self, uuid: &str, decision: &str) {
+        let decided = self.images.get(uuid).is_some_and(|e| e.fields.contains_key("image_decision"));
+        if !decided {
+            self.update(uuid, vec![("image_decision", json!(decision))]);
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // is_dirty:
+    // ---------------------------------------------------------------------------
+    pub fn is_dirty(&self) -> bool {!self.changed.is_empty()}
+
+    // ---------------------------------------------------------------------------
+    // num_decided:
+    // ---------------------------------------------------------------------------
+    /** The number of images with a final decision. */
+    pub fn num_decided(&self) -> usize {
+        self.images.values().filter(|e| e.fields.contains_key("image_decision")).count()
+    }
+
+    // ---------------------------------------------------------------------------
+    // add_terminating_entry:
+    // ---------------------------------------------------------------------------
+    /** Add the empty entry that tells the CKN daemon that the run is over. */
+    pub fn add_terminating_entry(&mut self) {
+        self.images.insert(TERMINATING_UUID.to_string(), ImageEntry {fields: Map::new(), joined: true});
+        self.changed.insert(TERMINATING_UUID.to_string());
+    }
+
+    // ---------------------------------------------------------------------------
+    // reset_files:
+    // ---------------------------------------------------------------------------
+    /** Replace the final file left by an earlier run with an empty mapping and
+     * empty the updates file, if there is one.
+     */
+    pub fn reset_files(&self) -> Result<(), Errors> {
+        self.write_final()?;
+        if let Some(updates_path) = &self.updates_path {
+            if let Err(e) = traps_utils::create_or_replace_file(updates_path, &[]) {
+                return Result::Err(Errors::OracleWriteError(updates_path.clone(), e.to_string()));
+            }
+        }
+        Result::Ok(())
+    }
+
+    // ---------------------------------------------------------------------------
+    // flush:
+    // ---------------------------------------------------------------------------
+    /** Join any images the generator had not yet written when they were first
+     * seen, replace the final file with the complete mapping and append the
+     * images that changed since the last flush to the updates file.
+     */
+    pub fn flush(&mut self) -> Result<(), Errors> {
+        self.join_pending();
+        self.write_final()?;
+
+        if let Some(updates_path) = &self.updates_path {
+            let mut lines = Vec::new();
+            for uuid in &self.changed {
+                if let Some(entry) = self.images.get(uuid) {
+                    let line = json!({uuid.as_str(): &entry.fields});
+                    lines.extend_from_slice(line.to_string().as_bytes());
+                    lines.push(b'\n');
+                }
+            }
+            let result = fs::OpenOptions::new().create(true).append(true).open(updates_path)
+                             .and_then(|mut f| f.write_all(&lines));
+            if let Err(e) = result {
+                return Result::Err(Errors::OracleWriteError(updates_path.clone(), e.to_string()));
+            }
+        }
+        self.changed.clear();
+        Result::Ok(())
+    }
+
+    // ---------------------------------------------------------------------------
+    // write_final:
+    // ---------------------------------------------------------------------------
+    /** Replace the final file with the complete mapping.  The file is written
+     * to a temporary file and renamed so that readers never see a partial
+     * mapping.
+     */
+    fn write_final(&self) -> Result<(), Errors> {
+        let mapping: BTreeMap<&String, &Map<String, Value>> = self.images.iter().map(|(k, e)| (k, &e.fields)).collect();
+        let json = match serde_json::to_vec_pretty(&mapping) {
+            Ok(j) => j,
+            Err(e) => return Result::Err(Errors::OracleWriteError(self.final_path.clone(), e.to_string())),
+        };
+        let tmp_path = format!("{}.tmp", self.final_path);
+        let result = traps_utils::create_or_replace_file(&tmp_path, &json)
+                         .and_then(|_| fs::rename(&tmp_path, &self.final_path));
+        if let Err(e) = result {
+            return Result::Err(Errors::OracleWriteError(self.final_path.clone(), e.to_string()));
+        }
+        Result::Ok(())
+    }
+
+    // ---------------------------------------------------------------------------
+    // join_pending:
+    // ---------------------------------------------------------------------------
+    /** Join the images the generator had not yet written when they were first
+     * seen, if it has written them since.
+     */
+    fn join_pending(&mut self) {
+        if self.unjoined.is_empty() {
+            return;
+        }
+        self.reload_generator_mapping();
+        let unjoined: Vec<String> = self.unjoined.iter().cloned().collect();
+        for uuid in unjoined {
+            self.join(&uuid);
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // join:
+    // ---------------------------------------------------------------------------
+    /** Add the generator's fields for an image if they are available.  Until
+     * then, the image is identified by its UUID field like in the Python oracle.
+     */
+    fn join(&mut self, uuid: &str) {
+        let entry = match self.images.get_mut(uuid) {
+            Some(e) => e,
+            None => return,
+        };
+        match self.generator_mapping.get(uuid) {
+            Some(Value::Object(base)) => {
+                for (key, value) in base {
+                    if !entry.fields.contains_key(key) {
+                        entry.fields.insert(key.clone(), value.clone());
+                    }
+                }
+                entry.joined = true;
+                self.unjoined.remove(uuid);
+                self.changed.insert(uuid.to_string());
+            },
+            _ => {
+                if !entry.fields.contains_key("UUID") {
+                    entry.fields.insert("UUID".to_string(), json!(uuid));
+                }
+                self.unjoined.insert(uuid.to_string());
+            },
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // reload_generator_mapping:
+    // ---------------------------------------------------------------------------
+    /** Reread the generator's mapping file if it changed since it was last read.
+     * The generator may be writing the file, so a file that can't be parsed is
+     * logged and read again later.
+     */
+    fn reload_generator_mapping(&mut self) {
+        let mtime = match fs::metadata(&self.mapping_path).and_then(|m| m.modified()) {
+            Ok(t) => t,
+            Err(e) => {
+                debug!("{}", Errors::OracleMappingReadError(self.mapping_path.clone(), e.to_string()));
+                return;
+            }
+        };
+        if self.generator_mtime == Some(mtime) {
+            return;
+        }
+
+        let result = fs::read_to_string(&self.mapping_path).map_err(|e| e.to_string())
+            .and_then(|c| serde_json::from_str::<Map<String, Value>>(&c).map_err(|e| e.to_string()));
+        match result {
+            Ok(mapping) => {
+                self.generator_mapping = mapping;
+                self.generator_mtime = Some(mtime);
+            },
+            Err(e) => error!("{}", Errors::OracleMappingReadError(self.mapping_path.clone(), e)),
+        }
+    }
+}
+
+// ***************************************************************************
+// PLUGIN
+// ***************************************************************************
+pub struct OraclePlugin {
+    name: String,
+    id: Uuid,
+    config: OracleConfig,
+}
+impl Plugin for OraclePlugin {
+    // ---------------------------------------------------------------------------
+    // start:
+    // ---------------------------------------------------------------------------
+    /// The entry point for the plugin. The engine will start the plugin in its own
+    /// thread and execute this function.  The pub_socket is used by the plugin to
+    /// publish new events.  The sub_socket is used by the plugin to get events
+    /// published by other plugins.
+    ///
+    /// This plugin polls its subscription socket so that changes are flushed to
+    /// the final mapping file even when no events arrive.  The final mapping
+    /// left by an earlier run is replaced with an empty mapping at start up.
+    fn start(
+        &self,
+        pub_socket: Socket,
+        sub_socket: Socket,
+    ) -> Result<(), EngineError> {
+
+        // Announce our arrival.
+        info!("{}", format!("{}", Errors::PluginStarted(self.name.clone(), self.get_id().hyphenated().to_string())));
+
+        // Determine the file locations.
+        let config = &self.config;
+        let output_dir = traps_utils::get_absolute_path(config.output_dir.as_deref().unwrap_or(DEFAULT_OUTPUT_DIR));
+        if let Err(e) = fs::create_dir_all(&output_dir) {
+            return Err(EngineError::PluginExecutionError(self.name.clone(), self.get_id().hyphenated().to_string(),
+                                                         Errors::AppDirCreateError(output_dir, e.to_string()).to_string()));
+        }
+        let path = |file: &str| Path::new(&output_dir).join(file).to_string_lossy().to_string();
+        let mut mapping = ImageMapping::new(path(config.mapping_file.as_deref().unwrap_or(DEFAULT_MAPPING_FILE)),
+                                            path(config.final_file.as_deref().unwrap_or(DEFAULT_FINAL_FILE)),
+                                            config.updates_file.as_deref().map(path));
+
+        // Each run starts with an empty mapping.
+        if let Err(e) = mapping.reset_files() {
+            return Err(EngineError::PluginExecutionError(self.name.clone(), self.get_id().hyphenated().to_string(),
+                                                         e.to_string()));
+        }
+        let flush_interval = Duration::from_secs(config.flush_interval_secs.unwrap_or(DEFAULT_FLUSH_INTERVAL_SECS));
+        let mut last_flush = Instant::now();
+
+        // Send the plugin start up event.
+        match traps_utils::send_started_event(self, &pub_socket) {
+            Ok(_) => (),
+            Err(e) => return Err(e),
+        };
+
+        // Enter our infinite work loop.
+        loop {
+            // ----------------- Flush Changes --------------------------------
+            if mapping.is_dirty() && last_flush.elapsed() >= flush_interval {
+                self.flush(&mut mapping);
+                last_flush = Instant::now();
+            }
+
+            // ----------------- Wait on the Next Event -----------------------
+            match sub_socket.poll(zmq::POLLIN, POLL_TIMEOUT_MILLIS) {
+                Ok(0) => continue,
+                Ok(_) => (),
+                Err(e) => {
+                    error!("{}", Errors::SocketRecvError(self.name.clone(), e.to_string()));
+                    continue;
+                }
+            }
+
+            // The bytes vector is an output parameter populated by the marshalling function
+            // with raw event bytes. The ev_in.gen_event field references these raw bytes
+            // so the bytes vector must be of a lifetime at least as long as ev_in.
+            let mut bytes: Vec<u8> = vec![];
+            let ev_in = match traps_utils::marshal_next_event(self, &sub_socket, &mut bytes) {
+                Some(ev) => ev,
+                None => continue,
+            };
+
+            // ----------------- Process Subscription Events ------------------
+            // Process events we expect; log and disregard all others.
+            let terminate = match ev_in.prefix_array {
+                IMAGE_RECEIVED_PREFIX | IMAGE_SCORED_PREFIX | IMAGE_STORED_PREFIX | IMAGE_DELETED_PREFIX |
+                IMAGE_PROCESSING_ERROR_PREFIX => {
+                    if let Err(e) = record_event(&mut mapping, ev_in.prefix_array, ev_in.gen_event) {
+                        error!("{}", e);
+                    }
+                    false
+                },
+                PLUGIN_TERMINATE_PREFIX => {
+                    // Determine whether we are the target of this terminate event. The called method
+                    // will return true if this plugin should shutdown.
+                    info!("\n  -> {} received event {}", self.name, String::from("PluginTerminateEvent"));
+                    traps_utils::process_plugin_terminate_event(ev_in.gen_event, &self.id, &self.name)
+                },
+                unexpected => {
+                    // This should only happen for valid events to which we are not subscribed.
+                    // Completely invalid event prefixes are detected above in check_event_prefix().
+                    let pre = format!("{:?}", unexpected);
+                    let err = Errors::EventNotHandledError(self.name.clone(), pre);
+                    error!("{}", err);
+                    dead_letter::capture(&self.name, DeadLetterReason::NotHandled, &err, ev_in.raw_bytes);
+                    false
+                }
+            };
+
+            // Determine if we should terminate our event read loop.
+            if terminate {
+                break;
+            }
+        }
+
+        // Write the complete mapping and send the terminating event.
+        mapping.add_terminating_entry();
+        self.flush(&mut mapping);
+        info!("{}", Errors::OracleFinalMapping(mapping.num_decided(), mapping.final_path.clone()));
+        traps_utils::send_terminating_event(&self.name, self.id, &pub_socket);
+
+        // Shutting down.
+        Ok(())
+    }
+
+    /// Return the event subscriptions, as a vector of strings, that this plugin is interested in.
+    fn get_subscriptions(&self) -> Result<Vec<Box<dyn EventType>>, EngineError> {
+        Ok(vec![
+            Box::new(ImageReceivedEvent::new(Uuid::new_v4(), "fake".to_string())),
+            Box::new(ImageScoredEvent::new(Uuid::new_v4(), "fake".to_string(), vec![])),
+            Box::new(ImageStoredEvent::new(Uuid::new_v4(), "fake".to_string(), "fake".to_string())),
+            Box::new(ImageDeletedEvent::new(Uuid::new_v4(), "fake".to_string())),
+            Box::new(ImageProcessingErrorEvent::new(Uuid::new_v4(), "fake".to_string(), "fake".to_string(),
+                                                    "fake".to_string(), "fake".to_string())),
+            Box::new(events::PluginTerminateEvent::new(Uuid::new_v4(), String::from("*"))),
+        ])
+    }
+
+    /// Simple accessors for this plugin.
+    fn get_name(&self) -> String {self.name.clone()}
+    fn get_id(&self) -> Uuid {self.id}
+}
+
+impl OraclePlugin {
+    // ---------------------------------------------------------------------------
+    // new:
+    // ---------------------------------------------------------------------------
+    /** Create the plugin with its [plugins.config.oracle_plugin] table. */
+    pub fn new(runctx: &'static RuntimeCtx) -> Result<Self, Errors> {
+        let config = &runctx.parms.config;
+        Result::Ok(OraclePlugin {
+            name: "OraclePlugin".to_string(),
+            id: Uuid::new_v4(),
+            config: config.get_plugin_config(CONFIG_NAME)?,
+        })
+    }
+
+    // ---------------------------------------------------------------------------
+    // flush:
+    // ---------------------------------------------------------------------------
+    fn flush(&self, mapping: &mut ImageMapping) {
+        if let Err(e) = mapping.flush() {
+            error!("{}", e);
+        }
+    }
+}
+
+// ***************************************************************************
+// PRIVATE FUNCTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// record_event:
+// ---------------------------------------------------------------------------
+/** Record an image lifecycle event using the same field names as the Python
+ * oracle.  Images abandoned with an ImageProcessingErrorEvent are given the
+ * Error decision unless a decision was already made.
+ */
+fn record_event(mapping: &mut ImageMapping, prefix: [u8; 2], gen_event: gen_events::Event) -> Result<(), Errors> {
+    match prefix {
+        IMAGE_RECEIVED_PREFIX => {
+            let ev = events::gen_to_event::<ImageReceivedEvent>(gen_event)?;
+            mapping.update(&uuid_key(ev.get_image_uuid()),
+                           vec![("image_receiving_timestamp", json!(ev.get_created()))]);
+        },
+        IMAGE_SCORED_PREFIX => {
+            let ev = events::gen_to_event::<ImageScoredEvent>(gen_event)?;
+            let scores: Vec<Value> = ev.get_scores().iter()
+                .map(|s| json!({"label": s.get_label(), "probability": s.get_probability() as f64}))
+                .collect();
+            mapping.update(&uuid_key(ev.get_image_uuid()),
+                           vec![("image_scoring_timestamp", json!(ev.get_created())), ("score", json!(scores))]);
+        },
+        IMAGE_STORED_PREFIX => {
+            let ev = events::gen_to_event::<ImageStoredEvent>(gen_event)?;
+            mapping.update(&uuid_key(ev.get_image_uuid()),
+                           vec![("image_store_delete_time", json!(ev.get_created())),
+                                ("image_decision", json!(ev.get_destination()))]);
+        },
+        IMAGE_DELETED_PREFIX => {
+            let ev = events::gen_to_event::<ImageDeletedEvent>(gen_event)?;
+            mapping.update(&uuid_key(ev.get_image_uuid()),
+                           vec![("image_delete_time", json!(ev.get_created())),
+                                ("image_decision", json!(DECISION_DELETED))]);
+        },
+        IMAGE_PROCESSING_ERROR_PREFIX => {
+            let ev = events::gen_to_event::<ImageProcessingErrorEvent>(gen_event)?;
+            let uuid = uuid_key(ev.get_image_uuid());
+            let image_error = json!({"plugin_name": ev.get_plugin_name(), "stage": ev.get_stage(),
+                                     "error_code": ev.get_error_code(), "error_message": ev.get_error_message()});
+            mapping.update(&uuid, vec![("image_error_time", json!(ev.get_created())), ("image_error", image_error)]);
+            mapping.set_decision_if_absent(&uuid, DECISION_ERROR);
+        },
+        _ => (),
+    }
+    Result::Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// uuid_key:
+// ---------------------------------------------------------------------------
+fn uuid_key(uuid: &Uuid) -> String {
+    uuid.hyphenated().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: oracle_plugin.rs");
+    }
+
+    #[test]
+    fn image_mapping() {
+        let dir = std::env::temp_dir().join(format!("oracle_test_{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let mapping_path = dir.join(DEFAULT_MAPPING_FILE).to_string_lossy().to_string();
+        let final_path = dir.join(DEFAULT_FINAL_FILE).to_string_lossy().to_string();
+        let updates_path = dir.join("image_mapping_updates.jsonl").to_string_lossy().to_string();
+        let read_final = || -> Value { serde_json::from_slice(&fs::read(&final_path).unwrap()).unwrap() };
+        let read_updates = || -> Vec<Value> {
+            fs::read_to_string(&updates_path).unwrap().lines().map(|l| serde_json::from_str(l).unwrap()).collect()
+        };
+        fs::write(&mapping_path, r#"{"a": {"UUID": "a", "image_name": "a.jpg", "ground_truth": "bird"}}"#).unwrap();
+
+        // A run starts by replacing an earlier run's files.
+        fs::write(&final_path, r#"{"old": {}}"#).unwrap();
+        fs::write(&updates_path, "{\"old\": {}}\n").unwrap();
+        let mut mapping = ImageMapping::new(mapping_path.clone(), final_path.clone(), Some(updates_path.clone()));
+        mapping.reset_files().unwrap();
+        assert_eq!(read_final(), json!({}));
+        assert!(read_updates().is_empty());
+
+        // Known images are joined with the generator's fields and each flush
+        // rewrites the final file.
+        mapping.update("a", vec![("image_receiving_timestamp", json!("t1"))]);
+        mapping.update("a", vec![("image_decision", json!("Save"))]);
+        mapping.set_decision_if_absent("a", DECISION_ERROR);
+        assert!(mapping.is_dirty());
+        mapping.flush().unwrap();
+        assert!(!mapping.is_dirty());
+        let a = json!({"UUID": "a", "image_name": "a.jpg", "ground_truth": "bird",
+                       "image_receiving_timestamp": "t1", "image_decision": "Save"});
+        assert_eq!(read_final(), json!({"a": a}));
+        assert_eq!(read_updates(), vec![json!({"a": a})]);
+
+        // Only changed images are appended to the updates file.  Images the
+        // generator has not written yet are joined when flushed.
+        mapping.update("b", vec![("image_delete_time", json!("t2"))]);
+        mapping.flush().unwrap();
+        assert_eq!(read_final()["b"], json!({"UUID": "b", "image_delete_time": "t2"}));
+        assert_eq!(read_updates()[1..], [json!({"b": {"UUID": "b", "image_delete_time": "t2"}})]);
+        std::thread::sleep(Duration::from_millis(20));
+        fs::write(&mapping_path, r#"{"a": {"UUID": "a"}, "b": {"UUID": "b", "image_name": "b.jpg"}}"#).unwrap();
+        mapping.flush().unwrap();
+        assert_eq!(read_final()["b"]["image_name"], json!("b.jpg"));
+        assert_eq!(read_updates()[2]["b"]["image_name"], json!("b.jpg"));
+        mapping.flush().unwrap();
+        assert_eq!(read_updates().len(), 3);
+
+        // The last flush adds the entry that ends the run.
+        mapping.add_terminating_entry();
+        mapping.flush().unwrap();
+        let result = read_final();
+        assert_eq!(result["a"]["image_decision"], json!("Save"));
+        assert_eq!(result[TERMINATING_UUID], json!({}));
+        assert_eq!(read_updates()[3], json!({TERMINATING_UUID: {}}));
+        assert_eq!(mapping.num_decided(), 1);
+
+        // The updates file is optional.
+        let mut mapping = ImageMapping::new(mapping_path.clone(), final_path.clone(), None);
+        fs::remove_file(&updates_path).unwrap();
+        mapping.reset_files().unwrap();
+        mapping.update("a", vec![("image_decision", json!("Delete"))]);
+        mapping.flush().unwrap();
+        assert_eq!(read_final()["a"]["image_decision"], json!("Delete"));
+        assert!(!Path::new(&updates_path).exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,598 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+use zmq::Socket;
+use event_engine::plugins::Plugin;
+use event_engine::errors::EngineError;
+use event_engine::events::{Event, EventType};
+use crate::{events, config::errors::Errors};
+use crate::{shutdown, traps_utils, RuntimeCtx};
+use crate::dead_letter::{self, DeadLetterReason};
+use crate::events::{IMAGE_DELETED_PREFIX, IMAGE_PROCESSING_ERROR_PREFIX, IMAGE_RECEIVED_PREFIX, IMAGE_STORED_PREFIX,
+                    PLUGIN_STARTED_PREFIX, PLUGIN_TERMINATING_PREFIX, PLUGIN_TERMINATE_PREFIX,
+                    ImageDeletedEvent, ImageProcessingErrorEvent, ImageReceivedEvent, ImageStoredEvent,
+                    PluginStartedEvent, PluginTerminatingEvent, PluginTerminateEvent, Traceable, TraceIds};
+use crate::events_generated::gen_events;
+use crate::plugin_registry::{PluginAlarm, PluginRegistry, PluginTermination};
+use crate::shutdown::{ImageOutcome, ImageTracker};
+
+use log::{info, warn, error};
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+// Defaults used when the [plugin_monitor] configuration does not specify a value.
+const DEFAULT_START_TIMEOUT_SECS: u64 = 60;
+const DEFAULT_STATUS_INTERVAL_SECS: u64 = 300;
+const DEFAULT_TERMINATE_WAIT_SECS: u64 = 5;
+
+// How long to wait for an event before checking timers.
+const POLL_TIMEOUT_MILLIS: i64 = 1000;
+
+pub struct PluginMonitorPlugin {
+    name: String,
+    id: Uuid,
+    runctx: &'static RuntimeCtx,
+    registry: Arc<Mutex<PluginRegistry>>,
+    images: Arc<Mutex<ImageTracker>>,
+}
+impl Plugin for PluginMonitorPlugin {
+    // ---------------------------------------------------------------------------
+    // start:
+    // ---------------------------------------------------------------------------
+    /// The entry point for the plugin. The engine will start the plugin in its own
+    /// thread and execute this function.  The pub_socket is used by the plugin to
+    /// publish new events.  The sub_socket is used by the plugin to get events
+    /// published by other plugins.
+    ///
+    /// Unlike other plugins, this plugin polls its subscription socket so that it
+    /// can check start up deadlines and report status while no events arrive.
+    ///
+    /// This plugin also coordinates the graceful shutdown requested by a signal:
+    /// it waits for the images in flight to drain and then broadcasts the
+    /// wildcard PluginTerminateEvent.  From then on it waits at most
+    /// terminate_wait_secs for the other plugins to terminate.
+    fn start(
+        &self,
+        pub_socket: Socket,
+        sub_socket: Socket,
+    ) -> Result<(), EngineError> {
+
+        // Announce our arrival.
+        info!("{}", format!("{}", Errors::PluginStarted(self.name.clone(), self.get_id().hyphenated().to_string())));
+
+        // Send the plugin start up event.
+        match traps_utils::send_started_event(self, &pub_socket) {
+            Ok(_) => (),
+            Err(e) => return Err(e),
+        };
+
+        // Get the configured timers.
+        let config = self.runctx.parms.config.plugin_monitor.clone().unwrap_or_default();
+        let start_timeout = Duration::from_secs(config.start_timeout_secs.unwrap_or(DEFAULT_START_TIMEOUT_SECS));
+        let status_interval = Duration::from_secs(config.status_interval_secs.unwrap_or(DEFAULT_STATUS_INTERVAL_SECS));
+        let terminate_wait = Duration::from_secs(config.terminate_wait_secs.unwrap_or(DEFAULT_TERMINATE_WAIT_SECS));
+        let drain_timeout = Duration::from_secs(self.runctx.parms.config.shutdown.clone().unwrap_or_default()
+                                                    .drain_timeout_secs.unwrap_or(shutdown::DEFAULT_DRAIN_TIMEOUT_SECS));
+        let start_time = Instant::now();
+        let mut last_status = Instant::now();
+        let mut terminate_time: Option<Instant> = None;
+        let mut drain_time: Option<Instant> = None;
+        let mut terminate_sent = false;
+
+        // When running to completion, the run ends once the generator finishes.
+        let completion = shutdown::get_completion_config(&self.runctx.parms.config);
+        let generator = completion.as_ref().map(|c| c.generator.clone().unwrap_or(shutdown::DEFAULT_GENERATOR.to_string()));
+        let mut generator_finished = false;
+
+        // Enter our infinite work loop.
+        loop {
+            // ----------------- Check Timers ---------------------------------
+            self.check_start_timeouts(start_time.elapsed(), start_timeout);
+            if !status_interval.is_zero() && last_status.elapsed() >= status_interval {
+                self.report_status();
+                last_status = Instant::now();
+            }
+
+            // A completed run shuts down like a signal does.
+            if let Some(c) = &completion {
+                if !shutdown::is_shutdown_requested() {
+                    let reason = shutdown::check_completion(c, &self.images.lock().unwrap(), generator_finished,
+                                                            start_time.elapsed());
+                    if let Some(reason) = reason {
+                        info!("{}", Errors::CompletionReached(reason));
+                        shutdown::request_shutdown();
+                    }
+                }
+            }
+
+            // A requested shutdown stops new images, drains the images in flight
+            // and then asks every plugin, including this one, to terminate.
+            if !terminate_sent && shutdown::is_shutdown_requested() && self.is_drained(&mut drain_time, drain_timeout) {
+                self.send_terminate_event(&pub_socket);
+                terminate_sent = true;
+                terminate_time.get_or_insert_with(Instant::now);
+            }
+
+            // Once we've sent or been sent the terminate event, we wait a short time
+            // for the other plugins' terminating events so that the final status is
+            // complete.
+            if let Some(t) = terminate_time {
+                if self.registry.lock().unwrap().all_terminated() || t.elapsed() >= terminate_wait {
+                    break;
+                }
+            }
+
+            // ----------------- Wait on the Next Event -----------------------
+            // Don't wait past the end of the terminate wait.
+            let timeout = match terminate_time {
+                Some(t) => terminate_wait.saturating_sub(t.elapsed()).as_millis().clamp(1, POLL_TIMEOUT_MILLIS as u128) as i64,
+                None => POLL_TIMEOUT_MILLIS,
+            };
+            match sub_socket.poll(zmq::POLLIN, timeout) {
+                Ok(0) => continue,
+                Ok(_) => (),
+                Err(e) => {
+                    error!("{}", Errors::SocketRecvError(self.name.clone(), e.to_string()));
+                    continue;
+                }
+            }
+
+            // The bytes vector is an output parameter populated by the marshalling function
+            // with raw event bytes. The ev_in.gen_event field references these raw bytes
+            // so the bytes vector must be of a lifetime at least as long as ev_in.
+            let mut bytes: Vec<u8> = vec![];
+            let ev_in = match traps_utils::marshal_next_event(self, &sub_socket, &mut bytes) {
+                Some(ev) => ev,
+                None => continue,
+            };
+
+            // ----------------- Process Subscription Events ------------------
+            // Process events we expect; log and disregard all others.
+            match ev_in.prefix_array {
+                IMAGE_RECEIVED_PREFIX => {
+                    self.process_image_received_event(ev_in.gen_event);
+                },
+                IMAGE_STORED_PREFIX => {
+                    if let Ok(ev) = events::gen_to_event::<ImageStoredEvent>(ev_in.gen_event) {
+                        self.images.lock().unwrap().record_outcome(ev.get_image_uuid(), ImageOutcome::Stored);
+                    }
+                },
+                IMAGE_DELETED_PREFIX => {
+                    if let Ok(ev) = events::gen_to_event::<ImageDeletedEvent>(ev_in.gen_event) {
+                        self.images.lock().unwrap().record_outcome(ev.get_image_uuid(), ImageOutcome::Deleted);
+                    }
+                },
+                IMAGE_PROCESSING_ERROR_PREFIX => {
+                    self.process_image_error_event(ev_in.gen_event);
+                },
+                PLUGIN_STARTED_PREFIX => {
+                    self.process_started_event(ev_in.gen_event);
+                },
+                PLUGIN_TERMINATING_PREFIX => {
+                    if self.process_terminating_event(ev_in.gen_event, generator.as_deref()) {
+                        generator_finished = true;
+                    }
+                },
+                PLUGIN_TERMINATE_PREFIX => {
+                    // Record which plugins are expected to terminate, then determine whether
+                    // we are also the target of this terminate event.
+                    self.process_terminate_event(ev_in.gen_event);
+                    if terminate_time.is_none() &&
+                       traps_utils::process_plugin_terminate_event(ev_in.gen_event, &self.id, &self.name) {
+                        terminate_time = Some(Instant::now());
+                    }
+                },
+                unexpected => {
+                    // This should only happen for valid events to which we are not subscribed.
+                    // Completely invalid event prefixes are detected above in check_event_prefix().
+                    let pre = format!("{:?}", unexpected);
+                    let err = Errors::EventNotHandledError(self.name.clone(), pre);
+                    error!("{}", err);
+                    dead_letter::capture(&self.name, DeadLetterReason::NotHandled, &err, ev_in.raw_bytes);
+                }
+            };
+        }
+
+        // Clean up and send the terminating event.
+        self.report_status();
+        traps_utils::send_terminating_event(&self.name, self.id, &pub_socket);
+
+        // Shutting down.
+        Ok(())
+    }
+
+    /// Return the event subscriptions as a vector of event types that this plugin is interested in.
+    fn get_subscriptions(&self) -> Result<Vec<Box<dyn EventType>>, EngineError> {
+        Ok(vec![
+            Box::new(ImageReceivedEvent::new(Uuid::new_v4(), "fake".to_string())),
+            Box::new(ImageStoredEvent::new(Uuid::new_v4(), "fake".to_string(), "fake".to_string())),
+            Box::new(ImageDeletedEvent::new(Uuid::new_v4(), "fake".to_string())),
+            Box::new(ImageProcessingErrorEvent::new(Uuid::new_v4(), "fake".to_string(), "fake".to_string(),
+                                                    "fake".to_string(), "fake".to_string())),
+            Box::new(PluginStartedEvent::new(Uuid::new_v4(), String::from("PluginMonitorPlugin"))),
+            Box::new(PluginTerminatingEvent::new(Uuid::new_v4(), String::from("PluginMonitorPlugin"))),
+            Box::new(PluginTerminateEvent::new(Uuid::new_v4(), String::from("*"))),
+        ])
+    }
+
+    /// Simple accessors for this plugin.
+    fn get_name(&self) -> String {self.name.clone()}
+    fn get_id(&self) -> Uuid {self.id}
+}
+
+impl PluginMonitorPlugin {
+    // ---------------------------------------------------------------------------
+    // new:
+    // ---------------------------------------------------------------------------
+    pub fn new(runctx: &'static RuntimeCtx, registry: Arc<Mutex<PluginRegistry>>,
+               images: Arc<Mutex<ImageTracker>>) -> Self {
+        PluginMonitorPlugin {
+            name: "PluginMonitorPlugin".to_string(),
+            id: Uuid::new_v4(),
+            runctx,
+            registry,
+            images,
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // process_image_received_event:
+    // ---------------------------------------------------------------------------
+    /** Start tracking a new image.  Images are tracked from the image_recv_plugin's
+     * ImageReceivedEvent rather than the NewImageEvent so that this plugin isn't
+     * sent a copy of every image.
+     */
+    fn process_image_received_event(&self, gen_event: gen_events::Event) {
+        match events::gen_to_event::<ImageReceivedEvent>(gen_event) {
+            Ok(ev) => self.images.lock().unwrap().record_new(*ev.get_image_uuid()),
+            Err(e) => error!("{}", e),
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // process_image_error_event:
+    // ---------------------------------------------------------------------------
+    /** Record an image that failed or, during a shutdown, was rejected.  The
+     * images the image_recv_plugin abandons were never announced with an
+     * ImageReceivedEvent, so they're counted as new images here.
+     */
+    fn process_image_error_event(&self, gen_event: gen_events::Event) {
+        let ev = match events::gen_to_event::<ImageProcessingErrorEvent>(gen_event) {
+            Ok(ev) => ev,
+            Err(e) => {
+                error!("{}", e);
+                return;
+            }
+        };
+        let outcome = if ev.get_stage() == traps_utils::STAGE_SHUTDOWN {ImageOutcome::Rejected}
+                      else {ImageOutcome::Failed};
+        let abandoned = self.registry.lock().unwrap()
+                            .get_config_name(ev.get_plugin_name(), &Uuid::nil()) == Some("image_recv_plugin");
+        let mut images = self.images.lock().unwrap();
+        if abandoned {
+            images.record_abandoned(*ev.get_image_uuid(), outcome);
+        } else {
+            images.record_outcome(ev.get_image_uuid(), outcome);
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // is_drained:
+    // ---------------------------------------------------------------------------
+    /** Called once a shutdown is requested to stop new images from being
+     * accepted and then to check whether the images in flight have reached a
+     * terminal event or the drain timeout has passed.
+     */
+    fn is_drained(&self, drain_time: &mut Option<Instant>, drain_timeout: Duration) -> bool {
+        let in_flight = self.images.lock().unwrap().in_flight();
+        let started = match drain_time {
+            Some(t) => *t,
+            None => {
+                shutdown::start_draining();
+                info!("{}", Errors::ShutdownDraining(in_flight, drain_timeout.as_secs()));
+                *drain_time.insert(Instant::now())
+            }
+        };
+        if in_flight == 0 {
+            info!("{}", Errors::ShutdownDrained());
+            true
+        } else if started.elapsed() >= drain_timeout {
+            warn!("{}", Errors::ShutdownDrainTimeout(in_flight, drain_timeout.as_secs()));
+            true
+        } else {
+            false
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // send_terminate_event:
+    // ---------------------------------------------------------------------------
+    /** Broadcast the PluginTerminateEvent that targets every plugin. */
+    fn send_terminate_event(&self, pub_socket: &Socket) {
+        info!("{}", Errors::ShutdownTerminatingPlugins());
+        let mut ev = PluginTerminateEvent::new(Uuid::nil(), String::from("*"));
+        ev.set_trace_ids(TraceIds::caused_by(None, &self.name));
+        let bytes = match ev.to_bytes() {
+            Ok(b) => b,
+            Err(e) => {
+                error!("{}", Errors::EventToBytesError(self.name.clone(), ev.get_name(), e.to_string()));
+                return;
+            }
+        };
+        if let Err(e) = pub_socket.send(bytes, 0) {
+            error!("{}", Errors::SocketSendError(self.name.clone(), ev.get_name(), e.to_string()));
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // process_started_event:
+    // ---------------------------------------------------------------------------
+    fn process_started_event(&self, gen_event: crate::events_generated::gen_events::Event) {
+        let ev = match events::gen_to_event::<PluginStartedEvent>(gen_event) {
+            Ok(ev) => ev,
+            Err(e) => {
+                error!("{}", e);
+                return;
+            }
+        };
+
+        // Our own start up event is not registered.
+        if *ev.get_plugin_uuid() == self.id {return;}
+        let known = self.registry.lock().unwrap().record_started(ev.get_plugin_name(), ev.get_plugin_uuid());
+        if !known {
+            warn!("{}", Errors::PluginUnregistered(self.name.clone(), ev.get_plugin_name().clone(),
+                                                   ev.get_plugin_uuid().hyphenated().to_string()));
+        }
+        self.write_status_file();
+    }
+
+    // ---------------------------------------------------------------------------
+    // process_terminating_event:
+    // ---------------------------------------------------------------------------
+    /** Record a plugin's termination and return true if the plugin is the
+     * generator of a run to completion, whose termination announces that it
+     * has generated all its images.
+     */
+    fn process_terminating_event(&self, gen_event: crate::events_generated::gen_events::Event,
+                                 generator: Option<&str>) -> bool {
+        let ev = match events::gen_to_event::<PluginTerminatingEvent>(gen_event) {
+            Ok(ev) => ev,
+            Err(e) => {
+                error!("{}", e);
+                return false;
+            }
+        };
+
+        // Our own terminating event is not registered.
+        if *ev.get_plugin_uuid() == self.id {return false;}
+
+        // The generator is expected to terminate on its own.
+        let mut registry = self.registry.lock().unwrap();
+        let is_generator = generator.is_some_and(|g| ev.get_plugin_name() == g ||
+                               registry.get_config_name(ev.get_plugin_name(), ev.get_plugin_uuid()) == Some(g));
+        if is_generator {
+            info!("{}", Errors::CompletionGeneratorFinished(ev.get_plugin_name().clone(),
+                                                            self.images.lock().unwrap().in_flight()));
+            registry.record_terminate_request(ev.get_plugin_name(), ev.get_plugin_uuid());
+        }
+        let termination = registry.record_terminating(ev.get_plugin_name(), ev.get_plugin_uuid());
+        drop(registry);
+        match termination {
+            PluginTermination::Requested => (),
+            PluginTermination::Unexpected(alarm) => self.raise_alarm(alarm),
+            PluginTermination::Unregistered =>
+                warn!("{}", Errors::PluginUnregistered(self.name.clone(), ev.get_plugin_name().clone(),
+                                                       ev.get_plugin_uuid().hyphenated().to_string())),
+        }
+        self.write_status_file();
+        is_generator
+    }
+
+    // ---------------------------------------------------------------------------
+    // process_terminate_event:
+    // ---------------------------------------------------------------------------
+    fn process_terminate_event(&self, gen_event: crate::events_generated::gen_events::Event) {
+        match events::gen_to_event::<PluginTerminateEvent>(gen_event) {
+            Ok(ev) => self.registry.lock().unwrap()
+                          .record_terminate_request(ev.get_target_plugin_name(), ev.get_target_plugin_uuid()),
+            Err(e) => error!("{}", e),
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // check_start_timeouts:
+    // ---------------------------------------------------------------------------
+    fn check_start_timeouts(&self, elapsed: Duration, timeout: Duration) {
+        let alarms = self.registry.lock().unwrap().check_start_timeouts(elapsed, timeout);
+        for alarm in alarms {
+            self.raise_alarm(alarm);
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // raise_alarm:
+    // ---------------------------------------------------------------------------
+    fn raise_alarm(&self, alarm: PluginAlarm) {
+        let timeout = self.runctx.parms.config.plugin_monitor.clone().unwrap_or_default()
+                          .start_timeout_secs.unwrap_or(DEFAULT_START_TIMEOUT_SECS);
+        match alarm {
+            PluginAlarm::StartTimeout(name, uuid) =>
+                error!("{}", Errors::PluginStartTimeout(name, uuid.hyphenated().to_string(), timeout)),
+            PluginAlarm::UnexpectedTermination(name, uuid) =>
+                error!("{}", Errors::PluginUnexpectedTermination(name, uuid.hyphenated().to_string())),
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // report_status:
+    // ---------------------------------------------------------------------------
+    /** Log the status summary and refresh the status file. */
+    fn report_status(&self) {
+        let summary = self.registry.lock().unwrap().summary();
+        info!("{}", Errors::PluginStatusSummary(summary));
+        self.write_status_file();
+    }
+
+    // ---------------------------------------------------------------------------
+    // write_status_file:
+    // ---------------------------------------------------------------------------
+    /** Write the registry as JSON to the configured status file, if any. */
+    fn write_status_file(&self) {
+        let status_file = match &self.runctx.parms.config.plugin_monitor {
+            Some(c) => match &c.status_file {
+                Some(f) => traps_utils::get_absolute_path(f),
+                None => return,
+            },
+            None => return,
+        };
+
+        let json = match serde_json::to_string_pretty(&*self.registry.lock().unwrap()) {
+            Ok(j) => j,
+            Err(e) => {
+                error!("{}", Errors::PluginStatusWriteError(status_file, e.to_string()));
+                return;
+            }
+        };
+        if let Err(e) = traps_utils::create_or_replace_file(&status_file, json.as_bytes()) {
+            error!("{}", Errors::PluginStatusWriteError(status_file, e.to_string()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use crate::Parms;
+    use crate::config::config::{Config, PluginMonitorConfig};
+    use crate::config::layers::ConfigSources;
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: plugin_monitor_plugin.rs");
+    }
+
+    // A monitor of the image_recv_plugin and an external generator that writes
+    // its status to the given file.
+    fn new_monitor(status_file: &str) -> (PluginMonitorPlugin, Uuid, Uuid) {
+        let mut config = Config::default();
+        config.plugin_monitor = Some(PluginMonitorConfig {status_file: Some(status_file.to_string()), ..Default::default()});
+        let parms = Parms {config_file: "traps.toml".to_string(), config, sources: ConfigSources::new("traps.toml")};
+        let runctx: &'static RuntimeCtx = Box::leak(Box::new(RuntimeCtx {parms, abs_image_dir: "/tmp".to_string()}));
+        let (recv, generator) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut registry = PluginRegistry::new();
+        registry.add_expected("image_recv_plugin", "ImageReceivePlugin", recv, false);
+        registry.add_expected("ext_image_gen_plugin", "ext_image_gen_plugin", generator, true);
+        let monitor = PluginMonitorPlugin::new(runctx, Arc::new(Mutex::new(registry)),
+                                               Arc::new(Mutex::new(ImageTracker::new())));
+        (monitor, recv, generator)
+    }
+
+    // The generated event of a serialized event.
+    fn gen_event(bytes: &[u8]) -> gen_events::Event<'_> {
+        traps_utils::bytes_to_gen_event(&bytes[events::EVENT_PREFIX_LEN..]).unwrap()
+    }
+
+    fn read_status(status_file: &str) -> serde_json::Value {
+        serde_json::from_str(&fs::read_to_string(status_file).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn start_timeout() {
+        let status_file = std::env::temp_dir().join(format!("traps-monitor-{}.json", Uuid::new_v4()));
+        let status_file = status_file.to_string_lossy().to_string();
+        let (monitor, recv, _) = new_monitor(&status_file);
+        let bytes = PluginStartedEvent::new(recv, "ImageReceivePlugin".to_string()).to_bytes().unwrap();
+        monitor.process_started_event(gen_event(&bytes));
+
+        // Only the plugin that never started is alarmed.
+        monitor.check_start_timeouts(Duration::from_secs(5), Duration::from_secs(60));
+        monitor.report_status();
+        assert_eq!(read_status(&status_file)["plugins"][1]["alarmed"], false);
+        monitor.check_start_timeouts(Duration::from_secs(61), Duration::from_secs(60));
+        monitor.report_status();
+        let status = read_status(&status_file);
+        assert_eq!((&status["plugins"][0]["state"], &status["plugins"][0]["alarmed"]), (&"Started".into(), &false.into()));
+        assert_eq!((&status["plugins"][1]["state"], &status["plugins"][1]["alarmed"]), (&"Expected".into(), &true.into()));
+        let _ = fs::remove_file(&status_file);
+    }
+
+    #[test]
+    fn terminations() {
+        let status_file = std::env::temp_dir().join(format!("traps-monitor-{}.json", Uuid::new_v4()));
+        let status_file = status_file.to_string_lossy().to_string();
+        let (monitor, recv, generator) = new_monitor(&status_file);
+        for (uuid, name) in [(recv, "ImageReceivePlugin"), (generator, "ext_image_gen_plugin")] {
+            let bytes = PluginStartedEvent::new(uuid, name.to_string()).to_bytes().unwrap();
+            monitor.process_started_event(gen_event(&bytes));
+        }
+
+        // The generator of a run to completion is expected to terminate on its own.
+        let bytes = PluginTerminatingEvent::new(generator, "ext_image_gen_plugin".to_string()).to_bytes().unwrap();
+        assert!(monitor.process_terminating_event(gen_event(&bytes),
+                                                  Some("ext_image_gen_plugin")));
+        let status = read_status(&status_file);
+        assert_eq!((&status["plugins"][1]["state"], &status["plugins"][1]["alarmed"]), (&"Terminated".into(), &false.into()));
+
+        // Any other plugin that terminates without being asked raises an alarm.
+        let bytes = PluginTerminatingEvent::new(recv, "ImageReceivePlugin".to_string()).to_bytes().unwrap();
+        assert!(!monitor.process_terminating_event(gen_event(&bytes),
+                                                   Some("ext_image_gen_plugin")));
+        let status = read_status(&status_file);
+        assert_eq!((&status["plugins"][0]["state"], &status["plugins"][0]["alarmed"]), (&"Terminated".into(), &true.into()));
+        assert!(monitor.registry.lock().unwrap().all_terminated());
+        let _ = fs::remove_file(&status_file);
+    }
+
+    #[test]
+    fn status_summary() {
+        let status_file = std::env::temp_dir().join(format!("traps-monitor-{}.json", Uuid::new_v4()));
+        let status_file = status_file.to_string_lossy().to_string();
+        let (monitor, recv, generator) = new_monitor(&status_file);
+        let bytes = PluginStartedEvent::new(recv, "ImageReceivePlugin".to_string()).to_bytes().unwrap();
+        monitor.process_started_event(gen_event(&bytes));
+
+        // The summary has a line per plugin and the status file has an entry per plugin.
+        let summary = monitor.registry.lock().unwrap().summary();
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("image_recv_plugin") && lines[0].contains("internal") && lines[0].contains("Started"));
+        assert!(lines[1].contains("ext_image_gen_plugin") && lines[1].contains("external") &&
+                lines[1].contains("Expected") && lines[1].contains(&generator.hyphenated().to_string()));
+        monitor.report_status();
+        let status = read_status(&status_file);
+        assert_eq!(status["plugins"].as_array().unwrap().len(), 2);
+        assert_eq!(status["plugins"][0]["plugin_uuid"], recv.hyphenated().to_string());
+        let _ = fs::remove_file(&status_file);
+    }
+
+    #[test]
+    fn image_tracking() {
+        let status_file = std::env::temp_dir().join(format!("traps-monitor-{}.json", Uuid::new_v4()));
+        let (monitor, _, _) = new_monitor(&status_file.to_string_lossy());
+        let (stored, failed, rejected) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        // Images are tracked from their ImageReceivedEvent.
+        for uuid in [stored, failed] {
+            let bytes = ImageReceivedEvent::new(uuid, "jpg".to_string()).to_bytes().unwrap();
+            monitor.process_image_received_event(gen_event(&bytes));
+        }
+        assert_eq!(monitor.images.lock().unwrap().in_flight(), 2);
+        monitor.images.lock().unwrap().record_outcome(&stored, ImageOutcome::Stored);
+
+        // An error from a later stage finishes a received image, while an image
+        // the image_recv_plugin rejects was never received.
+        let bytes = ImageProcessingErrorEvent::new(failed, "ImageScorePlugin".to_string(), "action".to_string(),
+                                                   "E".to_string(), "failed".to_string()).to_bytes().unwrap();
+        monitor.process_image_error_event(gen_event(&bytes));
+        let bytes = ImageProcessingErrorEvent::new(rejected, "ImageReceivePlugin".to_string(),
+                                                   traps_utils::STAGE_SHUTDOWN.to_string(), "E".to_string(),
+                                                   "rejected".to_string()).to_bytes().unwrap();
+        monitor.process_image_error_event(gen_event(&bytes));
+        let images = monitor.images.lock().unwrap();
+        assert_eq!((images.new_images, images.stored, images.failed, images.rejected, images.in_flight()), (3, 1, 1, 1, 0));
+        let _ = fs::remove_file(&status_file);
+    }
+}
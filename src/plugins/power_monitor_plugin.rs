@@ -0,0 +1,899 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+use zmq::Socket;
+use event_engine::plugins::Plugin;
+use event_engine::errors::EngineError;
+use event_engine::events::EventType;
+use crate::{events, config::errors::Errors};
+use crate::{traps_utils, RuntimeCtx};
+use crate::config::config::PowerMonitorConfig;
+use crate::dead_letter::{self, DeadLetterReason};
+use crate::events::{MONITOR_POWER_START_PREFIX, MONITOR_POWER_STOP_PREFIX, PLUGIN_TERMINATE_PREFIX,
+                    MonitorPowerStartEvent, MonitorPowerStopEvent, MonitorType};
+
+use log::{info, error, warn, debug};
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+// Defaults used when the [plugins.config.power_monitor_plugin] configuration does not specify a value.
+// The roots can be pointed at fake directory trees for testing.
+const DEFAULT_LOG_DIR: &str = "~/camera-traps/power";
+const DEFAULT_PROC_ROOT: &str = "/proc";
+const DEFAULT_SYSFS_ROOT: &str = "/sys";
+const DEFAULT_SAMPLE_INTERVAL_SECS: u64 = 1;
+
+// The name of this plugin's [plugins.config] table.
+const CONFIG_NAME: &str = "power_monitor_plugin";
+
+// The files written to the log directory.  The names and formats are those used
+// by the Python power measuring plugin and its generate_power_summary.py script.
+const CPU_LOG_FILE: &str = "cpu.json";
+const DRAM_LOG_FILE: &str = "dram.json";
+const RESOURCE_LOG_FILE: &str = "resources.json";
+const METADATA_FILE: &str = "metadata.json";
+const SUMMARY_FILE: &str = "power_summary_report.json";
+
+// The timestamp format of the measurement logs, which generate_power_summary.py parses.
+const LOG_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+// The Linux USER_HZ value in which /proc reports cpu times.
+const CLOCK_TICKS_PER_SEC: f64 = 100.0;
+
+// How long to wait for an event before checking whether a sample is due.
+const POLL_TIMEOUT_MILLIS: i64 = 250;
+
+// ***************************************************************************
+// SAMPLER
+// ***************************************************************************
+/** The resources used by one process at a point in time.  The I/O counters are
+ * absent when /proc/<pid>/io is not readable.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProcessSample {
+    pub cpu_ticks: u64,
+    pub rss_bytes: u64,
+    pub read_bytes: Option<u64>,
+    pub write_bytes: Option<u64>,
+}
+
+/** The hardware domains of a powercap zone that the monitor reports. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnergyDomain {
+    Package,
+    Dram,
+}
+
+/** The cumulative energy counter of one powercap zone. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnergyZone {
+    pub zone: String,
+    pub domain: EnergyDomain,
+    pub energy_uj: u64,
+    pub max_energy_range_uj: u64,
+}
+
+/** Reads process statistics from a proc file system and energy counters from
+ * the powercap (RAPL) zones of a sysfs file system.
+ */
+#[derive(Debug, Clone)]
+pub struct Sampler {
+    proc_root: PathBuf,
+    sysfs_root: PathBuf,
+}
+
+impl Sampler {
+    // ---------------------------------------------------------------------------
+    // new:
+    // ---------------------------------------------------------------------------
+    pub fn new(proc_root: &str, sysfs_root: &str) -> Self {
+        Sampler {proc_root: PathBuf::from(proc_root), sysfs_root: PathBuf::from(sysfs_root)}
+    }
+
+    // ---------------------------------------------------------------------------
+    // read_process:
+    // ---------------------------------------------------------------------------
+    /** Read a process's cpu time (utime + stime), resident set size and I/O
+     * counters.  None is returned if the process does not exist.
+     */
+    pub fn read_process(&self, pid: i32) -> Option<ProcessSample> {
+        let dir = self.proc_root.join(pid.to_string());
+
+        // The command name can contain spaces and parentheses, so the fields
+        // are counted from the last closing parenthesis.  utime and stime are
+        // the 14th and 15th fields.
+        let stat = fs::read_to_string(dir.join("stat")).ok()?;
+        let fields: Vec<&str> = stat[stat.rfind(')')? + 1..].split_whitespace().collect();
+        let utime: u64 = fields.get(11)?.parse().ok()?;
+        let stime: u64 = fields.get(12)?.parse().ok()?;
+
+        let status = fs::read_to_string(dir.join("status")).unwrap_or_default();
+        let rss_kb = status.lines()
+            .find_map(|l| l.strip_prefix("VmRSS:"))
+            .and_then(|v| v.split_whitespace().next()?.parse::<u64>().ok())
+            .unwrap_or(0);
+
+        let io = fs::read_to_string(dir.join("io")).unwrap_or_default();
+        let counter = |name: &str| io.lines()
+            .find_map(|l| l.strip_prefix(name))
+            .and_then(|v| v.trim().parse::<u64>().ok());
+
+        Some(ProcessSample {cpu_ticks: utime + stime, rss_bytes: rss_kb * 1024,
+                            read_bytes: counter("read_bytes:"), write_bytes: counter("write_bytes:")})
+    }
+
+    // ---------------------------------------------------------------------------
+    // read_process_info:
+    // ---------------------------------------------------------------------------
+    /** Return a process's name and command line, which identify the process in
+     * the metadata and summary report.
+     */
+    pub fn read_process_info(&self, pid: i32) -> (String, String) {
+        let dir = self.proc_root.join(pid.to_string());
+        let name = fs::read_to_string(dir.join("comm")).map(|s| s.trim().to_string())
+                       .unwrap_or_else(|_| pid.to_string());
+        let command_line = fs::read(dir.join("cmdline"))
+            .map(|b| b.split(|c| *c == 0).filter(|a| !a.is_empty())
+                      .map(|a| String::from_utf8_lossy(a).to_string()).collect::<Vec<_>>().join(" "))
+            .unwrap_or_default();
+        (name, command_line)
+    }
+
+    // ---------------------------------------------------------------------------
+    // read_total_cpu_ticks:
+    // ---------------------------------------------------------------------------
+    /** Read the cpu time spent by all processors in all states, which is used to
+     * apportion package energy to processes.
+     */
+    pub fn read_total_cpu_ticks(&self) -> Option<u64> {
+        let stat = fs::read_to_string(self.proc_root.join("stat")).ok()?;
+        let line = stat.lines().find(|l| l.starts_with("cpu "))?;
+        Some(line.split_whitespace().skip(1).filter_map(|v| v.parse::<u64>().ok()).sum())
+    }
+
+    // ---------------------------------------------------------------------------
+    // read_energy:
+    // ---------------------------------------------------------------------------
+    /** Read the package and dram energy counters of the RAPL powercap zones.  An
+     * empty list is returned when powercap is not available.
+     */
+    pub fn read_energy(&self) -> Vec<EnergyZone> {
+        let powercap = self.sysfs_root.join("class").join("powercap");
+        let mut zones = vec![];
+        let entries = match fs::read_dir(&powercap) {
+            Ok(entries) => entries,
+            Err(_) => return zones,
+        };
+        for entry in entries.flatten() {
+            let zone = entry.file_name().to_string_lossy().to_string();
+            if !zone.starts_with("intel-rapl:") {
+                continue;
+            }
+            let read = |file: &str| fs::read_to_string(entry.path().join(file)).map(|s| s.trim().to_string());
+            let domain = match read("name") {
+                Ok(n) if n.starts_with("package") => EnergyDomain::Package,
+                Ok(n) if n == "dram" => EnergyDomain::Dram,
+                _ => continue,
+            };
+            let energy_uj = match read("energy_uj").ok().and_then(|v| v.parse().ok()) {
+                Some(e) => e,
+                None => continue,
+            };
+            let max_energy_range_uj = read("max_energy_range_uj").ok().and_then(|v| v.parse().ok()).unwrap_or(0);
+            zones.push(EnergyZone {zone, domain, energy_uj, max_energy_range_uj});
+        }
+        zones.sort_by(|a, b| a.zone.cmp(&b.zone));
+        zones
+    }
+}
+
+// ***************************************************************************
+// POWER MONITOR
+// ***************************************************************************
+/** The processes and devices requested by one MonitorPowerStartEvent.  A
+ * session without an end time runs until its processes are stopped.
+ */
+#[derive(Debug, Clone)]
+struct MonitorSession {
+    pids: Vec<i32>,
+    cpu: bool,
+    dram: bool,
+    start: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+}
+
+impl MonitorSession {
+    fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.start <= now && self.end.map(|end| now <= end).unwrap_or(true)
+    }
+}
+
+/** The readings taken at one sample time. */
+#[derive(Debug)]
+struct Snapshot {
+    time: DateTime<Utc>,
+    total_ticks: Option<u64>,
+    processes: HashMap<i32, ProcessSample>,
+    energy: Vec<EnergyZone>,
+}
+
+/** What the reports say about one monitored process. */
+#[derive(Debug, Default)]
+struct ProcessInfo {
+    name: String,
+    command_line: String,
+    devices: BTreeSet<&'static str>,
+}
+
+/** Samples the processes requested by MonitorPowerStartEvents and accumulates
+ * the measurement logs, metadata and summary report written to the log
+ * directory.
+ */
+#[derive(Debug)]
+pub struct PowerMonitor {
+    sampler: Sampler,
+    log_dir: PathBuf,
+    sessions: Vec<MonitorSession>,
+    previous: Option<Snapshot>,
+    processes: BTreeMap<i32, ProcessInfo>,
+    cpu_log: Vec<Value>,
+    dram_log: Vec<Value>,
+    resource_log: Vec<Value>,
+    start_time: Option<DateTime<Utc>>,
+    last_update_time: Option<DateTime<Utc>>,
+}
+
+impl PowerMonitor {
+    // ---------------------------------------------------------------------------
+    // new:
+    // ---------------------------------------------------------------------------
+    pub fn new(sampler: Sampler, log_dir: &str) -> Self {
+        PowerMonitor {sampler, log_dir: PathBuf::from(log_dir), sessions: vec![], previous: None,
+                      processes: BTreeMap::new(), cpu_log: vec![], dram_log: vec![], resource_log: vec![],
+                      start_time: None, last_update_time: None}
+    }
+
+    // ---------------------------------------------------------------------------
+    // start:
+    // ---------------------------------------------------------------------------
+    /** Begin monitoring the processes at the requested start time, or now if the
+     * start time is empty or in the past.  Zero seconds means monitor until
+     * stopped.  GPU measurements are not supported natively and are ignored.
+     */
+    pub fn start(&mut self, pids: &[i32], monitor_types: &[MonitorType], monitor_start: &str,
+                 monitor_seconds: u32, now: DateTime<Utc>) -> Result<(), Errors> {
+        let start = if monitor_start.is_empty() {
+            now
+        } else {
+            match traps_utils::timestamp_str_to_datetime(monitor_start) {
+                Ok(ts) => now.max(ts.with_timezone(&Utc)),
+                Err(e) => return Result::Err(Errors::DateParseError(monitor_start.to_string(), e.to_string())),
+            }
+        };
+        let end = match monitor_seconds {
+            0 => None,
+            secs => Some(start + chrono::Duration::seconds(secs as i64)),
+        };
+
+        let all = monitor_types.contains(&MonitorType::ALL);
+        let cpu = all || monitor_types.contains(&MonitorType::CPU);
+        let dram = all || monitor_types.contains(&MonitorType::DRAM);
+        if monitor_types.contains(&MonitorType::GPU) {
+            warn!("{}", Errors::PowerMonitorUnsupportedDevice("gpu".to_string()));
+        }
+
+        for pid in pids {
+            let info = self.processes.entry(*pid).or_insert_with(|| {
+                let (name, command_line) = self.sampler.read_process_info(*pid);
+                ProcessInfo {name, command_line, devices: BTreeSet::new()}
+            });
+            if cpu {info.devices.insert("cpu");}
+            if dram {info.devices.insert("dram");}
+        }
+        self.sessions.push(MonitorSession {pids: pids.to_vec(), cpu, dram, start, end});
+        Result::Ok(())
+    }
+
+    // ---------------------------------------------------------------------------
+    // stop:
+    // ---------------------------------------------------------------------------
+    /** Stop monitoring the processes, which is a no-op for processes that are
+     * not being monitored.  True is returned if any monitoring was stopped.
+     */
+    pub fn stop(&mut self, pids: &[i32]) -> bool {
+        let mut stopped = false;
+        for session in self.sessions.iter_mut() {
+            let before = session.pids.len();
+            session.pids.retain(|p| !pids.contains(p));
+            stopped |= session.pids.len() != before;
+        }
+        self.sessions.retain(|s| !s.pids.is_empty());
+        stopped
+    }
+
+    // ---------------------------------------------------------------------------
+    // is_monitoring:
+    // ---------------------------------------------------------------------------
+    pub fn is_monitoring(&self) -> bool {!self.sessions.is_empty()}
+
+    // ---------------------------------------------------------------------------
+    // sample:
+    // ---------------------------------------------------------------------------
+    /** Take a sample of the processes in active sessions and log the resources
+     * and power each used since the previous sample.  Sessions that have run
+     * their requested number of seconds are then ended.  True is returned if
+     * any session ended.
+     */
+    pub fn sample(&mut self, now: DateTime<Utc>) -> bool {
+        // Determine the devices measured for each active process.
+        let mut active: BTreeMap<i32, (bool, bool)> = BTreeMap::new();
+        for session in self.sessions.iter().filter(|s| s.is_active(now)) {
+            for pid in &session.pids {
+                let devices = active.entry(*pid).or_default();
+                devices.0 |= session.cpu;
+                devices.1 |= session.dram;
+            }
+        }
+
+        if active.is_empty() {
+            self.previous = None;
+        } else {
+            let snapshot = Snapshot {
+                time: now,
+                total_ticks: self.sampler.read_total_cpu_ticks(),
+                processes: active.keys().filter_map(|p| self.sampler.read_process(*p).map(|s| (*p, s))).collect(),
+                energy: self.sampler.read_energy(),
+            };
+            if let Some(previous) = self.previous.take() {
+                self.log_measurements(&previous, &snapshot, &active);
+            }
+            self.previous = Some(snapshot);
+        }
+
+        // End the sessions that have run their course.
+        let before = self.sessions.len();
+        self.sessions.retain(|s| s.end.map(|end| now < end).unwrap_or(true));
+        before != self.sessions.len()
+    }
+
+    // ---------------------------------------------------------------------------
+    // write_reports:
+    // ---------------------------------------------------------------------------
+    /** Write the measurement logs, the metadata describing them and the power
+     * summary report to the log directory.
+     */
+    pub fn write_reports(&self) -> Result<(), Errors> {
+        self.write_json(CPU_LOG_FILE, &Value::from(self.cpu_log.clone()))?;
+        if !self.dram_log.is_empty() {
+            self.write_json(DRAM_LOG_FILE, &Value::from(self.dram_log.clone()))?;
+        }
+        self.write_json(RESOURCE_LOG_FILE, &Value::from(self.resource_log.clone()))?;
+        self.write_json(METADATA_FILE, &self.metadata())?;
+        self.write_json(SUMMARY_FILE, &self.summary())
+    }
+
+    // ---------------------------------------------------------------------------
+    // log_measurements:
+    // ---------------------------------------------------------------------------
+    /** Append the entries for one sample interval to the logs.  Each process is
+     * attributed the share of package power equal to its share of all cpu time
+     * used during the interval.  The dram power is shared the same way.
+     */
+    fn log_measurements(&mut self, previous: &Snapshot, current: &Snapshot, active: &BTreeMap<i32, (bool, bool)>) {
+        let secs = (current.time - previous.time).num_milliseconds() as f64 / 1000.0;
+        if secs <= 0.0 {
+            return;
+        }
+        let package_watts = domain_watts(previous, current, EnergyDomain::Package, secs);
+        let dram_watts = domain_watts(previous, current, EnergyDomain::Dram, secs);
+        let total_ticks = match (previous.total_ticks, current.total_ticks) {
+            (Some(p), Some(c)) if c > p => Some((c - p) as f64),
+            _ => None,
+        };
+
+        let time = current.time.format(LOG_TIME_FORMAT).to_string();
+        let (mut cpu_entries, mut dram_entries, mut resource_entries) = (vec![], vec![], vec![]);
+        for (pid, (cpu, dram)) in active {
+            let (before, after) = match (previous.processes.get(pid), current.processes.get(pid)) {
+                (Some(b), Some(a)) => (b, a),
+                _ => continue,
+            };
+            let ticks = after.cpu_ticks.saturating_sub(before.cpu_ticks) as f64;
+            let share = total_ticks.map(|t| ticks / t);
+            resource_entries.push(json!({
+                "pid": pid,
+                "cpu_percent": ticks / CLOCK_TICKS_PER_SEC / secs * 100.0,
+                "rss_bytes": after.rss_bytes,
+                "read_bytes": after.read_bytes,
+                "write_bytes": after.write_bytes,
+            }));
+            if let (true, Some(watts), Some(share)) = (*cpu, package_watts, share) {
+                cpu_entries.push(json!([watts * share, pid.to_string()]));
+            }
+            if let (true, Some(watts), Some(share)) = (*dram, dram_watts, share) {
+                dram_entries.push(json!([watts * share, pid.to_string()]));
+            }
+        }
+
+        for (log, entries) in [(&mut self.cpu_log, cpu_entries), (&mut self.dram_log, dram_entries),
+                               (&mut self.resource_log, resource_entries)] {
+            if !entries.is_empty() {
+                log.push(json!({time.clone(): entries}));
+            }
+        }
+        self.start_time.get_or_insert(previous.time);
+        self.last_update_time = Some(current.time);
+    }
+
+    // ---------------------------------------------------------------------------
+    // metadata:
+    // ---------------------------------------------------------------------------
+    /** The metadata.json document that describes the monitored processes and
+     * where each device's measurements are logged.
+     */
+    fn metadata(&self) -> Value {
+        let plugins: Vec<Value> = self.plugin_pids().into_iter().map(|(name, pids)| {
+            let info = &self.processes[&pids[0]];
+            let devices: BTreeSet<&str> = pids.iter().flat_map(|p| self.processes[p].devices.iter().copied()).collect();
+            json!({"name": name, "pids": pids, "devices_measured": devices, "command_line": info.command_line})
+        }).collect();
+
+        let mut devices = vec![self.device_metadata("cpu", CPU_LOG_FILE)];
+        if !self.dram_log.is_empty() {
+            devices.push(self.device_metadata("dram", DRAM_LOG_FILE));
+        }
+        let format = |t: &Option<DateTime<Utc>>| t.map(|t| t.format(LOG_TIME_FORMAT).to_string());
+        json!({"plugins": plugins, "tools": {"devices": devices},
+               "start_time": format(&self.start_time), "last_update_time": format(&self.last_update_time)})
+    }
+
+    // ---------------------------------------------------------------------------
+    // device_metadata:
+    // ---------------------------------------------------------------------------
+    fn device_metadata(&self, device_type: &str, log_file: &str) -> Value {
+        json!({"device_type": device_type, "tool_name": "rapl", "tool_params": "",
+               "power_units": "watts", "measurement_log_path": self.log_dir.join(log_file).to_string_lossy()})
+    }
+
+    // ---------------------------------------------------------------------------
+    // summary:
+    // ---------------------------------------------------------------------------
+    /** The power_summary_report.json document computed the same way as by
+     * generate_power_summary.py:  each process's power is the average of its
+     * logged measurements and each plugin's power is the sum over its processes.
+     */
+    fn summary(&self) -> Value {
+        let mut pid_summary = vec![];
+        let mut plugin_summary = vec![];
+        for (name, pids) in self.plugin_pids() {
+            let mut plugin = Map::new();
+            plugin.insert("plugin".to_string(), json!(name));
+            plugin.insert("cpu_power_consumption".to_string(), json!(0.0));
+            plugin.insert("gpu_power_consumption".to_string(), Value::Null);
+            for pid in pids {
+                let (start_time, end_time) = log_times(&self.cpu_log, pid);
+                let cpu = average_watts(&self.cpu_log, pid);
+                let mut report = Map::new();
+                report.insert("pid".to_string(), json!(pid));
+                report.insert("plugin_name".to_string(), json!(name));
+                report.insert("start_time".to_string(), json!(start_time));
+                report.insert("end_time".to_string(), json!(end_time));
+                report.insert("cpu_power_consumption".to_string(), json!(cpu));
+                report.insert("gpu_power_consumption".to_string(), Value::Null);
+                add_watts(&mut plugin, "cpu_power_consumption", cpu);
+                if !self.dram_log.is_empty() {
+                    let dram = average_watts(&self.dram_log, pid);
+                    report.insert("dram_power_consumption".to_string(), json!(dram));
+                    add_watts(&mut plugin, "dram_power_consumption", dram);
+                }
+                pid_summary.push(Value::Object(report));
+            }
+            plugin_summary.push(Value::Object(plugin));
+        }
+        json!({"plugin power summary report": plugin_summary, "pid power summary report": pid_summary})
+    }
+
+    // ---------------------------------------------------------------------------
+    // plugin_pids:
+    // ---------------------------------------------------------------------------
+    /** The monitored process ids grouped by process name. */
+    fn plugin_pids(&self) -> BTreeMap<&str, Vec<i32>> {
+        let mut plugins: BTreeMap<&str, Vec<i32>> = BTreeMap::new();
+        for (pid, info) in &self.processes {
+            plugins.entry(&info.name).or_default().push(*pid);
+        }
+        plugins
+    }
+
+    // ---------------------------------------------------------------------------
+    // write_json:
+    // ---------------------------------------------------------------------------
+    fn write_json(&self, file: &str, value: &Value) -> Result<(), Errors> {
+        let path = self.log_dir.join(file).to_string_lossy().to_string();
+        let result = serde_json::to_vec_pretty(value).map_err(|e| e.to_string())
+            .and_then(|json| traps_utils::create_or_replace_file(&path, &json).map_err(|e| e.to_string()));
+        match result {
+            Ok(_) => Result::Ok(()),
+            Err(e) => Result::Err(Errors::PowerMonitorWriteError(path, e)),
+        }
+    }
+}
+
+// ***************************************************************************
+// PLUGIN
+// ***************************************************************************
+pub struct PowerMonitorPlugin {
+    name: String,
+    id: Uuid,
+    config: PowerMonitorConfig,
+}
+impl Plugin for PowerMonitorPlugin {
+    // ---------------------------------------------------------------------------
+    // start:
+    // ---------------------------------------------------------------------------
+    /// The entry point for the plugin. The engine will start the plugin in its own
+    /// thread and execute this function.  The pub_socket is used by the plugin to
+    /// publish new events.  The sub_socket is used by the plugin to get events
+    /// published by other plugins.
+    ///
+    /// This plugin polls its subscription socket so that the monitored processes
+    /// are sampled even when no events arrive.
+    fn start(
+        &self,
+        pub_socket: Socket,
+        sub_socket: Socket,
+    ) -> Result<(), EngineError> {
+
+        // Announce our arrival.
+        info!("{}", format!("{}", Errors::PluginStarted(self.name.clone(), self.get_id().hyphenated().to_string())));
+
+        // Determine the file locations.
+        let config = &self.config;
+        let log_dir = traps_utils::get_absolute_path(config.log_dir.as_deref().unwrap_or(DEFAULT_LOG_DIR));
+        if let Err(e) = fs::create_dir_all(&log_dir) {
+            return Err(EngineError::PluginExecutionError(self.name.clone(), self.get_id().hyphenated().to_string(),
+                                                         Errors::AppDirCreateError(log_dir, e.to_string()).to_string()));
+        }
+        let sysfs_root = config.sysfs_root.as_deref().unwrap_or(DEFAULT_SYSFS_ROOT);
+        let sampler = Sampler::new(config.proc_root.as_deref().unwrap_or(DEFAULT_PROC_ROOT), sysfs_root);
+        if sampler.read_energy().is_empty() {
+            info!("{}", Errors::PowerMonitorNoEnergyCounters(Path::new(sysfs_root).join("class/powercap")
+                                                                 .to_string_lossy().to_string()));
+        }
+        let mut monitor = PowerMonitor::new(sampler, &log_dir);
+        let interval = Duration::from_secs(config.sample_interval_secs.unwrap_or(DEFAULT_SAMPLE_INTERVAL_SECS).max(1));
+        let mut last_sample = Instant::now();
+
+        // Send the plugin start up event.
+        match traps_utils::send_started_event(self, &pub_socket) {
+            Ok(_) => (),
+            Err(e) => return Err(e),
+        };
+
+        // Enter our infinite work loop.
+        loop {
+            // ----------------- Sample Processes -----------------------------
+            if monitor.is_monitoring() && last_sample.elapsed() >= interval {
+                if monitor.sample(Utc::now()) {
+                    self.write_reports(&monitor);
+                }
+                last_sample = Instant::now();
+            }
+
+            // ----------------- Wait on the Next Event -----------------------
+            match sub_socket.poll(zmq::POLLIN, POLL_TIMEOUT_MILLIS) {
+                Ok(0) => continue,
+                Ok(_) => (),
+                Err(e) => {
+                    error!("{}", Errors::SocketRecvError(self.name.clone(), e.to_string()));
+                    continue;
+                }
+            }
+
+            // The bytes vector is an output parameter populated by the marshalling function
+            // with raw event bytes. The ev_in.gen_event field references these raw bytes
+            // so the bytes vector must be of a lifetime at least as long as ev_in.
+            let mut bytes: Vec<u8> = vec![];
+            let ev_in = match traps_utils::marshal_next_event(self, &sub_socket, &mut bytes) {
+                Some(ev) => ev,
+                None => continue,
+            };
+
+            // ----------------- Process Subscription Events ------------------
+            // Process events we expect; log and disregard all others.
+            let terminate = match ev_in.prefix_array {
+                MONITOR_POWER_START_PREFIX => {
+                    info!("\n  -> {} received event {}", self.name, String::from("MonitorPowerStartEvent"));
+                    let result = events::gen_to_event::<MonitorPowerStartEvent>(ev_in.gen_event).and_then(|ev| {
+                        monitor.start(ev.get_pids(), ev.get_monitor_types(), ev.get_monitor_start(),
+                                      ev.get_monitor_seconds(), Utc::now())
+                    });
+                    if let Err(e) = result {
+                        error!("{}", e);
+                    }
+                    false
+                },
+                MONITOR_POWER_STOP_PREFIX => {
+                    info!("\n  -> {} received event {}", self.name, String::from("MonitorPowerStopEvent"));
+                    match events::gen_to_event::<MonitorPowerStopEvent>(ev_in.gen_event) {
+                        Ok(ev) => {
+                            if monitor.stop(ev.get_pids()) {
+                                self.write_reports(&monitor);
+                            } else {
+                                debug!("{} is not monitoring pids {:?}", self.name, ev.get_pids());
+                            }
+                        },
+                        Err(e) => error!("{}", e),
+                    }
+                    false
+                },
+                PLUGIN_TERMINATE_PREFIX => {
+                    // Determine whether we are the target of this terminate event. The called method
+                    // will return true if this plugin should shutdown.
+                    info!("\n  -> {} received event {}", self.name, String::from("PluginTerminateEvent"));
+                    traps_utils::process_plugin_terminate_event(ev_in.gen_event, &self.id, &self.name)
+                },
+                unexpected => {
+                    // This should only happen for valid events to which we are not subscribed.
+                    // Completely invalid event prefixes are detected above in check_event_prefix().
+                    let pre = format!("{:?}", unexpected);
+                    let err = Errors::EventNotHandledError(self.name.clone(), pre);
+                    error!("{}", err);
+                    dead_letter::capture(&self.name, DeadLetterReason::NotHandled, &err, ev_in.raw_bytes);
+                    false
+                }
+            };
+
+            // Determine if we should terminate our event read loop.
+            if terminate {
+                break;
+            }
+        }
+
+        // Write the final reports and send the terminating event.
+        self.write_reports(&monitor);
+        traps_utils::send_terminating_event(&self.name, self.id, &pub_socket);
+
+        // Shutting down.
+        Ok(())
+    }
+
+    /// Return the event subscriptions, as a vector of strings, that this plugin is interested in.
+    fn get_subscriptions(&self) -> Result<Vec<Box<dyn EventType>>, EngineError> {
+        Ok(vec![
+            Box::new(MonitorPowerStartEvent::new(vec![0], vec![MonitorType::ALL], String::new(), 0)),
+            Box::new(MonitorPowerStopEvent::new(vec![0])),
+            Box::new(events::PluginTerminateEvent::new(Uuid::new_v4(), String::from("*"))),
+        ])
+    }
+
+    /// Simple accessors for this plugin.
+    fn get_name(&self) -> String {self.name.clone()}
+    fn get_id(&self) -> Uuid {self.id}
+}
+
+impl PowerMonitorPlugin {
+    // ---------------------------------------------------------------------------
+    // new:
+    // ---------------------------------------------------------------------------
+    /** Create the plugin with its [plugins.config.power_monitor_plugin] table. */
+    pub fn new(runctx: &'static RuntimeCtx) -> Result<Self, Errors> {
+        let config = &runctx.parms.config;
+        Result::Ok(PowerMonitorPlugin {
+            name: "PowerMonitorPlugin".to_string(),
+            id: Uuid::new_v4(),
+            config: config.get_plugin_config(CONFIG_NAME)?,
+        })
+    }
+
+    // ---------------------------------------------------------------------------
+    // write_reports:
+    // ---------------------------------------------------------------------------
+    fn write_reports(&self, monitor: &PowerMonitor) {
+        if let Err(e) = monitor.write_reports() {
+            error!("{}", e);
+        }
+    }
+}
+
+// ***************************************************************************
+// PRIVATE FUNCTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// domain_watts:
+// ---------------------------------------------------------------------------
+/** The average power of a domain's zones over an interval, accounting for
+ * counters that wrapped around.  None is returned if no zone is available.
+ */
+fn domain_watts(previous: &Snapshot, current: &Snapshot, domain: EnergyDomain, secs: f64) -> Option<f64> {
+    let mut joules = None;
+    for zone in current.energy.iter().filter(|z| z.domain == domain) {
+        let before = match previous.energy.iter().find(|z| z.zone == zone.zone) {
+            Some(b) => b.energy_uj,
+            None => continue,
+        };
+        let delta = if zone.energy_uj >= before {
+            zone.energy_uj - before
+        } else {
+            zone.max_energy_range_uj.saturating_sub(before) + zone.energy_uj
+        };
+        *joules.get_or_insert(0.0) += delta as f64 / 1_000_000.0;
+    }
+    joules.map(|j| j / secs)
+}
+
+// ---------------------------------------------------------------------------
+// log_pid_watts:
+// ---------------------------------------------------------------------------
+/** The times and watts logged for a process in a measurement log. */
+fn log_pid_watts(log: &[Value], pid: i32) -> Vec<(&str, f64)> {
+    let pid = pid.to_string();
+    let mut measurements = vec![];
+    for entry in log.iter().filter_map(|e| e.as_object()) {
+        for (time, values) in entry {
+            for value in values.as_array().into_iter().flatten() {
+                if value[1].as_str() == Some(&pid) {
+                    measurements.push((time.as_str(), value[0].as_f64().unwrap_or(0.0)));
+                }
+            }
+        }
+    }
+    measurements
+}
+
+// ---------------------------------------------------------------------------
+// average_watts:
+// ---------------------------------------------------------------------------
+fn average_watts(log: &[Value], pid: i32) -> f64 {
+    let measurements = log_pid_watts(log, pid);
+    if measurements.is_empty() {
+        return 0.0;
+    }
+    measurements.iter().map(|(_, w)| w).sum::<f64>() / measurements.len() as f64
+}
+
+// ---------------------------------------------------------------------------
+// log_times:
+// ---------------------------------------------------------------------------
+/** The first and last times a process was logged.  The log time format sorts
+ * chronologically.
+ */
+fn log_times(log: &[Value], pid: i32) -> (Option<String>, Option<String>) {
+    let measurements = log_pid_watts(log, pid);
+    let start = measurements.iter().map(|(t, _)| *t).min().map(String::from);
+    let end = measurements.iter().map(|(t, _)| *t).max().map(String::from);
+    (start, end)
+}
+
+// ---------------------------------------------------------------------------
+// add_watts:
+// ---------------------------------------------------------------------------
+fn add_watts(report: &mut Map<String, Value>, key: &str, watts: f64) {
+    let total = report.get(key).and_then(|v| v.as_f64()).unwrap_or(0.0) + watts;
+    report.insert(key.to_string(), json!(total));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Write a file in the fake root, creating its directory.
+    fn write(root: &Path, file: &str, contents: &str) {
+        let path = root.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    // Set a fake process's cpu ticks and the total cpu ticks.
+    fn set_ticks(root: &Path, pid: i32, utime: u64, stime: u64, total: u64) {
+        write(root, &format!("proc/{}/stat", pid),
+              &format!("{} (image (score)) S 1 1 1 0 -1 0 0 0 0 0 {} {} 0 0 20 0 1 0", pid, utime, stime));
+        write(root, "proc/stat", &format!("cpu  {} 0 0 0 0 0 0 0 0 0\ncpu0 1 0 0 0\n", total));
+    }
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: power_monitor_plugin.rs");
+    }
+
+    #[test]
+    fn sampler() {
+        let root = std::env::temp_dir().join(format!("power_monitor_sampler_{}", Uuid::new_v4()));
+        set_ticks(&root, 42, 30, 12, 1000);
+        write(&root, "proc/42/status", "Name:\tpython3\nVmRSS:\t    2048 kB\nThreads:\t4\n");
+        write(&root, "proc/42/io", "rchar: 10\nread_bytes: 4096\nwrite_bytes: 512\n");
+        write(&root, "proc/42/comm", "python3\n");
+        write(&root, "proc/42/cmdline", "python3\0scoring.py\0");
+        write(&root, "sys/class/powercap/intel-rapl:0/name", "package-0\n");
+        write(&root, "sys/class/powercap/intel-rapl:0/energy_uj", "5000000\n");
+        write(&root, "sys/class/powercap/intel-rapl:0/max_energy_range_uj", "262143328850\n");
+        write(&root, "sys/class/powercap/intel-rapl:0:0/name", "core\n");
+        write(&root, "sys/class/powercap/intel-rapl:0:0/energy_uj", "1\n");
+        write(&root, "sys/class/powercap/intel-rapl:0:1/name", "dram\n");
+        write(&root, "sys/class/powercap/intel-rapl:0:1/energy_uj", "7\n");
+
+        let sampler = Sampler::new(&root.join("proc").to_string_lossy(), &root.join("sys").to_string_lossy());
+        assert_eq!(sampler.read_process(42),
+                   Some(ProcessSample {cpu_ticks: 42, rss_bytes: 2048 * 1024, read_bytes: Some(4096), write_bytes: Some(512)}));
+        assert_eq!(sampler.read_process(43), None);
+        assert_eq!(sampler.read_process_info(42), ("python3".to_string(), "python3 scoring.py".to_string()));
+        assert_eq!(sampler.read_total_cpu_ticks(), Some(1000));
+        let zones = sampler.read_energy();
+        assert_eq!(zones.len(), 2);
+        assert_eq!(zones[0], EnergyZone {zone: "intel-rapl:0".to_string(), domain: EnergyDomain::Package,
+                                         energy_uj: 5000000, max_energy_range_uj: 262143328850});
+        assert_eq!(zones[1].domain, EnergyDomain::Dram);
+
+        // Missing powercap support yields no zones.
+        assert!(Sampler::new("/nonexistent", "/nonexistent").read_energy().is_empty());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn monitor_reports() {
+        let root = std::env::temp_dir().join(format!("power_monitor_reports_{}", Uuid::new_v4()));
+        let log_dir = root.join("logs");
+        fs::create_dir_all(&log_dir).unwrap();
+        write(&root, "proc/42/comm", "scoring\n");
+        write(&root, "sys/class/powercap/intel-rapl:0/name", "package-0\n");
+        write(&root, "sys/class/powercap/intel-rapl:0/max_energy_range_uj", "10000000\n");
+        let set_energy = |uj: u64| write(&root, "sys/class/powercap/intel-rapl:0/energy_uj", &uj.to_string());
+        let read_log = |file: &str| -> Value { serde_json::from_slice(&fs::read(log_dir.join(file)).unwrap()).unwrap() };
+
+        let sampler = Sampler::new(&root.join("proc").to_string_lossy(), &root.join("sys").to_string_lossy());
+        let mut monitor = PowerMonitor::new(sampler, &log_dir.to_string_lossy());
+        let t0 = DateTime::parse_from_rfc3339("2024-02-05T15:05:15+00:00").unwrap().with_timezone(&Utc);
+        let secs = |s: i64| t0 + chrono::Duration::seconds(s);
+
+        // Monitoring begins at the requested start time and lasts the requested seconds.
+        let start = secs(10).to_rfc3339();
+        assert!(monitor.start(&[42], &[MonitorType::CPU, MonitorType::GPU], &start, 4, t0).is_ok());
+        assert!(monitor.start(&[42], &[MonitorType::CPU], "yesterday", 0, t0).is_err());
+        set_ticks(&root, 42, 0, 0, 0);
+        set_energy(9000000);
+        assert!(!monitor.sample(secs(5)));
+        assert!(monitor.previous.is_none());
+        assert!(!monitor.sample(secs(10)));
+
+        // 2 joules over 2 seconds with a 25% share of the cpu time, across a wraparound.
+        set_ticks(&root, 42, 40, 10, 200);
+        set_energy(1000000);
+        assert!(!monitor.sample(secs(12)));
+        set_ticks(&root, 42, 90, 10, 400);
+        set_energy(3000000);
+        assert!(monitor.sample(secs(14)));
+        assert!(!monitor.is_monitoring());
+        monitor.write_reports().unwrap();
+
+        assert_eq!(read_log(CPU_LOG_FILE), json!([{"2024-02-05 15:05:27": [[0.25, "42"]]},
+                                                  {"2024-02-05 15:05:29": [[0.25, "42"]]}]));
+        let resources = read_log(RESOURCE_LOG_FILE);
+        assert_eq!(resources[1]["2024-02-05 15:05:29"][0]["cpu_percent"], json!(25.0));
+        assert_eq!(resources[1]["2024-02-05 15:05:29"][0]["read_bytes"], Value::Null);
+        let metadata = read_log(METADATA_FILE);
+        assert_eq!(metadata["plugins"], json!([{"name": "scoring", "pids": [42], "devices_measured": ["cpu"],
+                                                "command_line": ""}]));
+        assert_eq!(metadata["tools"]["devices"][0]["measurement_log_path"],
+                   json!(log_dir.join(CPU_LOG_FILE).to_string_lossy()));
+        assert_eq!(read_log(SUMMARY_FILE), json!({
+            "plugin power summary report": [{"plugin": "scoring", "cpu_power_consumption": 0.25,
+                                             "gpu_power_consumption": null}],
+            "pid power summary report": [{"pid": 42, "plugin_name": "scoring",
+                                          "start_time": "2024-02-05 15:05:27", "end_time": "2024-02-05 15:05:29",
+                                          "cpu_power_consumption": 0.25, "gpu_power_consumption": null}]}));
+
+        // Stopping is a no-op for processes that are not monitored.
+        assert!(monitor.start(&[42, 43], &[MonitorType::ALL], "", 0, secs(20)).is_ok());
+        assert!(!monitor.stop(&[44]));
+        assert!(monitor.stop(&[42, 43]));
+        assert!(!monitor.is_monitoring());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
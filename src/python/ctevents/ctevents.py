@@ -1,10 +1,14 @@
 from cProfile import label
 import datetime
+import hashlib
+import os
 import uuid
 from tokenize import String
 import flatbuffers
-from ctevents.gen_events import NewImageEvent, ImageReceivedEvent, ImageScoredEvent, ImageStoredEvent, ImageDeletedEvent, ImageLabelScore, PluginStartedEvent, PluginTerminateEvent, PluginTerminatingEvent, MonitorPowerStartEvent, MonitorPowerStopEvent, MonitorType
-from ctevents.gen_events import Event
+from ctevents.gen_events import NewImageEvent, ImageReceivedEvent, ImageScoredEvent, ImageStoredEvent, ImageDeletedEvent, ImageProcessingErrorEvent, DetectionEvent, ImageLabelScore, PluginStartedEvent, PluginTerminateEvent, PluginTerminatingEvent, ConfigReloadedEvent, MonitorPowerStartEvent, MonitorPowerStopEvent, MonitorType
+from ctevents.gen_events import Event, ImageReference
+from ctevents.gen_events.ImageLocationType import ImageLocationType
+from ctevents.gen_events.ImageCompression import ImageCompression
 from ctevents.gen_events.EventType import EventType
 
 # zmq and socket helper lib
@@ -18,9 +22,12 @@ PYPLUGIN_TCP_PORT = 6000
 # pub const IMAGE_SCORED_PREFIX:        [u8; 2] = [0x03, 0x00];
 # pub const IMAGE_STORED_PREFIX:        [u8; 2] = [0x04, 0x00];
 # pub const IMAGE_DELETED_PREFIX:       [u8; 2] = [0x05, 0x00];
+# pub const IMAGE_PROCESSING_ERROR_PREFIX: [u8; 2] = [0x06, 0x00];
+# pub const DETECTION_PREFIX:           [u8; 2] = [0x07, 0x00];
 # pub const PLUGIN_STARTED_PREFIX:      [u8; 2] = [0x10, 0x00];
 # pub const PLUGIN_TERMINATING_PREFIX:  [u8; 2] = [0x11, 0x00];
 # pub const PLUGIN_TERMINATE_PREFIX:    [u8; 2] = [0x12, 0x00];
+# pub const CONFIG_RELOADED_PREFIX:     [u8; 2] = [0x13, 0x00];
 # pub const MONITOR_POWER_START_PREFIX: [u8; 2] = [0x20, 0x00];
 # pub const MONITOR_POWER_STOP_PREFIX:  [u8; 2] = [0x21, 0x00];
 
@@ -30,13 +37,33 @@ EVENT_TYPE_BYTE_PREFIX = {
     "IMAGE_SCORED": b'\x03\x00',
     "IMAGE_STORED": b'\x04\x00',
     "IMAGE_DELETED": b'\x05\x00',
+    "IMAGE_PROCESSING_ERROR": b'\x06\x00',
+    "DETECTION": b'\x07\x00',
     "PLUGIN_STARTED": b'\x10\x00',
     "PLUGIN_TERMINATING": b'\x11\x00',
     "PLUGIN_TERMINATE": b'\x12\x00',
+    "CONFIG_RELOADED": b'\x13\x00',
     "MONITOR_POWER_START": b'\x20\x00',
     "MONITOR_POWER_STOP": b'\x21\x00'
 }
 
+# The schema version of resources/events.fbs that these helpers implement.  Keep in sync with
+# the ct_version attribute on the root Event table.
+EVENT_SCHEMA_MAJOR = 1
+EVENT_SCHEMA_MINOR = 7
+
+# Embedded images smaller than this many bytes are sent uncompressed even when compression is
+# requested, since compressing them saves little or nothing.  Producers can pass their own cutoff.
+IMAGE_COMPRESSION_MIN_SIZE = 64 * 1024
+
+# The optional tracing fields carried by every event and the generated function that adds each one.
+TRACE_FIELDS = {
+    "event_uuid": "AddEventUuid",
+    "correlation_id": "AddCorrelationId",
+    "causation_id": "AddCausationId",
+    "source_plugin": "AddSourcePlugin",
+}
+
 
 def _prepend_event_prefix(msg_type, fb_data):
     """
@@ -63,9 +90,114 @@ def _remove_event_prefix(data):
         del data[0]
     return data
 
-def _generate_new_image_fb_event(uuid: String, format: String, image: bytearray) -> bytearray:
+def _add_schema_version(builder):
+    """
+    Add our schema version to the root Event table being built.
+    """
+    Event.AddSchemaMajor(builder, EVENT_SCHEMA_MAJOR)
+    Event.AddSchemaMinor(builder, EVENT_SCHEMA_MINOR)
+
+def _check_schema_version(event):
+    """
+    Raise an exception if the root event was written with a different major schema version.  Events
+    from newer minor versions are accepted and the fields we don't know about are ignored.
+    """
+    if event.SchemaMajor() != EVENT_SCHEMA_MAJOR:
+        try:
+            sender = get_trace(_event_to_typed_event(event))["source_plugin"] or "<unknown>"
+        except Exception:
+            sender = "<unknown>"
+        raise Exception(f"Rejected event from sender {sender} with incompatible schema version "
+                        f"{event.SchemaMajor()}.{event.SchemaMinor()}; expected major version {EVENT_SCHEMA_MAJOR}")
+
+def _create_trace_fields(builder, trace: dict):
+    """
+    Create the optional tracing strings of an event before its table is started.  The trace dict
+    may contain the keys event_uuid, correlation_id, causation_id and source_plugin; missing keys
+    and a None trace leave the fields unset.
+    """
+    trace_fb = {}
+    for key, value in (trace or {}).items():
+        if key not in TRACE_FIELDS:
+            raise Exception(f"Unrecognized trace field {key}")
+        if value is not None:
+            trace_fb[key] = builder.CreateString(str(value))
+    return trace_fb
+
+def _add_trace_fields(event_module, builder, trace_fb: dict):
+    """
+    Add the tracing strings created by _create_trace_fields to the event table being built.
+    """
+    for key, add_fn in TRACE_FIELDS.items():
+        if key in trace_fb:
+            getattr(event_module, add_fn)(builder, trace_fb[key])
+
+def get_trace(event) -> dict:
+    """
+    Return the tracing fields of a typed event as a dict; absent fields are None.
+    """
+    return {
+        "event_uuid": _decode(event.EventUuid()),
+        "correlation_id": _decode(event.CorrelationId()),
+        "causation_id": _decode(event.CausationId()),
+        "source_plugin": _decode(event.SourcePlugin()),
+    }
+
+def response_trace(event, plugin_name: str) -> dict:
+    """
+    Return the tracing fields for an event that plugin_name publishes in response to the typed
+    event.  The response inherits the event's correlation_id, or its event_uuid if the event has
+    no correlation_id, and records the event as its cause.  Pass event=None to start a new chain.
+    """
+    event_uuid = str(uuid.uuid4())
+    cause = get_trace(event) if event is not None else {}
+    return {
+        "event_uuid": event_uuid,
+        "correlation_id": cause.get("correlation_id") or cause.get("event_uuid") or event_uuid,
+        "causation_id": cause.get("event_uuid"),
+        "source_plugin": plugin_name,
+    }
+
+def _decode(value):
+    return value.decode('utf-8') if isinstance(value, bytes) else value
+
+def compress_image(image, compression: int = ImageCompression.Uncompressed,
+                   min_size: int = IMAGE_COMPRESSION_MIN_SIZE):
     """
-    Create a new image event flatbuffers object
+    Compress the image with an ImageCompression algorithm unless it is smaller than min_size bytes.
+    Returns the possibly compressed image and the ImageCompression value actually used.  Zstd
+    requires the zstandard package and Lz4 requires the lz4 package.
+    """
+    if compression == ImageCompression.Uncompressed or len(image) < min_size:
+        return image, ImageCompression.Uncompressed
+    if compression == ImageCompression.Zstd:
+        import zstandard
+        return zstandard.ZstdCompressor().compress(bytes(image)), compression
+    if compression == ImageCompression.Lz4:
+        import lz4.frame
+        return lz4.frame.compress(bytes(image)), compression
+    raise Exception(f"Unknown image compression {compression}")
+
+def decompress_image(image, compression: int) -> bytes:
+    """
+    Decompress an image that was compressed with an ImageCompression algorithm.
+    """
+    if compression == ImageCompression.Uncompressed:
+        return bytes(image)
+    if compression == ImageCompression.Zstd:
+        import zstandard
+        return zstandard.ZstdDecompressor().decompress(bytes(image))
+    if compression == ImageCompression.Lz4:
+        import lz4.frame
+        return lz4.frame.decompress(bytes(image))
+    raise Exception(f"Unknown image compression {compression}")
+
+def _generate_new_image_fb_event(uuid: String, format: String, image: bytearray, trace: dict = None,
+                                 compression: int = ImageCompression.Uncompressed,
+                                 min_compress_size: int = IMAGE_COMPRESSION_MIN_SIZE) -> bytearray:
+    """
+    Create a new image event flatbuffers object.  The image is compressed with the ImageCompression
+    algorithm if it is at least min_compress_size bytes.
     """
     # everything we do will utilize a builder; we can look at whether it would be better to share a singleton
     # builder later.
@@ -77,6 +209,7 @@ def _generate_new_image_fb_event(uuid: String, format: String, image: bytearray)
     ts_fb = builder.CreateString(ts)
     uuid_fb = builder.CreateString(uuid)
     format_fb = builder.CreateString(format)
+    image, compression = compress_image(image, compression, min_compress_size)
     # for vectors, you need to call a "Start" method with the builder and the total length you plan to add
     NewImageEvent.NewImageEventStartImageVector(builder, len(image))
     # add the bytes one at a time and be sure to loop over bytes of image in reverse order so they are added
@@ -96,11 +229,14 @@ def _generate_new_image_fb_event(uuid: String, format: String, image: bytearray)
     #    2) AddField1(builder, data)
     #    3) ... add more fields
     #    4) End(builder)
+    trace_fb = _create_trace_fields(builder, trace=trace)
     NewImageEvent.Start(builder)
     NewImageEvent.AddEventCreateTs(builder, ts_fb)
     NewImageEvent.AddImageUuid(builder, uuid_fb)
     NewImageEvent.AddImageFormat(builder, format_fb)
     NewImageEvent.AddImage(builder, image_fb)
+    NewImageEvent.AddImageCompression(builder, compression)
+    _add_trace_fields(NewImageEvent, builder, trace_fb)
     new_image_event = NewImageEvent.End(builder)
 
     # ----- Create the "root" Event object -----
@@ -109,30 +245,145 @@ def _generate_new_image_fb_event(uuid: String, format: String, image: bytearray)
     Event.Start(builder)
     Event.EventAddEventType(builder, EventType.NewImageEvent)
     Event.AddEvent(builder, new_image_event)
+    _add_schema_version(builder)
     root_event = Event.End(builder)
 
     # call finish to instruct the builder that we are done
     builder.Finish(root_event)
     return builder.Output() # Of type `bytearray`
 
-def _generate_new_image_fb_with_prefix(uuid: String, format: String, image: bytearray) -> bytearray:
+def _generate_new_image_fb_with_prefix(uuid: String, format: String, image: bytearray, trace: dict = None,
+                                      compression: int = ImageCompression.Uncompressed,
+                                      min_compress_size: int = IMAGE_COMPRESSION_MIN_SIZE) -> bytearray:
     """
     Create a new image event message with prefix.
     """
-    fb = _generate_new_image_fb_event(uuid, format, image)
+    fb = _generate_new_image_fb_event(uuid, format, image, trace=trace, compression=compression,
+                                      min_compress_size=min_compress_size)
     return _prepend_event_prefix("NEW_IMAGE", fb)
 
-def send_new_image_fb_event(socket, uuid: String, format: String, image: bytearray) -> str:
+def send_new_image_fb_event(socket, uuid: String, format: String, image: bytearray, trace: dict = None,
+                            compression: int = ImageCompression.Uncompressed,
+                            min_compress_size: int = IMAGE_COMPRESSION_MIN_SIZE) -> str:
     """
-    Send a new image event over the zmq socket.
+    Send a new image event over the zmq socket.  The image is compressed with the ImageCompression
+    algorithm if it is at least min_compress_size bytes.
     Returns a string which is the reply from the event-engine thread or raises an 
     exception on error.
     """
-    data = _generate_new_image_fb_with_prefix(uuid, format, image)
+    data = _generate_new_image_fb_with_prefix(uuid, format, image, trace=trace, compression=compression,
+                                              min_compress_size=min_compress_size)
     # send the message over the socket
     return publish_msg(socket, data)
 
-def _generate_image_received_fb_event(image_uuid: String, image_format: String) -> bytearray:
+def _generate_new_image_ref_fb_event(uuid: String, format: String, location_type: int, location: String,
+                                     size: int, checksum: String, trace: dict = None) -> bytearray:
+    """
+    Create a new image event flatbuffers object that references the image instead of embedding it.
+    The location_type is an ImageLocationType value, the size is the image length in bytes and the
+    checksum is the lowercase hex SHA-256 digest of the image.
+    """
+    builder = flatbuffers.Builder(1024)
+
+    ts = datetime.datetime.utcnow().isoformat()
+    ts_fb = builder.CreateString(ts)
+    uuid_fb = builder.CreateString(uuid)
+    format_fb = builder.CreateString(format)
+    location_fb = builder.CreateString(location)
+    checksum_fb = builder.CreateString(checksum)
+    # the image vector is required but empty when the image is referenced
+    NewImageEvent.StartImageVector(builder, 0)
+    image_fb = builder.EndVector()
+
+    ImageReference.Start(builder)
+    ImageReference.AddLocationType(builder, location_type)
+    ImageReference.AddLocation(builder, location_fb)
+    ImageReference.AddSize(builder, size)
+    ImageReference.AddChecksum(builder, checksum_fb)
+    image_ref = ImageReference.End(builder)
+
+    trace_fb = _create_trace_fields(builder, trace)
+    NewImageEvent.Start(builder)
+    NewImageEvent.AddEventCreateTs(builder, ts_fb)
+    NewImageEvent.AddImageUuid(builder, uuid_fb)
+    NewImageEvent.AddImageFormat(builder, format_fb)
+    NewImageEvent.AddImage(builder, image_fb)
+    NewImageEvent.AddImageRef(builder, image_ref)
+    _add_trace_fields(NewImageEvent, builder, trace_fb)
+    new_image_event = NewImageEvent.End(builder)
+
+    Event.Start(builder)
+    Event.EventAddEventType(builder, EventType.NewImageEvent)
+    Event.AddEvent(builder, new_image_event)
+    _add_schema_version(builder)
+    root_event = Event.End(builder)
+
+    builder.Finish(root_event)
+    return builder.Output()
+
+def _generate_new_image_ref_fb_with_prefix(uuid: String, format: String, location_type: int, location: String,
+                                           size: int, checksum: String, trace: dict = None) -> bytearray:
+    """
+    Create a by-reference new image event message with prefix.
+    """
+    fb = _generate_new_image_ref_fb_event(uuid, format, location_type, location, size, checksum, trace=trace)
+    return _prepend_event_prefix("NEW_IMAGE", fb)
+
+def send_new_image_file_ref_fb_event(socket, uuid: String, format: String, path: String, trace: dict = None) -> str:
+    """
+    Send a new image event that references the image file at the absolute path over the zmq socket.
+    The image is read once to calculate its size and checksum.  The file must remain in place until
+    the image has been received.
+    Returns a string which is the reply from the event-engine thread or raises an 
+    exception on error.
+    """
+    with open(path, 'rb') as f:
+        image = f.read()
+    data = _generate_new_image_ref_fb_with_prefix(uuid, format, ImageLocationType.FilePath, os.path.abspath(path),
+                                                  len(image), image_checksum(image), trace=trace)
+    return publish_msg(socket, data)
+
+def image_checksum(image) -> str:
+    """
+    Return the checksum used in image references, which is the lowercase hex SHA-256 digest.
+    """
+    return hashlib.sha256(image).hexdigest()
+
+def resolve_new_image(new_image_event, content_dir: String = None) -> bytes:
+    """
+    Return the image of a typed NewImageEvent whether it is embedded or referenced.  Embedded
+    images are decompressed if necessary.  Referenced images are verified against their size and
+    checksum; ContentHash references are resolved in content_dir and SharedMemory references in
+    /dev/shm.
+    """
+    image_ref = new_image_event.ImageRef()
+    if image_ref is None:
+        image = bytes(new_image_event.Image(i) for i in range(new_image_event.ImageLength()))
+        return decompress_image(image, new_image_event.ImageCompression())
+    location = _decode(image_ref.Location())
+    location_type = image_ref.LocationType()
+    if location_type == ImageLocationType.FilePath:
+        if not os.path.isabs(location):
+            raise Exception(f"Image reference {location} must be an absolute path")
+        path = location
+    else:
+        if not location or '/' in location or location in ('.', '..'):
+            raise Exception(f"Image reference {location} must be a simple name")
+        if location_type == ImageLocationType.SharedMemory:
+            path = os.path.join('/dev/shm', location)
+        elif content_dir is None:
+            raise Exception(f"Image reference {location} requires a content directory")
+        else:
+            path = os.path.join(content_dir, location)
+    with open(path, 'rb') as f:
+        image = f.read()
+    if len(image) != image_ref.Size():
+        raise Exception(f"Image reference {location} expected {image_ref.Size()} bytes but read {len(image)}")
+    if image_checksum(image) != _decode(image_ref.Checksum()).lower():
+        raise Exception(f"Image reference {location} has an invalid checksum")
+    return image
+
+def _generate_image_received_fb_event(image_uuid: String, image_format: String, trace: dict = None) -> bytearray:
     builder = flatbuffers.Builder(1024)
     
     ts = datetime.datetime.utcnow().isoformat()
@@ -140,29 +391,32 @@ def _generate_image_received_fb_event(image_uuid: String, image_format: String)
     uuid_fb = builder.CreateString(image_uuid)
     format_fb = builder.CreateString(image_format)
 
+    trace_fb = _create_trace_fields(builder, trace=trace)
     ImageReceivedEvent.Start(builder)
     ImageReceivedEvent.AddEventCreateTs(builder, ts_fb)
     ImageReceivedEvent.AddImageUuid(builder, uuid_fb)
     ImageReceivedEvent.AddImageFormat(builder, format_fb)
 
+    _add_trace_fields(ImageReceivedEvent, builder, trace_fb)
     image_received_event = ImageReceivedEvent.End(builder)
 
     Event.Start(builder)
     Event.EventAddEventType(builder, EventType.ImageReceivedEvent)
     Event.AddEvent(builder,image_received_event)
+    _add_schema_version(builder)
     root_event = Event.End(builder)
 
     builder.Finish(root_event)
     return builder.Output()
 
-def _generate_image_received_fb_with_prefix(image_uuid, image_format):
+def _generate_image_received_fb_with_prefix(image_uuid, image_format, trace: dict = None):
     """
     Create an image received event message with prefix.
     """
-    fb = _generate_image_received_fb_event(image_uuid, image_format)
+    fb = _generate_image_received_fb_event(image_uuid, image_format, trace=trace)
     return _prepend_event_prefix("IMAGE_RECEIVED", fb)
 
-def _generate_image_scored_fb_event(image_uuid, image_format, scores: "list(dict)"):
+def _generate_image_scored_fb_event(image_uuid, image_format, scores: "list(dict)", trace: dict = None):
     """
     Create a new image scored event flatubuffers object
     """    
@@ -191,41 +445,46 @@ def _generate_image_scored_fb_event(image_uuid, image_format, scores: "list(dict
     for s in reversed(image_label_scores):
         builder.PrependUOffsetTRelative(s)
     scores_fb_vector = builder.EndVector()
+    trace_fb = _create_trace_fields(builder, trace=trace)
     ImageScoredEvent.Start(builder)
     ImageScoredEvent.AddScores(builder, scores_fb_vector)
     ImageScoredEvent.AddEventCreateTs(builder, ts_fb)
     ImageScoredEvent.AddImageUuid(builder, uuid_fb)
     ImageScoredEvent.AddImageFormat(builder, format_fb)
+    _add_trace_fields(ImageScoredEvent, builder, trace_fb)
     image_scored_event = ImageScoredEvent.End(builder)
 
     Event.Start(builder)
     Event.EventAddEventType(builder, EventType.ImageScoredEvent)
     Event.AddEvent(builder, image_scored_event)
+    _add_schema_version(builder)
     root_event = Event.End(builder)
 
     # call finish to instruct the builder that we are done
     builder.Finish(root_event)
     return builder.Output() # Of type `bytearray`
 
-def _generate_image_scored_fb_with_prefix(image_uuid, image_format, scores: "list(dict)") -> bytearray:
+def _generate_image_scored_fb_with_prefix(image_uuid, image_format, scores: "list(dict)", trace: dict = None) -> bytearray:
     """
     Create an image scored event message with prefix.
     """
-    fb = _generate_image_scored_fb_event(image_uuid, image_format, scores)
+    fb = _generate_image_scored_fb_event(image_uuid, image_format, scores, trace=trace)
     return _prepend_event_prefix("IMAGE_SCORED", fb)
 
-def send_image_scored_fb_event(socket, image_uuid, image_format, scores: "list(dict)") -> str:
+def send_image_scored_fb_event(socket, image_uuid, image_format, scores: "list(dict)", trace: dict = None) -> str:
     """
     Send an image scored event over the zmq socket.
     Returns a string which is the reply from the event-engine thread or raises an 
     exception on error.
     """
-    fb_data = _generate_image_scored_fb_with_prefix(image_uuid, image_format, scores)
+    fb_data = _generate_image_scored_fb_with_prefix(image_uuid, image_format, scores, trace=trace)
     return publish_msg(socket, fb_data)
 
-def _generate_store_image_fb_event(image_uuid: String, image_format: String, destination: String)-> bytearray:
+def _generate_store_image_fb_event(image_uuid: String, image_format: String, destination: String,
+                                   location: String = None, trace: dict = None)-> bytearray:
     """
-    Create a new event to indicate image has been written to external destination
+    Create a new event to indicate image has been written to external destination.
+    The optional location is where the image was stored, such as its file path.
     """
     builder = flatbuffers.Builder(1024)
 
@@ -234,41 +493,50 @@ def _generate_store_image_fb_event(image_uuid: String, image_format: String, des
     image_uuid_fb = builder.CreateString(image_uuid)
     destination_fb = builder.CreateString(destination)
     image_format_fb = builder.CreateString(image_format)
+    location_fb = None
+    if location is not None:
+        location_fb = builder.CreateString(location)
 
+    trace_fb = _create_trace_fields(builder, trace=trace)
     ImageStoredEvent.Start(builder)
     ImageStoredEvent.AddEventCreateTs(builder, ts_fb)
     ImageStoredEvent.AddImageUuid(builder, image_uuid_fb)
     ImageStoredEvent.AddDestination(builder, destination_fb)
     ImageStoredEvent.AddImageFormat(builder, image_format_fb)
+    if location_fb is not None:
+        ImageStoredEvent.AddLocation(builder, location_fb)
     
+    _add_trace_fields(ImageStoredEvent, builder, trace_fb)
     image_stored_event = ImageStoredEvent.End(builder)
 
     # -- root object --
     Event.Start(builder)
     Event.EventAddEventType(builder, EventType.ImageStoredEvent)
     Event.AddEvent(builder, image_stored_event)
+    _add_schema_version(builder)
     root_event = Event.End(builder)
 
     builder.Finish(root_event)
     return builder.Output()
 
-def _generate_store_image_fb_with_prefix(image_uuid: String, image_format: String, destination: String) -> bytearray:
+def _generate_store_image_fb_with_prefix(image_uuid: String, image_format: String, destination: String,
+                                         location: String = None, trace: dict = None) -> bytearray:
     """
     Create a store image event message with prefix.
     """
-    fb = _generate_store_image_fb_event(image_uuid, image_format, destination)
+    fb = _generate_store_image_fb_event(image_uuid, image_format, destination, location=location, trace=trace)
     return _prepend_event_prefix("IMAGE_STORED", fb)
 
-def send_store_image_fb_event(socket, image_uuid, destination) -> str:
+def send_store_image_fb_event(socket, image_uuid, destination, location: String = None, trace: dict = None) -> str:
     """
     Send a store image event over the zmq socket.
     Returns a string which is the reply from the event-engine thread or raises an 
     exception on error.
     """
-    fb_data = _generate_store_image_fb_with_prefix(image_uuid, destination)
+    fb_data = _generate_store_image_fb_with_prefix(image_uuid, destination, location=location, trace=trace)
     return publish_msg(socket, fb_data)
 
-def _generate_delete_image_fb_event(image_uuid: String, image_format: String)-> bytearray:
+def _generate_delete_image_fb_event(image_uuid: String, image_format: String, trace: dict = None)-> bytearray:
     """
     Create an event that indicates image has been deleted from database
     """
@@ -279,39 +547,163 @@ def _generate_delete_image_fb_event(image_uuid: String, image_format: String)->
     image_uuid_fb = builder.CreateString(image_uuid)
     image_format_fb = builder.CreateString(image_format)
 
+    trace_fb = _create_trace_fields(builder, trace=trace)
     ImageDeletedEvent.Start(builder)
     ImageDeletedEvent.AddEventCreateTs(builder, ts_fb)
     ImageDeletedEvent.AddImageUuid(builder, image_uuid_fb)
     ImageDeletedEvent.AddImageFormat(builder, image_format_fb)
 
+    _add_trace_fields(ImageDeletedEvent, builder, trace_fb)
     image_deleted_event = ImageDeletedEvent.End(builder)
 
     # -- root object --
     Event.Start(builder)
     Event.EventAddEventType(builder, EventType.ImageDeletedEvent)
     Event.AddEvent(builder, image_deleted_event)
+    _add_schema_version(builder)
     root_event = Event.End(builder)
 
     builder.Finish(root_event)
     return builder.Output()
 
-def _generate_delete_image_fb_with_prefix(image_uuid: String, image_format: String) -> bytearray:
+def _generate_delete_image_fb_with_prefix(image_uuid: String, image_format: String, trace: dict = None) -> bytearray:
     """
     Create a delete image event message with prefix.
     """
-    fb = _generate_delete_image_fb_event(image_uuid, image_format)
+    fb = _generate_delete_image_fb_event(image_uuid, image_format, trace=trace)
     return _prepend_event_prefix("IMAGE_DELETED", fb)
 
-def send_delete_image_fb_event(socket, image_uuid) -> str:
+def send_delete_image_fb_event(socket, image_uuid, trace: dict = None) -> str:
     """
     Send a delete image event over the zmq socket.
     Returns a string which is the reply from the event-engine thread or raises an 
     exception on error.
     """
-    fb_data = _generate_delete_image_fb_with_prefix(image_uuid)
+    fb_data = _generate_delete_image_fb_with_prefix(image_uuid, trace=trace)
+    return publish_msg(socket, fb_data)
+
+def _generate_image_processing_error_fb_event(image_uuid: String, plugin_name: String, stage: String,
+                                              error_code: String, error_message: String,
+                                              trace: dict = None) -> bytearray:
+    """
+    Create an event that indicates a plugin abandoned the processing of an image
+    """
+    builder = flatbuffers.Builder(1024)
+
+    ts = datetime.datetime.utcnow().isoformat()
+    ts_fb = builder.CreateString(ts)
+    image_uuid_fb = builder.CreateString(image_uuid)
+    plugin_name_fb = builder.CreateString(plugin_name)
+    stage_fb = builder.CreateString(stage)
+    error_code_fb = builder.CreateString(error_code)
+    error_message_fb = builder.CreateString(error_message)
+
+    trace_fb = _create_trace_fields(builder, trace=trace)
+    ImageProcessingErrorEvent.Start(builder)
+    ImageProcessingErrorEvent.AddEventCreateTs(builder, ts_fb)
+    ImageProcessingErrorEvent.AddImageUuid(builder, image_uuid_fb)
+    ImageProcessingErrorEvent.AddPluginName(builder, plugin_name_fb)
+    ImageProcessingErrorEvent.AddStage(builder, stage_fb)
+    ImageProcessingErrorEvent.AddErrorCode(builder, error_code_fb)
+    ImageProcessingErrorEvent.AddErrorMessage(builder, error_message_fb)
+
+    _add_trace_fields(ImageProcessingErrorEvent, builder, trace_fb)
+    image_processing_error_event = ImageProcessingErrorEvent.End(builder)
+
+    # -- root object --
+    Event.Start(builder)
+    Event.EventAddEventType(builder, EventType.ImageProcessingErrorEvent)
+    Event.AddEvent(builder, image_processing_error_event)
+    _add_schema_version(builder)
+    root_event = Event.End(builder)
+
+    builder.Finish(root_event)
+    return builder.Output()
+
+def _generate_image_processing_error_fb_with_prefix(image_uuid: String, plugin_name: String, stage: String,
+                                                    error_code: String, error_message: String,
+                                                    trace: dict = None) -> bytearray:
+    """
+    Create an image processing error event message with prefix.
+    """
+    fb = _generate_image_processing_error_fb_event(image_uuid, plugin_name, stage, error_code, error_message,
+                                                   trace=trace)
+    return _prepend_event_prefix("IMAGE_PROCESSING_ERROR", fb)
+
+def send_image_processing_error_fb_event(socket, image_uuid, plugin_name, stage, error_code, error_message,
+                                         trace: dict = None) -> str:
+    """
+    Send an image processing error event over the zmq socket.  Plugins send this event when
+    they abandon an image so that every image can be accounted for.
+    Returns a string which is the reply from the event-engine thread or raises an 
+    exception on error.
+    """
+    fb_data = _generate_image_processing_error_fb_with_prefix(image_uuid, plugin_name, stage, error_code,
+                                                              error_message, trace=trace)
+    return publish_msg(socket, fb_data)
+
+def _generate_detection_fb_event(image_uuid: String, image_format: String, label: String, probability: float,
+                                 threshold: float, stored_location: String = None, trace: dict = None) -> bytearray:
+    """
+    Create an event that indicates an image was scored above a label's detection threshold
+    """
+    builder = flatbuffers.Builder(1024)
+
+    ts = datetime.datetime.utcnow().isoformat()
+    ts_fb = builder.CreateString(ts)
+    image_uuid_fb = builder.CreateString(image_uuid)
+    image_format_fb = builder.CreateString(image_format)
+    label_fb = builder.CreateString(label)
+    stored_location_fb = None
+    if stored_location is not None:
+        stored_location_fb = builder.CreateString(stored_location)
+
+    trace_fb = _create_trace_fields(builder, trace=trace)
+    DetectionEvent.Start(builder)
+    DetectionEvent.AddEventCreateTs(builder, ts_fb)
+    DetectionEvent.AddImageUuid(builder, image_uuid_fb)
+    DetectionEvent.AddImageFormat(builder, image_format_fb)
+    DetectionEvent.AddLabel(builder, label_fb)
+    DetectionEvent.AddProbability(builder, probability)
+    DetectionEvent.AddThreshold(builder, threshold)
+    if stored_location_fb is not None:
+        DetectionEvent.AddStoredLocation(builder, stored_location_fb)
+
+    _add_trace_fields(DetectionEvent, builder, trace_fb)
+    detection_event = DetectionEvent.End(builder)
+
+    # -- root object --
+    Event.Start(builder)
+    Event.EventAddEventType(builder, EventType.DetectionEvent)
+    Event.AddEvent(builder, detection_event)
+    _add_schema_version(builder)
+    root_event = Event.End(builder)
+
+    builder.Finish(root_event)
+    return builder.Output()
+
+def _generate_detection_fb_with_prefix(image_uuid: String, image_format: String, label: String, probability: float,
+                                       threshold: float, stored_location: String = None,
+                                       trace: dict = None) -> bytearray:
+    """
+    Create a detection event message with prefix.
+    """
+    fb = _generate_detection_fb_event(image_uuid, image_format, label, probability, threshold,
+                                      stored_location=stored_location, trace=trace)
+    return _prepend_event_prefix("DETECTION", fb)
+
+def send_detection_fb_event(socket, image_uuid, image_format, label, probability, threshold,
+                            stored_location: String = None, trace: dict = None) -> str:
+    """
+    Send a detection event over the zmq socket.
+    Returns a string which is the reply from the event-engine thread or raises an 
+    exception on error.
+    """
+    fb_data = _generate_detection_fb_with_prefix(image_uuid, image_format, label, probability, threshold,
+                                                 stored_location=stored_location, trace=trace)
     return publish_msg(socket, fb_data)
 
-def _generate_start_plugin_fb_event(plugin_name: String, plugin_uuid: String)-> bytearray:
+def _generate_start_plugin_fb_event(plugin_name: String, plugin_uuid: String, trace: dict = None)-> bytearray:
     """
     Create a plugin started event flatbuffers object
     """
@@ -322,39 +714,42 @@ def _generate_start_plugin_fb_event(plugin_name: String, plugin_uuid: String)->
     plugin_name_fb = builder.CreateString(plugin_name)
     plugin_uuid_fb = builder.CreateString(plugin_uuid)
 
+    trace_fb = _create_trace_fields(builder, trace=trace)
     PluginStartedEvent.Start(builder)
     PluginStartedEvent.AddEventCreateTs(builder, ts_fb)
     PluginStartedEvent.AddPluginUuid(builder, plugin_uuid_fb)
     PluginStartedEvent.AddPluginName(builder, plugin_name_fb)
     
+    _add_trace_fields(PluginStartedEvent, builder, trace_fb)
     plugin_started_event = PluginStartedEvent.End(builder)
 
     # -- root object --
     Event.Start(builder)
     Event.EventAddEventType(builder, EventType.PluginStartedEvent)
     Event.AddEvent(builder, plugin_started_event)
+    _add_schema_version(builder)
     root_event = Event.End(builder)
 
     builder.Finish(root_event)
     return builder.Output()
 
-def _generate_start_plugin_fb_with_prefix(plugin_name: String, plugin_uuid: String) -> bytearray:
+def _generate_start_plugin_fb_with_prefix(plugin_name: String, plugin_uuid: String, trace: dict = None) -> bytearray:
     """
     Create a start plugin event message with prefix.
     """
-    fb = _generate_start_plugin_fb_event(plugin_name, plugin_uuid)
+    fb = _generate_start_plugin_fb_event(plugin_name, plugin_uuid, trace=trace)
     return _prepend_event_prefix("PLUGIN_STARTED", fb)
 
-def send_start_plugin_fb_event(socket, plugin_name, plugin_uuid) -> str:
+def send_start_plugin_fb_event(socket, plugin_name, plugin_uuid, trace: dict = None) -> str:
     """
     Send a start plugin event over the zmq socket.
     Returns a string which is the reply from the event-engine thread or raises an 
     exception on error.
     """
-    fb_data = _generate_store_image_fb_with_prefix(plugin_name, plugin_uuid)
+    fb_data = _generate_store_image_fb_with_prefix(plugin_name, plugin_uuid, trace=trace)
     return publish_msg(socket, fb_data)
 
-def _generate_terminating_plugin_fb_event(plugin_name: String, plugin_uuid: String)-> bytearray:
+def _generate_terminating_plugin_fb_event(plugin_name: String, plugin_uuid: String, trace: dict = None)-> bytearray:
     """
     Create a plugin terminating event flatbuffers object
     """
@@ -365,11 +760,13 @@ def _generate_terminating_plugin_fb_event(plugin_name: String, plugin_uuid: Stri
     plugin_name_fb = builder.CreateString(plugin_name)
     plugin_uuid_fb = builder.CreateString(plugin_uuid)
 
+    trace_fb = _create_trace_fields(builder, trace=trace)
     PluginTerminatingEvent.Start(builder)
     PluginTerminatingEvent.AddEventCreateTs(builder, ts_fb)
     PluginTerminatingEvent.AddPluginUuid(builder, plugin_uuid_fb)
     PluginTerminatingEvent.AddPluginName(builder, plugin_name_fb)
     
+    _add_trace_fields(PluginTerminatingEvent, builder, trace_fb)
     plugin_terminating_event = PluginTerminatingEvent.End(builder)
 
 
@@ -377,28 +774,29 @@ def _generate_terminating_plugin_fb_event(plugin_name: String, plugin_uuid: Stri
     Event.Start(builder)
     Event.EventAddEventType(builder, EventType.PluginTerminatingEvent)
     Event.AddEvent(builder, plugin_terminating_event)
+    _add_schema_version(builder)
     root_event = Event.End(builder)
 
     builder.Finish(root_event)
     return builder.Output()
 
-def _generate_terminating_plugin_fb_with_prefix(plugin_name: String, plugin_uuid: String) -> bytearray:
+def _generate_terminating_plugin_fb_with_prefix(plugin_name: String, plugin_uuid: String, trace: dict = None) -> bytearray:
     """
     Create a terminating plugin event message with prefix.
     """
-    fb = _generate_terminating_plugin_fb_event(plugin_name, plugin_uuid)
+    fb = _generate_terminating_plugin_fb_event(plugin_name, plugin_uuid, trace=trace)
     return _prepend_event_prefix("PLUGIN_TERMINATING", fb)
 
-def send_terminating_plugin_fb_event(socket, plugin_name, plugin_uuid) -> str:
+def send_terminating_plugin_fb_event(socket, plugin_name, plugin_uuid, trace: dict = None) -> str:
     """
     Send a terminating plugin event over the zmq socket.
     Returns a string which is the reply from the event-engine thread or raises an 
     exception on error.
     """
-    fb_data = _generate_terminating_plugin_fb_with_prefix(plugin_name, plugin_uuid)
+    fb_data = _generate_terminating_plugin_fb_with_prefix(plugin_name, plugin_uuid, trace=trace)
     return publish_msg(socket, fb_data)
 
-def _generate_terminate_plugin_fb_event(target_plugin_name: String, target_plugin_uuid: String)-> bytearray:
+def _generate_terminate_plugin_fb_event(target_plugin_name: String, target_plugin_uuid: String, trace: dict = None)-> bytearray:
     """
     Create a terminate plugin flatbuffers object event
     """
@@ -409,40 +807,94 @@ def _generate_terminate_plugin_fb_event(target_plugin_name: String, target_plugi
     target_plugin_name_fb = builder.CreateString(target_plugin_name)
     target_plugin_uuid_fb = builder.CreateString(target_plugin_uuid)
 
+    trace_fb = _create_trace_fields(builder, trace=trace)
     PluginTerminateEvent.Start(builder)
     PluginTerminateEvent.AddEventCreateTs(builder, ts_fb)
     PluginTerminateEvent.AddTargetPluginUuid(builder, target_plugin_uuid_fb)
     PluginTerminateEvent.AddTargetPluginName(builder, target_plugin_name_fb)
     
+    _add_trace_fields(PluginTerminateEvent, builder, trace_fb)
     plugin_terminate_event = PluginTerminateEvent.End(builder)
 
     # -- root object --
     Event.Start(builder)
     Event.EventAddEventType(builder, EventType.PluginTerminateEvent)
     Event.AddEvent(builder, plugin_terminate_event)
+    _add_schema_version(builder)
     root_event = Event.End(builder)
 
     builder.Finish(root_event)
     return builder.Output()
 
-def _generate_terminate_plugin_fb_with_prefix(target_plugin_name: String, target_plugin_uuid: String) -> bytearray:
+def _generate_terminate_plugin_fb_with_prefix(target_plugin_name: String, target_plugin_uuid: String, trace: dict = None) -> bytearray:
     """
     Create a terminate plugin event message with prefix.
     """
-    fb = _generate_terminate_plugin_fb_event(target_plugin_name, target_plugin_uuid)
+    fb = _generate_terminate_plugin_fb_event(target_plugin_name, target_plugin_uuid, trace=trace)
     return _prepend_event_prefix("PLUGIN_TERMINATE", fb)
 
-def send_terminate_plugin_fb_event(socket, target_plugin_name, target_plugin_uuid) -> str:
+def send_terminate_plugin_fb_event(socket, target_plugin_name, target_plugin_uuid, trace: dict = None) -> str:
     """
     Send a terminating plugin event over the zmq socket.
     Returns a string which is the reply from the event-engine thread or raises an 
     exception on error.
     """
-    fb_data = _generate_terminate_plugin_fb_with_prefix(target_plugin_name, target_plugin_uuid)
+    fb_data = _generate_terminate_plugin_fb_with_prefix(target_plugin_name, target_plugin_uuid, trace=trace)
     return publish_msg(socket, fb_data)
 
+def _generate_config_reloaded_fb_event(config_file: String, applied: list, requires_restart: list,
+                                       trace: dict = None) -> bytearray:
+    """
+    Create an event that announces that the application re-read its configuration file
+    """
+    builder = flatbuffers.Builder(1024)
+
+    ts = datetime.datetime.utcnow().isoformat()
+    ts_fb = builder.CreateString(ts)
+    config_file_fb = builder.CreateString(config_file)
+
+    # String vectors are built from strings created ahead of the vector.
+    applied_strs = [builder.CreateString(a) for a in applied]
+    ConfigReloadedEvent.StartAppliedVector(builder, len(applied_strs))
+    for a in reversed(applied_strs):
+        builder.PrependUOffsetTRelative(a)
+    applied_fb = builder.EndVector()
+    restart_strs = [builder.CreateString(r) for r in requires_restart]
+    ConfigReloadedEvent.StartRequiresRestartVector(builder, len(restart_strs))
+    for r in reversed(restart_strs):
+        builder.PrependUOffsetTRelative(r)
+    requires_restart_fb = builder.EndVector()
+
+    trace_fb = _create_trace_fields(builder, trace=trace)
+    ConfigReloadedEvent.Start(builder)
+    ConfigReloadedEvent.AddEventCreateTs(builder, ts_fb)
+    ConfigReloadedEvent.AddConfigFile(builder, config_file_fb)
+    ConfigReloadedEvent.AddApplied(builder, applied_fb)
+    ConfigReloadedEvent.AddRequiresRestart(builder, requires_restart_fb)
+
+    _add_trace_fields(ConfigReloadedEvent, builder, trace_fb)
+    config_reloaded_event = ConfigReloadedEvent.End(builder)
+
+    # -- root object --
+    Event.Start(builder)
+    Event.EventAddEventType(builder, EventType.ConfigReloadedEvent)
+    Event.AddEvent(builder, config_reloaded_event)
+    _add_schema_version(builder)
+    root_event = Event.End(builder)
+
+    builder.Finish(root_event)
+    return builder.Output()
+
+def _generate_config_reloaded_fb_with_prefix(config_file: String, applied: list, requires_restart: list,
+                                             trace: dict = None) -> bytearray:
+    """
+    Create a config reloaded event message with prefix.
+    """
+    fb = _generate_config_reloaded_fb_event(config_file, applied, requires_restart, trace=trace)
+    return _prepend_event_prefix("CONFIG_RELOADED", fb)
+
 
-def _generate_monitor_power_start_event(pids: list, monitor_types: list, monitor_seconds: int) -> bytearray:
+def _generate_monitor_power_start_event(pids: list, monitor_types: list, monitor_seconds: int, trace: dict = None) -> bytearray:
     """
     Create a monitor power start event message
     """
@@ -469,36 +921,39 @@ def _generate_monitor_power_start_event(pids: list, monitor_types: list, monitor
     monitor_types_fb = builder.EndVector()
     
     # Start building the MonitorPowerStartEvent
+    trace_fb = _create_trace_fields(builder, trace=trace)
     MonitorPowerStartEvent.MonitorPowerStartEventStart(builder)
     MonitorPowerStartEvent.MonitorPowerStartEventAddEventCreateTs(builder, ts_fb)
     MonitorPowerStartEvent.MonitorPowerStartEventAddPids(builder, pids_fb)
     MonitorPowerStartEvent.MonitorPowerStartEventAddMonitorTypes(builder, monitor_types_fb)
     MonitorPowerStartEvent.MonitorPowerStartEventAddMonitorStartTs(builder, monitor_start_ts_fb)
     MonitorPowerStartEvent.MonitorPowerStartEventAddMonitorSeconds(builder, monitor_seconds)
+    _add_trace_fields(MonitorPowerStartEvent, builder, trace_fb)
     monitor_power_start_event = MonitorPowerStartEvent.MonitorPowerStartEventEnd(builder)
     
     # Start building the generic Event
     Event.EventStart(builder)
     Event.EventAddEventType(builder, EventType.MonitorPowerStartEvent)
     Event.EventAddEvent(builder, monitor_power_start_event)
+    _add_schema_version(builder)
     root_event = Event.EventEnd(builder)
 
     builder.Finish(root_event)
     return builder.Output()
 
-def _generate_monitor_power_start_event_with_prefix(pids: list, monitor_types: list, monitor_seconds: int) -> bytearray:
+def _generate_monitor_power_start_event_with_prefix(pids: list, monitor_types: list, monitor_seconds: int, trace: dict = None) -> bytearray:
     """
     Create a monitor power start event message with prefix
     """
-    fb = _generate_monitor_power_start_event(pids, monitor_types, monitor_seconds)
+    fb = _generate_monitor_power_start_event(pids, monitor_types, monitor_seconds, trace=trace)
     return _prepend_event_prefix("MONITOR_POWER_START", fb)
 
-def send_monitor_power_start_fb_event(socket, pids: list, monitor_types: list, monitor_seconds: int) -> str:
+def send_monitor_power_start_fb_event(socket, pids: list, monitor_types: list, monitor_seconds: int, trace: dict = None) -> str:
     """
     Send a monitor power event over the zmq socket
     TODO: need way to handle multiple pids in future
     """
-    fb_data = _generate_monitor_power_start_event_with_prefix(pids, monitor_types, monitor_seconds)
+    fb_data = _generate_monitor_power_start_event_with_prefix(pids, monitor_types, monitor_seconds, trace=trace)
     a = publish_msg(socket, fb_data)
     return a
 
@@ -541,6 +996,14 @@ def _event_to_typed_event(event):
         union_image_deleted_event = ImageDeletedEvent.ImageDeletedEvent()
         union_image_deleted_event.Init(event.Event().Bytes, event.Event().Pos)
         return union_image_deleted_event
+    if event_type_int == EventType.ImageProcessingErrorEvent:
+        union_image_processing_error_event = ImageProcessingErrorEvent.ImageProcessingErrorEvent()
+        union_image_processing_error_event.Init(event.Event().Bytes, event.Event().Pos)
+        return union_image_processing_error_event
+    if event_type_int == EventType.DetectionEvent:
+        union_detection_event = DetectionEvent.DetectionEvent()
+        union_detection_event.Init(event.Event().Bytes, event.Event().Pos)
+        return union_detection_event
     if event_type_int == EventType.PluginStartedEvent:
         union_plugin_started_event = PluginStartedEvent.PluginStartedEvent()
         union_plugin_started_event.Init(event.Event().Bytes, event.Event().Pos)
@@ -553,6 +1016,10 @@ def _event_to_typed_event(event):
         union_plugin_terminate_event = PluginTerminateEvent.PluginTerminateEvent()
         union_plugin_terminate_event.Init(event.Event().Bytes, event.Event().Pos)
         return union_plugin_terminate_event
+    if event_type_int == EventType.ConfigReloadedEvent:
+        union_config_reloaded_event = ConfigReloadedEvent.ConfigReloadedEvent()
+        union_config_reloaded_event.Init(event.Event().Bytes, event.Event().Pos)
+        return union_config_reloaded_event
 
     if event_type_int == EventType.MonitorPowerStartEvent:
         union_plugin_terminate_event = MonitorPowerStartEvent.MonitorPowerStartEvent()
@@ -576,4 +1043,5 @@ def socket_message_to_typed_event(msg: bytearray):
         msg = bytearray(msg)
     b = _remove_event_prefix(msg)
     e = _bytes_to_event(b)
+    _check_schema_version(e)
     return _event_to_typed_event(e)
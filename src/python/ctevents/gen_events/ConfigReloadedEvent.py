@@ -0,0 +1,144 @@
+# automatically generated by the FlatBuffers compiler, do not modify
+
+# namespace: gen_events
+
+import flatbuffers
+from flatbuffers.compat import import_numpy
+np = import_numpy()
+
+class ConfigReloadedEvent(object):
+    __slots__ = ['_tab']
+
+    @classmethod
+    def GetRootAs(cls, buf, offset=0):
+        n = flatbuffers.encode.Get(flatbuffers.packer.uoffset, buf, offset)
+        x = ConfigReloadedEvent()
+        x.Init(buf, n + offset)
+        return x
+
+    @classmethod
+    def GetRootAsConfigReloadedEvent(cls, buf, offset=0):
+        """This method is deprecated. Please switch to GetRootAs."""
+        return cls.GetRootAs(buf, offset)
+    # ConfigReloadedEvent
+    def Init(self, buf, pos):
+        self._tab = flatbuffers.table.Table(buf, pos)
+
+    # ConfigReloadedEvent
+    def EventCreateTs(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(4))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ConfigReloadedEvent
+    def ConfigFile(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(6))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ConfigReloadedEvent
+    def Applied(self, j):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(8))
+        if o != 0:
+            a = self._tab.Vector(o)
+            return self._tab.String(a + flatbuffers.number_types.UOffsetTFlags.py_type(j * 4))
+        return ""
+
+    # ConfigReloadedEvent
+    def AppliedLength(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(8))
+        if o != 0:
+            return self._tab.VectorLen(o)
+        return 0
+
+    # ConfigReloadedEvent
+    def AppliedIsNone(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(8))
+        return o == 0
+
+    # ConfigReloadedEvent
+    def RequiresRestart(self, j):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(10))
+        if o != 0:
+            a = self._tab.Vector(o)
+            return self._tab.String(a + flatbuffers.number_types.UOffsetTFlags.py_type(j * 4))
+        return ""
+
+    # ConfigReloadedEvent
+    def RequiresRestartLength(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(10))
+        if o != 0:
+            return self._tab.VectorLen(o)
+        return 0
+
+    # ConfigReloadedEvent
+    def RequiresRestartIsNone(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(10))
+        return o == 0
+
+    # ConfigReloadedEvent
+    def EventUuid(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(12))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ConfigReloadedEvent
+    def CorrelationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(14))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ConfigReloadedEvent
+    def CausationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(16))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ConfigReloadedEvent
+    def SourcePlugin(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(18))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+def ConfigReloadedEventStart(builder): builder.StartObject(8)
+def Start(builder):
+    return ConfigReloadedEventStart(builder)
+def ConfigReloadedEventAddEventCreateTs(builder, eventCreateTs): builder.PrependUOffsetTRelativeSlot(0, flatbuffers.number_types.UOffsetTFlags.py_type(eventCreateTs), 0)
+def AddEventCreateTs(builder, eventCreateTs):
+    return ConfigReloadedEventAddEventCreateTs(builder, eventCreateTs)
+def ConfigReloadedEventAddConfigFile(builder, configFile): builder.PrependUOffsetTRelativeSlot(1, flatbuffers.number_types.UOffsetTFlags.py_type(configFile), 0)
+def AddConfigFile(builder, configFile):
+    return ConfigReloadedEventAddConfigFile(builder, configFile)
+def ConfigReloadedEventAddApplied(builder, applied): builder.PrependUOffsetTRelativeSlot(2, flatbuffers.number_types.UOffsetTFlags.py_type(applied), 0)
+def AddApplied(builder, applied):
+    return ConfigReloadedEventAddApplied(builder, applied)
+def ConfigReloadedEventStartAppliedVector(builder, numElems): return builder.StartVector(4, numElems, 4)
+def StartAppliedVector(builder, numElems):
+    return ConfigReloadedEventStartAppliedVector(builder, numElems)
+def ConfigReloadedEventAddRequiresRestart(builder, requiresRestart): builder.PrependUOffsetTRelativeSlot(3, flatbuffers.number_types.UOffsetTFlags.py_type(requiresRestart), 0)
+def AddRequiresRestart(builder, requiresRestart):
+    return ConfigReloadedEventAddRequiresRestart(builder, requiresRestart)
+def ConfigReloadedEventStartRequiresRestartVector(builder, numElems): return builder.StartVector(4, numElems, 4)
+def StartRequiresRestartVector(builder, numElems):
+    return ConfigReloadedEventStartRequiresRestartVector(builder, numElems)
+def ConfigReloadedEventAddEventUuid(builder, eventUuid): builder.PrependUOffsetTRelativeSlot(4, flatbuffers.number_types.UOffsetTFlags.py_type(eventUuid), 0)
+def AddEventUuid(builder, eventUuid):
+    return ConfigReloadedEventAddEventUuid(builder, eventUuid)
+def ConfigReloadedEventAddCorrelationId(builder, correlationId): builder.PrependUOffsetTRelativeSlot(5, flatbuffers.number_types.UOffsetTFlags.py_type(correlationId), 0)
+def AddCorrelationId(builder, correlationId):
+    return ConfigReloadedEventAddCorrelationId(builder, correlationId)
+def ConfigReloadedEventAddCausationId(builder, causationId): builder.PrependUOffsetTRelativeSlot(6, flatbuffers.number_types.UOffsetTFlags.py_type(causationId), 0)
+def AddCausationId(builder, causationId):
+    return ConfigReloadedEventAddCausationId(builder, causationId)
+def ConfigReloadedEventAddSourcePlugin(builder, sourcePlugin): builder.PrependUOffsetTRelativeSlot(7, flatbuffers.number_types.UOffsetTFlags.py_type(sourcePlugin), 0)
+def AddSourcePlugin(builder, sourcePlugin):
+    return ConfigReloadedEventAddSourcePlugin(builder, sourcePlugin)
+def ConfigReloadedEventEnd(builder): return builder.EndObject()
+def End(builder):
+    return ConfigReloadedEventEnd(builder)
\ No newline at end of file
@@ -0,0 +1,142 @@
+# automatically generated by the FlatBuffers compiler, do not modify
+
+# namespace: gen_events
+
+import flatbuffers
+from flatbuffers.compat import import_numpy
+np = import_numpy()
+
+class DetectionEvent(object):
+    __slots__ = ['_tab']
+
+    @classmethod
+    def GetRootAs(cls, buf, offset=0):
+        n = flatbuffers.encode.Get(flatbuffers.packer.uoffset, buf, offset)
+        x = DetectionEvent()
+        x.Init(buf, n + offset)
+        return x
+
+    @classmethod
+    def GetRootAsDetectionEvent(cls, buf, offset=0):
+        """This method is deprecated. Please switch to GetRootAs."""
+        return cls.GetRootAs(buf, offset)
+    # DetectionEvent
+    def Init(self, buf, pos):
+        self._tab = flatbuffers.table.Table(buf, pos)
+
+    # DetectionEvent
+    def EventCreateTs(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(4))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # DetectionEvent
+    def ImageUuid(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(6))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # DetectionEvent
+    def ImageFormat(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(8))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # DetectionEvent
+    def Label(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(10))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # DetectionEvent
+    def Probability(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(12))
+        if o != 0:
+            return self._tab.Get(flatbuffers.number_types.Float32Flags, o + self._tab.Pos)
+        return 0.0
+
+    # DetectionEvent
+    def Threshold(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(14))
+        if o != 0:
+            return self._tab.Get(flatbuffers.number_types.Float32Flags, o + self._tab.Pos)
+        return 0.0
+
+    # DetectionEvent
+    def StoredLocation(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(16))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # DetectionEvent
+    def EventUuid(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(18))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # DetectionEvent
+    def CorrelationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(20))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # DetectionEvent
+    def CausationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(22))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # DetectionEvent
+    def SourcePlugin(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(24))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+def DetectionEventStart(builder): builder.StartObject(11)
+def Start(builder):
+    return DetectionEventStart(builder)
+def DetectionEventAddEventCreateTs(builder, eventCreateTs): builder.PrependUOffsetTRelativeSlot(0, flatbuffers.number_types.UOffsetTFlags.py_type(eventCreateTs), 0)
+def AddEventCreateTs(builder, eventCreateTs):
+    return DetectionEventAddEventCreateTs(builder, eventCreateTs)
+def DetectionEventAddImageUuid(builder, imageUuid): builder.PrependUOffsetTRelativeSlot(1, flatbuffers.number_types.UOffsetTFlags.py_type(imageUuid), 0)
+def AddImageUuid(builder, imageUuid):
+    return DetectionEventAddImageUuid(builder, imageUuid)
+def DetectionEventAddImageFormat(builder, imageFormat): builder.PrependUOffsetTRelativeSlot(2, flatbuffers.number_types.UOffsetTFlags.py_type(imageFormat), 0)
+def AddImageFormat(builder, imageFormat):
+    return DetectionEventAddImageFormat(builder, imageFormat)
+def DetectionEventAddLabel(builder, label): builder.PrependUOffsetTRelativeSlot(3, flatbuffers.number_types.UOffsetTFlags.py_type(label), 0)
+def AddLabel(builder, label):
+    return DetectionEventAddLabel(builder, label)
+def DetectionEventAddProbability(builder, probability): builder.PrependFloat32Slot(4, probability, 0.0)
+def AddProbability(builder, probability):
+    return DetectionEventAddProbability(builder, probability)
+def DetectionEventAddThreshold(builder, threshold): builder.PrependFloat32Slot(5, threshold, 0.0)
+def AddThreshold(builder, threshold):
+    return DetectionEventAddThreshold(builder, threshold)
+def DetectionEventAddStoredLocation(builder, storedLocation): builder.PrependUOffsetTRelativeSlot(6, flatbuffers.number_types.UOffsetTFlags.py_type(storedLocation), 0)
+def AddStoredLocation(builder, storedLocation):
+    return DetectionEventAddStoredLocation(builder, storedLocation)
+def DetectionEventAddEventUuid(builder, eventUuid): builder.PrependUOffsetTRelativeSlot(7, flatbuffers.number_types.UOffsetTFlags.py_type(eventUuid), 0)
+def AddEventUuid(builder, eventUuid):
+    return DetectionEventAddEventUuid(builder, eventUuid)
+def DetectionEventAddCorrelationId(builder, correlationId): builder.PrependUOffsetTRelativeSlot(8, flatbuffers.number_types.UOffsetTFlags.py_type(correlationId), 0)
+def AddCorrelationId(builder, correlationId):
+    return DetectionEventAddCorrelationId(builder, correlationId)
+def DetectionEventAddCausationId(builder, causationId): builder.PrependUOffsetTRelativeSlot(9, flatbuffers.number_types.UOffsetTFlags.py_type(causationId), 0)
+def AddCausationId(builder, causationId):
+    return DetectionEventAddCausationId(builder, causationId)
+def DetectionEventAddSourcePlugin(builder, sourcePlugin): builder.PrependUOffsetTRelativeSlot(10, flatbuffers.number_types.UOffsetTFlags.py_type(sourcePlugin), 0)
+def AddSourcePlugin(builder, sourcePlugin):
+    return DetectionEventAddSourcePlugin(builder, sourcePlugin)
+def DetectionEventEnd(builder): return builder.EndObject()
+def End(builder):
+    return DetectionEventEnd(builder)
\ No newline at end of file
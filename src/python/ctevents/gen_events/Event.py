@@ -41,7 +41,21 @@ class Event(object):
             return obj
         return None
 
-def EventStart(builder): builder.StartObject(2)
+    # Event
+    def SchemaMajor(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(8))
+        if o != 0:
+            return self._tab.Get(flatbuffers.number_types.Uint16Flags, o + self._tab.Pos)
+        return 1
+
+    # Event
+    def SchemaMinor(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(10))
+        if o != 0:
+            return self._tab.Get(flatbuffers.number_types.Uint16Flags, o + self._tab.Pos)
+        return 0
+
+def EventStart(builder): builder.StartObject(4)
 def Start(builder):
     return EventStart(builder)
 def EventAddEventType(builder, eventType): builder.PrependUint8Slot(0, eventType, 0)
@@ -50,6 +64,12 @@ def AddEventType(builder, eventType):
 def EventAddEvent(builder, event): builder.PrependUOffsetTRelativeSlot(1, flatbuffers.number_types.UOffsetTFlags.py_type(event), 0)
 def AddEvent(builder, event):
     return EventAddEvent(builder, event)
+def EventAddSchemaMajor(builder, schemaMajor): builder.PrependUint16Slot(2, schemaMajor, 1)
+def AddSchemaMajor(builder, schemaMajor):
+    return EventAddSchemaMajor(builder, schemaMajor)
+def EventAddSchemaMinor(builder, schemaMinor): builder.PrependUint16Slot(3, schemaMinor, 0)
+def AddSchemaMinor(builder, schemaMinor):
+    return EventAddSchemaMinor(builder, schemaMinor)
 def EventEnd(builder): return builder.EndObject()
 def End(builder):
     return EventEnd(builder)
\ No newline at end of file
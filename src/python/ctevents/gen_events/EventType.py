@@ -14,3 +14,6 @@ class EventType(object):
     PluginTerminateEvent = 8
     MonitorPowerStartEvent = 9
     MonitorPowerStopEvent = 10
+    ImageProcessingErrorEvent = 11
+    DetectionEvent = 12
+    ConfigReloadedEvent = 13
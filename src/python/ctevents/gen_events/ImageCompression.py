@@ -0,0 +1,8 @@
+# automatically generated by the FlatBuffers compiler, do not modify
+
+# namespace: gen_events
+
+class ImageCompression(object):
+    Uncompressed = 0
+    Zstd = 1
+    Lz4 = 2
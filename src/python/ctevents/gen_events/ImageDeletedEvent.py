@@ -45,7 +45,35 @@ class ImageDeletedEvent(object):
             return self._tab.String(o + self._tab.Pos)
         return None
 
-def ImageDeletedEventStart(builder): builder.StartObject(3)
+    # ImageDeletedEvent
+    def EventUuid(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(10))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageDeletedEvent
+    def CorrelationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(12))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageDeletedEvent
+    def CausationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(14))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageDeletedEvent
+    def SourcePlugin(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(16))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+def ImageDeletedEventStart(builder): builder.StartObject(7)
 def Start(builder):
     return ImageDeletedEventStart(builder)
 def ImageDeletedEventAddEventCreateTs(builder, eventCreateTs): builder.PrependUOffsetTRelativeSlot(0, flatbuffers.number_types.UOffsetTFlags.py_type(eventCreateTs), 0)
@@ -57,6 +85,18 @@ def AddImageUuid(builder, imageUuid):
 def ImageDeletedEventAddImageFormat(builder, imageFormat): builder.PrependUOffsetTRelativeSlot(2, flatbuffers.number_types.UOffsetTFlags.py_type(imageFormat), 0)
 def AddImageFormat(builder, imageFormat):
     return ImageDeletedEventAddImageFormat(builder, imageFormat)
+def ImageDeletedEventAddEventUuid(builder, eventUuid): builder.PrependUOffsetTRelativeSlot(3, flatbuffers.number_types.UOffsetTFlags.py_type(eventUuid), 0)
+def AddEventUuid(builder, eventUuid):
+    return ImageDeletedEventAddEventUuid(builder, eventUuid)
+def ImageDeletedEventAddCorrelationId(builder, correlationId): builder.PrependUOffsetTRelativeSlot(4, flatbuffers.number_types.UOffsetTFlags.py_type(correlationId), 0)
+def AddCorrelationId(builder, correlationId):
+    return ImageDeletedEventAddCorrelationId(builder, correlationId)
+def ImageDeletedEventAddCausationId(builder, causationId): builder.PrependUOffsetTRelativeSlot(5, flatbuffers.number_types.UOffsetTFlags.py_type(causationId), 0)
+def AddCausationId(builder, causationId):
+    return ImageDeletedEventAddCausationId(builder, causationId)
+def ImageDeletedEventAddSourcePlugin(builder, sourcePlugin): builder.PrependUOffsetTRelativeSlot(6, flatbuffers.number_types.UOffsetTFlags.py_type(sourcePlugin), 0)
+def AddSourcePlugin(builder, sourcePlugin):
+    return ImageDeletedEventAddSourcePlugin(builder, sourcePlugin)
 def ImageDeletedEventEnd(builder): return builder.EndObject()
 def End(builder):
     return ImageDeletedEventEnd(builder)
\ No newline at end of file
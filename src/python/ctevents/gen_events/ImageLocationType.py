@@ -0,0 +1,8 @@
+# automatically generated by the FlatBuffers compiler, do not modify
+
+# namespace: gen_events
+
+class ImageLocationType(object):
+    FilePath = 0
+    SharedMemory = 1
+    ContentHash = 2
@@ -0,0 +1,132 @@
+# automatically generated by the FlatBuffers compiler, do not modify
+
+# namespace: gen_events
+
+import flatbuffers
+from flatbuffers.compat import import_numpy
+np = import_numpy()
+
+class ImageProcessingErrorEvent(object):
+    __slots__ = ['_tab']
+
+    @classmethod
+    def GetRootAs(cls, buf, offset=0):
+        n = flatbuffers.encode.Get(flatbuffers.packer.uoffset, buf, offset)
+        x = ImageProcessingErrorEvent()
+        x.Init(buf, n + offset)
+        return x
+
+    @classmethod
+    def GetRootAsImageProcessingErrorEvent(cls, buf, offset=0):
+        """This method is deprecated. Please switch to GetRootAs."""
+        return cls.GetRootAs(buf, offset)
+    # ImageProcessingErrorEvent
+    def Init(self, buf, pos):
+        self._tab = flatbuffers.table.Table(buf, pos)
+
+    # ImageProcessingErrorEvent
+    def EventCreateTs(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(4))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageProcessingErrorEvent
+    def ImageUuid(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(6))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageProcessingErrorEvent
+    def PluginName(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(8))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageProcessingErrorEvent
+    def Stage(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(10))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageProcessingErrorEvent
+    def ErrorCode(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(12))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageProcessingErrorEvent
+    def ErrorMessage(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(14))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageProcessingErrorEvent
+    def EventUuid(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(16))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageProcessingErrorEvent
+    def CorrelationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(18))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageProcessingErrorEvent
+    def CausationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(20))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageProcessingErrorEvent
+    def SourcePlugin(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(22))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+def ImageProcessingErrorEventStart(builder): builder.StartObject(10)
+def Start(builder):
+    return ImageProcessingErrorEventStart(builder)
+def ImageProcessingErrorEventAddEventCreateTs(builder, eventCreateTs): builder.PrependUOffsetTRelativeSlot(0, flatbuffers.number_types.UOffsetTFlags.py_type(eventCreateTs), 0)
+def AddEventCreateTs(builder, eventCreateTs):
+    return ImageProcessingErrorEventAddEventCreateTs(builder, eventCreateTs)
+def ImageProcessingErrorEventAddImageUuid(builder, imageUuid): builder.PrependUOffsetTRelativeSlot(1, flatbuffers.number_types.UOffsetTFlags.py_type(imageUuid), 0)
+def AddImageUuid(builder, imageUuid):
+    return ImageProcessingErrorEventAddImageUuid(builder, imageUuid)
+def ImageProcessingErrorEventAddPluginName(builder, pluginName): builder.PrependUOffsetTRelativeSlot(2, flatbuffers.number_types.UOffsetTFlags.py_type(pluginName), 0)
+def AddPluginName(builder, pluginName):
+    return ImageProcessingErrorEventAddPluginName(builder, pluginName)
+def ImageProcessingErrorEventAddStage(builder, stage): builder.PrependUOffsetTRelativeSlot(3, flatbuffers.number_types.UOffsetTFlags.py_type(stage), 0)
+def AddStage(builder, stage):
+    return ImageProcessingErrorEventAddStage(builder, stage)
+def ImageProcessingErrorEventAddErrorCode(builder, errorCode): builder.PrependUOffsetTRelativeSlot(4, flatbuffers.number_types.UOffsetTFlags.py_type(errorCode), 0)
+def AddErrorCode(builder, errorCode):
+    return ImageProcessingErrorEventAddErrorCode(builder, errorCode)
+def ImageProcessingErrorEventAddErrorMessage(builder, errorMessage): builder.PrependUOffsetTRelativeSlot(5, flatbuffers.number_types.UOffsetTFlags.py_type(errorMessage), 0)
+def AddErrorMessage(builder, errorMessage):
+    return ImageProcessingErrorEventAddErrorMessage(builder, errorMessage)
+def ImageProcessingErrorEventAddEventUuid(builder, eventUuid): builder.PrependUOffsetTRelativeSlot(6, flatbuffers.number_types.UOffsetTFlags.py_type(eventUuid), 0)
+def AddEventUuid(builder, eventUuid):
+    return ImageProcessingErrorEventAddEventUuid(builder, eventUuid)
+def ImageProcessingErrorEventAddCorrelationId(builder, correlationId): builder.PrependUOffsetTRelativeSlot(7, flatbuffers.number_types.UOffsetTFlags.py_type(correlationId), 0)
+def AddCorrelationId(builder, correlationId):
+    return ImageProcessingErrorEventAddCorrelationId(builder, correlationId)
+def ImageProcessingErrorEventAddCausationId(builder, causationId): builder.PrependUOffsetTRelativeSlot(8, flatbuffers.number_types.UOffsetTFlags.py_type(causationId), 0)
+def AddCausationId(builder, causationId):
+    return ImageProcessingErrorEventAddCausationId(builder, causationId)
+def ImageProcessingErrorEventAddSourcePlugin(builder, sourcePlugin): builder.PrependUOffsetTRelativeSlot(9, flatbuffers.number_types.UOffsetTFlags.py_type(sourcePlugin), 0)
+def AddSourcePlugin(builder, sourcePlugin):
+    return ImageProcessingErrorEventAddSourcePlugin(builder, sourcePlugin)
+def ImageProcessingErrorEventEnd(builder): return builder.EndObject()
+def End(builder):
+    return ImageProcessingErrorEventEnd(builder)
\ No newline at end of file
@@ -45,7 +45,35 @@ class ImageReceivedEvent(object):
             return self._tab.String(o + self._tab.Pos)
         return None
 
-def ImageReceivedEventStart(builder): builder.StartObject(3)
+    # ImageReceivedEvent
+    def EventUuid(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(10))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageReceivedEvent
+    def CorrelationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(12))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageReceivedEvent
+    def CausationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(14))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageReceivedEvent
+    def SourcePlugin(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(16))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+def ImageReceivedEventStart(builder): builder.StartObject(7)
 def Start(builder):
     return ImageReceivedEventStart(builder)
 def ImageReceivedEventAddEventCreateTs(builder, eventCreateTs): builder.PrependUOffsetTRelativeSlot(0, flatbuffers.number_types.UOffsetTFlags.py_type(eventCreateTs), 0)
@@ -57,6 +85,18 @@ def AddImageUuid(builder, imageUuid):
 def ImageReceivedEventAddImageFormat(builder, imageFormat): builder.PrependUOffsetTRelativeSlot(2, flatbuffers.number_types.UOffsetTFlags.py_type(imageFormat), 0)
 def AddImageFormat(builder, imageFormat):
     return ImageReceivedEventAddImageFormat(builder, imageFormat)
+def ImageReceivedEventAddEventUuid(builder, eventUuid): builder.PrependUOffsetTRelativeSlot(3, flatbuffers.number_types.UOffsetTFlags.py_type(eventUuid), 0)
+def AddEventUuid(builder, eventUuid):
+    return ImageReceivedEventAddEventUuid(builder, eventUuid)
+def ImageReceivedEventAddCorrelationId(builder, correlationId): builder.PrependUOffsetTRelativeSlot(4, flatbuffers.number_types.UOffsetTFlags.py_type(correlationId), 0)
+def AddCorrelationId(builder, correlationId):
+    return ImageReceivedEventAddCorrelationId(builder, correlationId)
+def ImageReceivedEventAddCausationId(builder, causationId): builder.PrependUOffsetTRelativeSlot(5, flatbuffers.number_types.UOffsetTFlags.py_type(causationId), 0)
+def AddCausationId(builder, causationId):
+    return ImageReceivedEventAddCausationId(builder, causationId)
+def ImageReceivedEventAddSourcePlugin(builder, sourcePlugin): builder.PrependUOffsetTRelativeSlot(6, flatbuffers.number_types.UOffsetTFlags.py_type(sourcePlugin), 0)
+def AddSourcePlugin(builder, sourcePlugin):
+    return ImageReceivedEventAddSourcePlugin(builder, sourcePlugin)
 def ImageReceivedEventEnd(builder): return builder.EndObject()
 def End(builder):
     return ImageReceivedEventEnd(builder)
\ No newline at end of file
@@ -0,0 +1,72 @@
+# automatically generated by the FlatBuffers compiler, do not modify
+
+# namespace: gen_events
+
+import flatbuffers
+from flatbuffers.compat import import_numpy
+np = import_numpy()
+
+class ImageReference(object):
+    __slots__ = ['_tab']
+
+    @classmethod
+    def GetRootAs(cls, buf, offset=0):
+        n = flatbuffers.encode.Get(flatbuffers.packer.uoffset, buf, offset)
+        x = ImageReference()
+        x.Init(buf, n + offset)
+        return x
+
+    @classmethod
+    def GetRootAsImageReference(cls, buf, offset=0):
+        """This method is deprecated. Please switch to GetRootAs."""
+        return cls.GetRootAs(buf, offset)
+    # ImageReference
+    def Init(self, buf, pos):
+        self._tab = flatbuffers.table.Table(buf, pos)
+
+    # ImageReference
+    def LocationType(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(4))
+        if o != 0:
+            return self._tab.Get(flatbuffers.number_types.Int8Flags, o + self._tab.Pos)
+        return 0
+
+    # ImageReference
+    def Location(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(6))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageReference
+    def Size(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(8))
+        if o != 0:
+            return self._tab.Get(flatbuffers.number_types.Uint64Flags, o + self._tab.Pos)
+        return 0
+
+    # ImageReference
+    def Checksum(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(10))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+def ImageReferenceStart(builder): builder.StartObject(4)
+def Start(builder):
+    return ImageReferenceStart(builder)
+def ImageReferenceAddLocationType(builder, locationType): builder.PrependInt8Slot(0, locationType, 0)
+def AddLocationType(builder, locationType):
+    return ImageReferenceAddLocationType(builder, locationType)
+def ImageReferenceAddLocation(builder, location): builder.PrependUOffsetTRelativeSlot(1, flatbuffers.number_types.UOffsetTFlags.py_type(location), 0)
+def AddLocation(builder, location):
+    return ImageReferenceAddLocation(builder, location)
+def ImageReferenceAddSize(builder, size): builder.PrependUint64Slot(2, size, 0)
+def AddSize(builder, size):
+    return ImageReferenceAddSize(builder, size)
+def ImageReferenceAddChecksum(builder, checksum): builder.PrependUOffsetTRelativeSlot(3, flatbuffers.number_types.UOffsetTFlags.py_type(checksum), 0)
+def AddChecksum(builder, checksum):
+    return ImageReferenceAddChecksum(builder, checksum)
+def ImageReferenceEnd(builder): return builder.EndObject()
+def End(builder):
+    return ImageReferenceEnd(builder)
\ No newline at end of file
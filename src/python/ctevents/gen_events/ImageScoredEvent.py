@@ -70,7 +70,35 @@ class ImageScoredEvent(object):
         o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(10))
         return o == 0
 
-def ImageScoredEventStart(builder): builder.StartObject(4)
+    # ImageScoredEvent
+    def EventUuid(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(12))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageScoredEvent
+    def CorrelationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(14))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageScoredEvent
+    def CausationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(16))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageScoredEvent
+    def SourcePlugin(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(18))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+def ImageScoredEventStart(builder): builder.StartObject(8)
 def Start(builder):
     return ImageScoredEventStart(builder)
 def ImageScoredEventAddEventCreateTs(builder, eventCreateTs): builder.PrependUOffsetTRelativeSlot(0, flatbuffers.number_types.UOffsetTFlags.py_type(eventCreateTs), 0)
@@ -88,6 +116,18 @@ def AddScores(builder, scores):
 def ImageScoredEventStartScoresVector(builder, numElems): return builder.StartVector(4, numElems, 4)
 def StartScoresVector(builder, numElems):
     return ImageScoredEventStartScoresVector(builder, numElems)
+def ImageScoredEventAddEventUuid(builder, eventUuid): builder.PrependUOffsetTRelativeSlot(4, flatbuffers.number_types.UOffsetTFlags.py_type(eventUuid), 0)
+def AddEventUuid(builder, eventUuid):
+    return ImageScoredEventAddEventUuid(builder, eventUuid)
+def ImageScoredEventAddCorrelationId(builder, correlationId): builder.PrependUOffsetTRelativeSlot(5, flatbuffers.number_types.UOffsetTFlags.py_type(correlationId), 0)
+def AddCorrelationId(builder, correlationId):
+    return ImageScoredEventAddCorrelationId(builder, correlationId)
+def ImageScoredEventAddCausationId(builder, causationId): builder.PrependUOffsetTRelativeSlot(6, flatbuffers.number_types.UOffsetTFlags.py_type(causationId), 0)
+def AddCausationId(builder, causationId):
+    return ImageScoredEventAddCausationId(builder, causationId)
+def ImageScoredEventAddSourcePlugin(builder, sourcePlugin): builder.PrependUOffsetTRelativeSlot(7, flatbuffers.number_types.UOffsetTFlags.py_type(sourcePlugin), 0)
+def AddSourcePlugin(builder, sourcePlugin):
+    return ImageScoredEventAddSourcePlugin(builder, sourcePlugin)
 def ImageScoredEventEnd(builder): return builder.EndObject()
 def End(builder):
     return ImageScoredEventEnd(builder)
\ No newline at end of file
@@ -52,7 +52,42 @@ class ImageStoredEvent(object):
             return self._tab.String(o + self._tab.Pos)
         return None
 
-def ImageStoredEventStart(builder): builder.StartObject(4)
+    # ImageStoredEvent
+    def EventUuid(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(12))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageStoredEvent
+    def CorrelationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(14))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageStoredEvent
+    def CausationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(16))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageStoredEvent
+    def SourcePlugin(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(18))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # ImageStoredEvent
+    def Location(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(20))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+def ImageStoredEventStart(builder): builder.StartObject(9)
 def Start(builder):
     return ImageStoredEventStart(builder)
 def ImageStoredEventAddEventCreateTs(builder, eventCreateTs): builder.PrependUOffsetTRelativeSlot(0, flatbuffers.number_types.UOffsetTFlags.py_type(eventCreateTs), 0)
@@ -67,6 +102,21 @@ def AddImageFormat(builder, imageFormat):
 def ImageStoredEventAddDestination(builder, destination): builder.PrependUOffsetTRelativeSlot(3, flatbuffers.number_types.UOffsetTFlags.py_type(destination), 0)
 def AddDestination(builder, destination):
     return ImageStoredEventAddDestination(builder, destination)
+def ImageStoredEventAddEventUuid(builder, eventUuid): builder.PrependUOffsetTRelativeSlot(4, flatbuffers.number_types.UOffsetTFlags.py_type(eventUuid), 0)
+def AddEventUuid(builder, eventUuid):
+    return ImageStoredEventAddEventUuid(builder, eventUuid)
+def ImageStoredEventAddCorrelationId(builder, correlationId): builder.PrependUOffsetTRelativeSlot(5, flatbuffers.number_types.UOffsetTFlags.py_type(correlationId), 0)
+def AddCorrelationId(builder, correlationId):
+    return ImageStoredEventAddCorrelationId(builder, correlationId)
+def ImageStoredEventAddCausationId(builder, causationId): builder.PrependUOffsetTRelativeSlot(6, flatbuffers.number_types.UOffsetTFlags.py_type(causationId), 0)
+def AddCausationId(builder, causationId):
+    return ImageStoredEventAddCausationId(builder, causationId)
+def ImageStoredEventAddSourcePlugin(builder, sourcePlugin): builder.PrependUOffsetTRelativeSlot(7, flatbuffers.number_types.UOffsetTFlags.py_type(sourcePlugin), 0)
+def AddSourcePlugin(builder, sourcePlugin):
+    return ImageStoredEventAddSourcePlugin(builder, sourcePlugin)
+def ImageStoredEventAddLocation(builder, location): builder.PrependUOffsetTRelativeSlot(8, flatbuffers.number_types.UOffsetTFlags.py_type(location), 0)
+def AddLocation(builder, location):
+    return ImageStoredEventAddLocation(builder, location)
 def ImageStoredEventEnd(builder): return builder.EndObject()
 def End(builder):
     return ImageStoredEventEnd(builder)
\ No newline at end of file
@@ -3,17 +3,23 @@
 # namespace: gen_events
 
 import flatbuffers
+from flatbuffers.compat import import_numpy
+np = import_numpy()
 
 class MonitorPowerStartEvent(object):
     __slots__ = ['_tab']
 
     @classmethod
-    def GetRootAsMonitorPowerStartEvent(cls, buf, offset):
+    def GetRootAs(cls, buf, offset=0):
         n = flatbuffers.encode.Get(flatbuffers.packer.uoffset, buf, offset)
         x = MonitorPowerStartEvent()
         x.Init(buf, n + offset)
         return x
 
+    @classmethod
+    def GetRootAsMonitorPowerStartEvent(cls, buf, offset=0):
+        """This method is deprecated. Please switch to GetRootAs."""
+        return cls.GetRootAs(buf, offset)
     # MonitorPowerStartEvent
     def Init(self, buf, pos):
         self._tab = flatbuffers.table.Table(buf, pos)
@@ -47,6 +53,11 @@ class MonitorPowerStartEvent(object):
             return self._tab.VectorLen(o)
         return 0
 
+    # MonitorPowerStartEvent
+    def PidsIsNone(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(6))
+        return o == 0
+
     # MonitorPowerStartEvent
     def MonitorTypes(self, j):
         o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(8))
@@ -69,6 +80,11 @@ class MonitorPowerStartEvent(object):
             return self._tab.VectorLen(o)
         return 0
 
+    # MonitorPowerStartEvent
+    def MonitorTypesIsNone(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(8))
+        return o == 0
+
     # MonitorPowerStartEvent
     def MonitorStartTs(self):
         o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(10))
@@ -83,12 +99,70 @@ class MonitorPowerStartEvent(object):
             return self._tab.Get(flatbuffers.number_types.Uint32Flags, o + self._tab.Pos)
         return 0
 
-def MonitorPowerStartEventStart(builder): builder.StartObject(5)
+    # MonitorPowerStartEvent
+    def EventUuid(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(14))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # MonitorPowerStartEvent
+    def CorrelationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(16))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # MonitorPowerStartEvent
+    def CausationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(18))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # MonitorPowerStartEvent
+    def SourcePlugin(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(20))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+def MonitorPowerStartEventStart(builder): builder.StartObject(9)
+def Start(builder):
+    return MonitorPowerStartEventStart(builder)
 def MonitorPowerStartEventAddEventCreateTs(builder, eventCreateTs): builder.PrependUOffsetTRelativeSlot(0, flatbuffers.number_types.UOffsetTFlags.py_type(eventCreateTs), 0)
+def AddEventCreateTs(builder, eventCreateTs):
+    return MonitorPowerStartEventAddEventCreateTs(builder, eventCreateTs)
 def MonitorPowerStartEventAddPids(builder, pids): builder.PrependUOffsetTRelativeSlot(1, flatbuffers.number_types.UOffsetTFlags.py_type(pids), 0)
+def AddPids(builder, pids):
+    return MonitorPowerStartEventAddPids(builder, pids)
 def MonitorPowerStartEventStartPidsVector(builder, numElems): return builder.StartVector(4, numElems, 4)
+def StartPidsVector(builder, numElems):
+    return MonitorPowerStartEventStartPidsVector(builder, numElems)
 def MonitorPowerStartEventAddMonitorTypes(builder, monitorTypes): builder.PrependUOffsetTRelativeSlot(2, flatbuffers.number_types.UOffsetTFlags.py_type(monitorTypes), 0)
+def AddMonitorTypes(builder, monitorTypes):
+    return MonitorPowerStartEventAddMonitorTypes(builder, monitorTypes)
 def MonitorPowerStartEventStartMonitorTypesVector(builder, numElems): return builder.StartVector(1, numElems, 1)
+def StartMonitorTypesVector(builder, numElems):
+    return MonitorPowerStartEventStartMonitorTypesVector(builder, numElems)
 def MonitorPowerStartEventAddMonitorStartTs(builder, monitorStartTs): builder.PrependUOffsetTRelativeSlot(3, flatbuffers.number_types.UOffsetTFlags.py_type(monitorStartTs), 0)
+def AddMonitorStartTs(builder, monitorStartTs):
+    return MonitorPowerStartEventAddMonitorStartTs(builder, monitorStartTs)
 def MonitorPowerStartEventAddMonitorSeconds(builder, monitorSeconds): builder.PrependUint32Slot(4, monitorSeconds, 0)
+def AddMonitorSeconds(builder, monitorSeconds):
+    return MonitorPowerStartEventAddMonitorSeconds(builder, monitorSeconds)
+def MonitorPowerStartEventAddEventUuid(builder, eventUuid): builder.PrependUOffsetTRelativeSlot(5, flatbuffers.number_types.UOffsetTFlags.py_type(eventUuid), 0)
+def AddEventUuid(builder, eventUuid):
+    return MonitorPowerStartEventAddEventUuid(builder, eventUuid)
+def MonitorPowerStartEventAddCorrelationId(builder, correlationId): builder.PrependUOffsetTRelativeSlot(6, flatbuffers.number_types.UOffsetTFlags.py_type(correlationId), 0)
+def AddCorrelationId(builder, correlationId):
+    return MonitorPowerStartEventAddCorrelationId(builder, correlationId)
+def MonitorPowerStartEventAddCausationId(builder, causationId): builder.PrependUOffsetTRelativeSlot(7, flatbuffers.number_types.UOffsetTFlags.py_type(causationId), 0)
+def AddCausationId(builder, causationId):
+    return MonitorPowerStartEventAddCausationId(builder, causationId)
+def MonitorPowerStartEventAddSourcePlugin(builder, sourcePlugin): builder.PrependUOffsetTRelativeSlot(8, flatbuffers.number_types.UOffsetTFlags.py_type(sourcePlugin), 0)
+def AddSourcePlugin(builder, sourcePlugin):
+    return MonitorPowerStartEventAddSourcePlugin(builder, sourcePlugin)
 def MonitorPowerStartEventEnd(builder): return builder.EndObject()
+def End(builder):
+    return MonitorPowerStartEventEnd(builder)
\ No newline at end of file
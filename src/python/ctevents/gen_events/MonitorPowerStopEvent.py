@@ -3,17 +3,23 @@
 # namespace: gen_events
 
 import flatbuffers
+from flatbuffers.compat import import_numpy
+np = import_numpy()
 
 class MonitorPowerStopEvent(object):
     __slots__ = ['_tab']
 
     @classmethod
-    def GetRootAsMonitorPowerStopEvent(cls, buf, offset):
+    def GetRootAs(cls, buf, offset=0):
         n = flatbuffers.encode.Get(flatbuffers.packer.uoffset, buf, offset)
         x = MonitorPowerStopEvent()
         x.Init(buf, n + offset)
         return x
 
+    @classmethod
+    def GetRootAsMonitorPowerStopEvent(cls, buf, offset=0):
+        """This method is deprecated. Please switch to GetRootAs."""
+        return cls.GetRootAs(buf, offset)
     # MonitorPowerStopEvent
     def Init(self, buf, pos):
         self._tab = flatbuffers.table.Table(buf, pos)
@@ -47,8 +53,63 @@ class MonitorPowerStopEvent(object):
             return self._tab.VectorLen(o)
         return 0
 
-def MonitorPowerStopEventStart(builder): builder.StartObject(2)
+    # MonitorPowerStopEvent
+    def PidsIsNone(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(6))
+        return o == 0
+
+    # MonitorPowerStopEvent
+    def EventUuid(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(8))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # MonitorPowerStopEvent
+    def CorrelationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(10))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # MonitorPowerStopEvent
+    def CausationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(12))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # MonitorPowerStopEvent
+    def SourcePlugin(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(14))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+def MonitorPowerStopEventStart(builder): builder.StartObject(6)
+def Start(builder):
+    return MonitorPowerStopEventStart(builder)
 def MonitorPowerStopEventAddEventCreateTs(builder, eventCreateTs): builder.PrependUOffsetTRelativeSlot(0, flatbuffers.number_types.UOffsetTFlags.py_type(eventCreateTs), 0)
+def AddEventCreateTs(builder, eventCreateTs):
+    return MonitorPowerStopEventAddEventCreateTs(builder, eventCreateTs)
 def MonitorPowerStopEventAddPids(builder, pids): builder.PrependUOffsetTRelativeSlot(1, flatbuffers.number_types.UOffsetTFlags.py_type(pids), 0)
+def AddPids(builder, pids):
+    return MonitorPowerStopEventAddPids(builder, pids)
 def MonitorPowerStopEventStartPidsVector(builder, numElems): return builder.StartVector(4, numElems, 4)
+def StartPidsVector(builder, numElems):
+    return MonitorPowerStopEventStartPidsVector(builder, numElems)
+def MonitorPowerStopEventAddEventUuid(builder, eventUuid): builder.PrependUOffsetTRelativeSlot(2, flatbuffers.number_types.UOffsetTFlags.py_type(eventUuid), 0)
+def AddEventUuid(builder, eventUuid):
+    return MonitorPowerStopEventAddEventUuid(builder, eventUuid)
+def MonitorPowerStopEventAddCorrelationId(builder, correlationId): builder.PrependUOffsetTRelativeSlot(3, flatbuffers.number_types.UOffsetTFlags.py_type(correlationId), 0)
+def AddCorrelationId(builder, correlationId):
+    return MonitorPowerStopEventAddCorrelationId(builder, correlationId)
+def MonitorPowerStopEventAddCausationId(builder, causationId): builder.PrependUOffsetTRelativeSlot(4, flatbuffers.number_types.UOffsetTFlags.py_type(causationId), 0)
+def AddCausationId(builder, causationId):
+    return MonitorPowerStopEventAddCausationId(builder, causationId)
+def MonitorPowerStopEventAddSourcePlugin(builder, sourcePlugin): builder.PrependUOffsetTRelativeSlot(5, flatbuffers.number_types.UOffsetTFlags.py_type(sourcePlugin), 0)
+def AddSourcePlugin(builder, sourcePlugin):
+    return MonitorPowerStopEventAddSourcePlugin(builder, sourcePlugin)
 def MonitorPowerStopEventEnd(builder): return builder.EndObject()
+def End(builder):
+    return MonitorPowerStopEventEnd(builder)
\ No newline at end of file
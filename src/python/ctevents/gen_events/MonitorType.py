@@ -3,8 +3,7 @@
 # namespace: gen_events
 
 class MonitorType(object):
-    ALL = 0
-    CPU = 1
-    GPU = 2
-    DRAM = 3
-
+    ALL = 1
+    CPU = 2
+    GPU = 3
+    DRAM = 4
@@ -72,7 +72,53 @@ class NewImageEvent(object):
         o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(10))
         return o == 0
 
-def NewImageEventStart(builder): builder.StartObject(4)
+    # NewImageEvent
+    def EventUuid(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(12))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # NewImageEvent
+    def CorrelationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(14))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # NewImageEvent
+    def CausationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(16))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # NewImageEvent
+    def SourcePlugin(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(18))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # NewImageEvent
+    def ImageRef(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(20))
+        if o != 0:
+            x = self._tab.Indirect(o + self._tab.Pos)
+            from gen_events.ImageReference import ImageReference
+            obj = ImageReference()
+            obj.Init(self._tab.Bytes, x)
+            return obj
+        return None
+
+    # NewImageEvent
+    def ImageCompression(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(22))
+        if o != 0:
+            return self._tab.Get(flatbuffers.number_types.Int8Flags, o + self._tab.Pos)
+        return 0
+
+def NewImageEventStart(builder): builder.StartObject(10)
 def Start(builder):
     return NewImageEventStart(builder)
 def NewImageEventAddEventCreateTs(builder, eventCreateTs): builder.PrependUOffsetTRelativeSlot(0, flatbuffers.number_types.UOffsetTFlags.py_type(eventCreateTs), 0)
@@ -90,6 +136,24 @@ def AddImage(builder, image):
 def NewImageEventStartImageVector(builder, numElems): return builder.StartVector(1, numElems, 1)
 def StartImageVector(builder, numElems):
     return NewImageEventStartImageVector(builder, numElems)
+def NewImageEventAddEventUuid(builder, eventUuid): builder.PrependUOffsetTRelativeSlot(4, flatbuffers.number_types.UOffsetTFlags.py_type(eventUuid), 0)
+def AddEventUuid(builder, eventUuid):
+    return NewImageEventAddEventUuid(builder, eventUuid)
+def NewImageEventAddCorrelationId(builder, correlationId): builder.PrependUOffsetTRelativeSlot(5, flatbuffers.number_types.UOffsetTFlags.py_type(correlationId), 0)
+def AddCorrelationId(builder, correlationId):
+    return NewImageEventAddCorrelationId(builder, correlationId)
+def NewImageEventAddCausationId(builder, causationId): builder.PrependUOffsetTRelativeSlot(6, flatbuffers.number_types.UOffsetTFlags.py_type(causationId), 0)
+def AddCausationId(builder, causationId):
+    return NewImageEventAddCausationId(builder, causationId)
+def NewImageEventAddSourcePlugin(builder, sourcePlugin): builder.PrependUOffsetTRelativeSlot(7, flatbuffers.number_types.UOffsetTFlags.py_type(sourcePlugin), 0)
+def AddSourcePlugin(builder, sourcePlugin):
+    return NewImageEventAddSourcePlugin(builder, sourcePlugin)
+def NewImageEventAddImageRef(builder, imageRef): builder.PrependUOffsetTRelativeSlot(8, flatbuffers.number_types.UOffsetTFlags.py_type(imageRef), 0)
+def AddImageRef(builder, imageRef):
+    return NewImageEventAddImageRef(builder, imageRef)
+def NewImageEventAddImageCompression(builder, imageCompression): builder.PrependInt8Slot(9, imageCompression, 0)
+def AddImageCompression(builder, imageCompression):
+    return NewImageEventAddImageCompression(builder, imageCompression)
 def NewImageEventEnd(builder): return builder.EndObject()
 def End(builder):
     return NewImageEventEnd(builder)
\ No newline at end of file
@@ -45,7 +45,35 @@ class PluginStartedEvent(object):
             return self._tab.String(o + self._tab.Pos)
         return None
 
-def PluginStartedEventStart(builder): builder.StartObject(3)
+    # PluginStartedEvent
+    def EventUuid(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(10))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # PluginStartedEvent
+    def CorrelationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(12))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # PluginStartedEvent
+    def CausationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(14))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # PluginStartedEvent
+    def SourcePlugin(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(16))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+def PluginStartedEventStart(builder): builder.StartObject(7)
 def Start(builder):
     return PluginStartedEventStart(builder)
 def PluginStartedEventAddEventCreateTs(builder, eventCreateTs): builder.PrependUOffsetTRelativeSlot(0, flatbuffers.number_types.UOffsetTFlags.py_type(eventCreateTs), 0)
@@ -57,6 +85,18 @@ def AddPluginName(builder, pluginName):
 def PluginStartedEventAddPluginUuid(builder, pluginUuid): builder.PrependUOffsetTRelativeSlot(2, flatbuffers.number_types.UOffsetTFlags.py_type(pluginUuid), 0)
 def AddPluginUuid(builder, pluginUuid):
     return PluginStartedEventAddPluginUuid(builder, pluginUuid)
+def PluginStartedEventAddEventUuid(builder, eventUuid): builder.PrependUOffsetTRelativeSlot(3, flatbuffers.number_types.UOffsetTFlags.py_type(eventUuid), 0)
+def AddEventUuid(builder, eventUuid):
+    return PluginStartedEventAddEventUuid(builder, eventUuid)
+def PluginStartedEventAddCorrelationId(builder, correlationId): builder.PrependUOffsetTRelativeSlot(4, flatbuffers.number_types.UOffsetTFlags.py_type(correlationId), 0)
+def AddCorrelationId(builder, correlationId):
+    return PluginStartedEventAddCorrelationId(builder, correlationId)
+def PluginStartedEventAddCausationId(builder, causationId): builder.PrependUOffsetTRelativeSlot(5, flatbuffers.number_types.UOffsetTFlags.py_type(causationId), 0)
+def AddCausationId(builder, causationId):
+    return PluginStartedEventAddCausationId(builder, causationId)
+def PluginStartedEventAddSourcePlugin(builder, sourcePlugin): builder.PrependUOffsetTRelativeSlot(6, flatbuffers.number_types.UOffsetTFlags.py_type(sourcePlugin), 0)
+def AddSourcePlugin(builder, sourcePlugin):
+    return PluginStartedEventAddSourcePlugin(builder, sourcePlugin)
 def PluginStartedEventEnd(builder): return builder.EndObject()
 def End(builder):
     return PluginStartedEventEnd(builder)
\ No newline at end of file
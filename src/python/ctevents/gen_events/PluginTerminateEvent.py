@@ -45,7 +45,35 @@ class PluginTerminateEvent(object):
             return self._tab.String(o + self._tab.Pos)
         return None
 
-def PluginTerminateEventStart(builder): builder.StartObject(3)
+    # PluginTerminateEvent
+    def EventUuid(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(10))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # PluginTerminateEvent
+    def CorrelationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(12))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # PluginTerminateEvent
+    def CausationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(14))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # PluginTerminateEvent
+    def SourcePlugin(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(16))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+def PluginTerminateEventStart(builder): builder.StartObject(7)
 def Start(builder):
     return PluginTerminateEventStart(builder)
 def PluginTerminateEventAddEventCreateTs(builder, eventCreateTs): builder.PrependUOffsetTRelativeSlot(0, flatbuffers.number_types.UOffsetTFlags.py_type(eventCreateTs), 0)
@@ -57,6 +85,18 @@ def AddTargetPluginName(builder, targetPluginName):
 def PluginTerminateEventAddTargetPluginUuid(builder, targetPluginUuid): builder.PrependUOffsetTRelativeSlot(2, flatbuffers.number_types.UOffsetTFlags.py_type(targetPluginUuid), 0)
 def AddTargetPluginUuid(builder, targetPluginUuid):
     return PluginTerminateEventAddTargetPluginUuid(builder, targetPluginUuid)
+def PluginTerminateEventAddEventUuid(builder, eventUuid): builder.PrependUOffsetTRelativeSlot(3, flatbuffers.number_types.UOffsetTFlags.py_type(eventUuid), 0)
+def AddEventUuid(builder, eventUuid):
+    return PluginTerminateEventAddEventUuid(builder, eventUuid)
+def PluginTerminateEventAddCorrelationId(builder, correlationId): builder.PrependUOffsetTRelativeSlot(4, flatbuffers.number_types.UOffsetTFlags.py_type(correlationId), 0)
+def AddCorrelationId(builder, correlationId):
+    return PluginTerminateEventAddCorrelationId(builder, correlationId)
+def PluginTerminateEventAddCausationId(builder, causationId): builder.PrependUOffsetTRelativeSlot(5, flatbuffers.number_types.UOffsetTFlags.py_type(causationId), 0)
+def AddCausationId(builder, causationId):
+    return PluginTerminateEventAddCausationId(builder, causationId)
+def PluginTerminateEventAddSourcePlugin(builder, sourcePlugin): builder.PrependUOffsetTRelativeSlot(6, flatbuffers.number_types.UOffsetTFlags.py_type(sourcePlugin), 0)
+def AddSourcePlugin(builder, sourcePlugin):
+    return PluginTerminateEventAddSourcePlugin(builder, sourcePlugin)
 def PluginTerminateEventEnd(builder): return builder.EndObject()
 def End(builder):
     return PluginTerminateEventEnd(builder)
\ No newline at end of file
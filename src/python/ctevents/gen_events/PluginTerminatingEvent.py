@@ -45,7 +45,35 @@ class PluginTerminatingEvent(object):
             return self._tab.String(o + self._tab.Pos)
         return None
 
-def PluginTerminatingEventStart(builder): builder.StartObject(3)
+    # PluginTerminatingEvent
+    def EventUuid(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(10))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # PluginTerminatingEvent
+    def CorrelationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(12))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # PluginTerminatingEvent
+    def CausationId(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(14))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+    # PluginTerminatingEvent
+    def SourcePlugin(self):
+        o = flatbuffers.number_types.UOffsetTFlags.py_type(self._tab.Offset(16))
+        if o != 0:
+            return self._tab.String(o + self._tab.Pos)
+        return None
+
+def PluginTerminatingEventStart(builder): builder.StartObject(7)
 def Start(builder):
     return PluginTerminatingEventStart(builder)
 def PluginTerminatingEventAddEventCreateTs(builder, eventCreateTs): builder.PrependUOffsetTRelativeSlot(0, flatbuffers.number_types.UOffsetTFlags.py_type(eventCreateTs), 0)
@@ -57,6 +85,18 @@ def AddPluginName(builder, pluginName):
 def PluginTerminatingEventAddPluginUuid(builder, pluginUuid): builder.PrependUOffsetTRelativeSlot(2, flatbuffers.number_types.UOffsetTFlags.py_type(pluginUuid), 0)
 def AddPluginUuid(builder, pluginUuid):
     return PluginTerminatingEventAddPluginUuid(builder, pluginUuid)
+def PluginTerminatingEventAddEventUuid(builder, eventUuid): builder.PrependUOffsetTRelativeSlot(3, flatbuffers.number_types.UOffsetTFlags.py_type(eventUuid), 0)
+def AddEventUuid(builder, eventUuid):
+    return PluginTerminatingEventAddEventUuid(builder, eventUuid)
+def PluginTerminatingEventAddCorrelationId(builder, correlationId): builder.PrependUOffsetTRelativeSlot(4, flatbuffers.number_types.UOffsetTFlags.py_type(correlationId), 0)
+def AddCorrelationId(builder, correlationId):
+    return PluginTerminatingEventAddCorrelationId(builder, correlationId)
+def PluginTerminatingEventAddCausationId(builder, causationId): builder.PrependUOffsetTRelativeSlot(5, flatbuffers.number_types.UOffsetTFlags.py_type(causationId), 0)
+def AddCausationId(builder, causationId):
+    return PluginTerminatingEventAddCausationId(builder, causationId)
+def PluginTerminatingEventAddSourcePlugin(builder, sourcePlugin): builder.PrependUOffsetTRelativeSlot(6, flatbuffers.number_types.UOffsetTFlags.py_type(sourcePlugin), 0)
+def AddSourcePlugin(builder, sourcePlugin):
+    return PluginTerminatingEventAddSourcePlugin(builder, sourcePlugin)
 def PluginTerminatingEventEnd(builder): return builder.EndObject()
 def End(builder):
     return PluginTerminatingEventEnd(builder)
\ No newline at end of file
@@ -1,10 +1,18 @@
 import datetime
+import os
 import uuid
 import sys
 from ctevents.ctevents import _bytes_to_event, _event_to_typed_event
 from ctevents.ctevents import _generate_new_image_fb_event, _generate_image_received_fb_event, _generate_image_scored_fb_event, _generate_delete_image_fb_event, _generate_start_plugin_fb_event, _generate_terminating_plugin_fb_event, _generate_terminate_plugin_fb_event, _generate_store_image_fb_event
 from ctevents.ctevents import _generate_new_image_fb_with_prefix, _generate_image_received_fb_with_prefix, _generate_store_image_fb_with_prefix, _generate_terminating_plugin_fb_with_prefix, _generate_delete_image_fb_with_prefix, _generate_image_scored_fb_with_prefix, _generate_start_plugin_fb_with_prefix, _generate_terminate_plugin_fb_with_prefix
 from ctevents.ctevents import EVENT_TYPE_BYTE_PREFIX
+from ctevents.ctevents import get_trace, response_trace
+from ctevents.ctevents import _generate_new_image_ref_fb_event, image_checksum, resolve_new_image, ImageLocationType
+from ctevents.ctevents import socket_message_to_typed_event, EVENT_SCHEMA_MAJOR, EVENT_SCHEMA_MINOR
+from ctevents.ctevents import ImageCompression
+from ctevents.ctevents import _generate_image_processing_error_fb_event, _generate_image_processing_error_fb_with_prefix
+from ctevents.ctevents import _generate_detection_fb_event, _generate_detection_fb_with_prefix
+from ctevents.ctevents import _generate_config_reloaded_fb_event, _generate_config_reloaded_fb_with_prefix
 
 
 def test_new_image_event_with_prefix():
@@ -152,7 +160,18 @@ def test_image_stored_event_with_prefix():
     
     # check that prefix is the right thing
     assert stored_image_fb[0:2] == EVENT_TYPE_BYTE_PREFIX['IMAGE_STORED']
-    
+
+def test_image_stored_event_location():
+    """
+    Check that the optional location of image stored events is serialized when given.
+    """
+    uuid_str = str(uuid.uuid4())
+    fb = _generate_store_image_fb_event(uuid_str, 'jpg', 'Save', location='/images/x.jpg')
+    assert _event_to_typed_event(_bytes_to_event(fb)).Location() == b'/images/x.jpg'
+
+    fb = _generate_store_image_fb_event(uuid_str, 'jpg', 'Save')
+    assert _event_to_typed_event(_bytes_to_event(fb)).Location() is None
+
 def test_delete_image_event_fb():
     """
     A basic test function to check that serializing and deserializing delete image event flatbuffers
@@ -363,6 +382,150 @@ def test_image_scored_event_with_prefix():
     # check that prefix is the right thing
     assert image_scored_fb[0:2] == EVENT_TYPE_BYTE_PREFIX['IMAGE_SCORED']
 
+def test_trace_fields():
+    """
+    Check that the optional tracing fields are absent by default and that a response event inherits
+    the correlation id and records its cause.
+    """
+    image_uuid = str(uuid.uuid4())
+
+    # events without trace fields read back as None
+    received_event = _event_to_typed_event(_bytes_to_event(_generate_image_received_fb_event(image_uuid, 'jpg')))
+    assert get_trace(received_event) == {"event_uuid": None, "correlation_id": None,
+                                         "causation_id": None, "source_plugin": None}
+
+    # a new chain is correlated by its first event
+    first = response_trace(None, "image_generating_plugin")
+    assert first["correlation_id"] == first["event_uuid"]
+    received_event = _event_to_typed_event(_bytes_to_event(
+        _generate_image_received_fb_event(image_uuid, 'jpg', trace=first)))
+    assert get_trace(received_event) == first
+
+    # the response points back to its cause
+    trace = response_trace(received_event, "image_scoring_plugin")
+    scores = [{"image_uuid": image_uuid, "label": "lab", "probability": 0.95}]
+    scored_event = _event_to_typed_event(_bytes_to_event(
+        _generate_image_scored_fb_event(image_uuid, 'jpg', scores, trace=trace)))
+    assert get_trace(scored_event)["correlation_id"] == first["correlation_id"]
+    assert get_trace(scored_event)["causation_id"] == first["event_uuid"]
+    assert get_trace(scored_event)["source_plugin"] == "image_scoring_plugin"
+
+def test_schema_version():
+    """
+    Check that generated events carry our schema version and are accepted when received.
+    """
+    image_uuid = str(uuid.uuid4())
+    e = _bytes_to_event(_generate_image_received_fb_event(image_uuid, 'jpg'))
+    assert e.SchemaMajor() == EVENT_SCHEMA_MAJOR
+    assert e.SchemaMinor() == EVENT_SCHEMA_MINOR
+    received_event = socket_message_to_typed_event(_generate_image_received_fb_with_prefix(image_uuid, 'jpg'))
+    assert received_event.ImageUuid() == image_uuid.encode('utf-8')
+
+def test_new_image_ref_event_fb():
+    """
+    Check that a new image event can reference an image file and that both forms resolve to the image.
+    """
+    uuid_str = str(uuid.uuid4())
+    path = os.path.abspath('labrador-pup.jpg')
+    with open(path, 'rb') as f:
+        image = f.read()
+
+    # the embedded form
+    e = _bytes_to_event(_generate_new_image_fb_event(uuid_str, 'jpg', image))
+    assert resolve_new_image(_event_to_typed_event(e)) == image
+
+    # the by-reference form
+    fb = _generate_new_image_ref_fb_event(uuid_str, 'jpg', ImageLocationType.FilePath, path,
+                                          len(image), image_checksum(image))
+    new_image_event = _event_to_typed_event(_bytes_to_event(fb))
+    assert new_image_event.ImageLength() == 0
+    assert new_image_event.ImageRef().Size() == len(image)
+    assert resolve_new_image(new_image_event) == image
+
+    # a bad checksum is detected
+    fb = _generate_new_image_ref_fb_event(uuid_str, 'jpg', ImageLocationType.FilePath, path,
+                                          len(image), image_checksum(b'other'))
+    try:
+        resolve_new_image(_event_to_typed_event(_bytes_to_event(fb)))
+        assert False
+    except Exception as e:
+        assert "checksum" in str(e)
+
+def test_new_image_compression():
+    """
+    Check that compressed images are smaller and resolve to the original image, and that images
+    below the cutoff are not compressed.
+    """
+    uuid_str = str(uuid.uuid4())
+    image = b'camera traps ' * 1000
+    plain = _generate_new_image_fb_event(uuid_str, 'jpg', image)
+    for compression in (ImageCompression.Zstd, ImageCompression.Lz4):
+        fb = _generate_new_image_fb_event(uuid_str, 'jpg', image, compression=compression, min_compress_size=1024)
+        assert len(fb) < len(plain)
+        new_image_event = _event_to_typed_event(_bytes_to_event(fb))
+        assert new_image_event.ImageCompression() == compression
+        assert resolve_new_image(new_image_event) == image
+
+        fb = _generate_new_image_fb_event(uuid_str, 'jpg', image, compression=compression, min_compress_size=len(image) + 1)
+        new_image_event = _event_to_typed_event(_bytes_to_event(fb))
+        assert new_image_event.ImageCompression() == ImageCompression.Uncompressed
+        assert resolve_new_image(new_image_event) == image
+
+def test_image_processing_error_event_fb():
+    """
+    Check that serializing and deserializing image processing error events works as expected.
+    """
+    uuid_str = str(uuid.uuid4())
+    fb = _generate_image_processing_error_fb_event(uuid_str, 'image_recv_plugin', 'action',
+                                                   'ActionWriteFileError', 'disk full')
+    e = _event_to_typed_event(_bytes_to_event(fb))
+    assert e.ImageUuid() == uuid_str.encode('utf-8')
+    assert e.PluginName() == b'image_recv_plugin'
+    assert e.Stage() == b'action'
+    assert e.ErrorCode() == b'ActionWriteFileError'
+    assert e.ErrorMessage() == b'disk full'
+
+    fb = _generate_image_processing_error_fb_with_prefix(uuid_str, 'image_recv_plugin', 'action',
+                                                         'ActionWriteFileError', 'disk full')
+    assert fb[0:2] == EVENT_TYPE_BYTE_PREFIX['IMAGE_PROCESSING_ERROR']
+
+def test_detection_event_fb():
+    """
+    Check that serializing and deserializing detection events works as expected.
+    """
+    uuid_str = str(uuid.uuid4())
+    fb = _generate_detection_fb_event(uuid_str, 'jpg', 'animal', 0.75, 0.5, stored_location='/images/x.jpg')
+    e = _event_to_typed_event(_bytes_to_event(fb))
+    assert e.ImageUuid() == uuid_str.encode('utf-8')
+    assert e.Label() == b'animal'
+    assert e.Probability() == 0.75
+    assert e.Threshold() == 0.5
+    assert e.StoredLocation() == b'/images/x.jpg'
+
+    fb = _generate_detection_fb_event(uuid_str, 'jpg', 'animal', 0.75, 0.5)
+    assert _event_to_typed_event(_bytes_to_event(fb)).StoredLocation() is None
+
+    fb = _generate_detection_fb_with_prefix(uuid_str, 'jpg', 'animal', 0.75, 0.5)
+    assert fb[0:2] == EVENT_TYPE_BYTE_PREFIX['DETECTION']
+
+def test_config_reloaded_event_fb():
+    """
+    Check that serializing and deserializing config reloaded events works as expected.
+    """
+    fb = _generate_config_reloaded_fb_event('/traps.toml', ['log_level'], ['publish_port', 'plugins.external'])
+    e = _event_to_typed_event(_bytes_to_event(fb))
+    assert e.ConfigFile() == b'/traps.toml'
+    assert [e.Applied(i) for i in range(e.AppliedLength())] == [b'log_level']
+    assert [e.RequiresRestart(i) for i in range(e.RequiresRestartLength())] == [b'publish_port', b'plugins.external']
+
+    fb = _generate_config_reloaded_fb_event('/traps.toml', [], [])
+    e = _event_to_typed_event(_bytes_to_event(fb))
+    assert e.AppliedLength() == 0
+    assert not e.AppliedIsNone()
+
+    fb = _generate_config_reloaded_fb_with_prefix('/traps.toml', [], [])
+    assert fb[0:2] == EVENT_TYPE_BYTE_PREFIX['CONFIG_RELOADED']
+
 
 if __name__ == "__main__":
     test_new_image_event_fb()
@@ -380,4 +543,11 @@ if __name__ == "__main__":
     test_terminating_plugin_event_fb()
     test_terminating_plugin_event_with_prefix()
     test_terminate_plugin_event_fb()
-    test_terminate_plugin_event_with_prefix()
\ No newline at end of file
+    test_terminate_plugin_event_with_prefix()
+    test_trace_fields()
+    test_schema_version()
+    test_new_image_ref_event_fb()
+    test_new_image_compression()
+    test_image_processing_error_event_fb()
+    test_detection_event_fb()
+    test_config_reloaded_event_fb()
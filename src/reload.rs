@@ -0,0 +1,273 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use crate::config::config::Config;
+use crate::config::errors::Errors;
+use crate::plugins::actions;
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+// The internal plugins that only report on the application's progress.  They
+// stay running when removed from plugins.internal by a reload but skip their
+// work until they are enabled again.
+pub const REPORTER_PLUGIN_NAMES: [&str; 3] = ["observer_plugin", "detection_reporter_plugin", "mqtt_publisher_plugin"];
+
+// The settings, and the tables whose settings, the running plugins apply when
+// the configuration is reloaded.  Action tables are added from the registry.
+const LIVE_SETTINGS: [&str; 7] = [
+    "log_level",
+    "title",
+    "detection_reporter.thresholds",
+    "detection_reporter.detection_file",
+    "plugins.config.image_store_plugin",
+    "plugins.config.detection_reporter_plugin.thresholds",
+    "plugins.config.detection_reporter_plugin.detection_file",
+];
+
+// Actions whose settings are only read when their plugin starts.
+const RESTART_ACTIONS: [&str; 1] = ["observer_journal_action"];
+
+// The list of internal plugins, which is reconciled separately.
+const INTERNAL_PLUGINS_KEY: &str = "plugins.internal";
+
+// ***************************************************************************
+// LIVE CONFIGURATION
+// ***************************************************************************
+// The configuration currently in effect.  It starts as the configuration the
+// application was started with and is replaced by each successful reload.
+static LIVE_CONFIG: RwLock<Option<Arc<Config>>> = RwLock::new(None);
+
+// ---------------------------------------------------------------------------
+// set_config:
+// ---------------------------------------------------------------------------
+pub fn set_config(config: Config) {
+    *LIVE_CONFIG.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(config));
+}
+
+// ---------------------------------------------------------------------------
+// get_config:
+// ---------------------------------------------------------------------------
+/** The configuration in effect, which is None until the application sets it. */
+pub fn get_config() -> Option<Arc<Config>> {
+    LIVE_CONFIG.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+// ---------------------------------------------------------------------------
+// is_plugin_enabled:
+// ---------------------------------------------------------------------------
+/** Check if an internal plugin is listed in the configuration in effect.
+ * Plugins are always enabled when no configuration has been set.
+ */
+pub fn is_plugin_enabled(name: &str) -> bool {
+    match get_config() {
+        Some(config) => config.plugins.internal.as_ref().is_some_and(|p| p.iter().any(|n| n == name)),
+        None => true,
+    }
+}
+
+// ***************************************************************************
+// RELOAD PLAN
+// ***************************************************************************
+/** The outcome of comparing a reloaded configuration with the running one.
+ * The config is the running configuration with the live settings replaced,
+ * applied lists the settings that changed and take effect immediately and
+ * requires_restart lists the changed settings that are ignored until the
+ * application restarts.
+ */
+#[derive(Debug)]
+pub struct ReloadPlan {
+    pub config: Config,
+    pub applied: Vec<String>,
+    pub requires_restart: Vec<String>,
+}
+
+// ---------------------------------------------------------------------------
+// plan_reload:
+// ---------------------------------------------------------------------------
+/** Compare the reloaded configuration with the running one setting by
+ * setting.  Changes to live settings are copied into the running
+ * configuration and all others require a restart.  Reporter plugins can be
+ * removed from and restored to plugins.internal, but only the plugins that
+ * were started with the application can run.
+ */
+pub fn plan_reload(startup: &Config, running: &Config, new: &Config) -> Result<ReloadPlan, Errors> {
+    let to_value = |c: &Config| toml::Value::try_from(c).map_err(|e| Errors::ConfigReloadError(e.to_string()));
+    let mut running_value = to_value(running)?;
+    let mut running_settings = BTreeMap::new();
+    let mut new_settings = BTreeMap::new();
+    flatten("", &running_value, &mut running_settings);
+    flatten("", &to_value(new)?, &mut new_settings);
+
+    // Visit every setting that was added, removed or changed.
+    let mut keys: Vec<&String> = running_settings.keys().chain(new_settings.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    let mut applied = vec![];
+    let mut requires_restart = vec![];
+    for key in keys {
+        let new_setting = new_settings.get(key);
+        if running_settings.get(key) == new_setting || key == INTERNAL_PLUGINS_KEY {
+            continue;
+        }
+        if is_live(key) {
+            set_setting(&mut running_value, key, new_setting.cloned());
+            applied.push(key.clone());
+        } else {
+            requires_restart.push(key.clone());
+        }
+    }
+
+    // Reporters can be disabled and re-enabled; other plugins can't change.
+    let startup_plugins = startup.plugins.internal.clone().unwrap_or_default();
+    let running_plugins = running.plugins.internal.clone().unwrap_or_default();
+    let new_plugins = new.plugins.internal.clone().unwrap_or_default();
+    let effective: Vec<String> = startup_plugins.iter()
+        .filter(|p| !REPORTER_PLUGIN_NAMES.contains(&p.as_str()) || new_plugins.contains(p))
+        .cloned().collect();
+    if effective != running_plugins {
+        let list = effective.iter().map(|p| toml::Value::String(p.clone())).collect();
+        set_setting(&mut running_value, INTERNAL_PLUGINS_KEY, Some(toml::Value::Array(list)));
+        applied.push(INTERNAL_PLUGINS_KEY.to_string());
+    }
+    if new_plugins.len() != effective.len() || new_plugins.iter().any(|p| !effective.contains(p)) {
+        requires_restart.push(INTERNAL_PLUGINS_KEY.to_string());
+    }
+
+    let config = running_value.try_into().map_err(|e: toml::de::Error| Errors::ConfigReloadError(e.to_string()))?;
+    Result::Ok(ReloadPlan {config, applied, requires_restart})
+}
+
+// ---------------------------------------------------------------------------
+// is_live:
+// ---------------------------------------------------------------------------
+/** Check if a setting is applied by the running plugins. */
+fn is_live(key: &str) -> bool {
+    let within = |prefix: &str| key == prefix || key.starts_with(&format!("{}.", prefix));
+    LIVE_SETTINGS.iter().any(|s| within(s)) ||
+        actions::get_action_names().iter()
+            .filter(|a| !RESTART_ACTIONS.contains(&a.as_str()))
+            .any(|a| within(&format!("plugins.config.{}", a)))
+}
+
+// ---------------------------------------------------------------------------
+// flatten:
+// ---------------------------------------------------------------------------
+/** Collect the settings of a toml value by their dotted keys.  Arrays are
+ * single settings.
+ */
+fn flatten(prefix: &str, value: &toml::Value, settings: &mut BTreeMap<String, toml::Value>) {
+    match value {
+        toml::Value::Table(table) => {
+            for (k, v) in table {
+                let key = if prefix.is_empty() {k.clone()} else {format!("{}.{}", prefix, k)};
+                flatten(&key, v, settings);
+            }
+        },
+        other => {
+            settings.insert(prefix.to_string(), other.clone());
+        },
+    }
+}
+
+// ---------------------------------------------------------------------------
+// set_setting:
+// ---------------------------------------------------------------------------
+/** Assign or, when the value is None, remove the setting with a dotted key,
+ * creating the tables that lead to it.
+ */
+fn set_setting(root: &mut toml::Value, key: &str, value: Option<toml::Value>) {
+    let mut parts: Vec<&str> = key.split('.').collect();
+    let last = match parts.pop() {
+        Some(l) => l,
+        None => return,
+    };
+    let mut table = match root.as_table_mut() {
+        Some(t) => t,
+        None => return,
+    };
+    for part in parts {
+        let entry = table.entry(part.to_string()).or_insert_with(|| toml::Value::Table(Default::default()));
+        table = match entry.as_table_mut() {
+            Some(t) => t,
+            None => return,
+        };
+    }
+    match value {
+        Some(v) => {table.insert(last.to_string(), v);},
+        None => {table.remove(last);},
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: reload.rs");
+    }
+
+    #[test]
+    fn reload_plans() {
+        actions::register_builtin_actions();
+        let startup: Config = toml::from_str(r#"
+            publish_port = 5559
+            subscribe_port = 5560
+            images_output_dir = "/tmp/images"
+            [plugins]
+            internal = ["image_store_plugin", "observer_plugin", "detection_reporter_plugin"]
+            [plugins.config.image_store_plugin.action_thresholds]
+            Save = 0.8
+            [plugins.config.observer_journal_action]
+            max_files = 2
+        "#).unwrap();
+        let new: Config = toml::from_str(r#"
+            log_level = "debug"
+            publish_port = 6559
+            subscribe_port = 5560
+            images_output_dir = "/tmp/images"
+            [plugins]
+            internal = ["image_store_plugin", "detection_reporter_plugin", "oracle_plugin"]
+            [plugins.config.image_store_plugin.action_thresholds]
+            Save = 0.6
+            ReduceSave = 0.3
+            [plugins.config.observer_journal_action]
+            max_files = 3
+        "#).unwrap();
+
+        // Live settings are applied and the observer is disabled.
+        let plan = plan_reload(&startup, &startup, &new).unwrap();
+        assert_eq!(plan.applied, vec![
+            "log_level",
+            "plugins.config.image_store_plugin.action_thresholds.ReduceSave",
+            "plugins.config.image_store_plugin.action_thresholds.Save",
+            "plugins.internal",
+        ]);
+        assert_eq!(plan.requires_restart, vec![
+            "plugins.config.observer_journal_action.max_files",
+            "publish_port",
+            "plugins.internal",
+        ]);
+        assert_eq!(plan.config.log_level.as_deref(), Some("debug"));
+        assert_eq!(plan.config.publish_port, 5559);
+        assert_eq!(plan.config.plugins.internal, Some(vec!["image_store_plugin".to_string(),
+                                                           "detection_reporter_plugin".to_string()]));
+        let thresholds = plan.config.plugins.config.as_ref().unwrap()["image_store_plugin"]["action_thresholds"].clone();
+        assert_eq!(thresholds.get("ReduceSave").and_then(|v| v.as_float()), Some(0.3));
+
+        // Reloading the startup configuration re-enables the observer and
+        // removes the log level.
+        let plan = plan_reload(&startup, &plan.config, &startup).unwrap();
+        assert_eq!(plan.applied, vec![
+            "log_level",
+            "plugins.config.image_store_plugin.action_thresholds.ReduceSave",
+            "plugins.config.image_store_plugin.action_thresholds.Save",
+            "plugins.internal",
+        ]);
+        assert!(plan.requires_restart.is_empty());
+        assert_eq!(plan.config.log_level, None);
+        assert_eq!(plan.config.plugins.internal, startup.plugins.internal);
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::config::config::{CompletionConfig, Config};
+use crate::config::errors::Errors;
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+// Defaults used when the [shutdown] configuration does not specify a value.
+pub const DEFAULT_DRAIN_TIMEOUT_SECS: u64 = 30;
+
+// Defaults used when the [completion] configuration does not specify a value.
+pub const DEFAULT_GENERATOR: &str = "ext_image_gen_plugin";
+
+// The exit status of a run to completion in which images failed or did not finish.
+pub const COMPLETION_FAILURE_EXIT_CODE: i32 = 2;
+
+// ***************************************************************************
+// SHUTDOWN STATE
+// ***************************************************************************
+// Set when a signal asks the application to shut down.  The plugin monitor
+// coordinates the shutdown once it sees the request.
+static SHUTDOWN_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+// Set when the plugin monitor is registered to coordinate the shutdown.
+static COORDINATED: AtomicBool = AtomicBool::new(false);
+
+// Set while in-flight images drain; new images are rejected from then on.
+static DRAINING: AtomicBool = AtomicBool::new(false);
+
+// ---------------------------------------------------------------------------
+// request_shutdown:
+// ---------------------------------------------------------------------------
+/** Ask the application to shut down gracefully.  Returns false if a shutdown
+ * had already been requested.
+ */
+pub fn request_shutdown() -> bool {
+    !SHUTDOWN_REQUESTED.swap(true, Ordering::SeqCst)
+}
+
+// ---------------------------------------------------------------------------
+// is_shutdown_requested:
+// ---------------------------------------------------------------------------
+pub fn is_shutdown_requested() -> bool {
+    SHUTDOWN_REQUESTED.load(Ordering::SeqCst)
+}
+
+// ---------------------------------------------------------------------------
+// set_coordinated:
+// ---------------------------------------------------------------------------
+/** Record that the plugin monitor will coordinate a requested shutdown. */
+pub fn set_coordinated() {
+    COORDINATED.store(true, Ordering::SeqCst);
+}
+
+// ---------------------------------------------------------------------------
+// is_coordinated:
+// ---------------------------------------------------------------------------
+/** True if the plugin monitor coordinates a requested shutdown.  Otherwise no
+ * plugins run and the application can simply exit.
+ */
+pub fn is_coordinated() -> bool {
+    COORDINATED.load(Ordering::SeqCst)
+}
+
+// ---------------------------------------------------------------------------
+// start_draining:
+// ---------------------------------------------------------------------------
+pub fn start_draining() {
+    DRAINING.store(true, Ordering::SeqCst);
+}
+
+// ---------------------------------------------------------------------------
+// is_draining:
+// ---------------------------------------------------------------------------
+/** True once the application has stopped accepting new images. */
+pub fn is_draining() -> bool {
+    DRAINING.load(Ordering::SeqCst)
+}
+
+// ***************************************************************************
+// IMAGE TRACKER
+// ***************************************************************************
+/** The final outcomes of an image's trip through the pipeline.  Images
+ * abandoned because the application is shutting down are rejected rather
+ * than failed.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageOutcome {
+    Stored,
+    Deleted,
+    Failed,
+    Rejected,
+}
+
+/** Tracks the images announced by the image_recv_plugin's ImageReceivedEvents
+ * until they reach a terminal event, which is an ImageStoredEvent,
+ * ImageDeletedEvent or ImageProcessingErrorEvent.  Images are in flight in
+ * between.  The images the image_recv_plugin abandons before announcing them
+ * are counted as new images that reached their terminal event at once.
+ */
+#[derive(Debug, Default)]
+pub struct ImageTracker {
+    in_flight: HashSet<Uuid>,
+    pub new_images: usize,
+    pub stored: usize,
+    pub deleted: usize,
+    pub failed: usize,
+    pub rejected: usize,
+}
+
+impl ImageTracker {
+    // ----------------------------------------------------------------------
+    // new:
+    // ----------------------------------------------------------------------
+    pub fn new() -> Self {
+        ImageTracker::default()
+    }
+
+    // ----------------------------------------------------------------------
+    // record_new:
+    // ----------------------------------------------------------------------
+    /** Record an ImageReceivedEvent.  Repeated announcements of an image in
+     * flight are counted once.
+     */
+    pub fn record_new(&mut self, image_uuid: Uuid) {
+        if self.in_flight.insert(image_uuid) {
+            self.new_images += 1;
+        }
+    }
+
+    // ----------------------------------------------------------------------
+    // record_outcome:
+    // ----------------------------------------------------------------------
+    /** Record a terminal event.  Only the first terminal event of an image in
+     * flight is counted, so that an image that's stored is not also counted as
+     * failed by a later plugin.
+     */
+    pub fn record_outcome(&mut self, image_uuid: &Uuid, outcome: ImageOutcome) {
+        if !self.in_flight.remove(image_uuid) {
+            return;
+        }
+        match outcome {
+            ImageOutcome::Stored => self.stored += 1,
+            ImageOutcome::Deleted => self.deleted += 1,
+            ImageOutcome::Failed => self.failed += 1,
+            ImageOutcome::Rejected => self.rejected += 1,
+        }
+    }
+
+    // ----------------------------------------------------------------------
+    // record_abandoned:
+    // ----------------------------------------------------------------------
+    /** Record an image that the image_recv_plugin abandoned before announcing
+     * it with an ImageReceivedEvent.
+     */
+    pub fn record_abandoned(&mut self, image_uuid: Uuid, outcome: ImageOutcome) {
+        self.record_new(image_uuid);
+        self.record_outcome(&image_uuid, outcome);
+    }
+
+    // ----------------------------------------------------------------------
+    // in_flight:
+    // ----------------------------------------------------------------------
+    /** The number of images that haven't reached a terminal event. */
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+}
+
+// ***************************************************************************
+// RUN TO COMPLETION
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// get_completion_config:
+// ---------------------------------------------------------------------------
+/** The [completion] settings if the application runs to completion. */
+pub fn get_completion_config(config: &Config) -> Option<CompletionConfig> {
+    config.completion.clone().filter(|c| c.enabled.unwrap_or(false))
+}
+
+// ---------------------------------------------------------------------------
+// check_completion_stages:
+// ---------------------------------------------------------------------------
+/** Check that the pipeline reaches the stage whose events finish an image.
+ * Images only finish with the image_store_plugin's ImageStoredEvent or
+ * ImageDeletedEvent, or with an ImageProcessingErrorEvent, so without that
+ * plugin no image would ever finish and the run could only end at a cap.
+ */
+pub fn check_completion_stages(config: &Config) -> Result<(), Errors> {
+    let internal = config.plugins.internal.clone().unwrap_or_default();
+    if internal.iter().any(|p| p == "image_store_plugin") {
+        Result::Ok(())
+    } else {
+        Result::Err(Errors::CompletionImageStoreMissing())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// check_completion:
+// ---------------------------------------------------------------------------
+/** Return the reason the run is complete, if it is.  A run is complete when
+ * either cap is reached or when the generator has finished and every image it
+ * generated has reached a terminal event.  The elapsed time is measured from
+ * application start.
+ */
+pub fn check_completion(config: &CompletionConfig, tracker: &ImageTracker, generator_finished: bool,
+                        elapsed: Duration) -> Option<String> {
+    if let Some(secs) = config.max_duration_secs {
+        if elapsed >= Duration::from_secs(secs) {
+            return Some(format!("the max_duration_secs limit of {} seconds was reached", secs));
+        }
+    }
+    if let Some(max) = config.max_images {
+        if tracker.new_images >= max {
+            return Some(format!("the max_images limit of {} images was reached", max));
+        }
+    }
+    if generator_finished && tracker.in_flight() == 0 {
+        return Some(format!("the generator finished and all {} images reached a terminal event", tracker.new_images));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: shutdown.rs");
+    }
+
+    #[test]
+    fn image_tracking() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut tracker = ImageTracker::new();
+        tracker.record_new(a);
+        tracker.record_new(b);
+        tracker.record_new(b);
+        tracker.record_new(c);
+        assert_eq!((tracker.new_images, tracker.in_flight()), (3, 3));
+
+        // Only the first terminal event counts and unknown images are ignored.
+        tracker.record_outcome(&a, ImageOutcome::Stored);
+        tracker.record_outcome(&a, ImageOutcome::Failed);
+        tracker.record_outcome(&b, ImageOutcome::Failed);
+        tracker.record_outcome(&Uuid::new_v4(), ImageOutcome::Deleted);
+        assert_eq!((tracker.stored, tracker.deleted, tracker.failed), (1, 0, 1));
+        assert_eq!(tracker.in_flight(), 1);
+        tracker.record_outcome(&c, ImageOutcome::Rejected);
+        assert_eq!((tracker.deleted, tracker.rejected), (0, 1));
+        assert_eq!(tracker.in_flight(), 0);
+
+        // Abandoned images are new images that finished at once.
+        tracker.record_abandoned(Uuid::new_v4(), ImageOutcome::Rejected);
+        assert_eq!((tracker.new_images, tracker.rejected, tracker.in_flight()), (4, 2, 0));
+    }
+
+    #[test]
+    fn completion_stages() {
+        // Images only finish in the image_store_plugin.
+        let mut config = Config::default();
+        config.plugins.internal = Some(vec!["image_recv_plugin".to_string(), "image_score_plugin".to_string()]);
+        assert!(matches!(check_completion_stages(&config), Err(Errors::CompletionImageStoreMissing())));
+        config.plugins.internal.as_mut().unwrap().push("image_store_plugin".to_string());
+        assert!(check_completion_stages(&config).is_ok());
+    }
+
+    #[test]
+    fn completion() {
+        let config = CompletionConfig {enabled: Some(true), max_images: Some(2), max_duration_secs: Some(60),
+                                       ..Default::default()};
+        let mut tracker = ImageTracker::new();
+        let image = Uuid::new_v4();
+        tracker.record_new(image);
+        assert!(check_completion(&config, &tracker, false, Duration::from_secs(1)).is_none());
+
+        // The generator's images must all finish.
+        assert!(check_completion(&config, &tracker, true, Duration::from_secs(1)).is_none());
+        tracker.record_outcome(&image, ImageOutcome::Stored);
+        assert!(check_completion(&config, &tracker, true, Duration::from_secs(1)).unwrap().contains("generator"));
+
+        // Either cap ends the run.
+        assert!(check_completion(&config, &tracker, false, Duration::from_secs(60)).unwrap().contains("max_duration_secs"));
+        tracker.record_new(Uuid::new_v4());
+        assert!(check_completion(&config, &tracker, false, Duration::from_secs(1)).unwrap().contains("max_images"));
+    }
+}
@@ -0,0 +1,331 @@
+use std::collections::BTreeMap;
+
+use clap::Args;
+use serde::Serialize;
+
+use crate::config::errors::Errors;
+use crate::evaluation::{self, EvaluateParms, ImageRecord};
+use crate::plugins::actions::image_store_actions::{get_action_for_score, highest_score};
+use crate::plugins::image_store_plugin::{StoreAction, StoreConfig, StoreParms};
+use crate::traps_utils;
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+// The default threshold ranges, as start:end:step.
+const DEFAULT_SAVE_RANGE: &str = "0.05:1.0:0.05";
+const DEFAULT_REDUCE_SAVE_RANGE: &str = "0.05:1.0:0.05";
+
+// The fraction of an image's size stored by ReduceSave.  The image store does
+// not reduce images yet, so by default they cost as much as saved images.
+const DEFAULT_REDUCE_RATIO: f64 = 1.0;
+
+// Thresholds are rounded to this many decimal places to avoid float drift.
+const THRESHOLD_SCALE: f64 = 10000.0;
+
+// ***************************************************************************
+// THRESHOLD RANGES
+// ***************************************************************************
+/** The thresholds to try for one store action. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThresholdRange {
+    pub thresholds: Vec<f32>,
+}
+
+impl ThresholdRange {
+    // ----------------------------------------------------------------------
+    // parse:
+    // ----------------------------------------------------------------------
+    /** Parse either a single threshold or a start:end:step range with the end
+     * included.  Thresholds must be between 0.0 and 1.0, inclusive.
+     */
+    pub fn parse(range: &str) -> Result<Self, Errors> {
+        let invalid = || Errors::SweepInvalidArgument(format!("invalid threshold range {}", range));
+        let parts: Vec<f64> = range.split(':').map(|p| p.trim().parse::<f64>()).collect::<Result<_, _>>()
+                                   .map_err(|_| invalid())?;
+        let (start, end, step) = match parts[..] {
+            [value] => (value, value, 1.0),
+            [start, end, step] if step > 0.0 && start <= end => (start, end, step),
+            _ => return Result::Err(invalid()),
+        };
+        if start < 0.0 || end > 1.0 {
+            return Result::Err(invalid());
+        }
+
+        let steps = ((end - start) / step + 1e-9).floor() as usize;
+        let thresholds = (0..=steps)
+            .map(|i| ((start + i as f64 * step) * THRESHOLD_SCALE).round() / THRESHOLD_SCALE)
+            .map(|t| t as f32)
+            .collect();
+        Result::Ok(ThresholdRange {thresholds})
+    }
+}
+
+// ***************************************************************************
+// SWEEP
+// ***************************************************************************
+/** The outcome of one threshold combination.  A combination without a
+ * ReduceSave threshold only saves or deletes images.  Rates are absent when
+ * their denominator is zero.
+ */
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct SweepPoint {
+    pub save_threshold: f32,
+    pub reduce_save_threshold: Option<f32>,
+    pub saved: usize,
+    pub reduce_saved: usize,
+    pub deleted: usize,
+    pub storage_bytes: u64,
+    pub recall: Option<f64>,
+    pub precision: Option<f64>,
+}
+
+/** The sweep report.  Only images that were scored and have a ground truth
+ * are replayed.  The Pareto front holds the combinations for which no other
+ * combination uses no more storage and has a higher recall, ordered by
+ * storage.
+ */
+#[derive(Debug, Serialize)]
+pub struct Sweep {
+    pub images: usize,
+    pub images_replayed: usize,
+    pub positives: usize,
+    pub negative_labels: Vec<String>,
+    pub pareto_front: Vec<SweepPoint>,
+    pub points: Vec<SweepPoint>,
+}
+
+// ---------------------------------------------------------------------------
+// sweep:
+// ---------------------------------------------------------------------------
+/** Replay the recorded scores through the image store's policy for each
+ * combination of Save and ReduceSave thresholds.  ReduceSave thresholds at or
+ * above the Save threshold would never apply, so those combinations are
+ * skipped; each Save threshold is also tried without ReduceSave.
+ *
+ * An image's size is the size it was received with, or its size in the image
+ * directory, or else the average of the known sizes.
+ */
+pub fn sweep(images: &BTreeMap<String, ImageRecord>, negative_labels: &[String], save: &ThresholdRange,
+             reduce_save: &ThresholdRange, reduce_ratio: f64) -> Result<Sweep, Errors> {
+    // The images that can be replayed with their highest score, size and truth.
+    let known_sizes: Vec<u64> = images.values().filter_map(|i| i.image_bytes.or(i.stored_bytes)).collect();
+    let average_size = if known_sizes.is_empty() {0} else {known_sizes.iter().sum::<u64>() / known_sizes.len() as u64};
+    let replayed: Vec<(f32, u64, bool)> = images.values()
+        .filter_map(|i| {
+            let scores = i.scores.as_ref()?;
+            let positive = !negative_labels.contains(i.truth.as_ref()?);
+            Some((highest_score(scores.iter().map(|s| s.1)), i.image_bytes.or(i.stored_bytes).unwrap_or(average_size), positive))
+        })
+        .collect();
+    let positives = replayed.iter().filter(|r| r.2).count();
+
+    // Replay every combination.
+    let mut points = vec![];
+    for save_threshold in &save.thresholds {
+        let reduce_thresholds = std::iter::once(None)
+            .chain(reduce_save.thresholds.iter().filter(|r| *r < save_threshold).map(Some));
+        for reduce_save_threshold in reduce_thresholds {
+            let mut action_thresholds = BTreeMap::from([("Save".to_string(), *save_threshold)]);
+            if let Some(r) = reduce_save_threshold {
+                action_thresholds.insert("ReduceSave".to_string(), *r);
+            }
+            let store_parms = match StoreConfig::new_from_thresholds(&action_thresholds) {
+                Ok(config) => StoreParms {config_file: String::new(), config},
+                Err(e) => return Result::Err(Errors::SweepInvalidArgument(e.to_string())),
+            };
+            points.push(replay(&replayed, &store_parms, *save_threshold, reduce_save_threshold.copied(),
+                               reduce_ratio, positives));
+        }
+    }
+
+    // Order by storage then descending recall, and keep the points that improve recall.
+    points.sort_by(|a, b| a.storage_bytes.cmp(&b.storage_bytes)
+                          .then(b.recall.unwrap_or(0.0).total_cmp(&a.recall.unwrap_or(0.0))));
+    let mut pareto_front: Vec<SweepPoint> = vec![];
+    for point in &points {
+        let best = pareto_front.last().map(|p| p.recall.unwrap_or(0.0));
+        if best.map(|b| point.recall.unwrap_or(0.0) > b).unwrap_or(true) {
+            pareto_front.push(point.clone());
+        }
+    }
+
+    Result::Ok(Sweep {images: images.len(), images_replayed: replayed.len(), positives,
+                      negative_labels: negative_labels.to_vec(), pareto_front, points})
+}
+
+impl Sweep {
+    // ---------------------------------------------------------------------------
+    // to_markdown:
+    // ---------------------------------------------------------------------------
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# Image Store Threshold Sweep\n\n");
+        out.push_str(&format!("{} of {} images replayed, {} positive.  Negative labels: {}.  {} combinations tried.\n",
+                              self.images_replayed, self.images, self.positives, self.negative_labels.join(", "),
+                              self.points.len()));
+        out.push_str("\n## Pareto Front\n\n| Save | ReduceSave | Saved | Reduced | Deleted | Storage bytes | Recall | Precision |\n");
+        out.push_str("|---|---|---|---|---|---|---|---|\n");
+        for p in &self.pareto_front {
+            let rate = |r: Option<f64>| r.map_or("-".to_string(), |r| format!("{:.3}", r));
+            out.push_str(&format!("| {} | {} | {} | {} | {} | {} | {} | {} |\n", p.save_threshold,
+                                  p.reduce_save_threshold.map_or("-".to_string(), |r| r.to_string()),
+                                  p.saved, p.reduce_saved, p.deleted, p.storage_bytes, rate(p.recall), rate(p.precision)));
+        }
+        out
+    }
+}
+
+// ***************************************************************************
+// COMMAND
+// ***************************************************************************
+/** Replay a simulation run's scores through many image store thresholds.
+ * The images and their decisions are read the same way as by the evaluate
+ * command.  A threshold range is a single threshold or start:end:step.
+ */
+#[derive(Args, Debug)]
+pub struct SweepParms {
+    #[command(flatten)]
+    pub inputs: EvaluateParms,
+    /// The Save thresholds to try
+    #[arg(long, value_name = "RANGE", default_value = DEFAULT_SAVE_RANGE, value_parser = ThresholdRange::parse)]
+    pub save: ThresholdRange,
+    /// The ReduceSave thresholds to try
+    #[arg(long = "reduce-save", value_name = "RANGE", default_value = DEFAULT_REDUCE_SAVE_RANGE,
+          value_parser = ThresholdRange::parse)]
+    pub reduce_save: ThresholdRange,
+    /// The fraction of an image's size stored by ReduceSave
+    #[arg(long = "reduce-ratio", value_name = "FRACTION", default_value_t = DEFAULT_REDUCE_RATIO,
+          value_parser = parse_reduce_ratio)]
+    pub reduce_ratio: f64,
+}
+
+// ---------------------------------------------------------------------------
+// parse_reduce_ratio:
+// ---------------------------------------------------------------------------
+fn parse_reduce_ratio(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(r) if (0.0..=1.0).contains(&r) => Result::Ok(r),
+        _ => Result::Err(format!("invalid reduce ratio {}", s)),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// run:
+// ---------------------------------------------------------------------------
+/** Run the sweep and return the report, which is also written to the output
+ * file if one was specified.
+ */
+pub fn run(parms: &SweepParms) -> Result<String, Errors> {
+    let inputs = &parms.inputs;
+    let mut images = inputs.load_images()?;
+    if let Some(dir) = inputs.get_image_dir() {
+        evaluation::load_stored_bytes(&mut images, dir, &inputs.image_file_prefix)?;
+    }
+
+    let sweep = sweep(&images, &inputs.negative_labels, &parms.save, &parms.reduce_save, parms.reduce_ratio)?;
+    let report = if inputs.is_markdown() {
+        sweep.to_markdown()
+    } else {
+        serde_json::to_string_pretty(&sweep).unwrap_or_default() + "\n"
+    };
+    if let Some(file) = &inputs.output_file {
+        if let Err(e) = traps_utils::create_or_replace_file(file, report.as_bytes()) {
+            return Result::Err(Errors::EvaluationWriteError(file.clone(), e.to_string()));
+        }
+    }
+    Result::Ok(report)
+}
+
+// ***************************************************************************
+// PRIVATE FUNCTIONS
+// ***************************************************************************
+// ---------------------------------------------------------------------------
+// replay:
+// ---------------------------------------------------------------------------
+/** Apply one store policy to the replayed images' highest scores. */
+fn replay(replayed: &[(f32, u64, bool)], store_parms: &StoreParms, save_threshold: f32,
+          reduce_save_threshold: Option<f32>, reduce_ratio: f64, positives: usize) -> SweepPoint {
+    let mut point = SweepPoint {save_threshold, reduce_save_threshold, saved: 0, reduce_saved: 0, deleted: 0,
+                                storage_bytes: 0, recall: None, precision: None};
+    let mut kept_positives = 0;
+    for (score, bytes, positive) in replayed {
+        let kept = match get_action_for_score(store_parms, *score) {
+            StoreAction::Save => {
+                point.saved += 1;
+                point.storage_bytes += bytes;
+                true
+            },
+            StoreAction::ReduceSave => {
+                point.reduce_saved += 1;
+                point.storage_bytes += (*bytes as f64 * reduce_ratio).round() as u64;
+                true
+            },
+            _ => {
+                point.deleted += 1;
+                false
+            },
+        };
+        if kept && *positive {
+            kept_positives += 1;
+        }
+    }
+    let kept = point.saved + point.reduce_saved;
+    point.recall = if positives == 0 {None} else {Some(kept_positives as f64 / positives as f64)};
+    point.precision = if kept == 0 {None} else {Some(kept_positives as f64 / kept as f64)};
+    point
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: sweep.rs");
+    }
+
+    #[test]
+    fn threshold_ranges() {
+        assert_eq!(ThresholdRange::parse("0.5").unwrap().thresholds, vec![0.5]);
+        assert_eq!(ThresholdRange::parse("0.1:0.3:0.1").unwrap().thresholds, vec![0.1, 0.2, 0.3]);
+        assert_eq!(ThresholdRange::parse(DEFAULT_SAVE_RANGE).unwrap().thresholds.len(), 20);
+        assert!(ThresholdRange::parse("0.1:1.5:0.1").is_err());
+        assert!(ThresholdRange::parse("0.5:0.1:0.1").is_err());
+        assert!(ThresholdRange::parse("0.1:0.5").is_err());
+        assert!(ThresholdRange::parse("high").is_err());
+    }
+
+    #[test]
+    fn sweep_thresholds() {
+        let record = |uuid: &str, truth: &str, score: f32, bytes: Option<u64>| ImageRecord {
+            uuid: uuid.to_string(), truth: Some(truth.to_string()),
+            scores: Some(vec![("animal".to_string(), score), ("other".to_string(), 0.01)]),
+            image_bytes: bytes, ..Default::default()};
+        let mut images = BTreeMap::new();
+        for r in [record("1", "animal", 0.9, Some(100)), record("2", "animal", 0.6, Some(100)),
+                  record("3", "empty", 0.7, Some(100)), record("4", "animal", 0.2, None)] {
+            images.insert(r.uuid.clone(), r);
+        }
+        images.insert("5".to_string(), ImageRecord {uuid: "5".to_string(), ..Default::default()});
+
+        let save = ThresholdRange::parse("0.5:0.9:0.4").unwrap();
+        let reduce_save = ThresholdRange::parse("0.1:0.5:0.4").unwrap();
+        let sweep = sweep(&images, &["empty".to_string()], &save, &reduce_save, 0.5).unwrap();
+        assert_eq!((sweep.images, sweep.images_replayed, sweep.positives), (5, 4, 3));
+
+        // Save 0.5 / ReduceSave 0.1 keeps everything; 0.9 alone keeps one image.
+        assert_eq!(sweep.points.len(), 5);
+        let point = |s: f32, r: Option<f32>| sweep.points.iter().find(|p| p.save_threshold == s && p.reduce_save_threshold == r).unwrap();
+        assert_eq!(point(0.5, Some(0.1)), &SweepPoint {save_threshold: 0.5, reduce_save_threshold: Some(0.1), saved: 3,
+                                                     reduce_saved: 1, deleted: 0, storage_bytes: 350, recall: Some(1.0),
+                                                     precision: Some(0.75)});
+        assert_eq!(point(0.9, None).storage_bytes, 100);
+        assert_eq!(point(0.9, None).recall, Some(1.0 / 3.0));
+        assert_eq!(point(0.9, Some(0.5)).storage_bytes, 200);
+
+        // The front trades storage for recall.
+        let front: Vec<(u64, Option<f64>)> = sweep.pareto_front.iter().map(|p| (p.storage_bytes, p.recall)).collect();
+        assert_eq!(front, vec![(100, Some(1.0 / 3.0)), (200, Some(2.0 / 3.0)), (250, Some(1.0))]);
+        assert!(sweep.to_markdown().contains("| 0.9 | 0.1 | 1 | 3 | 0 | 250 | 1.000 | 0.750 |"));
+    }
+}
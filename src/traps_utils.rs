@@ -14,10 +14,10 @@ use zmq::Socket;
 
 use crate::events_generated::gen_events;
 use crate::events;
-use crate::config::{errors::Errors, config::Config};
-use crate::events::{NewImageEvent, ImageReceivedEvent, ImageScoredEvent, ImageStoredEvent,
-                    ImageDeletedEvent, PluginStartedEvent, PluginTerminateEvent, PluginTerminatingEvent,
-                    MonitorPowerStartEvent, MonitorPowerStopEvent};
+use crate::config::errors::Errors;
+use crate::dead_letter::{self, DeadLetterReason};
+use crate::events::{ImageProcessingErrorEvent, PluginStartedEvent, PluginTerminateEvent, PluginTerminatingEvent,
+                    Traceable, TraceIds};
 use log::{error};
 
 // ***************************************************************************
@@ -30,6 +30,18 @@ const PLUGIN_NAME_WILDCARD: &str = "*";
 #[allow(dead_code)]
 pub const PAST_DATETIME: &str = "2000-01-01T00:00:00+00:00";
 
+// The processing stages reported in ImageProcessingErrorEvents.
+#[allow(dead_code)]
+pub const STAGE_READ_EVENT: &str = "read_event";
+#[allow(dead_code)]
+pub const STAGE_RESOLVE_IMAGE: &str = "resolve_image";
+#[allow(dead_code)]
+pub const STAGE_ACTION: &str = "action";
+#[allow(dead_code)]
+pub const STAGE_PUBLISH: &str = "publish";
+#[allow(dead_code)]
+pub const STAGE_SHUTDOWN: &str = "shutdown";
+
 // ***************************************************************************
 // GENERAL PUBLIC FUNCTIONS
 // ***************************************************************************
@@ -79,6 +91,29 @@ pub fn get_absolute_path(path: &str) -> String {
     p2.to_owned()
 }
 
+// ---------------------------------------------------------------------------
+// parse_absolute_path:
+// ---------------------------------------------------------------------------
+/** A command line value parser that converts a file or directory argument to
+ * an absolute path.
+ */
+#[allow(dead_code)]
+pub fn parse_absolute_path(path: &str) -> Result<String, String> {
+    Result::Ok(get_absolute_path(path))
+}
+
+// ---------------------------------------------------------------------------
+// parse_args:
+// ---------------------------------------------------------------------------
+/** Parse the arguments of one subcommand, which lets the modules test their
+ * parameters without the rest of the command line.
+ */
+#[cfg(test)]
+pub fn parse_args<T: clap::Args + clap::FromArgMatches>(args: &[&str]) -> Result<T, clap::Error> {
+    let cmd = T::augment_args(clap::Command::new("camera-traps").no_binary_name(true));
+    T::from_arg_matches(&cmd.try_get_matches_from(args)?)
+}
+
 // ---------------------------------------------------------------------------
 // create_image_filepath:
 // ---------------------------------------------------------------------------
@@ -92,9 +127,8 @@ pub fn create_image_filepath(abs_dir: &str, image_file_prefix: &Option<String>,
     filepath.push_str(slash);
 
     // Prepend the file prefix if one is specified.
-    match image_file_prefix {
-        Some(s) => filepath.push_str(s),
-        None => {}
+    if let Some(s) = image_file_prefix {
+        filepath.push_str(s);
     }
 
     // Append the image uuid as the file name and 
@@ -129,9 +163,8 @@ pub fn create_image_wildcard_path(abs_dir: &str, image_file_prefix: &Option<Stri
     filepath.push_str(slash);
 
     // Prepend the file prefix if one is specified.
-    match image_file_prefix {
-        Some(s) => filepath.push_str(s),
-        None => {}
+    if let Some(s) = image_file_prefix {
+        filepath.push_str(s);
     }
 
     // Append the image uuid as the file name and the wildcare.
@@ -143,24 +176,15 @@ pub fn create_image_wildcard_path(abs_dir: &str, image_file_prefix: &Option<Stri
 // ---------------------------------------------------------------------------
 // validate_image_dir:
 // ---------------------------------------------------------------------------
-/** Check if we have r/w/x permission on the local image directory if that
- * directory is used by one of the plugins.  This method will create the directory 
- * if necessary.  
+/** Check if we have r/w/x permission on the local image directory.  This method
+ * will create the directory if necessary.  
  * 
- * This method is expected to be called once at application start up so that 
- * the execution can be aborted if no image I/O will be possible.
+ * This method is expected to be called once at application start up when one
+ * of the configured actions requires the local image directory so that the
+ * execution can be aborted if no image I/O will be possible.
  */
 #[allow(dead_code)]
-pub fn validate_image_dir(config: &Config, abs_dir: &String) -> Result<(), Errors> {
-
-    // Internal plugins are optional.
-    let int_actions = match config.plugins.internal_actions.clone() {
-        Some(v) => v,
-        None => return Ok(()), 
-    };
-    
-    // Determine if we are using local storage for image files.
-    if !uses_local_image_dir(&int_actions) {return Ok(());}
+pub fn validate_image_dir(abs_dir: &String) -> Result<(), Errors> {
 
     // Create the directory if it doesn't exist.  If the path
     // leads to an existing file, this call will fail (not tested
@@ -200,30 +224,6 @@ pub fn validate_image_dir(config: &Config, abs_dir: &String) -> Result<(), Error
     Ok(())
 }    
 
-// ---------------------------------------------------------------------------
-// uses_local_image_dir:
-// ---------------------------------------------------------------------------
-/** Check if any of the configured plugin actions requires a local image
- * directory for I/O.  We hardcode the plugin actions that use the image
- * directory, which requires maintenance when new actions are introduced. 
- */
-fn uses_local_image_dir(configured_actions: &Vec<String>) -> bool {
-    
-    // Hardcode the actions that require a local image directory. Since
-    // this method only gets called once per application execution, we
-    // put the list on the stack so it gets cleaned up.  This vector 
-    // may need to be updated when new actions are developed.
-    let local_store_actions = vec!["image_recv_write_file_action", "image_store_file_action"];
-    
-    // Determine if any of the configured actions match the 
-    // hardcoded actions that requre a local image directory.
-    for action in configured_actions {
-        if local_store_actions.contains(&action.as_str()) {return true;}
-    }
-    
-    // Local image directory is not needed.
-    false
-}
 
 // ---------------------------------------------------------------------------
 // timestamp_str:
@@ -266,7 +266,7 @@ pub fn create_or_replace_file(filepath: &String, buf: &[u8]) -> Result<(), std::
 // bytes_to_gen_event:
 // ---------------------------------------------------------------------------
 #[allow(dead_code)]
-pub fn bytes_to_gen_event(msg_bytes: &[u8]) -> Result<gen_events::Event, Errors> {
+pub fn bytes_to_gen_event(msg_bytes: &[u8]) -> Result<gen_events::Event<'_>, Errors> {
     // Read the byte array into a generated event backed by a flatbuffer.
     match gen_events::root_as_event(msg_bytes) {
         Ok(ev) => Result::Ok(ev),
@@ -287,7 +287,7 @@ pub fn bytes_to_gen_event(msg_bytes: &[u8]) -> Result<gen_events::Event, Errors>
  */
 #[allow(dead_code)]
 pub fn process_plugin_terminate_event(gen_event: gen_events::Event, uuid: &Uuid, plugin_name: &String) -> bool {
-    let event = match gen_to_plugin_terminate_event(gen_event) {
+    let event = match events::gen_to_event::<PluginTerminateEvent>(gen_event) {
         Ok(ev) => ev,
         Err(e) => {
             error!("{}", e.to_string());
@@ -297,13 +297,13 @@ pub fn process_plugin_terminate_event(gen_event: gen_events::Event, uuid: &Uuid,
 
     // See if the calling plugin is the target either
     // explicitly by name or by wildcard.
-    if event.target_plugin_name.eq(plugin_name) || 
-       event.target_plugin_name.eq(PLUGIN_NAME_WILDCARD) {
+    if event.get_target_plugin_name().eq(plugin_name) || 
+       event.get_target_plugin_name().eq(PLUGIN_NAME_WILDCARD) {
         return true;
     }
 
     // See if the calling plugin's uuid is the target.
-    if event.target_plugin_uuid.eq(uuid) {
+    if event.get_target_plugin_uuid().eq(uuid) {
         return true;
     }
 
@@ -320,7 +320,8 @@ pub fn process_plugin_terminate_event(gen_event: gen_events::Event, uuid: &Uuid,
 #[allow(dead_code)]
 pub fn send_terminating_event(plugin_name: &String, plugin_uuid: Uuid, pub_socket: &Socket) {
     // Create the event for the calling plugin.
-    let ev = PluginTerminatingEvent::new(plugin_uuid, plugin_name.to_string());
+    let mut ev = PluginTerminatingEvent::new(plugin_uuid, plugin_name.to_string());
+    ev.set_trace_ids(TraceIds::caused_by(None, plugin_name));
 
     // Serialize the event.
     let data = match ev.to_bytes() {
@@ -354,7 +355,8 @@ pub fn send_terminating_event(plugin_name: &String, plugin_uuid: Uuid, pub_socke
 #[allow(dead_code)]
 pub fn send_started_event(plugin: &dyn Plugin, pub_socket: &Socket) -> Result<(), EngineError> {
     // Send our alive event.
-    let ev = PluginStartedEvent::new(plugin.get_id(), plugin.get_name());
+    let mut ev = PluginStartedEvent::new(plugin.get_id(), plugin.get_name());
+    ev.set_trace_ids(TraceIds::caused_by(None, &plugin.get_name()));
         let bytes = match ev.to_bytes() {
         Ok(v) => v,
         Err(e) => {
@@ -380,6 +382,56 @@ pub fn send_started_event(plugin: &dyn Plugin, pub_socket: &Socket) -> Result<()
     Result::Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// response_trace_ids:
+// ---------------------------------------------------------------------------
+/** Plugins call this method to get the trace ids of an event they publish in
+ * response to the incoming event.  The new event inherits the incoming event's
+ * correlation id and is caused by it.  If the incoming event's trace ids can't
+ * be read, the error is logged and a new chain is started.
+ */
+#[allow(dead_code)]
+pub fn response_trace_ids(plugin_name: &str, cause: gen_events::Event) -> TraceIds {
+    match events::gen_to_trace_ids(cause) {
+        Ok(ids) => TraceIds::caused_by(Some(&ids), plugin_name),
+        Err(e) => {
+            error!("{}", e);
+            TraceIds::caused_by(None, plugin_name)
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// send_image_processing_error_event:
+// ---------------------------------------------------------------------------
+/** Plugins call this method when they abandon the processing of an image so
+ * that subscribers can account for every image.  The stage names the step
+ * that failed, the error is the one that caused the failure and the cause is
+ * the incoming event being processed.  Errors are logged but not surfaced.
+ */
+#[allow(dead_code)]
+pub fn send_image_processing_error_event(plugin_name: &str, image_uuid: Uuid, stage: &str, err: &Errors,
+                                         cause: gen_events::Event, pub_socket: &Socket) {
+    // Create the event.
+    let mut ev = ImageProcessingErrorEvent::new(image_uuid, plugin_name.to_string(), stage.to_string(),
+                                                err.code(), err.to_string());
+    ev.set_trace_ids(response_trace_ids(plugin_name, cause));
+
+    // Serialize the event.
+    let data = match ev.to_bytes() {
+        Ok(d) => d,
+        Err(e) => {
+            error!("{}", Errors::EventToBytesError(plugin_name.to_string(), ev.get_name(), e.to_string()));
+            return;
+        }
+    };
+
+    // Send the event.
+    if let Err(e) = pub_socket.send(&data, 0) {
+        error!("{}", Errors::EventSendError(plugin_name.to_string(), ev.get_name(), e.to_string()));
+    }
+}
+
 // ***************************************************************************
 // INCOMING EVENT COMMON PROCESSING
 // ***************************************************************************
@@ -389,6 +441,7 @@ pub fn send_started_event(plugin: &dyn Plugin, pub_socket: &Socket) -> Result<()
 pub struct IncomingEvent<'a> {
     pub prefix_array: [u8; 2],
     pub gen_event: gen_events::Event<'a>,
+    pub raw_bytes: &'a [u8],
 }
 
 // ---------------------------------------------------------------------------
@@ -401,10 +454,12 @@ pub struct IncomingEvent<'a> {
  *  - Copy the raw bytes into the caller's buffer
  *  - Validate the minimum input length
  *  - Parse the flatbuffer into a generated event type
+ *  - Check that the sender's schema version is compatible
  *  - Validate that the prefix bytes and the event name agree
- *  - Return the prefix bytes and generated event
+ *  - Return the prefix bytes, generated event and raw event bytes
  * 
- * Any failure skips the rest of the processing and returns None. 
+ * Any failure skips the rest of the processing and returns None.  Rejected
+ * payloads are captured as dead letters.
  */
 #[allow(dead_code)]
 pub fn marshal_next_event<'a>(plugin: &dyn Plugin, sub_socket: &Socket, bytes: &'a mut Vec<u8>)
@@ -426,11 +481,14 @@ pub fn marshal_next_event<'a>(plugin: &dyn Plugin, sub_socket: &Socket, bytes: &
     // for all further processing. Specifically, the references returned by this
     // function are backed by the caller's vector which has the required lifetime. 
     bytes.extend_from_slice(&temp);
+    let bytes: &'a Vec<u8> = bytes;
 
     // Basic buffer length checking to make sure we have
     // the event prefix and at least 1 other byte.
     if bytes.len() < events::EVENT_PREFIX_LEN + 1 {
-        error!("{}", Errors::EventInvalidLen(plugin.get_name(), bytes.len()));
+        let err = Errors::EventInvalidLen(plugin.get_name(), bytes.len());
+        error!("{}", err);
+        dead_letter::capture(&plugin.get_name(), DeadLetterReason::TooShort, &err, bytes);
         return Option::None;
     }
 
@@ -444,15 +502,31 @@ pub fn marshal_next_event<'a>(plugin: &dyn Plugin, sub_socket: &Socket, bytes: &
         Ok(tuple)=> tuple,
         Err(e)=> {
             error!("{}", e.to_string());
+            dead_letter::capture(&plugin.get_name(), DeadLetterReason::InvalidFlatbuffer, &e, bytes);
             return Option::None;
         }
     };
 
+    // Reject events written with an incompatible schema version.
+    if let Err(e) = events::check_schema_version(&plugin.get_name(), gen_event) {
+        error!("{}", e);
+        dead_letter::capture(&plugin.get_name(), DeadLetterReason::SchemaVersionMismatch, &e, bytes);
+        return Option::None;
+    }
+
     // Get the event name from the generated event and check it against prefix slice.
+    // Senders with a newer minor schema version can send event types we don't know.
     let event_name = match gen_event.event_type().variant_name() {  
         Some(n) => n,
         None => {
-            error!("{}", Errors::EventNoneError(plugin.get_name()));
+            let err = if gen_event.schema_minor() > events::EVENT_SCHEMA_MINOR {
+                let version = format!("{}.{}", gen_event.schema_major(), gen_event.schema_minor());
+                Errors::EventUnknownNewerType(plugin.get_name(), version)
+            } else {
+                Errors::EventNoneError(plugin.get_name())
+            };
+            error!("{}", err);
+            dead_letter::capture(&plugin.get_name(), DeadLetterReason::UnknownEventType, &err, bytes);
             return Option::None;
         },
     };
@@ -464,197 +538,17 @@ pub fn marshal_next_event<'a>(plugin: &dyn Plugin, sub_socket: &Socket, bytes: &
     if !events::check_event_prefix(prefix_array, event_name) {
         let pre = format!("{:?}", prefix_array);
         let name = event_name.to_string();
-        error!("{}", Errors::EventPrefixMismatch(plugin.get_name(), pre, name));
+        let err = Errors::EventPrefixMismatch(plugin.get_name(), pre, name);
+        error!("{}", err);
+        dead_letter::capture(&plugin.get_name(), DeadLetterReason::PrefixMismatch, &err, bytes);
         return Option::None;
     }
 
     // Pass back the event components.
-    Option::Some(IncomingEvent { prefix_array, gen_event })
-}
-
-// ***************************************************************************
-// GENERATED-EVENT TO CAMERA-EVENT FUNCTIONS
-// ***************************************************************************
-// ---------------------------------------------------------------------------
-// gen_to_new_image_event:
-// ---------------------------------------------------------------------------
-#[allow(dead_code)]
-pub fn gen_to_new_image_event(gen_event: gen_events::Event) -> Result<NewImageEvent, Errors> {
-    // Create the generated event from the raw flatbuffer.
-    let flatbuf_event = match gen_event.event_as_new_image_event() {
-        Some(ev) => ev,
-        None =>  return Result::Err(Errors::EventCreateFromFlatbuffer("NewImageEvent".to_string())), 
-    };
-
-    // Return a camera-trap event given the flatbuffer generated event.
-    match NewImageEvent::new_from_gen(flatbuf_event) {
-        Ok(ev) => Result::Ok(ev),
-        Err(e) => Result::Err(e),
-    }
-}
-
-// ---------------------------------------------------------------------------
-// gen_to_image_received_event:
-// ---------------------------------------------------------------------------
-#[allow(dead_code)]
-pub fn gen_to_image_received_event(gen_event: gen_events::Event) -> Result<ImageReceivedEvent, Errors> {
-    // Create the generated event from the raw flatbuffer.
-    let flatbuf_event = match gen_event.event_as_image_received_event() {
-        Some(ev) => ev,
-        None =>  return Result::Err(Errors::EventCreateFromFlatbuffer("ImageReceivedEvent".to_string())), 
-    };
-
-    // Return a camera-trap event given the flatbuffer generated event.
-    match ImageReceivedEvent::new_from_gen(flatbuf_event) {
-        Ok(ev) => Result::Ok(ev),
-        Err(e) => Result::Err(e),
-    }
-}
-
-// ---------------------------------------------------------------------------
-// gen_to_image_stored_event:
-// ---------------------------------------------------------------------------
-#[allow(dead_code)]
-pub fn gen_to_image_scored_event(gen_event: gen_events::Event) -> Result<ImageScoredEvent, Errors> {
-    // Create the generated event from the raw flatbuffer.
-    let flatbuf_event = match gen_event.event_as_image_scored_event() {
-        Some(ev) => ev,
-        None =>  return Result::Err(Errors::EventCreateFromFlatbuffer("ImageScoredEvent".to_string())), 
-    };
-
-    // Return a camera-trap event given the flatbuffer generated event.
-    match ImageScoredEvent::new_from_gen(flatbuf_event) {
-        Ok(ev) => Result::Ok(ev),
-        Err(e) => Result::Err(e),
-    }
-}
-
-// ---------------------------------------------------------------------------
-// gen_to_image_deleted_event:
-// ---------------------------------------------------------------------------
-#[allow(dead_code)]
-pub fn gen_to_image_stored_event(gen_event: gen_events::Event) -> Result<ImageDeletedEvent, Errors> {
-    // Create the generated event from the raw flatbuffer.
-    let flatbuf_event = match gen_event.event_as_image_deleted_event() {
-        Some(ev) => ev,
-        None =>  return Result::Err(Errors::EventCreateFromFlatbuffer("ImageDeletedEvent".to_string())), 
-    };
-
-    // Return a camera-trap event given the flatbuffer generated event.
-    match ImageDeletedEvent::new_from_gen(flatbuf_event) {
-        Ok(ev) => Result::Ok(ev),
-        Err(e) => Result::Err(e),
-    }
-}
-
-// ---------------------------------------------------------------------------
-// gen_to_image_deleted_event:
-// ---------------------------------------------------------------------------
-#[allow(dead_code)]
-pub fn gen_to_image_deleted_event(gen_event: gen_events::Event) -> Result<ImageStoredEvent, Errors> {
-    // Create the generated event from the raw flatbuffer.
-    let flatbuf_event = match gen_event.event_as_image_stored_event() {
-        Some(ev) => ev,
-        None =>  return Result::Err(Errors::EventCreateFromFlatbuffer("ImageStoredEvent".to_string())), 
-    };
-
-    // Return a camera-trap event given the flatbuffer generated event.
-    match ImageStoredEvent::new_from_gen(flatbuf_event) {
-        Ok(ev) => Result::Ok(ev),
-        Err(e) => Result::Err(e),
-    }
-}
-
-// ---------------------------------------------------------------------------
-// gen_to_pluging_started_event:
-// ---------------------------------------------------------------------------
-#[allow(dead_code)]
-pub fn gen_to_plugin_started_event(gen_event: gen_events::Event) -> Result<PluginStartedEvent, Errors> {
-    // Create the generated event from the raw flatbuffer.
-    let flatbuf_event = match gen_event.event_as_plugin_started_event() {
-        Some(ev) => ev,
-        None =>  return Result::Err(Errors::EventCreateFromFlatbuffer("PluginStartedEvent".to_string())), 
-    };
-
-    // Return a camera-trap event given the flatbuffer generated event.
-    match PluginStartedEvent::new_from_gen(flatbuf_event) {
-        Ok(ev) => Result::Ok(ev),
-        Err(e) => Result::Err(e),
-    }
+    Option::Some(IncomingEvent { prefix_array, gen_event, raw_bytes: bytes.as_slice() })
 }
 
-// ---------------------------------------------------------------------------
-// gen_to_plugin_terminate_event:
-// ---------------------------------------------------------------------------
-pub fn gen_to_plugin_terminate_event(gen_event: gen_events::Event) -> Result<PluginTerminateEvent, Errors> {
-    // Create the generated event from the raw flatbuffer.
-    let flatbuf_event = match gen_event.event_as_plugin_terminate_event() {
-        Some(ev) => ev,
-        None =>  return Result::Err(Errors::EventCreateFromFlatbuffer("PluginTerminateEvent".to_string())), 
-    };
-
-    // Return a camera-trap event given the flatbuffer generated event.
-    match PluginTerminateEvent::new_from_gen(flatbuf_event) {
-        Ok(ev) => Result::Ok(ev),
-        Err(e) => Result::Err(e),
-    }
-}
-
-// ---------------------------------------------------------------------------
-// gen_to_plugin_terminating_event:
-// ---------------------------------------------------------------------------
-#[allow(dead_code)]
-pub fn gen_to_plugin_terminating_event(gen_event: gen_events::Event) -> Result<PluginTerminatingEvent, Errors> {
-    // Create the generated event from the raw flatbuffer.
-    let flatbuf_event = match gen_event.event_as_plugin_terminating_event() {
-        Some(ev) => ev,
-        None =>  return Result::Err(Errors::EventCreateFromFlatbuffer("PluginTerminatingEvent".to_string())), 
-    };
-
-    // Return a camera-trap event given the flatbuffer generated event.
-    match PluginTerminatingEvent::new_from_gen(flatbuf_event) {
-        Ok(ev) => Result::Ok(ev),
-        Err(e) => Result::Err(e),
-    }
-}
-
-// ---------------------------------------------------------------------------
-// gen_to_monitor_power_start_event:
-// ---------------------------------------------------------------------------
-#[allow(dead_code)]
-pub fn gen_to_monitor_power_start_event(gen_event: gen_events::Event) -> Result<MonitorPowerStartEvent, Errors> {
-    // Create the generated event from the raw flatbuffer.
-    let flatbuf_event = match gen_event.event_as_monitor_power_start_event() {
-        Some(ev) => ev,
-        None =>  return Result::Err(Errors::EventCreateFromFlatbuffer("MonitorPowerStartEvent".to_string())), 
-    };
-
-    // Return a camera-trap event given the flatbuffer generated event.
-    match MonitorPowerStartEvent::new_from_gen(flatbuf_event) {
-        Ok(ev) => Result::Ok(ev),
-        Err(e) => Result::Err(e),
-    }
-}
-
-// ---------------------------------------------------------------------------
-// gen_to_monitor_power_stop_event:
-// ---------------------------------------------------------------------------
-#[allow(dead_code)]
-pub fn gen_to_monitor_power_stop_event(gen_event: gen_events::Event) -> Result<MonitorPowerStopEvent, Errors> {
-    // Create the generated event from the raw flatbuffer.
-    let flatbuf_event = match gen_event.event_as_monitor_power_stop_event() {
-        Some(ev) => ev,
-        None =>  return Result::Err(Errors::EventCreateFromFlatbuffer("MonitorPowerStoptEvent".to_string())), 
-    };
-
-    // Return a camera-trap event given the flatbuffer generated event.
-    match MonitorPowerStopEvent::new_from_gen(flatbuf_event) {
-        Ok(ev) => Result::Ok(ev),
-        Err(e) => Result::Err(e),
-    }
-}
-
-
+#[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
     use crate::traps_utils::*;
@@ -670,4 +564,14 @@ mod tests {
         let s2 = ts1.to_rfc3339();
         assert_eq!(s1, s2);
     }
+
+    #[test]
+    fn error_codes() {
+        // Error codes are the variant names reported in ImageProcessingErrorEvents.
+        let err = Errors::ActionWriteFileError("p".to_string(), "a".to_string(), "f".to_string(), "e".to_string());
+        assert_eq!(err.code(), "ActionWriteFileError");
+        assert_eq!(Errors::PluginNone().code(), "PluginNone");
+        let err = Errors::IOError(std::io::Error::other("disk full"));
+        assert_eq!(err.code(), "IOError");
+    }
 }
@@ -0,0 +1,453 @@
+use std::fs;
+
+use clap::Args;
+use uuid::Uuid;
+
+use crate::config::config::{Config, ImageStoreConfig};
+use crate::config::errors::Errors;
+use crate::config::layers::{self, ConfigLayer, ConfigSources, Override};
+use crate::events;
+use crate::plugins::{self, INTERNAL_PLUGIN_NAMES};
+use crate::plugins::actions;
+use crate::plugins::image_store_plugin::{self, StoreConfig};
+use crate::plugins::mqtt_publisher_plugin::MqttParms;
+use crate::shutdown;
+use crate::traps_utils;
+
+// ***************************************************************************
+// CONSTANTS
+// ***************************************************************************
+// The subscription every external plugin needs to be shut down.
+const TERMINATE_SUBSCRIPTION: &str = "PluginTerminateEvent";
+
+// The action that uses the image store thresholds.
+const IMAGE_STORE_FILE_ACTION: &str = "image_store_file_action";
+
+// The plugin whose configuration is checked by building its parameters.
+const MQTT_PUBLISHER_PLUGIN: &str = "mqtt_publisher_plugin";
+
+// ***************************************************************************
+// PROBLEMS
+// ***************************************************************************
+/** A problem found in a configuration.  The location is the file and, when
+ * the problem concerns a setting, the setting's key path such as
+ * plugins.external[1].id.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    pub location: String,
+    pub message: String,
+}
+
+// ---------------------------------------------------------------------------
+// validate_config:
+// ---------------------------------------------------------------------------
+/** Run the semantic checks on a loaded application configuration and return
+ * every problem found.  The sources are only used to name the problems'
+ * locations, which are the layers that supplied the settings.
+ */
+pub fn validate_config(config: &Config, sources: &ConfigSources) -> Vec<Problem> {
+    let mut problems = vec![];
+    let mut report = |key: String, message: String| problems.push(Problem {location: sources.locate(&key), message});
+
+    // Event engine ports.
+    if config.publish_port == config.subscribe_port {
+        report("subscribe_port".to_string(), format!("port {} is also the publish_port", config.subscribe_port));
+    }
+
+    // Log level.
+    if let Some(level) = &config.log_level {
+        if level.parse::<log::LevelFilter>().is_err() {
+            report("log_level".to_string(), format!("\"{}\" is not a log level; the levels are off, error, warn, info, debug and trace", level));
+        }
+    }
+
+    // Internal plugins.
+    let internal = config.plugins.internal.clone().unwrap_or_default();
+    for (i, name) in internal.iter().enumerate() {
+        let key = format!("plugins.internal[{}]", i);
+        if !INTERNAL_PLUGIN_NAMES.contains(&name.as_str()) {
+            report(key, format!("unknown internal plugin \"{}\"; the internal plugins are {}", name,
+                                INTERNAL_PLUGIN_NAMES.join(", ")));
+        } else if internal[..i].contains(name) {
+            report(key, format!("internal plugin \"{}\" is listed more than once", name));
+        }
+    }
+
+    // Internal actions.  Each plugin runs its actions as a chain in the order listed.
+    let action_names = actions::get_action_names();
+    let actions = config.plugins.internal_actions.clone().unwrap_or_default();
+    for (i, action) in actions.iter().enumerate() {
+        let key = format!("plugins.internal_actions[{}]", i);
+        if !action_names.contains(action) {
+            report(key, format!("unknown internal action \"{}\"; the internal actions are {}", action,
+                                action_names.join(", ")));
+            continue;
+        }
+        let plugin = match actions::get_action_plugin(action) {
+            Some(p) => p,
+            None => continue,
+        };
+        if actions[..i].contains(action) {
+            report(key, format!("action \"{}\" is listed more than once in {}'s action chain", action, plugin));
+        } else if !internal.iter().any(|p| p == plugin) {
+            report(key, format!("action \"{}\" is for {}, which is not an internal plugin", action, plugin));
+        }
+    }
+
+    // External plugins.
+    let external = config.plugins.external.clone().unwrap_or_default();
+    for (i, ext) in external.iter().enumerate() {
+        let key = |field: &str| format!("plugins.external[{}].{}", i, field);
+        let earlier = &external[..i];
+
+        if ext.plugin_name.trim().is_empty() {
+            report(key("plugin_name"), "plugin_name is empty".to_string());
+        } else if earlier.iter().any(|e| e.plugin_name == ext.plugin_name) || internal.contains(&ext.plugin_name) {
+            report(key("plugin_name"), format!("plugin name \"{}\" is used by another plugin", ext.plugin_name));
+        }
+
+        match Uuid::parse_str(&ext.id) {
+            Err(e) => report(key("id"), format!("\"{}\" is not a valid UUID: {}", ext.id, e)),
+            Ok(id) => if earlier.iter().any(|e| Uuid::parse_str(&e.id).is_ok_and(|u| u == id)) {
+                report(key("id"), format!("id {} is used by another external plugin", ext.id));
+            },
+        }
+
+        if ext.external_port == 0 {
+            report(key("external_port"), "port 0 is not a valid port".to_string());
+        } else if ext.external_port == config.publish_port || ext.external_port == config.subscribe_port {
+            report(key("external_port"), format!("port {} is also an event engine port (publish_port or subscribe_port)",
+                                                 ext.external_port));
+        } else if let Some(other) = earlier.iter().find(|e| e.external_port == ext.external_port) {
+            report(key("external_port"), format!("port {} is also used by external plugin \"{}\"",
+                                                 ext.external_port, other.plugin_name));
+        }
+
+        for (j, subscription) in ext.subscriptions.iter().enumerate() {
+            let sub_key = format!("plugins.external[{}].subscriptions[{}]", i, j);
+            if events::get_event_info_by_name(subscription).is_none() {
+                report(sub_key, format!("unknown event type \"{}\"", subscription));
+            } else if ext.subscriptions[..j].contains(subscription) {
+                report(sub_key, format!("event type \"{}\" is listed more than once", subscription));
+            }
+        }
+        if !ext.subscriptions.iter().any(|s| s == TERMINATE_SUBSCRIPTION) {
+            report(key("subscriptions"), format!("external plugins must subscribe to {}", TERMINATE_SUBSCRIPTION));
+        }
+    }
+
+    // Plugin and action configuration tables.
+    let tables = config.plugins.config.clone().unwrap_or_default();
+    for name in tables.keys() {
+        let key = format!("plugins.config.{}", name);
+        if let Some(action) = actions::get_action(name) {
+            for problem in actions::check_action_config(action.as_ref(), &tables[name]) {
+                report(key.clone(), problem);
+            }
+        } else if !INTERNAL_PLUGIN_NAMES.contains(&name.as_str()) {
+            report(key, format!("\"{}\" is not an internal plugin or action", name));
+        } else if let Err(e) = plugins::check_plugin_config(config, name) {
+            let message = match e {
+                Errors::PluginConfigInvalid(_, msg) => msg,
+                other => other.to_string(),
+            };
+            report(key, message);
+        }
+    }
+    if let Ok(store) = config.get_plugin_config::<ImageStoreConfig>(image_store_plugin::CONFIG_NAME) {
+        if let Some(Err(e)) = store.action_thresholds.as_ref().map(StoreConfig::new_from_thresholds) {
+            report(format!("plugins.config.{}.action_thresholds", image_store_plugin::CONFIG_NAME), e.to_string());
+        }
+    }
+    if tables.contains_key(MQTT_PUBLISHER_PLUGIN) {
+        if let Ok(mqtt) = config.get_plugin_config(MQTT_PUBLISHER_PLUGIN) {
+            if let Err(e) = MqttParms::new_from_config(&mqtt) {
+                report(format!("plugins.config.{}", MQTT_PUBLISHER_PLUGIN), e.to_string());
+            }
+        }
+    }
+
+    // Run to completion.  Without a cap, the run only ends when the generator finishes.
+    if let Some(completion) = shutdown::get_completion_config(config) {
+        let generator = completion.generator.clone().unwrap_or(shutdown::DEFAULT_GENERATOR.to_string());
+        let configured = internal.contains(&generator) || external.iter().any(|e| e.plugin_name == generator);
+        if !configured && completion.max_images.is_none() && completion.max_duration_secs.is_none() {
+            report("completion.generator".to_string(),
+                   format!("generator \"{}\" is not a configured plugin and no max_images or max_duration_secs cap is set, so the run can't complete", generator));
+        }
+        if completion.max_images == Some(0) {
+            report("completion.max_images".to_string(), "max_images must be greater than 0".to_string());
+        }
+        if let Err(e) = shutdown::check_completion_stages(config) {
+            report("completion.enabled".to_string(), e.to_string());
+        }
+    }
+
+    problems
+}
+
+// ---------------------------------------------------------------------------
+// validate_store_config:
+// ---------------------------------------------------------------------------
+/** Check that the image store configuration file can be read and that its
+ * action thresholds are valid.
+ */
+pub fn validate_store_config(file: &str) -> Vec<Problem> {
+    match StoreConfig::new_from_file(file) {
+        Ok(_) => vec![],
+        Err(e) => vec![Problem {location: file.to_string(), message: e.to_string()}],
+    }
+}
+
+// ***************************************************************************
+// COMMAND
+// ***************************************************************************
+/** Report every problem in a configuration.  When no image store file is
+ * given, the file the image_store_plugin would read is checked.  The
+ * overrides are the environment and command line configuration settings,
+ * which main adds after parsing.
+ */
+#[derive(Args, Debug)]
+pub struct ValidateParms {
+    /// The application configuration file
+    #[arg(value_name = "CONFIG_FILE", default_value_t = crate::get_config_file(None),
+          value_parser = traps_utils::parse_absolute_path)]
+    pub config_file: String,
+    /// Also check this image store configuration file
+    #[arg(long = "image-store", value_name = "IMAGE_STORE_FILE", value_parser = traps_utils::parse_absolute_path)]
+    pub image_store_file: Option<String>,
+    #[arg(skip)]
+    pub overrides: Vec<Override>,
+}
+
+// ---------------------------------------------------------------------------
+// validate:
+// ---------------------------------------------------------------------------
+/** Load the application configuration with its overrides and, if the
+ * image_store_plugin uses it, the image store configuration, and return every
+ * problem found in either.  A configuration that doesn't load is located by
+ * the layer that made it invalid.
+ */
+pub fn validate(parms: &ValidateParms) -> Vec<Problem> {
+    let contents = match fs::read_to_string(&parms.config_file) {
+        Ok(c) => c,
+        Err(e) => return vec![Problem {location: parms.config_file.clone(), message: e.to_string()}],
+    };
+    let (config, sources) = match layers::load_config(&parms.config_file, &contents, &parms.overrides) {
+        Ok(c) => c,
+        Err(Errors::ConfigLayerInvalid(layer, message)) => {
+            let file_layer = ConfigLayer::File(parms.config_file.clone()).to_string();
+            let location = if layer == file_layer {parms.config_file.clone()} else {layer};
+            return vec![Problem {location, message}];
+        },
+        Err(e) => return vec![Problem {location: parms.config_file.clone(), message: e.to_string()}],
+    };
+
+    // The image store file is only read when the plugin's table doesn't assign thresholds.
+    let mut problems = validate_config(&config, &sources);
+    let has_thresholds = config.get_plugin_config::<ImageStoreConfig>(image_store_plugin::CONFIG_NAME)
+                               .is_ok_and(|c| c.action_thresholds.is_some());
+    let uses_store_file = !has_thresholds &&
+                          config.plugins.internal.as_ref().is_some_and(|p| p.iter().any(|n| n == "image_store_plugin")) &&
+                          config.plugins.internal_actions.as_ref().is_some_and(|a| a.iter().any(|n| n == IMAGE_STORE_FILE_ACTION));
+    if uses_store_file || parms.image_store_file.is_some() {
+        let store_file = parms.image_store_file.clone().unwrap_or_else(image_store_plugin::get_store_config_file);
+        problems.extend(validate_store_config(&store_file));
+    }
+    problems
+}
+
+// ---------------------------------------------------------------------------
+// format_problems:
+// ---------------------------------------------------------------------------
+pub fn format_problems(parms: &ValidateParms, problems: &[Problem]) -> String {
+    let mut out = format!("{} problem(s) found in {}\n", problems.len(), parms.config_file);
+    for problem in problems {
+        out.push_str(&format!("  {}: {}\n", problem.location, problem.message));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn here_i_am() {
+        println!("file test: validate.rs");
+    }
+
+    #[test]
+    fn actions_belong_to_plugins() {
+        actions::register_builtin_actions();
+        for action in actions::get_action_names() {
+            assert!(actions::get_action_plugin(&action).is_some(), "{}", action);
+        }
+    }
+
+    #[test]
+    fn config_problems() {
+        actions::register_builtin_actions();
+        let config: Config = toml::from_str(r#"
+            log_level = "loud"
+            publish_port = 5559
+            subscribe_port = 5560
+            images_output_dir = "/tmp/images"
+            [plugins]
+            internal = ["image_recv_plugin", "image_scorer_plugin", "image_recv_plugin"]
+            internal_actions = ["image_recv_write_file_action", "image_recv_write_file_action", "image_store_fille_action",
+                                "observer_journal_action"]
+            [[plugins.external]]
+            plugin_name = "ext_score"
+            id = "d6e8e42a-41ec-11ed-a36f-a3dcc1cc761a"
+            external_port = 6001
+            subscriptions = ["ImageReceivedEvent", "PluginTerminateEvent"]
+            [[plugins.external]]
+            plugin_name = "ext_score"
+            id = "d6e8e42a-41ec-11ed-a36f"
+            external_port = 6001
+            subscriptions = ["ImageRecievedEvent"]
+            [[plugins.external]]
+            plugin_name = "ext_gen"
+            id = "D6E8E42A-41EC-11ED-A36F-A3DCC1CC761A"
+            external_port = 5559
+            subscriptions = ["PluginTerminateEvent", "PluginTerminateEvent"]
+        "#).unwrap();
+
+        let problems = validate_config(&config, &ConfigSources::new("traps.toml"));
+        let locations: Vec<&str> = problems.iter().map(|p| p.location.strip_prefix("traps.toml: ").unwrap()).collect();
+        assert_eq!(locations, vec![
+            "log_level", "plugins.internal[1]", "plugins.internal[2]",
+            "plugins.internal_actions[1]", "plugins.internal_actions[2]", "plugins.internal_actions[3]",
+            "plugins.external[1].plugin_name", "plugins.external[1].id", "plugins.external[1].external_port",
+            "plugins.external[1].subscriptions[0]", "plugins.external[1].subscriptions",
+            "plugins.external[2].id", "plugins.external[2].external_port", "plugins.external[2].subscriptions[1]",
+        ]);
+        assert!(problems[7].message.contains("not a valid UUID"));
+        assert!(problems[8].message.contains("ext_score"));
+
+        // A clean configuration has no problems.
+        let config: Config = toml::from_str(r#"
+            log_level = "debug"
+            publish_port = 5559
+            subscribe_port = 5559
+            images_output_dir = "/tmp/images"
+            [plugins]
+            internal = ["image_recv_plugin"]
+            internal_actions = ["image_recv_write_file_action"]
+        "#).unwrap();
+        let problems = validate_config(&config, &ConfigSources::new("traps.toml"));
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].location, "traps.toml: subscribe_port");
+    }
+
+    #[test]
+    fn store_config_problems() {
+        let dir = std::env::temp_dir().join(format!("traps-validate-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("traps-image-store.toml").to_string_lossy().to_string();
+        fs::write(&file, "[action_thresholds]\nSave = 0.8\nReduceSave = 1.5\n").unwrap();
+        assert_eq!(validate_store_config(&file).len(), 1);
+        fs::write(&file, "[action_thresholds]\nSave = 0.8\nReduceSave = 0.5\n").unwrap();
+        assert!(validate_store_config(&file).is_empty());
+
+        // The store file is checked when named explicitly.
+        let config_file = dir.join("traps.toml").to_string_lossy().to_string();
+        fs::write(&config_file, "publish_port = 5559\nsubscribe_port = 5560\nimages_output_dir = \"/tmp\"\n[plugins]\n").unwrap();
+        let missing = dir.join("missing.toml").to_string_lossy().to_string();
+        let parms: ValidateParms = traps_utils::parse_args(&[&config_file, "--image-store", &missing]).unwrap();
+        let problems = validate(&parms);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].location.ends_with("missing.toml"));
+
+        // Parse errors are reported for the configuration file.
+        fs::write(&config_file, "publish_port = \"x\"\n").unwrap();
+        let problems = validate(&traps_utils::parse_args(&[&config_file]).unwrap());
+        assert_eq!(problems[0].location, config_file);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn plugin_config_problems() {
+        actions::register_builtin_actions();
+        let config: Config = toml::from_str(r#"
+            publish_port = 5559
+            subscribe_port = 5560
+            images_output_dir = "/tmp/images"
+            [plugins]
+            internal = ["image_store_plugin", "oracle_plugin"]
+            [plugins.config.image_store_plugin.action_thresholds]
+            Save = 1.5
+            [plugins.config.oracle_plugin]
+            flush_interval_secs = -1
+            [plugins.config.image_recv_write_file_action]
+            [plugins.config.image_shrink_action]
+            [plugins.config.mqtt_publisher_plugin]
+            qos = 3
+        "#).unwrap();
+
+        let problems = validate_config(&config, &ConfigSources::new("traps.toml"));
+        let locations: Vec<&str> = problems.iter().map(|p| p.location.strip_prefix("traps.toml: ").unwrap()).collect();
+        assert_eq!(locations, vec![
+            "plugins.config.image_shrink_action",
+            "plugins.config.oracle_plugin",
+            "plugins.config.image_store_plugin.action_thresholds",
+            "plugins.config.mqtt_publisher_plugin",
+        ]);
+    }
+
+    #[test]
+    fn completion_problems() {
+        let mut config: Config = toml::from_str(r#"
+            publish_port = 5559
+            subscribe_port = 5560
+            images_output_dir = "/tmp/images"
+            [plugins]
+            internal = ["image_recv_plugin", "image_store_plugin"]
+            [completion]
+            enabled = true
+            max_images = 0
+        "#).unwrap();
+        let problems = validate_config(&config, &ConfigSources::new("traps.toml"));
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].location, "traps.toml: completion.max_images");
+
+        // The generator must be configured when no cap is set.
+        config.completion.as_mut().unwrap().max_images = None;
+        let problems = validate_config(&config, &ConfigSources::new("traps.toml"));
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].location, "traps.toml: completion.generator");
+        config.completion.as_mut().unwrap().generator = Some("image_recv_plugin".to_string());
+        assert!(validate_config(&config, &ConfigSources::new("traps.toml")).is_empty());
+
+        // Images only finish once the image_store_plugin stores or deletes them.
+        config.plugins.internal = Some(vec!["image_recv_plugin".to_string()]);
+        let problems = validate_config(&config, &ConfigSources::new("traps.toml"));
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].location, "traps.toml: completion.enabled");
+    }
+
+    #[test]
+    fn layered_problems() {
+        let dir = std::env::temp_dir().join(format!("traps-validate-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let config_file = dir.join("traps.toml").to_string_lossy().to_string();
+        fs::write(&config_file, "publish_port = 5559\nsubscribe_port = 5560\nimages_output_dir = \"/tmp\"\n[plugins]\n").unwrap();
+        let mut parms: ValidateParms = traps_utils::parse_args(&[&config_file]).unwrap();
+
+        // Problems with overridden settings are located by their layer.
+        let vars = vec![("TRAPS_SUBSCRIBE_PORT".to_string(), "5559".to_string())];
+        parms.overrides = layers::get_env_overrides(vars).unwrap();
+        let problems = validate(&parms);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].location, "environment variable TRAPS_SUBSCRIBE_PORT: subscribe_port");
+
+        // So are overrides that don't load.
+        parms.overrides = layers::get_cli_overrides(&["publish_port=x".to_string()]).unwrap();
+        let problems = validate(&parms);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].location, "command line setting --set publish_port=x");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,113 @@
+use std::any::Any;
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use camera_traps::action_registry::{self, Action, ActionContext, ActionInput, ActionOutcome, ConfigSetting};
+use camera_traps::config::config::Config;
+use camera_traps::config::errors::Errors;
+use camera_traps::events;
+use camera_traps::plugins::actions::{self, ActionChain};
+use event_engine::events::Event;
+
+/** An action kept outside the camera-traps crate that counts the bytes of
+ * the images it receives.
+ */
+struct ImageRecvSizeAction;
+impl Action for ImageRecvSizeAction {
+    fn name(&self) -> &str {"image_recv_size_action"}
+    fn config_schema(&self) -> Vec<ConfigSetting> {
+        vec![ConfigSetting {key: "max_bytes", kind: "integer", description: "The largest image accepted"}]
+    }
+    fn execute(&self, input: &ActionInput, config: &toml::Value) -> Result<ActionOutcome, Errors> {
+        if input.get_plugin_name() != "image_recv_plugin" || input.get_event_name() != Some("NewImageEvent") {
+            return Result::Err(input.unexpected(self.name()));
+        }
+        let max_bytes = config.get("max_bytes").and_then(|v| v.as_integer()).unwrap_or(i64::MAX);
+        match input.image {
+            Some(image) if (image.len() as i64) <= max_bytes => Result::Ok(ActionOutcome::Done),
+            _ => Result::Err(Errors::ActionNoImageError(input.context.get_config().title.clone().unwrap_or_default(),
+                                                       self.name().to_string(), "NewImageEvent".to_string())),
+        }
+    }
+}
+
+/** Stands in for the receive plugin that runs the action chain. */
+struct TestContext {
+    config: Config,
+}
+impl ActionContext for TestContext {
+    fn get_plugin_name(&self) -> &str {"image_recv_plugin"}
+    fn get_config(&self) -> &Config {&self.config}
+    fn as_any(&self) -> &dyn Any {self}
+}
+
+#[test]
+fn register_external_action() {
+    // Actions are registered by name and only for plugins that run actions.
+    action_registry::register_action(Arc::new(ImageRecvSizeAction)).unwrap();
+    assert!(action_registry::get_action_names().iter().any(|n| n == "image_recv_size_action"));
+    assert_eq!(action_registry::get_action_plugin("image_recv_size_action"), Some("image_recv_plugin"));
+    assert!(action_registry::register_action(Arc::new(ImageRecvSizeAction)).is_err());
+
+    // The registered action runs on event bytes and the plugin's context.
+    let action = action_registry::get_action("image_recv_size_action").unwrap();
+    let image = vec![0u8; 10];
+    let event = events::NewImageEvent::new(Uuid::new_v4(), "jpg".to_string(), image.clone()).to_bytes().unwrap();
+    let context = TestContext {config: Config::default()};
+    let input = ActionInput {event: &event, image: Some(&image), context: &context};
+    let config: toml::Value = toml::from_str("max_bytes = 10").unwrap();
+    assert_eq!(action.execute(&input, &config).unwrap(), ActionOutcome::Done);
+    let config: toml::Value = toml::from_str("max_bytes = 9").unwrap();
+    assert!(action.execute(&input, &config).is_err());
+    assert!(action_registry::check_action_config(action.as_ref(), &config).is_empty());
+}
+
+#[test]
+fn select_external_action() {
+    /** An action that passes every image it receives. */
+    struct ImageRecvPassAction;
+    impl Action for ImageRecvPassAction {
+        fn name(&self) -> &str {"image_recv_pass_action"}
+        fn execute(&self, _input: &ActionInput, _config: &toml::Value) -> Result<ActionOutcome, Errors> {
+            Result::Ok(ActionOutcome::Done)
+        }
+    }
+
+    // The receive plugin's chain has the action registered through the library
+    // along with its table, and not the actions of other plugins.
+    actions::register_builtin_actions();
+    action_registry::register_action(Arc::new(ImageRecvPassAction)).unwrap();
+    let mut config = Config::default();
+    config.plugins.internal_actions = Some(vec!["image_store_file_action".to_string(),
+                                                "image_recv_pass_action".to_string()]);
+    let table: toml::Value = toml::from_str("limit = 3").unwrap();
+    config.plugins.config = Some([("image_recv_pass_action".to_string(), table.clone())].into_iter().collect());
+    let chain = ActionChain::select(&config, "image_recv_plugin", "ImageReceivePlugin").unwrap();
+    let names: Vec<&str> = chain.actions.iter().map(|a| a.action.name()).collect();
+    assert_eq!(names, ["image_recv_pass_action"]);
+    assert_eq!(chain.actions[0].config, table);
+
+    // The chain runs it.
+    let context = TestContext {config: config.clone()};
+    let input = ActionInput {event: &[], image: None, context: &context};
+    assert_eq!(chain.run(&input).unwrap(), [ActionOutcome::Done]);
+
+    // Actions that aren't registered can't be selected.
+    config.plugins.internal_actions = Some(vec!["image_recv_missing_action".to_string()]);
+    assert!(matches!(ActionChain::select(&config, "image_recv_plugin", "ImageReceivePlugin"),
+                     Err(Errors::ActionNotFound(..))));
+}
+
+#[test]
+fn reject_non_chain_plugins() {
+    struct OracleAction;
+    impl Action for OracleAction {
+        fn name(&self) -> &str {"oracle_extra_action"}
+        fn execute(&self, _input: &ActionInput, _config: &toml::Value) -> Result<ActionOutcome, Errors> {
+            Result::Ok(ActionOutcome::Done)
+        }
+    }
+    assert!(matches!(action_registry::register_action(Arc::new(OracleAction)), Err(Errors::ActionRegisterError(..))));
+    assert_eq!(action_registry::get_action_plugin("mqtt_publisher_extra_action"), None);
+}